@@ -1,7 +1,11 @@
 #[macro_use]
 extern crate criterion;
 
-use arboriter_mcts::{Action, GameState, MCTSConfig, Player, MCTS};
+use arboriter_mcts::{
+    policy::selection::{SelectionContext, SelectionPolicy, UCB1Policy},
+    tree::MCTSNode,
+    Action, GameState, MCTSConfig, Player, MCTS,
+};
 use criterion::{black_box, BenchmarkId, Criterion};
 use std::time::Duration;
 
@@ -126,6 +130,50 @@ fn bench_mcts_search(c: &mut Criterion) {
         );
     }
 
+    // Test deep trees, where the selection route is long on every iteration
+    for depth in [16, 32].iter() {
+        let initial_state = BenchGameState::new(2, *depth);
+
+        let config = MCTSConfig::default()
+            .with_exploration_constant(1.414)
+            .with_max_iterations(5000);
+
+        group.bench_with_input(BenchmarkId::new("depth", depth), depth, |b, &_| {
+            b.iter(|| {
+                let mut mcts = MCTS::new(initial_state.clone(), config.clone());
+                black_box(mcts.search())
+            })
+        });
+    }
+
+    // Test cached selection statistics against live atomic loads
+    for bf in [5, 20].iter() {
+        let initial_state = BenchGameState::new(*bf, 4);
+
+        let config = MCTSConfig::default()
+            .with_exploration_constant(1.414)
+            .with_max_iterations(2000);
+        let config_cached = config.clone().with_selection_stats_cache(true);
+
+        group.bench_with_input(BenchmarkId::new("live_stats/branching", bf), bf, |b, &_| {
+            b.iter(|| {
+                let mut mcts = MCTS::new(initial_state.clone(), config.clone());
+                black_box(mcts.search())
+            })
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("cached_stats/branching", bf),
+            bf,
+            |b, &_| {
+                b.iter(|| {
+                    let mut mcts = MCTS::new(initial_state.clone(), config_cached.clone());
+                    black_box(mcts.search())
+                })
+            },
+        );
+    }
+
     // Test different branching factors with and without node pool
     for bf in [2, 3, 5].iter() {
         // Constant depth = 4 for reasonable benchmarks
@@ -276,5 +324,134 @@ fn bench_mcts_search(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_mcts_search);
+/// Builds a root with `branching_factor` visited children for selection benchmarks
+fn wide_root(branching_factor: usize) -> MCTSNode<BenchGameState> {
+    let mut root = MCTSNode::new(BenchGameState::new(branching_factor, 2), None, None, 0);
+    while root.expand(0).is_some() {}
+
+    for (i, child) in root.children.iter().enumerate() {
+        for _ in 0..(1 + i % 25) {
+            root.increment_visits();
+            child.increment_visits();
+            child.add_reward((i % 7) as f64 / 7.0);
+        }
+    }
+
+    root
+}
+
+fn bench_wide_node_selection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wide_node_selection");
+    let policy = UCB1Policy::new(1.414);
+
+    for bf in [50, 100, 200, 300].iter() {
+        let root = wide_root(*bf);
+
+        // Scalar reference: ln(parent_visits) is recomputed for every child
+        group.bench_with_input(BenchmarkId::new("scalar", bf), bf, |b, &_| {
+            b.iter(|| {
+                let parent_visits = root.visits();
+                let mut best_value = f64::NEG_INFINITY;
+                let mut best_index = 0;
+                for (i, child) in root.children.iter().enumerate() {
+                    let value = policy.ucb1_value(child.value(), child.visits(), parent_visits);
+                    if value > best_value {
+                        best_value = value;
+                        best_index = i;
+                    }
+                }
+                black_box(best_index)
+            })
+        });
+
+        // Batched fast path used by UCB1Policy for wide nodes
+        group.bench_with_input(BenchmarkId::new("batched", bf), bf, |b, &_| {
+            let mut rng = rand::thread_rng();
+            b.iter(|| {
+                let mut context = SelectionContext::new(&mut rng);
+                black_box(policy.select_child(black_box(&root), &mut context))
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Compares how the tree's nodes are allocated across a run of searches
+///
+/// `examples/perf_report.rs` runs the same workload once and prints nodes/sec,
+/// heap allocations and peak RSS side by side.
+fn bench_allocation_strategies(c: &mut Criterion) {
+    let mut group = c.benchmark_group("allocation_strategies");
+    group.measurement_time(Duration::from_secs(10));
+
+    let create_state = || BenchGameState::new(4, 10);
+    let search_iterations = 2000;
+    let search_count = 10;
+    let pool_size = 4000;
+
+    let config = MCTSConfig::default()
+        .with_exploration_constant(1.414)
+        .with_max_iterations(search_iterations);
+    let config_pooled = config.clone().with_node_pool_config(pool_size);
+    let config_bounded = config_pooled.clone().with_node_pool_max_free(pool_size / 4);
+
+    // A new searcher, and so a newly allocated tree, for every search
+    group.bench_function("fresh_tree", |b| {
+        b.iter(|| {
+            for _ in 0..search_count {
+                let mut mcts = MCTS::new(create_state(), config.clone());
+                let _ = black_box(mcts.search());
+            }
+        })
+    });
+
+    // One searcher whose old tree is dropped on every reset
+    group.bench_function("unpooled", |b| {
+        b.iter(|| {
+            let mut mcts = MCTS::new(create_state(), config.clone());
+            for i in 0..search_count {
+                if i > 0 {
+                    mcts.reset_root(create_state());
+                }
+                let _ = black_box(mcts.search());
+            }
+        })
+    });
+
+    // One searcher whose old tree is recycled into the node pool
+    group.bench_function("pooled", |b| {
+        b.iter(|| {
+            let mut mcts = MCTS::with_node_pool(create_state(), config_pooled.clone(), pool_size);
+            for i in 0..search_count {
+                if i > 0 {
+                    mcts.reset_root(create_state());
+                }
+                let _ = black_box(mcts.search());
+            }
+        })
+    });
+
+    // As above, but the pool keeps at most a quarter of its nodes between searches
+    group.bench_function("pooled_bounded", |b| {
+        b.iter(|| {
+            let mut mcts = MCTS::with_node_pool(create_state(), config_bounded.clone(), pool_size);
+            for i in 0..search_count {
+                if i > 0 {
+                    mcts.reset_root(create_state());
+                }
+                let _ = black_box(mcts.search());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_mcts_search,
+    bench_wide_node_selection,
+    bench_allocation_strategies
+);
 criterion_main!(benches);
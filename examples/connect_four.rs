@@ -7,6 +7,7 @@ use std::fmt;
 use std::io::{self, Write};
 
 use arboriter_mcts::{
+    config::RewardPerspective,
     policy::{backpropagation::StandardPolicy, selection::UCB1Policy, simulation::RandomPolicy},
     Action, GameState, MCTSConfig, MCTS,
 };
@@ -28,7 +29,9 @@ fn main() {
     // Create MCTS configuration
     let config = MCTSConfig::default()
         .with_exploration_constant(1.414)
-        .with_max_iterations(20_000);
+        .with_max_iterations(20_000)
+        // Each node keeps the result of the player who moved into it
+        .with_reward_perspective(RewardPerspective::PerPlayer);
 
     // Main game loop
     while !game.is_terminal() {
@@ -79,7 +82,7 @@ fn main() {
                     game = game.apply_action(&action);
 
                     // Show stats
-                    println!("{}", mcts.get_statistics().summary());
+                    println!("{}", mcts.report());
                 }
                 Err(e) => {
                     println!("Error: {:?}", e);
@@ -102,7 +105,7 @@ fn main() {
 
 /// Players in Connect Four
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Player {
+pub(crate) enum Player {
     Human,
     AI,
 }
@@ -111,7 +114,7 @@ impl arboriter_mcts::Player for Player {}
 
 /// Connect Four move
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Move {
+pub(crate) struct Move {
     /// Column to drop the piece into (0-6)
     column: usize,
 }
@@ -124,7 +127,7 @@ impl Action for Move {
 
 /// Connect Four game state
 #[derive(Clone)]
-struct ConnectFour {
+pub(crate) struct ConnectFour {
     /// Board representation (None = empty, Some(Player) = occupied)
     board: [[Option<Player>; COLS]; ROWS],
 
@@ -137,7 +140,7 @@ struct ConnectFour {
 
 impl ConnectFour {
     /// Creates a new empty Connect Four board
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         ConnectFour {
             board: [[None; COLS]; ROWS],
             current_player: Player::Human,
@@ -157,12 +160,9 @@ impl ConnectFour {
 
     /// Finds the row where a piece would land in a given column
     fn find_row(&self, column: usize) -> Option<usize> {
-        for row in (0..ROWS).rev() {
-            if self.board[row][column].is_none() {
-                return Some(row);
-            }
-        }
-        None
+        (0..ROWS)
+            .rev()
+            .find(|&row| self.board[row][column].is_none())
     }
 
     /// Returns the winner of the game, if any
@@ -0,0 +1,160 @@
+//! Greedy expansion example for the MCTS algorithm
+//!
+//! This example plays the subtraction game, a one-pile Nim where each move
+//! takes one to three stones and whoever takes the last stone wins, and
+//! compares how often a small search finds the winning move with random
+//! expansion and with a `GreedyExpansionPolicy` driven by a cheap heuristic:
+//!
+//! ```bash
+//! cargo run --example greedy_expansion
+//! ```
+//!
+//! The heuristic likes moves that leave a multiple of four stones, which is
+//! the winning strategy, so the greedy policy expands those moves first and
+//! PUCT follows their larger priors.
+
+use arboriter_mcts::{
+    config::RewardPerspective,
+    policy::{
+        expansion::{ExpansionPolicy, GreedyExpansionPolicy, RandomExpansionPolicy},
+        selection::PUCTPolicy,
+    },
+    Action, GameState, MCTSConfig, Player, MCTS,
+};
+
+/// Largest pile the comparison starts from
+const MAX_STONES: u32 = 40;
+/// Iterations per search, deliberately too few for the larger piles
+const ITERATIONS: usize = 60;
+/// Searches per pile size
+const TRIALS: usize = 20;
+
+fn main() {
+    println!("MCTS Greedy Expansion Example");
+    println!("=============================");
+    println!();
+
+    let policy = GreedyExpansionPolicy::new(leave_multiple_of_four);
+    let mcts = MCTS::new(Pile::new(10), config());
+    let priors = policy.action_priors(mcts.root()).unwrap_or_default();
+    println!("Priors with 10 stones left: {:?}", priors);
+    println!();
+
+    let mut random_hits = 0;
+    let mut greedy_hits = 0;
+    let mut positions = 0;
+    for stones in (5..=MAX_STONES).filter(|stones| !stones.is_multiple_of(4)) {
+        let winning = Take(stones % 4);
+        for _ in 0..TRIALS {
+            positions += 1;
+            if search(stones, RandomExpansionPolicy::new()) == winning {
+                random_hits += 1;
+            }
+            if search(stones, GreedyExpansionPolicy::new(leave_multiple_of_four)) == winning {
+                greedy_hits += 1;
+            }
+        }
+    }
+
+    println!(
+        "Winning move found with {} iterations per search:",
+        ITERATIONS
+    );
+    println!("  random expansion: {}/{}", random_hits, positions);
+    println!("  greedy expansion: {}/{}", greedy_hits, positions);
+}
+
+/// Scores a move by whether it leaves a multiple of four stones
+fn leave_multiple_of_four(state: &Pile, action: &Take) -> f64 {
+    if (state.stones - action.0).is_multiple_of(4) {
+        6.0
+    } else {
+        1.0
+    }
+}
+
+fn config() -> MCTSConfig {
+    MCTSConfig::default()
+        .with_max_iterations(ITERATIONS)
+        // Each node keeps the result of the player who moved into it
+        .with_reward_perspective(RewardPerspective::PerPlayer)
+}
+
+/// Searches a pile of `stones` with the given expansion policy
+fn search(stones: u32, expansion: impl ExpansionPolicy<Pile> + 'static) -> Take {
+    let mut mcts = MCTS::new(Pile::new(stones), config())
+        .with_selection_policy(PUCTPolicy::new(1.0))
+        .with_expansion_policy(expansion);
+    mcts.search().expect("the pile has legal moves")
+}
+
+/// The two players
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    First,
+    Second,
+}
+
+impl Player for Side {}
+
+/// Takes some stones from the pile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Take(u32);
+
+impl Action for Take {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A pile of stones and the player to move
+#[derive(Debug, Clone)]
+struct Pile {
+    stones: u32,
+    to_move: Side,
+}
+
+impl Pile {
+    fn new(stones: u32) -> Self {
+        Pile {
+            stones,
+            to_move: Side::First,
+        }
+    }
+}
+
+impl GameState for Pile {
+    type Action = Take;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.stones.min(3)).map(Take).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Pile {
+            stones: self.stones - action.0,
+            to_move: match self.to_move {
+                Side::First => Side::Second,
+                Side::Second => Side::First,
+            },
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stones == 0
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        // The player who just moved took the last stone
+        if *for_player == self.to_move {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.to_move
+    }
+}
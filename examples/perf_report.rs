@@ -0,0 +1,344 @@
+//! Allocation strategy report for the MCTS algorithm
+//!
+//! This example runs the same workload, a series of searches on a synthetic
+//! game, once per node allocation strategy and prints nodes/sec, heap
+//! allocations and peak memory side by side:
+//!
+//! ```bash
+//! cargo run --release --example perf_report
+//! ```
+//!
+//! Each strategy runs in its own child process so that the peak resident set
+//! size (read from `/proc/self/status`, Linux only) belongs to that strategy
+//! alone. Heap allocations are counted by a wrapper around the system
+//! allocator. The crate has no arena allocator, so the comparison covers the
+//! strategies it does offer: a fresh tree per search, one searcher that drops
+//! its old tree, and the node pool with and without a bound on its free list.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::env;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use arboriter_mcts::{Action, GameState, MCTSConfig, Player, MCTS};
+
+/// Branching factor of the synthetic game
+const BRANCHING_FACTOR: usize = 4;
+/// Plies until the synthetic game ends
+const MAX_DEPTH: usize = 10;
+/// Searches in one run of the workload
+const SEARCHES: usize = 20;
+/// Iterations per search
+const ITERATIONS: usize = 5_000;
+/// Free nodes the pooled strategies start with
+const POOL_SIZE: usize = 10_000;
+
+/// Counts heap allocations and tracks the peak number of live heap bytes
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// How the search tree's nodes are allocated
+#[derive(Clone, Copy, Debug)]
+enum Strategy {
+    /// A new searcher, and so a newly allocated tree, for every search
+    FreshTree,
+    /// One searcher whose old tree is dropped on every reset
+    Unpooled,
+    /// One searcher whose old tree is recycled into the node pool
+    Pooled,
+    /// As `Pooled`, but keeping at most a quarter of the pool between searches
+    PooledBounded,
+}
+
+impl Strategy {
+    const ALL: [Strategy; 4] = [
+        Strategy::FreshTree,
+        Strategy::Unpooled,
+        Strategy::Pooled,
+        Strategy::PooledBounded,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Strategy::FreshTree => "fresh-tree",
+            Strategy::Unpooled => "unpooled",
+            Strategy::Pooled => "pooled",
+            Strategy::PooledBounded => "pooled-bounded",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|strategy| strategy.name() == name)
+    }
+}
+
+/// What one run of the workload measured
+struct Measurement {
+    nodes: usize,
+    elapsed: Duration,
+    allocations: usize,
+    peak_heap_bytes: usize,
+    peak_rss_kb: Option<usize>,
+}
+
+impl Measurement {
+    /// Serializes the measurement for the parent process
+    fn to_line(&self) -> String {
+        format!(
+            "{} {} {} {} {}",
+            self.nodes,
+            self.elapsed.as_nanos(),
+            self.allocations,
+            self.peak_heap_bytes,
+            self.peak_rss_kb
+                .map_or_else(|| "-".to_string(), |kb| kb.to_string()),
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 5 {
+            return None;
+        }
+        Some(Measurement {
+            nodes: fields[0].parse().ok()?,
+            elapsed: Duration::from_nanos(fields[1].parse().ok()?),
+            allocations: fields[2].parse().ok()?,
+            peak_heap_bytes: fields[3].parse().ok()?,
+            peak_rss_kb: fields[4].parse().ok(),
+        })
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    // Child mode: run a single strategy and report back on stdout
+    if let Some(name) = args.get(1) {
+        let strategy = match Strategy::from_name(name) {
+            Some(strategy) => strategy,
+            None => {
+                eprintln!("unknown strategy {:?}", name);
+                std::process::exit(2);
+            }
+        };
+        println!("{}", run(strategy).to_line());
+        return;
+    }
+
+    println!("MCTS Allocation Strategy Report");
+    println!("===============================");
+    println!();
+    println!(
+        "Workload: {} searches x {} iterations, branching factor {}, depth {}",
+        SEARCHES, ITERATIONS, BRANCHING_FACTOR, MAX_DEPTH
+    );
+    if cfg!(debug_assertions) {
+        println!("Note: debug build; pass --release for representative numbers");
+    }
+    println!();
+    println!(
+        "{:<16} {:>12} {:>14} {:>14} {:>14}",
+        "strategy", "nodes/sec", "allocations", "peak heap", "peak RSS"
+    );
+
+    for strategy in Strategy::ALL {
+        // Fall back to running in this process if the child cannot be spawned;
+        // the peak RSS then also covers the strategies that ran before it
+        let measurement = measure_in_child(strategy).unwrap_or_else(|| run(strategy));
+        let nodes_per_sec = measurement.nodes as f64 / measurement.elapsed.as_secs_f64();
+        println!(
+            "{:<16} {:>12.0} {:>14} {:>14} {:>14}",
+            strategy.name(),
+            nodes_per_sec,
+            measurement.allocations,
+            format_kb(measurement.peak_heap_bytes / 1024),
+            measurement
+                .peak_rss_kb
+                .map_or_else(|| "n/a".to_string(), format_kb),
+        );
+    }
+}
+
+/// Runs `strategy` in a fresh copy of this binary
+fn measure_in_child(strategy: Strategy) -> Option<Measurement> {
+    let output = Command::new(env::current_exe().ok()?)
+        .arg(strategy.name())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Measurement::from_line(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// Runs the workload with `strategy` and measures it
+fn run(strategy: Strategy) -> Measurement {
+    let config = MCTSConfig::default()
+        .with_exploration_constant(1.414)
+        .with_max_iterations(ITERATIONS);
+    let config = match strategy {
+        Strategy::FreshTree | Strategy::Unpooled => config,
+        Strategy::Pooled => config.with_node_pool_config(POOL_SIZE),
+        Strategy::PooledBounded => config
+            .with_node_pool_config(POOL_SIZE)
+            .with_node_pool_max_free(POOL_SIZE / 4),
+    };
+
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+    PEAK_BYTES.store(LIVE_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+    let start = Instant::now();
+    let mut nodes = 0;
+
+    match strategy {
+        Strategy::FreshTree => {
+            for _ in 0..SEARCHES {
+                let mut mcts = MCTS::new(SyntheticGame::new(), config.clone());
+                let _ = mcts.search();
+                nodes += mcts.get_statistics().tree_size;
+            }
+        }
+        Strategy::Unpooled | Strategy::Pooled | Strategy::PooledBounded => {
+            let mut mcts = match strategy {
+                Strategy::Unpooled => MCTS::new(SyntheticGame::new(), config),
+                _ => MCTS::with_node_pool(SyntheticGame::new(), config, POOL_SIZE),
+            };
+            for i in 0..SEARCHES {
+                if i > 0 {
+                    mcts.reset_root(SyntheticGame::new());
+                }
+                let _ = mcts.search();
+                nodes += mcts.get_statistics().tree_size;
+            }
+        }
+    }
+
+    Measurement {
+        nodes,
+        elapsed: start.elapsed(),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations_before,
+        peak_heap_bytes: PEAK_BYTES.load(Ordering::Relaxed),
+        peak_rss_kb: peak_rss_kb(),
+    }
+}
+
+/// Peak resident set size of this process in KiB, where the OS reports it
+fn peak_rss_kb() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+fn format_kb(kb: usize) -> String {
+    if kb >= 10 * 1024 {
+        format!("{:.1} MiB", kb as f64 / 1024.0)
+    } else {
+        format!("{} KiB", kb)
+    }
+}
+
+/// A game with a fixed branching factor that ends after a fixed number of plies
+#[derive(Clone, Debug)]
+struct SyntheticGame {
+    depth: usize,
+    player: Side,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Side(usize);
+
+impl Player for Side {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Move(usize);
+
+impl Action for Move {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+impl SyntheticGame {
+    fn new() -> Self {
+        SyntheticGame {
+            depth: 0,
+            player: Side(0),
+        }
+    }
+}
+
+impl GameState for SyntheticGame {
+    type Action = Move;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return Vec::new();
+        }
+        (0..BRANCHING_FACTOR).map(Move).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        SyntheticGame {
+            depth: self.depth + 1,
+            player: Side((self.player.0 + action.0 + 1) % 2),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.depth >= MAX_DEPTH
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        if self.player == *for_player {
+            0.75
+        } else {
+            0.25
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.player.clone()
+    }
+}
@@ -0,0 +1,95 @@
+//! JSON-RPC search server example for the MCTS algorithm
+//!
+//! This example serves searches for a game of Nim over HTTP, so that a
+//! backend written in any language can ask the engine for moves:
+//!
+//! ```bash
+//! cargo run --example search_server --features server -- 127.0.0.1:8080
+//!
+//! curl -s localhost:8080 -d '{"jsonrpc": "2.0", "id": 1, "method": "search",
+//!   "params": {"state": {"stones": 10, "player": 0}, "budget": {"iterations": 5000}, "lines": 3}}'
+//! ```
+
+use std::env;
+
+use arboriter_mcts::{
+    config::{Budget, RewardPerspective},
+    server::{SearchServer, SerdeCodec},
+    Action, GameState, MCTSConfig, Player,
+};
+use serde::{Deserialize, Serialize};
+
+fn main() {
+    env_logger::init();
+
+    let addr = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:8080".to_string());
+
+    let config = MCTSConfig::default()
+        .with_max_iterations(10_000)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let server = SearchServer::<Nim, _>::new(SerdeCodec, config)
+        .with_max_budget(Budget::Both(200_000, std::time::Duration::from_secs(5)));
+
+    println!("Serving Nim searches on http://{}", addr);
+    if let Err(error) = server.serve(&addr) {
+        eprintln!("server failed: {}", error);
+        std::process::exit(1);
+    }
+}
+
+/// Nim with a single pile: take one to three stones, taking the last one wins
+#[derive(Clone, Debug, Deserialize)]
+struct Nim {
+    stones: u32,
+    player: Side,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+struct Side(u8);
+
+impl Player for Side {}
+
+/// Takes this many stones
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct Take(u32);
+
+impl Action for Take {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl GameState for Nim {
+    type Action = Take;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.stones.min(3)).map(Take).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Nim {
+            stones: self.stones - action.0,
+            player: Side(1 - self.player.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stones == 0
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        // The player to move faces an empty pile: the other one took the last stone
+        if self.player == *for_player {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.player
+    }
+}
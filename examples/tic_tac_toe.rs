@@ -7,6 +7,7 @@ use std::fmt;
 use std::io::{self, Write};
 
 use arboriter_mcts::{
+    config::RewardPerspective,
     policy::{selection::UCB1Policy, simulation::RandomPolicy},
     Action, GameState, MCTSConfig, MCTS,
 };
@@ -25,7 +26,9 @@ fn main() {
     // Create MCTS configuration
     let config = MCTSConfig::default()
         .with_exploration_constant(1.414)
-        .with_max_iterations(10_000);
+        .with_max_iterations(10_000)
+        // Each node keeps the result of the player who moved into it
+        .with_reward_perspective(RewardPerspective::PerPlayer);
 
     // Main game loop
     while !game.is_terminal() {
@@ -41,7 +44,6 @@ fn main() {
             io::stdin().read_line(&mut input).unwrap();
 
             let coords: Vec<usize> = input
-                .trim()
                 .split_whitespace()
                 .filter_map(|s| s.parse::<usize>().ok())
                 .collect();
@@ -87,7 +89,7 @@ fn main() {
                     game = game.apply_action(&action);
 
                     // Show stats
-                    println!("{}", mcts.get_statistics().summary());
+                    println!("{}", mcts.report());
                 }
                 Err(e) => {
                     println!("Error: {:?}", e);
@@ -110,7 +112,7 @@ fn main() {
 
 /// Players in Tic-Tac-Toe
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Player {
+pub(crate) enum Player {
     X,
     O,
 }
@@ -119,7 +121,7 @@ impl arboriter_mcts::Player for Player {}
 
 /// Tic-Tac-Toe move
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Move {
+pub(crate) struct Move {
     /// Board position index (0-8)
     index: usize,
 }
@@ -132,7 +134,7 @@ impl Action for Move {
 
 /// Tic-Tac-Toe game state
 #[derive(Clone)]
-struct TicTacToe {
+pub(crate) struct TicTacToe {
     /// Board representation (None = empty, Some(Player) = occupied)
     board: [Option<Player>; 9],
 
@@ -145,7 +147,7 @@ struct TicTacToe {
 
 impl TicTacToe {
     /// Creates a new empty Tic-Tac-Toe board
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         TicTacToe {
             board: [None; 9],
             current_player: Player::X,
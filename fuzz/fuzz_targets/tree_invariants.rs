@@ -0,0 +1,158 @@
+//! Fuzzes searches on random game trees and checks tree invariants
+//!
+//! Run with `cargo fuzz run tree_invariants` from the repository root. The
+//! input bytes choose the tree shape, the search budget, and which of the
+//! node pool and DAG mode are enabled.
+
+#![no_main]
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    tree::{MCTSNode, NodePath},
+    GameState, MCTSConfig, MCTS,
+};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Clone, Debug)]
+struct RandomTree {
+    seed: u64,
+    path_hash: u64,
+    depth: usize,
+    max_depth: usize,
+    max_branch: u64,
+    merge_states: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Branch(usize);
+
+impl Action for Branch {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Mover(usize);
+
+impl Player for Mover {}
+
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+impl RandomTree {
+    fn branching(&self) -> usize {
+        if self.depth >= self.max_depth {
+            return 0;
+        }
+        let low = if self.depth == 0 { 1 } else { 0 };
+        (low + mix(self.path_hash ^ self.seed) % (self.max_branch + 1 - low)) as usize
+    }
+}
+
+impl GameState for RandomTree {
+    type Action = Branch;
+    type Player = Mover;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (0..self.branching()).map(Branch).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut next = self.clone();
+        next.path_hash = mix(self.path_hash ^ (action.0 as u64 + 1));
+        next.depth += 1;
+        next
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.branching() == 0
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        (mix(self.path_hash) % 101) as f64 / 100.0
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Mover(self.depth % 2)
+    }
+
+    fn hash(&self) -> u64 {
+        if !self.merge_states {
+            return 0;
+        }
+        1 + (self.depth as u64) * 4 + self.path_hash % 3
+    }
+}
+
+fn check_node<S: GameState>(
+    root: &MCTSNode<S>,
+    node: &MCTSNode<S>,
+    path: &mut NodePath,
+    dag: bool,
+) {
+    let resolved = root.descendant(path).expect("path resolves");
+    assert!(std::ptr::eq(resolved, node));
+
+    for child in &node.children {
+        let id = child.action.as_ref().unwrap().id();
+        assert!(node.unexpanded_actions.iter().all(|a| a.id() != id));
+    }
+    if let Some(target) = &node.transposition {
+        assert!(root.descendant(target).is_some());
+    }
+    if !dag {
+        let child_visits: u64 = node.children.iter().map(|c| c.visits()).sum();
+        assert!(node.visits() >= child_visits);
+    }
+
+    for (i, child) in node.children.iter().enumerate() {
+        path.push(i);
+        check_node(root, child, path, dag);
+        path.indices.pop();
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 12 {
+        return;
+    }
+
+    let seed = u64::from_le_bytes(data[..8].try_into().unwrap());
+    let max_depth = 1 + data[8] as usize % 6;
+    let max_branch = 1 + data[9] as u64 % 4;
+    let iterations = 1 + u16::from_le_bytes([data[10], data[11]]) as usize % 500;
+    let flags = data.get(12).copied().unwrap_or(0);
+    let use_pool = flags & 1 != 0;
+    let dag = flags & 2 != 0;
+
+    let state = RandomTree {
+        seed,
+        path_hash: mix(seed),
+        depth: 0,
+        max_depth,
+        max_branch,
+        merge_states: dag,
+    };
+    let config = MCTSConfig::default()
+        .with_max_iterations(iterations)
+        .with_dag_mode(dag);
+
+    let mut mcts = if use_pool {
+        MCTS::with_node_pool(state, config, 16)
+    } else {
+        MCTS::new(state, config)
+    };
+
+    // Two searches exercise tree recycling (with a pool) or reuse (without)
+    for round in 1..=2 {
+        assert!(mcts.search().is_ok());
+        let searches = if use_pool { 1 } else { round };
+        assert_eq!(mcts.root().visits(), (iterations * searches) as u64);
+        check_node(mcts.root(), mcts.root(), &mut NodePath::new(), dag);
+    }
+});
@@ -0,0 +1,335 @@
+//! Adapters for game engines that do not implement [`GameState`]
+//!
+//! Game-logic crates usually expose the same handful of operations under
+//! their own names: generate the legal moves of a position, play one, ask
+//! who is to move and how the game ended. [`GameEngine`] is a bridge trait
+//! for exactly those operations, and [`EngineState`] turns any engine and a
+//! position into a [`GameState`] the search can use. The engine's move and
+//! player types need no traits from this crate; they are wrapped in
+//! [`EngineMove`] and [`EnginePlayer`].
+//!
+//! For prototyping, [`ClosureEngine`] builds an engine from closures, so an
+//! existing API can be searched without writing any trait implementation.
+//!
+//! # Example
+//!
+//! ```
+//! use arboriter_mcts::{adapters::ClosureEngine, MCTSConfig, MCTS};
+//!
+//! // Nim with one pile: take 1 to 3 stones, taking the last one wins
+//! let state = ClosureEngine::new(
+//!     |pile: &(u32, u8)| (1..=pile.0.min(3)).collect::<Vec<u32>>(),
+//!     |pile: &(u32, u8), take: &u32| (pile.0 - take, 1 - pile.1),
+//!     |pile: &(u32, u8)| pile.1,
+//!     // Whoever is to move on an empty pile has lost
+//!     |pile: &(u32, u8), player: &u8| if pile.1 == *player { 0.0 } else { 1.0 },
+//! )
+//! .into_state((5, 0));
+//!
+//! // Seeded, so the search plays out the same way on every run
+//! let config = MCTSConfig::default().with_max_iterations(2000).with_seed(1);
+//! let mut mcts = MCTS::new(state, config);
+//! let best = mcts.search().unwrap();
+//! assert_eq!(*best.inner(), 1);
+//! ```
+
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::game_state::{Action, ActionKey, GameState, Player};
+
+/// The operations a game engine must offer to be searched
+///
+/// Implement this for a thin handle to an engine, or use [`ClosureEngine`].
+/// The engine is shared by every state of a search, so it must be `Send`
+/// and `Sync`.
+pub trait GameEngine: Send + Sync {
+    /// The engine's position type
+    type Position: Clone + Send + Sync;
+
+    /// The engine's move type
+    type Move: Clone + fmt::Debug + Hash + Send + Sync;
+
+    /// The engine's player type
+    type Player: Clone + fmt::Debug + PartialEq + Send + Sync;
+
+    /// Returns the legal moves in a position
+    fn legal_moves(&self, position: &Self::Position) -> Vec<Self::Move>;
+
+    /// Returns the position after playing a move
+    fn play(&self, position: &Self::Position, mv: &Self::Move) -> Self::Position;
+
+    /// Returns the player to move
+    fn to_move(&self, position: &Self::Position) -> Self::Player;
+
+    /// Returns the result of a finished position for `player`, 0.0 to 1.0
+    fn result(&self, position: &Self::Position, player: &Self::Player) -> f64;
+
+    /// Returns true if the game is over
+    ///
+    /// Defaults to having no legal moves left.
+    fn is_over(&self, position: &Self::Position) -> bool {
+        self.legal_moves(position).is_empty()
+    }
+
+    /// Returns the key that identifies a move across the search
+    ///
+    /// Defaults to a hash of the move; see [`ActionKey::hashed`].
+    fn move_key(&self, mv: &Self::Move) -> ActionKey {
+        ActionKey::hashed(0, mv)
+    }
+
+    /// Returns a hash of a position for transpositions, or 0 for none
+    fn hash(&self, _position: &Self::Position) -> u64 {
+        0
+    }
+}
+
+/// An engine's move, usable as an [`Action`]
+///
+/// Its [`key`](Action::key) comes from [`GameEngine::move_key`] and its
+/// [`id`](Action::id) is the key's id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineMove<M> {
+    mv: M,
+    key: ActionKey,
+}
+
+impl<M> EngineMove<M> {
+    /// Returns the engine's move
+    pub fn inner(&self) -> &M {
+        &self.mv
+    }
+
+    /// Unwraps the engine's move
+    pub fn into_inner(self) -> M {
+        self.mv
+    }
+}
+
+impl<M: Clone + fmt::Debug + Send + Sync> Action for EngineMove<M> {
+    fn id(&self) -> usize {
+        self.key.id as usize
+    }
+
+    fn key(&self) -> ActionKey {
+        self.key
+    }
+}
+
+/// An engine's player, usable as a [`Player`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnginePlayer<P>(pub P);
+
+impl<P: Clone + fmt::Debug + PartialEq + Send + Sync> Player for EnginePlayer<P> {}
+
+/// A position of a [`GameEngine`], usable as a [`GameState`]
+pub struct EngineState<E: GameEngine> {
+    engine: Arc<E>,
+    position: E::Position,
+}
+
+impl<E: GameEngine> EngineState<E> {
+    /// Wraps a position of `engine`
+    pub fn new(engine: E, position: E::Position) -> Self {
+        Self::shared(Arc::new(engine), position)
+    }
+
+    /// Wraps a position of an engine that is already shared
+    pub fn shared(engine: Arc<E>, position: E::Position) -> Self {
+        EngineState { engine, position }
+    }
+
+    /// Returns the engine
+    pub fn engine(&self) -> &E {
+        &self.engine
+    }
+
+    /// Returns the wrapped position
+    pub fn position(&self) -> &E::Position {
+        &self.position
+    }
+
+    /// Unwraps the position
+    pub fn into_position(self) -> E::Position {
+        self.position
+    }
+
+    /// Wraps another position of the same engine
+    pub fn with_position(&self, position: E::Position) -> Self {
+        Self::shared(Arc::clone(&self.engine), position)
+    }
+}
+
+impl<E: GameEngine> Clone for EngineState<E> {
+    fn clone(&self) -> Self {
+        self.with_position(self.position.clone())
+    }
+}
+
+impl<E: GameEngine> fmt::Debug for EngineState<E>
+where
+    E::Position: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EngineState")
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E: GameEngine> GameState for EngineState<E> {
+    type Action = EngineMove<E::Move>;
+    type Player = EnginePlayer<E::Player>;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.engine.is_over(&self.position) {
+            return Vec::new();
+        }
+        self.engine
+            .legal_moves(&self.position)
+            .into_iter()
+            .map(|mv| EngineMove {
+                key: self.engine.move_key(&mv),
+                mv,
+            })
+            .collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        self.with_position(self.engine.play(&self.position, &action.mv))
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.engine.is_over(&self.position)
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        self.engine.result(&self.position, &for_player.0)
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        EnginePlayer(self.engine.to_move(&self.position))
+    }
+
+    fn hash(&self) -> u64 {
+        self.engine.hash(&self.position)
+    }
+}
+
+/// Boxed closure over a position
+type PositionFn<P, T> = Box<dyn Fn(&P) -> T + Send + Sync>;
+
+/// Boxed closure over a position and one argument
+type PositionArgFn<P, A, T> = Box<dyn Fn(&P, &A) -> T + Send + Sync>;
+
+/// A [`GameEngine`] built from closures
+///
+/// The four required operations are given to [`new`](Self::new); the game
+/// is over when there are no legal moves unless
+/// [`with_terminal`](Self::with_terminal) says otherwise.
+pub struct ClosureEngine<P, M, Pl> {
+    legal_moves: PositionFn<P, Vec<M>>,
+    play: PositionArgFn<P, M, P>,
+    to_move: PositionFn<P, Pl>,
+    result: PositionArgFn<P, Pl, f64>,
+    is_over: Option<PositionFn<P, bool>>,
+    hash: Option<PositionFn<P, u64>>,
+}
+
+impl<P, M, Pl> ClosureEngine<P, M, Pl>
+where
+    P: Clone + Send + Sync + 'static,
+    M: Clone + fmt::Debug + Hash + Send + Sync + 'static,
+    Pl: Clone + fmt::Debug + PartialEq + Send + Sync + 'static,
+{
+    /// Creates an engine from its move generator, move function, player to
+    /// move and result
+    pub fn new<L, A, T, R>(legal_moves: L, play: A, to_move: T, result: R) -> Self
+    where
+        L: Fn(&P) -> Vec<M> + Send + Sync + 'static,
+        A: Fn(&P, &M) -> P + Send + Sync + 'static,
+        T: Fn(&P) -> Pl + Send + Sync + 'static,
+        R: Fn(&P, &Pl) -> f64 + Send + Sync + 'static,
+    {
+        ClosureEngine {
+            legal_moves: Box::new(legal_moves),
+            play: Box::new(play),
+            to_move: Box::new(to_move),
+            result: Box::new(result),
+            is_over: None,
+            hash: None,
+        }
+    }
+
+    /// Decides when the game is over instead of waiting for moves to run out
+    pub fn with_terminal<F>(mut self, is_over: F) -> Self
+    where
+        F: Fn(&P) -> bool + Send + Sync + 'static,
+    {
+        self.is_over = Some(Box::new(is_over));
+        self
+    }
+
+    /// Hashes positions, enabling transpositions and other hash-based features
+    pub fn with_hash<F>(mut self, hash: F) -> Self
+    where
+        F: Fn(&P) -> u64 + Send + Sync + 'static,
+    {
+        self.hash = Some(Box::new(hash));
+        self
+    }
+
+    /// Wraps a position of this engine as a [`GameState`]
+    pub fn into_state(self, position: P) -> EngineState<Self> {
+        EngineState::new(self, position)
+    }
+}
+
+impl<P, M, Pl> GameEngine for ClosureEngine<P, M, Pl>
+where
+    P: Clone + Send + Sync + 'static,
+    M: Clone + fmt::Debug + Hash + Send + Sync + 'static,
+    Pl: Clone + fmt::Debug + PartialEq + Send + Sync + 'static,
+{
+    type Position = P;
+    type Move = M;
+    type Player = Pl;
+
+    fn legal_moves(&self, position: &P) -> Vec<M> {
+        (self.legal_moves)(position)
+    }
+
+    fn play(&self, position: &P, mv: &M) -> P {
+        (self.play)(position, mv)
+    }
+
+    fn to_move(&self, position: &P) -> Pl {
+        (self.to_move)(position)
+    }
+
+    fn result(&self, position: &P, player: &Pl) -> f64 {
+        (self.result)(position, player)
+    }
+
+    fn is_over(&self, position: &P) -> bool {
+        match &self.is_over {
+            Some(is_over) => is_over(position),
+            None => (self.legal_moves)(position).is_empty(),
+        }
+    }
+
+    fn hash(&self, position: &P) -> u64 {
+        self.hash.as_ref().map_or(0, |hash| hash(position))
+    }
+}
+
+impl<P, M, Pl> fmt::Debug for ClosureEngine<P, M, Pl> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClosureEngine")
+            .field("custom_terminal", &self.is_over.is_some())
+            .field("hashed", &self.hash.is_some())
+            .finish_non_exhaustive()
+    }
+}
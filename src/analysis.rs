@@ -0,0 +1,344 @@
+//! Persistent analysis of one position at a time
+//!
+//! GUIs and analysis tools want to point the engine at a position, let it
+//! think in the background, read the best lines while it does, and move on
+//! to the next position without losing the work already done. An
+//! [`AnalysisSession`] wraps an [`MCTS`] instance to do exactly that:
+//! [`set_position`](AnalysisSession::set_position) keeps the subtree of a
+//! position the tree already holds, [`go`](AnalysisSession::go) searches on
+//! a background thread within a [`Budget`],
+//! [`lines`](AnalysisSession::lines) reports the top lines as the search
+//! goes, and [`stop`](AnalysisSession::stop) returns the best move.
+//!
+//! The search runs in slices of [`DEFAULT_SLICE`] iterations, continuing on
+//! the same tree, and the lines are refreshed after every slice. With
+//! [`Budget::Unlimited`] it runs until stopped; pair it with a
+//! [`SubtreeGc`](crate::config::SubtreeGc) so the tree does not grow without
+//! bound.
+//!
+//! # Example
+//!
+//! ```
+//! # use arboriter_mcts::{GameState, Action, Player, MCTSConfig, MCTS};
+//! use arboriter_mcts::analysis::AnalysisSession;
+//! use arboriter_mcts::config::Budget;
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct Pick(usize);
+//! # impl Action for Pick { fn id(&self) -> usize { self.0 } }
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct P;
+//! # impl Player for P {}
+//! /// Two picks of 0, 1 or 2; bigger picks are better
+//! #[derive(Clone)]
+//! struct Picks(Vec<usize>);
+//! # impl GameState for Picks {
+//! #     type Action = Pick;
+//! #     type Player = P;
+//! #     fn get_legal_actions(&self) -> Vec<Pick> {
+//! #         if self.is_terminal() { vec![] } else { (0..3).map(Pick).collect() }
+//! #     }
+//! #     fn apply_action(&self, a: &Pick) -> Self {
+//! #         let mut picks = self.0.clone();
+//! #         picks.push(a.0);
+//! #         Picks(picks)
+//! #     }
+//! #     fn is_terminal(&self) -> bool { self.0.len() == 2 }
+//! #     fn get_result(&self, _: &P) -> f64 { self.0.iter().sum::<usize>() as f64 / 4.0 }
+//! #     fn get_current_player(&self) -> P { P }
+//! # }
+//!
+//! let mut session = AnalysisSession::new(MCTS::new(Picks(vec![]), MCTSConfig::default()));
+//! session.go(Budget::Iterations(500));
+//!
+//! // A GUI would poll the lines while the search runs
+//! let best = session.wait().unwrap();
+//! let lines = session.lines(3);
+//! assert_eq!(lines[0].action, best);
+//! assert_eq!(lines[0].moves.len(), 2);
+//! ```
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Instant;
+
+use crate::{
+    config::Budget, game_state::GameState, stats::AnalysisLine, Result, SearchOutcome, StopToken,
+    MCTS,
+};
+
+/// Iterations a session searches between refreshes of its lines
+pub const DEFAULT_SLICE: usize = 1_000;
+
+/// A search instance that analyzes one position after another
+///
+/// See the [module documentation](self).
+pub struct AnalysisSession<S: GameState + 'static> {
+    /// The search instance, while no search is running
+    mcts: Option<MCTS<S>>,
+
+    /// The thread running the current search, which hands the instance back
+    worker: Option<JoinHandle<(MCTS<S>, Result<()>)>>,
+
+    /// Stops the slice being searched
+    stop_token: StopToken,
+
+    /// Stops the worker before its next slice
+    stopping: Arc<AtomicBool>,
+
+    /// Iterations searched since the last `go`
+    iterations: Arc<AtomicUsize>,
+
+    /// Lines of the root as of the latest slice, every root child included
+    lines: Arc<Mutex<Vec<AnalysisLine<S::Action>>>>,
+
+    /// Iterations per slice
+    slice: usize,
+}
+
+impl<S: GameState + 'static> AnalysisSession<S> {
+    /// Creates a session analyzing the root of `mcts`
+    ///
+    /// The instance keeps its configuration, policies and tree; its iteration
+    /// and time limits are replaced by the budget passed to each
+    /// [`go`](Self::go).
+    pub fn new(mcts: MCTS<S>) -> Self {
+        let session = AnalysisSession {
+            stop_token: mcts.stop_token(),
+            mcts: Some(mcts),
+            worker: None,
+            stopping: Arc::new(AtomicBool::new(false)),
+            iterations: Arc::new(AtomicUsize::new(0)),
+            lines: Arc::new(Mutex::new(Vec::new())),
+            slice: DEFAULT_SLICE,
+        };
+        session.publish();
+        session
+    }
+
+    /// Sets how many iterations are searched between refreshes of the lines
+    ///
+    /// Smaller slices give fresher lines at the cost of more bookkeeping.
+    /// The slice is at least one iteration.
+    pub fn with_slice(mut self, iterations: usize) -> Self {
+        self.slice = iterations.max(1);
+        self
+    }
+
+    /// Moves the analysis to `state`
+    ///
+    /// Stops a running search first. If the tree holds `state` within a
+    /// couple of plies of the root, its subtree is kept, as with
+    /// [`MCTS::advance_root`]. Returns true if it was.
+    pub fn set_position(&mut self, state: S) -> bool {
+        let _ = self.stop();
+        let reused = self.engine().advance_root(state);
+        self.publish();
+        reused
+    }
+
+    /// Starts searching the current position on a background thread
+    ///
+    /// The search continues on the existing tree until `budget` is spent,
+    /// the tree is exhausted with
+    /// [`stop_when_exhausted`](crate::MCTSConfig::stop_when_exhausted)
+    /// enabled, or [`stop`](Self::stop) is called. A search already running
+    /// is stopped first.
+    pub fn go(&mut self, budget: Budget) {
+        let _ = self.stop();
+        let mcts = self.mcts.take().expect("no search is running");
+
+        self.stopping.store(false, Ordering::Relaxed);
+        self.stop_token.reset();
+        self.iterations.store(0, Ordering::Relaxed);
+
+        let stopping = Arc::clone(&self.stopping);
+        let iterations = Arc::clone(&self.iterations);
+        let lines = Arc::clone(&self.lines);
+        let slice = self.slice;
+        self.worker = Some(std::thread::spawn(move || {
+            run_slices(mcts, budget, slice, &stopping, &iterations, &lines)
+        }));
+    }
+
+    /// Stops the search and returns the best action found
+    ///
+    /// Without a running search this is the best action of the tree as it
+    /// stands. The action is chosen with the configured
+    /// [`best_child_criteria`](crate::MCTSConfig::best_child_criteria).
+    ///
+    /// # Errors
+    ///
+    /// Returns the error the search stopped with, if any, or
+    /// `MCTSError::NoLegalActions` if the root has no searched children.
+    pub fn stop(&mut self) -> Result<S::Action> {
+        if self.worker.is_some() {
+            self.stopping.store(true, Ordering::Relaxed);
+            self.stop_token.stop();
+        }
+        self.wait()
+    }
+
+    /// Waits for the search to spend its budget and returns the best action found
+    ///
+    /// See [`stop`](Self::stop). With [`Budget::Unlimited`] this only
+    /// returns once another thread stops the search through a
+    /// [`stop_token`](Self::stop_token).
+    pub fn wait(&mut self) -> Result<S::Action> {
+        if let Some(worker) = self.worker.take() {
+            let (mcts, result) = match worker.join() {
+                Ok(finished) => finished,
+                Err(panic) => std::panic::resume_unwind(panic),
+            };
+            self.mcts = Some(mcts);
+            // A stop that arrived after the last slice must not carry over
+            self.stop_token.reset();
+            result?;
+        }
+        let mcts = self.engine();
+        mcts.best_action(mcts.config().best_child_criteria)
+    }
+
+    /// Returns true while a search is running
+    pub fn is_searching(&self) -> bool {
+        self.worker
+            .as_ref()
+            .is_some_and(|worker| !worker.is_finished())
+    }
+
+    /// Returns the `k` most visited root actions with their expected continuations
+    ///
+    /// While a search runs, the lines are those of its latest slice. See
+    /// [`MCTS::top_lines`].
+    pub fn lines(&self, k: usize) -> Vec<AnalysisLine<S::Action>> {
+        let lines = self
+            .lines
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        lines.iter().take(k).cloned().collect()
+    }
+
+    /// Returns the number of iterations searched since the last [`go`](Self::go)
+    pub fn iterations(&self) -> usize {
+        self.iterations.load(Ordering::Relaxed)
+    }
+
+    /// Returns a token that stops the running search from another thread
+    ///
+    /// Unlike [`stop`](Self::stop), the token does not wait for the search;
+    /// call [`wait`](Self::wait) to get the result.
+    pub fn stop_token(&self) -> SessionStopToken {
+        SessionStopToken {
+            stopping: Arc::clone(&self.stopping),
+            stop_token: self.stop_token.clone(),
+        }
+    }
+
+    /// Returns the search instance, or `None` while a search is running
+    pub fn mcts(&self) -> Option<&MCTS<S>> {
+        self.mcts.as_ref()
+    }
+
+    /// Stops the search and hands back the search instance
+    pub fn into_inner(mut self) -> MCTS<S> {
+        let _ = self.stop();
+        self.mcts.take().expect("the search was joined")
+    }
+
+    /// Returns the search instance of an idle session
+    fn engine(&mut self) -> &mut MCTS<S> {
+        self.mcts.as_mut().expect("the search was joined")
+    }
+
+    /// Replaces the published lines with those of the idle instance
+    fn publish(&self) {
+        if let Some(mcts) = &self.mcts {
+            publish_lines(mcts, &self.lines);
+        }
+    }
+}
+
+impl<S: GameState + 'static> Drop for AnalysisSession<S> {
+    fn drop(&mut self) {
+        if self.worker.is_some() {
+            let _ = self.stop();
+        }
+    }
+}
+
+impl<S: GameState + 'static> std::fmt::Debug for AnalysisSession<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnalysisSession")
+            .field("searching", &self.is_searching())
+            .field("iterations", &self.iterations())
+            .field("slice", &self.slice)
+            .finish()
+    }
+}
+
+/// Stops an [`AnalysisSession`]'s search from another thread
+///
+/// Obtained from [`AnalysisSession::stop_token`].
+#[derive(Debug, Clone)]
+pub struct SessionStopToken {
+    stopping: Arc<AtomicBool>,
+    stop_token: StopToken,
+}
+
+impl SessionStopToken {
+    /// Requests that the search stop as soon as possible
+    pub fn stop(&self) {
+        self.stopping.store(true, Ordering::Relaxed);
+        self.stop_token.stop();
+    }
+}
+
+/// Searches `mcts` slice by slice until `budget` is spent or a stop is requested
+fn run_slices<S: GameState + 'static>(
+    mut mcts: MCTS<S>,
+    budget: Budget,
+    slice: usize,
+    stopping: &AtomicBool,
+    iterations: &AtomicUsize,
+    lines: &Mutex<Vec<AnalysisLine<S::Action>>>,
+) -> (MCTS<S>, Result<()>) {
+    let start = Instant::now();
+    let mut searched = 0;
+    let result = loop {
+        if stopping.load(Ordering::Relaxed) {
+            break Ok(());
+        }
+        let slice = budget
+            .iterations()
+            .map_or(slice, |limit| slice.min(limit.saturating_sub(searched)));
+        let time = budget
+            .time()
+            .map(|time| time.saturating_sub(start.elapsed()));
+        if slice == 0 || time.is_some_and(|time| time.is_zero()) {
+            break Ok(());
+        }
+
+        let outcome = mcts.continue_search(Budget::from_limits(Some(slice), time));
+        searched += mcts.get_statistics().iterations;
+        iterations.store(searched, Ordering::Relaxed);
+        publish_lines(&mcts, lines);
+
+        match outcome {
+            Err(error) => break Err(error),
+            Ok(_) if mcts.get_statistics().outcome == SearchOutcome::TreeExhausted => break Ok(()),
+            Ok(_) => {}
+        }
+    };
+    (mcts, result)
+}
+
+/// Replaces `lines` with every root line of `mcts`
+fn publish_lines<S: GameState + 'static>(
+    mcts: &MCTS<S>,
+    lines: &Mutex<Vec<AnalysisLine<S::Action>>>,
+) {
+    let fresh = mcts.top_lines(mcts.root().children.len());
+    *lines
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = fresh;
+}
@@ -0,0 +1,222 @@
+//! Synthetic game trees for profiling search configurations
+//!
+//! Enabled with the `bench-util` feature. [`BenchGameState`] builds a uniform
+//! tree with a configurable branching factor and depth, and [`run_profile`]
+//! runs repeated searches on it, so policy choices and configuration options
+//! can be compared without writing a game first.
+//!
+//! # Example
+//!
+//! ```
+//! use arboriter_mcts::bench::{run_profile, ProfileConfig};
+//! use arboriter_mcts::MCTSConfig;
+//!
+//! let profile = ProfileConfig::new(3, 4)
+//!     .with_mcts_config(MCTSConfig::default().with_max_iterations(200))
+//!     .with_searches(2);
+//!
+//! let report = run_profile(&profile);
+//! assert_eq!(report.runs.len(), 2);
+//! println!("{:.0} iterations/s", report.iterations_per_second());
+//! ```
+
+use std::time::Duration;
+
+use crate::{
+    config::MCTSConfig,
+    game_state::{Action, GameState, Player},
+    mcts::MCTS,
+    stats::SearchStatistics,
+};
+
+/// Uniform synthetic game with a fixed branching factor and depth
+///
+/// Every non-terminal state has `branching_factor` actions and the game ends
+/// after `max_depth` moves. Results depend on which player made the last
+/// move, so the tree is not trivially flat.
+#[derive(Clone, Debug)]
+pub struct BenchGameState {
+    /// Number of moves played so far
+    pub depth: usize,
+
+    /// Number of legal actions in every non-terminal state
+    pub branching_factor: usize,
+
+    /// Number of moves after which the game ends
+    pub max_depth: usize,
+
+    /// Player to move
+    pub player: BenchPlayer,
+}
+
+impl BenchGameState {
+    /// Creates the initial state of a game with the given shape
+    pub fn new(branching_factor: usize, max_depth: usize) -> Self {
+        BenchGameState {
+            depth: 0,
+            branching_factor,
+            max_depth,
+            player: BenchPlayer(0),
+        }
+    }
+}
+
+/// Action in a [`BenchGameState`], identified by its index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchAction(pub usize);
+
+impl Action for BenchAction {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Player in a [`BenchGameState`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchPlayer(pub usize);
+
+impl Player for BenchPlayer {}
+
+impl GameState for BenchGameState {
+    type Action = BenchAction;
+    type Player = BenchPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return vec![];
+        }
+        (0..self.branching_factor).map(BenchAction).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut new_state = self.clone();
+        new_state.depth += 1;
+        new_state.player = BenchPlayer((self.player.0 + action.0) % 2);
+        new_state
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.depth >= self.max_depth
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        if self.depth == self.max_depth {
+            if self.player.0 == for_player.0 {
+                0.75
+            } else {
+                0.25
+            }
+        } else {
+            0.5
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.player.clone()
+    }
+}
+
+/// Settings for [`run_profile`]
+#[derive(Debug, Clone)]
+pub struct ProfileConfig {
+    /// Branching factor of the synthetic game
+    pub branching_factor: usize,
+
+    /// Depth of the synthetic game
+    pub max_depth: usize,
+
+    /// Number of independent searches to run
+    pub searches: usize,
+
+    /// Configuration used for every search
+    pub mcts_config: MCTSConfig,
+}
+
+impl ProfileConfig {
+    /// Creates a profile of one 1000-iteration search on a game of the given shape
+    pub fn new(branching_factor: usize, max_depth: usize) -> Self {
+        ProfileConfig {
+            branching_factor,
+            max_depth,
+            searches: 1,
+            mcts_config: MCTSConfig::default().with_max_iterations(1000),
+        }
+    }
+
+    /// Sets the number of independent searches to run
+    pub fn with_searches(mut self, searches: usize) -> Self {
+        self.searches = searches;
+        self
+    }
+
+    /// Sets the configuration used for every search
+    pub fn with_mcts_config(mut self, config: MCTSConfig) -> Self {
+        self.mcts_config = config;
+        self
+    }
+}
+
+/// Statistics from every search in a profile run
+#[derive(Debug, Clone)]
+pub struct ProfileReport {
+    /// Statistics of each search, in the order they ran
+    pub runs: Vec<SearchStatistics>,
+}
+
+impl ProfileReport {
+    /// Returns the total number of iterations across all searches
+    pub fn total_iterations(&self) -> usize {
+        self.runs.iter().map(|run| run.iterations).sum()
+    }
+
+    /// Returns the total time spent searching
+    pub fn total_time(&self) -> Duration {
+        self.runs.iter().map(|run| run.total_time).sum()
+    }
+
+    /// Returns the iteration throughput across all searches
+    pub fn iterations_per_second(&self) -> f64 {
+        let seconds = self.total_time().as_secs_f64();
+        if seconds <= 0.0 {
+            return 0.0;
+        }
+        self.total_iterations() as f64 / seconds
+    }
+}
+
+/// Runs the profiled searches with the default policies
+pub fn run_profile(profile: &ProfileConfig) -> ProfileReport {
+    run_profile_with(profile, |mcts| mcts)
+}
+
+/// Runs the profiled searches, letting `setup` customize each search
+///
+/// `setup` receives a fresh search for every run and returns it with the
+/// policies under test attached.
+///
+/// # Example
+///
+/// ```
+/// use arboriter_mcts::bench::{run_profile_with, ProfileConfig};
+/// use arboriter_mcts::policy::selection::UCB1TunedPolicy;
+///
+/// let report = run_profile_with(&ProfileConfig::new(4, 3), |mcts| {
+///     mcts.with_selection_policy(UCB1TunedPolicy::new(1.0))
+/// });
+/// assert_eq!(report.total_iterations(), 1000);
+/// ```
+pub fn run_profile_with<F>(profile: &ProfileConfig, setup: F) -> ProfileReport
+where
+    F: Fn(MCTS<BenchGameState>) -> MCTS<BenchGameState>,
+{
+    let runs = (0..profile.searches)
+        .map(|_| {
+            let state = BenchGameState::new(profile.branching_factor, profile.max_depth);
+            let mut mcts = setup(MCTS::new(state, profile.mcts_config.clone()));
+            let _ = mcts.search();
+            mcts.get_statistics().clone()
+        })
+        .collect();
+
+    ProfileReport { runs }
+}
@@ -0,0 +1,229 @@
+//! Opening books accumulated from search trees
+//!
+//! The first moves of a game are searched again in every game that starts
+//! from the same position. [`export_records`] walks a search tree and emits
+//! one [`BookRecord`] per well-visited edge, keyed by the state's
+//! [`hash`](GameState::hash) and the action's [`key`](Action::key), and an
+//! [`OpeningBook`] sums records over many games and seeds later searches
+//! with them through [`MCTS::warm_start`].
+//!
+//! Records are only as stable as the keys: `GameState::hash` must give the
+//! same value for the same position in every process that shares the book,
+//! so it should not come from `std`'s `DefaultHasher` if books outlive a
+//! build, and action keys should be built with [`ActionKey::new`]. States
+//! hashing to `0`, the default, are never recorded.
+//!
+//! # Example
+//!
+//! ```
+//! # use arboriter_mcts::{GameState, Action, Player, MCTS, MCTSConfig};
+//! use arboriter_mcts::book::OpeningBook;
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct A(usize);
+//! # impl Action for A { fn id(&self) -> usize { self.0 } }
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct P;
+//! # impl Player for P {}
+//! # #[derive(Clone)]
+//! # struct G(u64);
+//! # impl GameState for G {
+//! #     type Action = A;
+//! #     type Player = P;
+//! #     fn get_legal_actions(&self) -> Vec<A> { if self.0 < 3 { vec![A(0), A(1)] } else { vec![] } }
+//! #     fn apply_action(&self, a: &A) -> Self { G(self.0 + 1 + a.0 as u64) }
+//! #     fn is_terminal(&self) -> bool { self.0 >= 3 }
+//! #     fn get_result(&self, _: &P) -> f64 { if self.0 > 3 { 1.0 } else { 0.0 } }
+//! #     fn get_current_player(&self) -> P { P }
+//! #     fn hash(&self) -> u64 { self.0 + 1 }
+//! # }
+//! let mut book = OpeningBook::new();
+//! let config = MCTSConfig::default().with_max_iterations(200);
+//!
+//! for _game in 0..3 {
+//!     let mut mcts = MCTS::new(G(0), config.clone());
+//!     book.warm_start(&mut mcts, 50).unwrap();
+//!     mcts.search().unwrap();
+//!     book.record(mcts.root(), 20);
+//! }
+//! assert!(book.lookup(&G(0)).len() == 2);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    game_state::{Action, ActionKey, GameState},
+    history::HistoryEntry,
+    mcts::MCTS,
+    tree::MCTSNode,
+    Result,
+};
+
+/// Statistics of one action in one position, as exported from a search tree
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookRecord {
+    /// [`GameState::hash`] of the position the action is played from
+    pub state_hash: u64,
+
+    /// [`Action::key`] of the action
+    pub action: ActionKey,
+
+    /// Visits of the child reached by the action
+    pub visits: u64,
+
+    /// Mean value of the child reached by the action
+    ///
+    /// As [`MCTSNode::value`] reports it, so with
+    /// [`RewardPerspective::PerPlayer`](crate::config::RewardPerspective::PerPlayer)
+    /// it is the result for the player to move in the position.
+    pub value: f64,
+}
+
+/// Returns a record for every edge below `root` whose child has at least `min_visits` visits
+///
+/// Records are listed parent before child. Subtrees below the threshold
+/// are not walked, so a high threshold keeps the export small. Edges from
+/// positions hashing to `0` and from chance nodes to their outcomes are
+/// left out.
+pub fn export_records<S: GameState>(root: &MCTSNode<S>, min_visits: u64) -> Vec<BookRecord> {
+    let min_visits = min_visits.max(1);
+    let mut records = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let state_hash = node.state.hash();
+        let children = node
+            .children
+            .iter()
+            .filter(|child| child.visits() >= min_visits);
+        if !node.is_chance() && state_hash != 0 {
+            records.extend(children.clone().filter_map(|child| {
+                Some(BookRecord {
+                    state_hash,
+                    action: child.action.as_ref()?.key(),
+                    visits: child.visits(),
+                    value: child.value(),
+                })
+            }));
+        }
+        stack.extend(children.rev());
+    }
+    records
+}
+
+/// Action statistics per position, summed over many searches
+///
+/// Each record adds its visits and visits times value to the entry of its
+/// position and action, so an entry's [`value`](HistoryEntry::value) is
+/// the visit-weighted mean over every search that recorded it. Trees reused
+/// from one move to the next share statistics, so record one search per
+/// game, e.g. the first, to avoid counting them twice.
+#[derive(Debug, Clone, Default)]
+pub struct OpeningBook {
+    /// Statistics keyed by state hash and action key
+    entries: HashMap<(u64, ActionKey), HistoryEntry>,
+}
+
+impl OpeningBook {
+    /// Creates an empty book
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restores a book exported with [`records`](Self::records)
+    pub fn from_records(records: impl IntoIterator<Item = BookRecord>) -> Self {
+        let mut book = Self::new();
+        book.add_records(records);
+        book
+    }
+
+    /// Adds records, e.g. from [`export_records`] or another book
+    pub fn add_records(&mut self, records: impl IntoIterator<Item = BookRecord>) {
+        for record in records {
+            let entry = self
+                .entries
+                .entry((record.state_hash, record.action))
+                .or_default();
+            entry.visits += record.visits as f64;
+            entry.total_reward += record.visits as f64 * record.value;
+        }
+    }
+
+    /// Adds the records of every edge below `root` with at least `min_visits` visits
+    pub fn record<S: GameState>(&mut self, root: &MCTSNode<S>, min_visits: u64) {
+        self.add_records(export_records(root, min_visits));
+    }
+
+    /// Returns the book's contents for export, sorted by state hash and action key
+    ///
+    /// Visits are rounded to whole visits.
+    pub fn records(&self) -> Vec<BookRecord> {
+        let mut records: Vec<BookRecord> = self
+            .entries
+            .iter()
+            .map(|(&(state_hash, action), entry)| BookRecord {
+                state_hash,
+                action,
+                visits: entry.visits.round() as u64,
+                value: entry.value(),
+            })
+            .collect();
+        records.sort_by_key(|record| (record.state_hash, record.action));
+        records
+    }
+
+    /// Returns the statistics of the legal actions of `state` that the book knows
+    ///
+    /// Entries come in the order of [`GameState::get_legal_actions`].
+    pub fn lookup<S: GameState>(&self, state: &S) -> Vec<(S::Action, HistoryEntry)> {
+        let state_hash = state.hash();
+        if state_hash == 0 {
+            return Vec::new();
+        }
+        state
+            .get_legal_actions()
+            .into_iter()
+            .filter_map(|action| {
+                let entry = self.entries.get(&(state_hash, action.key()))?;
+                Some((action, *entry))
+            })
+            .collect()
+    }
+
+    /// Returns the number of positions and actions in the book
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing was recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Seeds the root of `mcts` with the book's statistics for its position
+    ///
+    /// Calls [`MCTS::warm_start`] with the average value of each known root
+    /// action and its visits, capped at `max_visits` so the search can still
+    /// overrule the book. Returns the number of root actions seeded.
+    pub fn warm_start<S: GameState + 'static>(
+        &self,
+        mcts: &mut MCTS<S>,
+        max_visits: u64,
+    ) -> Result<usize> {
+        let root = mcts.root();
+        let priors: Vec<(S::Action, f64, u64)> = self
+            .lookup(&root.state)
+            .into_iter()
+            .filter(|(action, _)| {
+                root.find_child(action).is_some() || root.find_unexpanded(action).is_some()
+            })
+            .filter_map(|(action, entry)| {
+                let visits = (entry.visits.round() as u64).min(max_visits);
+                (visits > 0).then(|| (action, entry.value(), visits))
+            })
+            .collect();
+        if priors.is_empty() {
+            return Ok(0);
+        }
+        mcts.warm_start(&priors)?;
+        Ok(priors.len())
+    }
+}
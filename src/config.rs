@@ -5,6 +5,8 @@
 
 use std::time::Duration;
 
+use crate::game_state::GameState;
+
 /// Criteria for selecting the best child after search is complete
 ///
 /// This determines how the final action is selected after the search budget is exhausted.
@@ -23,11 +25,548 @@ pub enum BestChildCriteria {
     ///
     /// This can be more aggressive by favoring high-value moves even if they
     /// haven't been visited as often. May perform better in some domains but
-    /// can be less robust overall.
+    /// can be less robust overall. [`MCTSConfig::with_value_estimate`]
+    /// switches from the mean to the value of each child's best line.
     ///
     /// Use this approach when you want to maximize expected value and are willing
     /// to accept more risk.
     HighestValue,
+
+    /// Select the child with the highest lower confidence bound
+    ///
+    /// Scores each child with `value - c * sqrt(ln(N) / n)`, the mirror image
+    /// of UCB1 using the configured exploration constant `c`. A high value
+    /// only wins if enough visits back it up.
+    ///
+    /// Use this approach when a move must be reliably good rather than
+    /// promising on thin evidence.
+    SecureChild,
+}
+
+/// How the best child is chosen among children that tie under the criteria
+///
+/// Ties are common with [`BestChildCriteria::MostVisits`] in short searches
+/// and with exact values such as solved wins. Whatever the rule, the same
+/// tree always yields the same choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Keep the tied child that was expanded first
+    FirstChild,
+
+    /// Prefer the tied child with the higher mean value, then the one with
+    /// more visits, then the one expanded first
+    ///
+    /// This is the default.
+    #[default]
+    HighestValue,
+
+    /// As [`HighestValue`](Self::HighestValue), then pick among the children
+    /// still tied at random
+    ///
+    /// The draw is seeded, so it is repeatable for a given tree, but unlike
+    /// the other rules it does not favour early expansions.
+    Random(u64),
+}
+
+/// Which value [`BestChildCriteria::HighestValue`] ranks root children by
+///
+/// See [`MCTSConfig::with_value_estimate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValueEstimate {
+    /// The mean of every result backpropagated through the child
+    ///
+    /// This is the default.
+    #[default]
+    Mean,
+
+    /// The value of the child's best line, following the best child at every
+    /// level (see [`MCTSNode::max_value`](crate::tree::MCTSNode::max_value))
+    ///
+    /// Means average in the results of every move tried, so a forced win
+    /// found late in the search can hardly move its root child's mean. The
+    /// max value reports it at once, at the cost of trusting lines backed by
+    /// few visits.
+    Max,
+}
+
+/// How far a new best move must lead the planned one before the engine switches
+///
+/// See [`MCTSConfig::with_move_hysteresis`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveHysteresis {
+    /// Visit lead needed under [`BestChildCriteria::MostVisits`], as a
+    /// fraction of the root's visits
+    pub visit_margin: f64,
+
+    /// Mean value lead needed under the value-based criteria
+    pub value_margin: f64,
+}
+
+impl MoveHysteresis {
+    /// Creates a hysteresis setting
+    ///
+    /// Negative margins are raised to zero.
+    pub fn new(visit_margin: f64, value_margin: f64) -> Self {
+        MoveHysteresis {
+            visit_margin: visit_margin.max(0.0),
+            value_margin: value_margin.max(0.0),
+        }
+    }
+}
+
+/// Reward given to a playout that hit the length cap before the game ended
+///
+/// See [`MCTSConfig::with_max_playout_length`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unfinished {
+    /// Score the playout as a draw (0.5)
+    Draw,
+
+    /// Score the playout with [`GameState::get_result`] on the state where it stopped
+    ///
+    /// Use this when `get_result` gives a meaningful evaluation of
+    /// non-terminal states.
+    Heuristic,
+}
+
+impl Unfinished {
+    /// Returns the reward for a playout that stopped at `state`
+    pub fn score<S: GameState>(&self, state: &S, for_player: &S::Player) -> f64 {
+        match self {
+            Unfinished::Draw => 0.5,
+            Unfinished::Heuristic => state.get_result(for_player),
+        }
+    }
+}
+
+/// Which player simulation results are computed for
+///
+/// See [`MCTSConfig::with_perspective`]. To evaluate for a fixed player of
+/// your choice, use [`MCTS::with_perspective_player`](crate::MCTS::with_perspective_player),
+/// which overrides this setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Perspective {
+    /// The player to move at the state a playout starts from
+    ///
+    /// This is the default.
+    #[default]
+    NodePlayer,
+
+    /// The player to move at the root of the search
+    RootPlayer,
+}
+
+/// Whose point of view each node's statistics are kept from
+///
+/// See [`MCTSConfig::with_reward_perspective`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RewardPerspective {
+    /// Every node receives the same reward
+    ///
+    /// Suited to single-agent problems and cooperative games where all
+    /// players share one reward signal. This is the default.
+    #[default]
+    Shared,
+
+    /// Each node's reward is from the point of view of the player who moved into it
+    ///
+    /// Results are computed for the player to move at the simulated leaf and
+    /// passed unchanged to nodes entered by that player; every other node
+    /// receives `1 - result`. This is the usual two-player zero-sum backup,
+    /// which lets selection maximize a child's value on behalf of whichever
+    /// player is choosing.
+    PerPlayer,
+}
+
+impl RewardPerspective {
+    /// Returns the reward for a node entered by `mover`, given a result for `leaf_player`
+    pub fn reward_for<P: PartialEq>(&self, result: f64, leaf_player: &P, mover: &P) -> f64 {
+        match self {
+            RewardPerspective::PerPlayer if leaf_player != mover => 1.0 - result,
+            _ => result,
+        }
+    }
+}
+
+/// Transformation applied to every simulation result before backpropagation
+///
+/// The built-in policies assume rewards in `[0, 1]`. Domains whose
+/// [`GameState::get_result`] returns raw scores, like stones captured or money
+/// won, can map them into that range here instead of rescaling in every policy.
+/// See [`MCTSConfig::with_reward_transform`].
+#[derive(Debug, Clone, Copy, Default)]
+pub enum RewardTransform {
+    /// Use results as they are
+    #[default]
+    Identity,
+
+    /// Clamp results into `[min, max]`
+    Clamp {
+        /// Smallest reward passed on
+        min: f64,
+        /// Largest reward passed on
+        max: f64,
+    },
+
+    /// Rescale results to `[0, 1]` using the smallest and largest results seen so far
+    ///
+    /// The running range is kept in
+    /// [`SearchStatistics::reward_range`](crate::stats::SearchStatistics::reward_range)
+    /// and carries over between searches on the same tree. Until two distinct
+    /// results have been seen, results map to 0.5.
+    MinMax,
+
+    /// Squash results into `(0, 1)` with `0.5 * (1 + tanh(result / scale))`
+    Tanh {
+        /// Result magnitude that maps to roughly 0.88 (or 0.12 if negative)
+        scale: f64,
+    },
+
+    /// Apply a custom function
+    Custom(fn(f64) -> f64),
+}
+
+impl RewardTransform {
+    /// Transforms a raw result
+    ///
+    /// `range` is the `(min, max)` of the raw results seen so far, including
+    /// this one; only [`RewardTransform::MinMax`] uses it.
+    pub fn apply(&self, result: f64, range: Option<(f64, f64)>) -> f64 {
+        match *self {
+            RewardTransform::Identity => result,
+            RewardTransform::Clamp { min, max } => result.clamp(min, max),
+            RewardTransform::MinMax => match range {
+                Some((min, max)) if max > min => (result - min) / (max - min),
+                _ => 0.5,
+            },
+            RewardTransform::Tanh { scale } => 0.5 * (1.0 + (result / scale).tanh()),
+            RewardTransform::Custom(transform) => transform(result),
+        }
+    }
+}
+
+/// Progressive widening schedule: a node visited `n` times may have up to
+/// `ceil(coefficient * n^exponent)` children, and always at least one
+///
+/// See [`MCTSConfig::with_progressive_widening`] and
+/// [`MCTSConfig::with_outcome_widening`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressiveWidening {
+    /// Scale of the child limit
+    pub coefficient: f64,
+
+    /// How fast the limit grows with visits, usually between 0.25 and 0.5
+    pub exponent: f64,
+}
+
+impl ProgressiveWidening {
+    /// Creates a widening schedule
+    pub fn new(coefficient: f64, exponent: f64) -> Self {
+        ProgressiveWidening {
+            coefficient,
+            exponent,
+        }
+    }
+
+    /// Returns how many children a node with `visits` visits may have
+    pub fn max_children(&self, visits: u64) -> usize {
+        let limit = (self.coefficient * (visits as f64).powf(self.exponent)).ceil();
+        if limit >= 1.0 {
+            limit as usize
+        } else {
+            1
+        }
+    }
+}
+
+/// Shallow pre-search that orders and prunes root actions
+///
+/// See [`MCTSConfig::with_pre_search`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreSearch {
+    /// Share of the iteration budget spent on the pre-search, in `[0, 1]`
+    pub fraction: f64,
+
+    /// How far below the best root action's pre-search value an action may
+    /// fall before it is pruned
+    pub prune_margin: f64,
+}
+
+impl PreSearch {
+    /// Creates a pre-search setting
+    ///
+    /// `fraction` is clamped to `[0, 1]` and `prune_margin` to at least zero.
+    pub fn new(fraction: f64, prune_margin: f64) -> Self {
+        PreSearch {
+            fraction: fraction.clamp(0.0, 1.0),
+            prune_margin: prune_margin.max(0.0),
+        }
+    }
+
+    /// Returns how many playouts each of `actions` root actions gets out of `budget` iterations
+    ///
+    /// Zero means the share is too small to try every action once.
+    pub fn playouts_per_action(&self, budget: usize, actions: usize) -> usize {
+        if actions == 0 {
+            return 0;
+        }
+        (self.fraction * budget as f64 / actions as f64).floor() as usize
+    }
+}
+
+/// Root action filter by prior probability
+///
+/// See [`MCTSConfig::with_root_prior_filter`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootPriorFilter {
+    /// Prior below which a root action is dropped
+    pub min_prior: f64,
+
+    /// Number of root actions with the highest priors that are always kept
+    pub keep_at_least: usize,
+}
+
+impl RootPriorFilter {
+    /// Creates a root prior filter
+    ///
+    /// `keep_at_least` is raised to 1, so the root never loses every action.
+    pub fn new(min_prior: f64, keep_at_least: usize) -> Self {
+        RootPriorFilter {
+            min_prior,
+            keep_at_least: keep_at_least.max(1),
+        }
+    }
+}
+
+/// Gumbel root action selection with sequential halving
+///
+/// See [`MCTSConfig::with_gumbel_root`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GumbelRoot {
+    /// Number of root actions sampled with Gumbel-Top-k and searched
+    pub considered: usize,
+
+    /// Visit offset in the scaling of completed values, `c_visit` in the paper
+    pub c_visit: f64,
+
+    /// Scale of completed values, `c_scale` in the paper
+    pub c_scale: f64,
+}
+
+impl GumbelRoot {
+    /// Creates Gumbel root settings considering `considered` actions
+    ///
+    /// `considered` is raised to 1. The value scaling uses `c_visit = 50`
+    /// and `c_scale = 1`, as in the paper.
+    pub fn new(considered: usize) -> Self {
+        GumbelRoot {
+            considered: considered.max(1),
+            c_visit: 50.0,
+            c_scale: 1.0,
+        }
+    }
+
+    /// Scales a completed value for adding to Gumbel noise and log prior
+    ///
+    /// `max_visits` is the largest visit count of any root child.
+    pub fn sigma(&self, value: f64, max_visits: u64) -> f64 {
+        (self.c_visit + max_visits as f64) * self.c_scale * value
+    }
+}
+
+/// Which nodes on the route to a pending playout carry its virtual loss
+///
+/// See [`VirtualLoss`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VirtualLossMode {
+    /// Every node on the route, the root included
+    ///
+    /// The parents' visit counts grow along with their children's, so the
+    /// exploration term of the other children rises too. This is the default.
+    #[default]
+    PerNode,
+
+    /// Every edge on the route, i.e. every node below the root
+    ///
+    /// A node's statistics are those of the move leading to it, so this
+    /// only makes the moves already taken look worse and leaves the root's
+    /// visit count alone.
+    PerEdge,
+}
+
+/// Virtual loss applied while the leaves of a rollout batch are selected
+///
+/// A virtual loss is a visit without reward, added to the route to each
+/// selected leaf so the next selection is steered elsewhere, and removed
+/// again before the leaf's real result is backpropagated. Domains with many
+/// equally good moves cope with a small loss; domains where one line
+/// dominates need a larger one to spread a batch out. See
+/// [`MCTSConfig::with_virtual_loss`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VirtualLoss {
+    /// Visits without reward added per pending playout; zero disables virtual loss
+    pub magnitude: u64,
+
+    /// Which nodes on the route receive the loss
+    pub mode: VirtualLossMode,
+
+    /// Whether each route's loss stays until its own result is backpropagated
+    ///
+    /// By default all losses of a batch are removed as soon as its playouts
+    /// return. When enabled, the results are backpropagated while the losses
+    /// of the leaves after them are still in place.
+    pub revert_on_backpropagation: bool,
+}
+
+impl VirtualLoss {
+    /// Creates a per-node virtual loss of `magnitude` visits
+    pub fn new(magnitude: u64) -> Self {
+        VirtualLoss {
+            magnitude,
+            mode: VirtualLossMode::PerNode,
+            revert_on_backpropagation: false,
+        }
+    }
+
+    /// Sets which nodes on the route receive the loss
+    pub fn with_mode(mut self, mode: VirtualLossMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets whether each route's loss is only removed by its own backpropagation
+    pub fn with_revert_on_backpropagation(mut self, enabled: bool) -> Self {
+        self.revert_on_backpropagation = enabled;
+        self
+    }
+}
+
+impl Default for VirtualLoss {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+/// Periodic pruning of stale subtrees in a reused tree
+///
+/// Long analysis sessions that keep searching the same root grow the tree
+/// without bound. Every `interval` the search collapses each node that has
+/// gone unselected for longer than `max_idle`, or that holds less than
+/// `min_visit_fraction` of the root's visits, into a leaf: its descendants
+/// are returned to the node pool, if there is one, and the actions of its
+/// children become untried again. The node keeps its own statistics, so its
+/// parent's choice between it and its siblings is unchanged. The root is
+/// never collapsed. See [`MCTSConfig::with_subtree_gc`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubtreeGc {
+    /// Time between collections
+    pub interval: Duration,
+
+    /// Collapse nodes not selected through for longer than this
+    pub max_idle: Option<Duration>,
+
+    /// Collapse nodes with fewer than this fraction of the root's visits
+    pub min_visit_fraction: Option<f64>,
+}
+
+impl SubtreeGc {
+    /// Creates a collector that runs every `interval` and prunes nothing
+    /// until a criterion is set
+    pub fn new(interval: Duration) -> Self {
+        SubtreeGc {
+            interval,
+            max_idle: None,
+            min_visit_fraction: None,
+        }
+    }
+
+    /// Collapses nodes not selected through for longer than `max_idle`
+    pub fn with_max_idle(mut self, max_idle: Duration) -> Self {
+        self.max_idle = Some(max_idle);
+        self
+    }
+
+    /// Collapses nodes with fewer than `fraction` of the root's visits
+    pub fn with_min_visit_fraction(mut self, fraction: f64) -> Self {
+        self.min_visit_fraction = Some(fraction);
+        self
+    }
+}
+
+/// How much a search may spend before it returns
+///
+/// A view of [`MCTSConfig::max_iterations`] and [`MCTSConfig::max_time`]:
+/// see [`MCTSConfig::budget`] and [`MCTSConfig::with_budget`]. Whichever
+/// bound is reached first ends the search, and
+/// [`SearchStatistics::outcome`](crate::SearchStatistics::outcome) tells
+/// which one it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    /// A fixed number of iterations
+    Iterations(usize),
+
+    /// A wall-clock duration, however many iterations fit
+    Time(Duration),
+
+    /// Whichever of the iteration count and the duration runs out first
+    Both(usize, Duration),
+
+    /// No bound at all
+    ///
+    /// The search runs until it is stopped through a
+    /// [`StopToken`](crate::mcts::StopToken), or the tree is exhausted with
+    /// [`stop_when_exhausted`](MCTSConfig::stop_when_exhausted) enabled.
+    Unlimited,
+}
+
+impl Budget {
+    /// Builds a budget from optional iteration and time limits
+    pub fn from_limits(iterations: Option<usize>, time: Option<Duration>) -> Self {
+        match (iterations, time) {
+            (Some(iterations), Some(time)) => Budget::Both(iterations, time),
+            (Some(iterations), None) => Budget::Iterations(iterations),
+            (None, Some(time)) => Budget::Time(time),
+            (None, None) => Budget::Unlimited,
+        }
+    }
+
+    /// Returns the iteration limit, if any
+    pub fn iterations(&self) -> Option<usize> {
+        match self {
+            Budget::Iterations(iterations) | Budget::Both(iterations, _) => Some(*iterations),
+            Budget::Time(_) | Budget::Unlimited => None,
+        }
+    }
+
+    /// Returns the time limit, if any
+    pub fn time(&self) -> Option<Duration> {
+        match self {
+            Budget::Time(time) | Budget::Both(_, time) => Some(*time),
+            Budget::Iterations(_) | Budget::Unlimited => None,
+        }
+    }
+}
+
+/// Limits applied to every playout, derived from [`MCTSConfig`]
+///
+/// Passed to [`SimulationPolicy::simulate_limited`](crate::SimulationPolicy::simulate_limited).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayoutLimits {
+    /// Maximum number of moves per playout, if capped
+    pub max_length: Option<usize>,
+
+    /// How a playout cut off by `max_length` is scored
+    pub unfinished: Unfinished,
+
+    /// Reward for a playout that revisits a state, if cycle detection is on
+    pub cycle_reward: Option<f64>,
+}
+
+impl PlayoutLimits {
+    /// Returns true if no limit is active
+    pub fn is_unlimited(&self) -> bool {
+        self.max_length.is_none() && self.cycle_reward.is_none()
+    }
 }
 
 /// Configuration for the MCTS algorithm
@@ -60,7 +599,8 @@ pub struct MCTSConfig {
     /// Maximum number of iterations to run
     ///
     /// The search will stop after this many iterations, even if there's
-    /// still time available.
+    /// still time available. Zero means no iteration limit, leaving
+    /// [`max_time`](Self::max_time) as the only bound; see [`Budget`].
     pub max_iterations: usize,
 
     /// Maximum time to run the search
@@ -69,22 +609,161 @@ pub struct MCTSConfig {
     /// maximum iterations haven't been reached.
     pub max_time: Option<Duration>,
 
+    /// Number of iterations between checks of [`max_time`](Self::max_time)
+    ///
+    /// Defaults to 1, checking the clock before every iteration. Larger
+    /// values save the clock reads when iterations are very fast, at the cost
+    /// of overshooting the deadline by up to that many iterations.
+    pub time_check_interval: usize,
+
     /// Maximum depth to search
     ///
     /// If set, the tree will not be expanded beyond this depth.
     pub max_depth: Option<usize>,
 
+    /// Maximum number of children of any node
+    ///
+    /// Once a node has this many children, its remaining actions are dropped
+    /// and never expanded. Which actions make the cut is up to the
+    /// [`ExpansionPolicy`](crate::policy::expansion::ExpansionPolicy); use
+    /// [`HeuristicExpansionPolicy`](crate::policy::expansion::HeuristicExpansionPolicy)
+    /// to keep the most promising ones. `None` (the default) expands every
+    /// legal action.
+    pub max_children: Option<usize>,
+
+    /// Visits every root child gets before selection may pass it over
+    ///
+    /// While a root child has fewer visits than this, the next iteration
+    /// descends into the least visited such child instead of the one the
+    /// selection policy prefers. This keeps very short searches from
+    /// settling on a leader before every alternative had a few looks, so a
+    /// reply that refutes the leader is not missed for lack of visits, and
+    /// children created without visits, e.g. for a
+    /// [root constraint](crate::MCTS::add_root_constraint), are not decided
+    /// on blindly. The floor never
+    /// holds up expansion: it applies to the children the root has once it
+    /// cannot grow any further for now. Zero (the default) disables it.
+    pub root_exploration_floor: u64,
+
+    /// Estimated tree size, in bytes, at which the search stops expanding
+    ///
+    /// The estimate counts every node in the tree at the size of an
+    /// [`MCTSNode`](crate::tree::MCTSNode) plus an action list as long as the
+    /// root's. Once it reaches the limit, selection still descends through
+    /// the existing nodes and playouts still run from their leaves, but no
+    /// new node is added, and
+    /// [`SearchStatistics::memory_limited`](crate::stats::SearchStatistics::memory_limited)
+    /// is set. `None` (the default) lets the tree grow without bound.
+    pub max_memory_bytes: Option<usize>,
+
+    /// Periodic pruning of stale subtrees, for long searches on one root
+    ///
+    /// See [`SubtreeGc`]. Ignored in [DAG mode](Self::dag_mode), whose
+    /// transposition links point into the tree. `None` (the default) never
+    /// prunes.
+    pub subtree_gc: Option<SubtreeGc>,
+
+    /// Progressive widening of actions, if enabled
+    ///
+    /// A node only gets a new child once it has been visited often enough
+    /// for the schedule to allow one; until then selection descends into its
+    /// existing children. This keeps the tree deep in domains with more
+    /// actions than the budget could ever try. `None` (the default) expands
+    /// every action before descending.
+    pub action_widening: Option<ProgressiveWidening>,
+
+    /// Progressive widening of chance outcomes, if enabled
+    ///
+    /// Applies to chance nodes whose outcomes are drawn one at a time with
+    /// [`GameState::sample_chance_outcome`] instead of listed up front, e.g.
+    /// continuous noise. Such a node draws a new outcome only while the
+    /// schedule allows another child, and otherwise revisits an existing one
+    /// in proportion to its visits. Together with
+    /// [`action_widening`](Self::action_widening) this is double progressive
+    /// widening. `None` (the default) draws a fresh outcome on every visit.
+    pub outcome_widening: Option<ProgressiveWidening>,
+
+    /// Number of terminal states whose results are cached, zero to disable
+    ///
+    /// With a cache, terminal leaves of the tree are scored with
+    /// [`GameState::get_result`] for the player to move, bypassing the
+    /// simulation policy, and the result is remembered by the state's
+    /// [`hash`](GameState::hash) for the rest of the search and later
+    /// searches on the same [`MCTS`](crate::MCTS). States hashing to `0` are
+    /// scored every time. See [`ResultCache`](crate::result_cache::ResultCache).
+    pub result_cache_size: usize,
+
+    /// Extra plies expanded below each newly expanded node
+    ///
+    /// With greedy deepening, an iteration that creates a node keeps going:
+    /// it expands a child of the new node, then a child of that one, for up
+    /// to this many further plies, before the playout starts from the
+    /// deepest new node. Each step expands the action the
+    /// [`ExpansionPolicy`](crate::policy::expansion::ExpansionPolicy) picks,
+    /// so pair it with a heuristic policy to follow the most promising line.
+    /// Deepening stops early at terminal, solved or repeated states. Zero
+    /// (the default) expands one node per iteration.
+    pub greedy_depth: usize,
+
+    /// Shallow pre-search of the root actions, if enabled
+    ///
+    /// Before a search from a fresh root, a share of the budget is spread
+    /// evenly over the root actions as playouts. Their results seed the root
+    /// children's statistics and priors, and actions that fall too far behind
+    /// the best one are pruned for the rest of the search. `None` (the
+    /// default) starts the search directly.
+    pub pre_search: Option<PreSearch>,
+
+    /// Filter dropping root actions with low priors, if enabled
+    ///
+    /// Priors come from the expansion policy's
+    /// [`action_priors`](crate::policy::expansion::ExpansionPolicy::action_priors).
+    /// `None` (the default) keeps every root action.
+    pub root_prior_filter: Option<RootPriorFilter>,
+
+    /// Whether nodes track the range of their children's values for normalization
+    ///
+    /// See [`with_value_normalization`](Self::with_value_normalization).
+    /// Disabled by default.
+    pub normalize_values: bool,
+
+    /// Gumbel root action selection, if enabled
+    ///
+    /// `None` (the default) leaves the root to the selection policy and
+    /// picks the final move by [`best_child_criteria`](Self::best_child_criteria).
+    pub gumbel_root: Option<GumbelRoot>,
+
     /// Whether to use transposition tables
     ///
     /// Transposition tables allow reusing evaluations for states that
     /// can be reached through different sequences of moves.
     pub use_transpositions: bool,
 
+    /// Whether to search a DAG instead of a tree
+    ///
+    /// When enabled, expanding into a state whose [`GameState::hash`](crate::GameState::hash)
+    /// matches an existing node creates a link to that node instead of a fresh
+    /// subtree, so transposed positions share one set of statistics.
+    /// Backpropagation updates only the nodes on the path actually traversed.
+    /// States hashing to `0` are never merged.
+    pub dag_mode: bool,
+
     /// Criteria for selecting the best child after search
     ///
     /// Determines how the final action is selected once the search is complete.
     pub best_child_criteria: BestChildCriteria,
 
+    /// Which value [`BestChildCriteria::HighestValue`] ranks children by
+    pub value_estimate: ValueEstimate,
+
+    /// How ties under [`best_child_criteria`](Self::best_child_criteria) are broken
+    pub tie_break: TieBreak,
+
+    /// Hysteresis keeping the move planned on the previous turn, if enabled
+    ///
+    /// `None` (the default) always plays the best move of the current search.
+    pub move_hysteresis: Option<MoveHysteresis>,
+
     /// Node pool initial size
     ///
     /// If set (non-zero), enables the node pool allocator with this initial capacity.
@@ -92,6 +771,162 @@ pub struct MCTSConfig {
     /// If set (non-zero), enables the node pool allocator with this initial capacity.
     /// Node pooling can significantly improve performance by reducing allocation overhead.
     pub node_pool_size: usize,
+
+    /// Largest number of free nodes the node pool keeps
+    ///
+    /// Nodes recycled beyond this are dropped, so recycling a huge tree does
+    /// not pin its memory for the rest of the search's life. `None` (the
+    /// default) keeps every recycled node.
+    pub node_pool_max_free: Option<usize>,
+
+    /// Whether to cache per-node selection statistics
+    ///
+    /// When enabled, each node keeps a plain `(visits, value)` snapshot that is
+    /// refreshed during backpropagation, so selection avoids atomic loads and
+    /// fixed-point conversion on every traversal. Only worthwhile for
+    /// single-threaded searches where nothing else updates the atomics.
+    pub cache_selection_stats: bool,
+
+    /// Number of leaves selected per batch of playouts
+    ///
+    /// With a batch size above 1, each step of the search selects up to this
+    /// many distinct leaves, adding a [virtual loss](Self::virtual_loss) to
+    /// the route to each leaf before selecting the next, so later picks are
+    /// steered away from earlier ones. The batch's playouts then run
+    /// together, in parallel with the `parallel` feature, and are
+    /// backpropagated one by one. The tree itself is only touched by the
+    /// searching thread. Every leaf counts as one iteration. Defaults to 1,
+    /// the plain one-playout-per-iteration search.
+    pub rollout_batch_size: usize,
+
+    /// Virtual loss applied while a rollout batch is selected
+    ///
+    /// Only used with a [`rollout_batch_size`](Self::rollout_batch_size)
+    /// above 1. Defaults to one lost visit per node on the route.
+    pub virtual_loss: VirtualLoss,
+
+    /// Seed that makes the search reproducible
+    ///
+    /// When set, the search's own draws and every iteration's expansion and
+    /// playout use random generators seeded from it and the iteration
+    /// number, so a search with the same seed, configuration and batch size
+    /// returns the same tree on every run, however many threads play out a
+    /// batch. Batches are then backpropagated one by one, in selection order.
+    /// Policies and games must draw from [`search_rng`](crate::utils::search_rng)
+    /// instead of `thread_rng` for their moves to be covered. Time limits
+    /// still end a search after a varying number of iterations. Defaults to
+    /// `None`, seeding from the operating system.
+    pub seed: Option<u64>,
+
+    /// Whether to stop searching once the whole tree has been expanded
+    ///
+    /// Exhaustion is always detected and reported in the statistics. When this
+    /// is enabled the search also stops at that point instead of spending the
+    /// rest of its budget revisiting terminal states. Disabled by default
+    /// because visit-count based move selection benefits from those extra
+    /// visits in small games.
+    pub stop_when_exhausted: bool,
+
+    /// Whether to revalidate the game domain while searching
+    ///
+    /// When enabled, legal actions are re-queried on every expansion and child
+    /// indices returned by policies are checked, so nondeterministic
+    /// `get_legal_actions()` implementations or misbehaving policies produce
+    /// `MCTSError::DomainInconsistency` instead of a panic or a stuck search.
+    /// NaN or infinite simulation results fail the search the same way
+    /// instead of being dropped, and so does a selection score that is NaN,
+    /// with the offending child's statistics in the message; debug builds
+    /// panic on such scores even without strict mode. This costs an extra
+    /// `get_legal_actions()` call per expansion and a second scoring pass
+    /// per selection step.
+    pub strict_mode: bool,
+
+    /// Whether to drop legal actions that repeat an earlier action's key
+    ///
+    /// A legal-move generator that lists an action twice otherwise gets two
+    /// children that split its statistics. Each node's actions are checked
+    /// before its first expansion, by [`Action::key`](crate::Action::key);
+    /// dropped actions are counted in
+    /// [`SearchStatistics::duplicate_actions`](crate::SearchStatistics::duplicate_actions).
+    /// Disabled by default.
+    pub deduplicate_actions: bool,
+
+    /// How often to send an [`IterationEvent`](crate::stats::IterationEvent)
+    ///
+    /// An event is sent every `event_interval` iterations to the receiver
+    /// returned by [`MCTS::iteration_events`](crate::MCTS::iteration_events).
+    /// Zero (the default) disables events.
+    pub event_interval: usize,
+
+    /// Maximum number of moves played in a single playout
+    ///
+    /// Playouts that reach the cap stop and are scored according to
+    /// [`unfinished_playout`](Self::unfinished_playout). This protects loopy
+    /// state spaces, e.g. puzzles with reversible moves, from rollouts that
+    /// never terminate. `None` (the default) plays until the game ends.
+    pub max_playout_length: Option<usize>,
+
+    /// How a playout cut off by [`max_playout_length`](Self::max_playout_length) is scored
+    pub unfinished_playout: Unfinished,
+
+    /// Reward for reaching a repeated state, if cycle detection is enabled
+    ///
+    /// When set, playouts remember the [`GameState::hash`] of every state they
+    /// visit and stop with this reward as soon as one repeats. Selection does
+    /// the same along the path from the root: a node whose state repeats an
+    /// ancestor's is not expanded further and is scored with this reward.
+    /// States hashing to `0` are never treated as repeats.
+    pub cycle_reward: Option<f64>,
+
+    /// Reward for a non-terminal state without legal actions, if tolerated
+    ///
+    /// Such a dead end can never be expanded. When set, the search treats it
+    /// as terminal and scores it with this reward, for the same player a
+    /// terminal result would be for. When `None` (the default), reaching one
+    /// fails the search with `MCTSError::DomainInconsistency`.
+    pub dead_end_reward: Option<f64>,
+
+    /// Range simulation results are clipped to before any transform, if any
+    ///
+    /// Guards node statistics against a `get_result` that returns huge or
+    /// infinite values. Clipped results are counted in
+    /// [`SearchStatistics::clipped_rewards`](crate::SearchStatistics::clipped_rewards).
+    /// NaN is never clipped: like infinite results left unclipped, it is
+    /// dropped and counted in
+    /// [`dropped_rewards`](crate::SearchStatistics::dropped_rewards), or fails
+    /// the search in [`strict_mode`](Self::strict_mode). A dropped result
+    /// still counts as a visit, without reward, so the search does not keep
+    /// returning to the leaf that produced it.
+    pub reward_clip: Option<(f64, f64)>,
+
+    /// Transformation applied to simulation results before backpropagation
+    ///
+    /// Defaults to [`RewardTransform::Identity`]. Cycle rewards pass through
+    /// the transform too, so give them in the same units as `get_result`.
+    pub reward_transform: RewardTransform,
+
+    /// Which player simulation results are computed for
+    ///
+    /// Defaults to [`Perspective::NodePlayer`]. Together with
+    /// [`reward_perspective`](Self::reward_perspective) this decides what
+    /// each node's value means: with [`RewardPerspective::Shared`] every node
+    /// holds the result for this player.
+    pub perspective: Perspective,
+
+    /// Whose point of view node statistics are kept from
+    ///
+    /// Defaults to [`RewardPerspective::Shared`]. Use
+    /// [`RewardPerspective::PerPlayer`] for competitive two-player games
+    /// instead of flipping results in `get_result`.
+    pub reward_perspective: RewardPerspective,
+
+    /// Whether nodes back up expectimax values instead of averages
+    ///
+    /// Chance nodes take the expectation over their outcomes and every other
+    /// node the value of its best child, for single-player domains with
+    /// chance events such as solitaire or 2048. Requires
+    /// [`RewardPerspective::Shared`]. Defaults to false.
+    pub expectimax: bool,
 }
 
 impl Default for MCTSConfig {
@@ -100,15 +935,63 @@ impl Default for MCTSConfig {
             exploration_constant: 1.414, // sqrt(2)
             max_iterations: 10_000,
             max_time: None,
+            time_check_interval: 1,
             max_depth: None,
+            max_children: None,
+            root_exploration_floor: 0,
+            max_memory_bytes: None,
+            subtree_gc: None,
+            action_widening: None,
+            outcome_widening: None,
+            result_cache_size: 0,
+            greedy_depth: 0,
+            pre_search: None,
+            root_prior_filter: None,
+            gumbel_root: None,
+            normalize_values: false,
             use_transpositions: false,
+            dag_mode: false,
             best_child_criteria: BestChildCriteria::MostVisits,
+            value_estimate: ValueEstimate::default(),
+            tie_break: TieBreak::default(),
+            move_hysteresis: None,
             node_pool_size: 0, // Disabled by default
+            node_pool_max_free: None,
+            cache_selection_stats: false,
+            rollout_batch_size: 1,
+            virtual_loss: VirtualLoss::default(),
+            seed: None,
+            stop_when_exhausted: false,
+            strict_mode: false,
+            deduplicate_actions: false,
+            event_interval: 0,
+            max_playout_length: None,
+            unfinished_playout: Unfinished::Draw,
+            cycle_reward: None,
+            dead_end_reward: None,
+            reward_clip: None,
+            reward_transform: RewardTransform::Identity,
+            perspective: Perspective::NodePlayer,
+            reward_perspective: RewardPerspective::Shared,
+            expectimax: false,
         }
     }
 }
 
 impl MCTSConfig {
+    /// Returns a fingerprint that tells configurations apart in logs
+    ///
+    /// Equal configurations give equal fingerprints within one build. The
+    /// value is not stable across crate or compiler versions, and a
+    /// [`RewardTransform::Custom`] function makes it vary between runs.
+    pub fn fingerprint(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        format!("{:?}", self).hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Sets the exploration constant
     pub fn with_exploration_constant(mut self, constant: f64) -> Self {
         self.exploration_constant = constant;
@@ -116,34 +999,268 @@ impl MCTSConfig {
     }
 
     /// Sets the maximum number of iterations
+    ///
+    /// Zero removes the iteration limit.
     pub fn with_max_iterations(mut self, iterations: usize) -> Self {
         self.max_iterations = iterations;
         self
     }
 
+    /// Sets the iteration and time limits together
+    ///
+    /// Replaces both [`max_iterations`](Self::max_iterations) and
+    /// [`max_time`](Self::max_time). As there, an iteration count of zero
+    /// means no iteration limit.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.max_iterations = budget.iterations().unwrap_or(0);
+        self.max_time = budget.time();
+        self
+    }
+
+    /// Returns the budget a search with this configuration runs on
+    ///
+    /// An iteration count of zero or `usize::MAX` counts as no limit.
+    pub fn budget(&self) -> Budget {
+        let iterations = Some(self.max_iterations).filter(|&n| n != 0 && n != usize::MAX);
+        Budget::from_limits(iterations, self.max_time)
+    }
+
     /// Sets the maximum time to run the search
     pub fn with_max_time(mut self, duration: Duration) -> Self {
         self.max_time = Some(duration);
         self
     }
 
+    /// Sets how many iterations run between checks of the time limit
+    ///
+    /// Use 1 (the default) for strict deadlines. Zero is treated as 1.
+    pub fn with_time_check_interval(mut self, iterations: usize) -> Self {
+        self.time_check_interval = iterations.max(1);
+        self
+    }
+
+    /// Sets how many leaves are selected and played out per batch
+    ///
+    /// See [`rollout_batch_size`](Self::rollout_batch_size). Zero is treated
+    /// as 1.
+    pub fn with_rollout_batch_size(mut self, size: usize) -> Self {
+        self.rollout_batch_size = size.max(1);
+        self
+    }
+
+    /// Sets the virtual loss applied while a rollout batch is selected
+    ///
+    /// See [`VirtualLoss`].
+    pub fn with_virtual_loss(mut self, virtual_loss: VirtualLoss) -> Self {
+        self.virtual_loss = virtual_loss;
+        self
+    }
+
+    /// Sets the seed that makes the search reproducible
+    ///
+    /// See [`seed`](Self::seed).
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     /// Sets the maximum depth to search
     pub fn with_max_depth(mut self, depth: usize) -> Self {
         self.max_depth = Some(depth);
         self
     }
 
+    /// Caps the number of children of any node
+    ///
+    /// The cap is at least one child, so every non-terminal node can still be
+    /// searched through.
+    pub fn with_max_children(mut self, max_children: usize) -> Self {
+        self.max_children = Some(max_children.max(1));
+        self
+    }
+
+    /// Sets the visits every root child gets before selection may pass it over
+    ///
+    /// See [`root_exploration_floor`](Self::root_exploration_floor).
+    pub fn with_root_exploration_floor(mut self, min_visits: u64) -> Self {
+        self.root_exploration_floor = min_visits;
+        self
+    }
+
+    /// Stops expanding the tree once its estimated size reaches `bytes`
+    ///
+    /// See [`max_memory_bytes`](Self::max_memory_bytes).
+    pub fn with_max_memory_bytes(mut self, bytes: usize) -> Self {
+        self.max_memory_bytes = Some(bytes);
+        self
+    }
+
+    /// Periodically prunes stale subtrees during long searches
+    ///
+    /// See [`SubtreeGc`].
+    pub fn with_subtree_gc(mut self, gc: SubtreeGc) -> Self {
+        self.subtree_gc = Some(gc);
+        self
+    }
+
+    /// Enables progressive widening of actions
+    ///
+    /// A node visited `n` times may have up to `ceil(coefficient * n^exponent)`
+    /// children. Pair it with an expansion policy that tries the most
+    /// promising actions first.
+    pub fn with_progressive_widening(mut self, coefficient: f64, exponent: f64) -> Self {
+        self.action_widening = Some(ProgressiveWidening::new(coefficient, exponent));
+        self
+    }
+
+    /// Caches the results of up to `entries` terminal states
+    ///
+    /// Worthwhile in small games where many iterations end in the same
+    /// terminal states and `get_result` is not trivial. Requires
+    /// [`GameState::hash`].
+    pub fn with_result_cache(mut self, entries: usize) -> Self {
+        self.result_cache_size = entries;
+        self
+    }
+
+    /// Enables greedy deepening by up to `plies` extra expansions per iteration
+    ///
+    /// Suits domains with a low branching factor, where a deeper tree per
+    /// iteration pays off more than a wider one.
+    pub fn with_greedy_deepening(mut self, plies: usize) -> Self {
+        self.greedy_depth = plies;
+        self
+    }
+
+    /// Enables a shallow pre-search that orders and prunes root actions
+    ///
+    /// `fraction` of the iteration budget is spread evenly over the root
+    /// actions as playouts before the main search, which runs with the rest.
+    /// Each root child starts with its pre-search visits and mean value, and
+    /// a prior proportional to that value. Actions whose value is more than
+    /// `prune_margin` below the best are pruned, except those with a
+    /// [root constraint](crate::MCTS::add_root_constraint). Use a margin of
+    /// 1.0 or more to only order actions. The pre-search is skipped when the
+    /// root already has statistics or the share cannot pay for one playout
+    /// per action.
+    pub fn with_pre_search(mut self, fraction: f64, prune_margin: f64) -> Self {
+        self.pre_search = Some(PreSearch::new(fraction, prune_margin));
+        self
+    }
+
+    /// Drops root actions whose prior is below `min_prior`
+    ///
+    /// Before each search, the expansion policy's
+    /// [`action_priors`](crate::policy::expansion::ExpansionPolicy::action_priors)
+    /// are read for the root's unexpanded actions. Actions below `min_prior`
+    /// are removed for good, except the `keep_at_least` actions with the
+    /// highest priors and those with a
+    /// [root constraint](crate::MCTS::add_root_constraint). Root children
+    /// that were already expanded are kept, and nothing is dropped when the
+    /// policy does not provide priors. The number of dropped actions is
+    /// counted in [`SearchStatistics::filtered_root_actions`](crate::SearchStatistics::filtered_root_actions).
+    pub fn with_root_prior_filter(mut self, min_prior: f64, keep_at_least: usize) -> Self {
+        self.root_prior_filter = Some(RootPriorFilter::new(min_prior, keep_at_least));
+        self
+    }
+
+    /// Enables min-max normalization of values in PUCT selection
+    ///
+    /// During every backpropagation, each node on the route widens its
+    /// [value bounds](crate::MCTSNode::value_bounds) with the new value of
+    /// its child on the route, so the bounds span the values its children
+    /// have had. [`PUCTPolicy`](crate::policy::selection::PUCTPolicy) then
+    /// maps child values into `[0, 1]` with the parent's bounds, so its
+    /// exploration constant keeps its meaning when rewards are raw scores,
+    /// and completes the values of unvisited children with the mean of
+    /// their visited siblings instead of always trying them first.
+    pub fn with_value_normalization(mut self, enabled: bool) -> Self {
+        self.normalize_values = enabled;
+        self
+    }
+
+    /// Drives the root with Gumbel sampling and sequential halving
+    ///
+    /// Gumbel AlphaZero's root search, suited to budgets of a few dozen
+    /// simulations where visiting every root action is too expensive. At the
+    /// start of each search, `considered` root actions are sampled without
+    /// replacement by Gumbel noise plus log prior (Gumbel-Top-k) and
+    /// expanded. The iteration budget is then split into sequential halving
+    /// phases: in each, every remaining candidate gets an equal share of
+    /// visits, and afterwards the better half survives, ranked by noise plus
+    /// log prior plus the scaled completed value ([`GumbelRoot::sigma`]).
+    /// The search returns the best candidate left under the same ranking,
+    /// instead of using [`best_child_criteria`](Self::best_child_criteria).
+    ///
+    /// Priors come from the expansion policy's
+    /// [`action_priors`](crate::policy::expansion::ExpansionPolicy::action_priors),
+    /// or are those of existing children, or uniform. Below the root the
+    /// selection policy is used as usual. Searches need an iteration budget;
+    /// a search with only a time limit fails with
+    /// `MCTSError::InvalidConfiguration`.
+    pub fn with_gumbel_root(mut self, considered: usize) -> Self {
+        self.gumbel_root = Some(GumbelRoot::new(considered));
+        self
+    }
+
+    /// Enables progressive widening of sampled chance outcomes
+    ///
+    /// A chance node visited `n` times may draw up to
+    /// `ceil(coefficient * n^exponent)` distinct outcomes.
+    pub fn with_outcome_widening(mut self, coefficient: f64, exponent: f64) -> Self {
+        self.outcome_widening = Some(ProgressiveWidening::new(coefficient, exponent));
+        self
+    }
+
     /// Sets whether to use transposition tables
     pub fn with_transpositions(mut self, use_transpositions: bool) -> Self {
         self.use_transpositions = use_transpositions;
         self
     }
 
+    /// Sets whether to merge transposed states into a DAG
+    pub fn with_dag_mode(mut self, enabled: bool) -> Self {
+        self.dag_mode = enabled;
+        self
+    }
+
     /// Sets the criteria for selecting the best child
     pub fn with_best_child_criteria(mut self, criteria: BestChildCriteria) -> Self {
         self.best_child_criteria = criteria;
         self
     }
+
+    /// Sets which value [`BestChildCriteria::HighestValue`] ranks children by
+    ///
+    /// Defaults to [`ValueEstimate::Mean`]. [`ValueEstimate::Max`] follows
+    /// each root child's best line instead, which notices forced wins that
+    /// the mean still averages away.
+    pub fn with_value_estimate(mut self, estimate: ValueEstimate) -> Self {
+        self.value_estimate = estimate;
+        self
+    }
+
+    /// Sets how ties between equally good children are broken
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Sticks to the previously planned move unless the new best leads it by a margin
+    ///
+    /// Every search records its expected line of play, and moves played with
+    /// [`MCTS::apply_move`](crate::MCTS::apply_move) or
+    /// [`MCTS::advance_root`](crate::MCTS::advance_root) that follow the line
+    /// advance it, so after the opponent answers as expected the next search
+    /// knows the reply it planned. That reply is chosen unless the best root
+    /// child leads it by more than `visit_margin` of the root's visits under
+    /// [`BestChildCriteria::MostVisits`], or by more than `value_margin` in
+    /// mean value under the other criteria. This keeps engines on short
+    /// budgets from flip-flopping between near-equal moves.
+    pub fn with_move_hysteresis(mut self, visit_margin: f64, value_margin: f64) -> Self {
+        self.move_hysteresis = Some(MoveHysteresis::new(visit_margin, value_margin));
+        self
+    }
     /// Sets the node pool configuration
     ///
     /// Using a node pool can significantly improve performance by reducing
@@ -152,7 +1269,7 @@ impl MCTSConfig {
     ///
     /// # Arguments
     ///
-    /// * `initial_size` - Initial number of nodes to pre-allocate
+    /// * `initial_size` - Number of free nodes to reserve room for
     ///
     /// # Returns
     ///
@@ -162,6 +1279,12 @@ impl MCTSConfig {
         self
     }
 
+    /// Limits how many free nodes the node pool keeps
+    pub fn with_node_pool_max_free(mut self, max_free: usize) -> Self {
+        self.node_pool_max_free = Some(max_free);
+        self
+    }
+
     /// Disables the node pool
     ///
     /// This method disables the node pool, causing MCTS to allocate nodes
@@ -174,5 +1297,117 @@ impl MCTSConfig {
         self.node_pool_size = 0;
         self
     }
+
+    /// Caps every playout at `length` moves
+    ///
+    /// Applies to all simulation policies that play moves, not just a
+    /// particular one. Playouts cut off by the cap are scored as `unfinished`
+    /// says.
+    pub fn with_max_playout_length(mut self, length: usize, unfinished: Unfinished) -> Self {
+        self.max_playout_length = Some(length);
+        self.unfinished_playout = unfinished;
+        self
+    }
+
+    /// Enables cycle detection, scoring repeated states with `reward`
+    pub fn with_cycle_detection(mut self, reward: f64) -> Self {
+        self.cycle_reward = Some(reward);
+        self
+    }
+
+    /// Treats non-terminal states without legal actions as terminal, scored with `reward`
+    pub fn with_dead_end_reward(mut self, reward: f64) -> Self {
+        self.dead_end_reward = Some(reward);
+        self
+    }
+
+    /// Clips simulation results to `[min, max]` before any transform
+    ///
+    /// See [`reward_clip`](Self::reward_clip).
+    pub fn with_reward_clipping(mut self, min: f64, max: f64) -> Self {
+        self.reward_clip = Some((min.min(max), max.max(min)));
+        self
+    }
+
+    /// Sets the transformation applied to simulation results
+    pub fn with_reward_transform(mut self, transform: RewardTransform) -> Self {
+        self.reward_transform = transform;
+        self
+    }
+
+    /// Sets which player simulation results are computed for
+    ///
+    /// With [`Perspective::RootPlayer`], playouts, terminal states and
+    /// oracles are all scored for the player to move at the root, e.g. to
+    /// evaluate a position for one side in a game with more than two
+    /// players. Simulation policies that cannot score for another player
+    /// than the one to move fall back to `1 - result`; see
+    /// [`SimulationPolicy::simulate_for`](crate::SimulationPolicy::simulate_for).
+    pub fn with_perspective(mut self, perspective: Perspective) -> Self {
+        self.perspective = perspective;
+        self
+    }
+
+    /// Sets whose point of view node statistics are kept from
+    pub fn with_reward_perspective(mut self, perspective: RewardPerspective) -> Self {
+        self.reward_perspective = perspective;
+        self
+    }
+
+    /// Sets whether nodes back up expectimax values
+    ///
+    /// After the backpropagation policy has updated a node, its value is
+    /// replaced by [`MCTSNode::expectimax_value`](crate::tree::MCTSNode::expectimax_value):
+    /// the expectation over outcomes at chance nodes and the maximum over
+    /// children elsewhere. Visit counts are still updated as usual, so
+    /// selection policies and the final move choice need no changes. Searches
+    /// fail with `MCTSError::InvalidConfiguration` under
+    /// [`RewardPerspective::PerPlayer`], where a maximum over children is not
+    /// a value for the player who moved into the node.
+    pub fn with_expectimax(mut self, enabled: bool) -> Self {
+        self.expectimax = enabled;
+        self
+    }
+
+    /// Returns the limits every playout should respect
+    pub fn playout_limits(&self) -> PlayoutLimits {
+        PlayoutLimits {
+            max_length: self.max_playout_length,
+            unfinished: self.unfinished_playout,
+            cycle_reward: self.cycle_reward,
+        }
+    }
+
+    /// Sets how many iterations pass between iteration events (0 disables them)
+    pub fn with_event_interval(mut self, interval: usize) -> Self {
+        self.event_interval = interval;
+        self
+    }
+
+    /// Sets whether to revalidate legal actions and policy choices while searching
+    pub fn with_strict_mode(mut self, enabled: bool) -> Self {
+        self.strict_mode = enabled;
+        self
+    }
+
+    /// Sets whether to drop legal actions with a duplicate key
+    ///
+    /// See [`deduplicate_actions`](Self::deduplicate_actions).
+    pub fn with_action_deduplication(mut self, enabled: bool) -> Self {
+        self.deduplicate_actions = enabled;
+        self
+    }
+
+    /// Sets whether to stop searching once the tree is exhausted
+    pub fn with_stop_when_exhausted(mut self, enabled: bool) -> Self {
+        self.stop_when_exhausted = enabled;
+        self
+    }
+
+    /// Sets whether to cache per-node selection statistics
+    pub fn with_selection_stats_cache(mut self, enabled: bool) -> Self {
+        self.cache_selection_stats = enabled;
+        self
+    }
     // Thread-local pool support removed for now
 }
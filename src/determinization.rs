@@ -0,0 +1,172 @@
+//! Determinized ensemble search for hidden-information games
+//!
+//! In card games and other games with hidden information, the searching
+//! player does not know the true state. Root determinization samples several
+//! perfect-information states consistent with what the player knows, runs an
+//! independent search on each, and merges the root action statistics,
+//! weighting each sample by how likely it is. This is far less machinery than
+//! information-set trees and gives a usable baseline.
+//!
+//! Actions from different samples are matched by [`Action::key`], so the same
+//! move must have the same key in every sample.
+//!
+//! # Example
+//!
+//! ```
+//! # use arboriter_mcts::{GameState, Action, Player, MCTSConfig};
+//! use arboriter_mcts::determinization::search_determinizations;
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct Guess(usize);
+//! # impl Action for Guess { fn id(&self) -> usize { self.0 } }
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct P;
+//! # impl Player for P {}
+//! /// The opponent holds one of three cards; guessing it wins
+//! #[derive(Clone)]
+//! struct Hidden { card: usize, guess: Option<usize> }
+//! # impl GameState for Hidden {
+//! #     type Action = Guess;
+//! #     type Player = P;
+//! #     fn get_legal_actions(&self) -> Vec<Guess> {
+//! #         if self.guess.is_some() { vec![] } else { (0..3).map(Guess).collect() }
+//! #     }
+//! #     fn apply_action(&self, a: &Guess) -> Self { Hidden { card: self.card, guess: Some(a.0) } }
+//! #     fn is_terminal(&self) -> bool { self.guess.is_some() }
+//! #     fn get_result(&self, _: &P) -> f64 { if self.guess == Some(self.card) { 1.0 } else { 0.0 } }
+//! #     fn get_current_player(&self) -> P { P }
+//! # }
+//!
+//! // What we know: card 2 is twice as likely as the others
+//! let likelihoods = [0.25, 0.25, 0.5];
+//! let config = MCTSConfig::default().with_max_iterations(100);
+//! let result = search_determinizations(
+//!     |i| (Hidden { card: i, guess: None }, likelihoods[i]),
+//!     3,
+//!     &config,
+//! )
+//! .unwrap();
+//! assert_eq!(result.best_action, Some(Guess(2)));
+//! ```
+
+use crate::{
+    config::MCTSConfig,
+    game_state::{Action, GameState},
+    mcts::MCTS,
+    MCTSError, Result,
+};
+
+/// Merged statistics of one root action across determinizations
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnsembleAction<A> {
+    /// The root action
+    pub action: A,
+
+    /// Share of root visits, averaged over samples with their normalized weights
+    ///
+    /// Sums to 1 over all actions, so it reads as a vote for each action.
+    pub visits: f64,
+
+    /// Mean value, averaged over samples with their weights and visits
+    pub value: f64,
+
+    /// Number of samples in which the action was searched
+    pub support: usize,
+}
+
+/// Result of [`search_determinizations`]
+#[derive(Debug, Clone)]
+pub struct EnsembleResult<A> {
+    /// Merged root actions, most visited first
+    pub actions: Vec<EnsembleAction<A>>,
+
+    /// The action with the largest weighted visit share
+    pub best_action: Option<A>,
+
+    /// Number of determinizations searched
+    pub determinizations: usize,
+}
+
+/// Searches `count` determinizations and merges their root statistics
+///
+/// `sample(i)` returns the `i`-th perfect-information state together with its
+/// likelihood weight; weights need not sum to one. Every sample gets an
+/// independent search with `config`. Each action's visit share and value are
+/// then averaged over the samples, weighted by likelihood, and the action
+/// with the largest weighted visit share is recommended.
+///
+/// # Errors
+///
+/// Returns `MCTSError::InvalidConfiguration` if `count` is zero or the
+/// weights do not add up to a positive number, and the first error of any
+/// search.
+pub fn search_determinizations<S, F>(
+    mut sample: F,
+    count: usize,
+    config: &MCTSConfig,
+) -> Result<EnsembleResult<S::Action>>
+where
+    S: GameState + 'static,
+    F: FnMut(usize) -> (S, f64),
+{
+    if count == 0 {
+        return Err(MCTSError::InvalidConfiguration(
+            "determinized search needs at least one sample".to_string(),
+        ));
+    }
+
+    // (action, weighted visit share, weighted value sum, support)
+    let mut merged: Vec<(S::Action, f64, f64, usize)> = Vec::new();
+    let mut total_weight = 0.0;
+
+    for i in 0..count {
+        let (state, weight) = sample(i);
+        let weight = weight.max(0.0);
+        total_weight += weight;
+
+        let mut mcts = MCTS::new(state, config.clone());
+        mcts.search()?;
+        let snapshot = mcts.root_snapshot();
+        let root_visits = snapshot.root_visits.max(1) as f64;
+
+        for stats in snapshot.actions {
+            if stats.visits == 0 {
+                continue;
+            }
+            let share = weight * stats.visits as f64 / root_visits;
+            match merged
+                .iter_mut()
+                .find(|(action, ..)| action.key() == stats.action.key())
+            {
+                Some(entry) => {
+                    entry.1 += share;
+                    entry.2 += share * stats.value;
+                    entry.3 += 1;
+                }
+                None => merged.push((stats.action, share, share * stats.value, 1)),
+            }
+        }
+    }
+
+    if total_weight <= 0.0 {
+        return Err(MCTSError::InvalidConfiguration(
+            "determinization weights must add up to a positive number".to_string(),
+        ));
+    }
+
+    let mut actions: Vec<EnsembleAction<S::Action>> = merged
+        .into_iter()
+        .map(|(action, share, value_sum, support)| EnsembleAction {
+            action,
+            visits: share / total_weight,
+            value: if share > 0.0 { value_sum / share } else { 0.0 },
+            support,
+        })
+        .collect();
+    actions.sort_by(|a, b| b.visits.total_cmp(&a.visits));
+
+    Ok(EnsembleResult {
+        best_action: actions.first().map(|best| best.action.clone()),
+        actions,
+        determinizations: count,
+    })
+}
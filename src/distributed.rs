@@ -0,0 +1,415 @@
+//! Root parallelization across processes
+//!
+//! Large analysis jobs can spread one position over several machines: each
+//! process searches its own tree and, every few iterations, tells its peers
+//! how much its root children gained since it last did. A
+//! [`DistributedSearch`] wraps an [`MCTS`] instance to do this. After each
+//! slice of [`sync_interval`](DistributedSearch::with_sync_interval)
+//! iterations it encodes the new root statistics as a [`RootDelta`], hands
+//! the bytes to a user-provided [`Transport`], and grafts the deltas peers
+//! sent onto its own root children. Each process counts every peer's visits
+//! only once, so the root children converge on the statistics of one search
+//! with the combined budget.
+//!
+//! Only root children are shared; every process keeps its own deeper tree.
+//! Deltas carry the root's [`GameState::hash`], so messages about another
+//! position are dropped, provided the game implements it.
+//!
+//! # Example
+//!
+//! ```
+//! use arboriter_mcts::distributed::{ChannelTransport, DistributedSearch};
+//! # use arboriter_mcts::{config::RewardPerspective, Action, GameState, MCTSConfig, Player, MCTS};
+//! # #[derive(Clone, Copy, Debug, PartialEq)]
+//! # struct Side(u8);
+//! # impl Player for Side {}
+//! # #[derive(Clone, Debug, PartialEq)]
+//! # struct Take(u32);
+//! # impl Action for Take { fn id(&self) -> usize { self.0 as usize } }
+//! # /// Nim: take 1 to 3 stones, taking the last one wins
+//! # #[derive(Clone, Debug)]
+//! # struct Nim { stones: u32, to_move: u8 }
+//! # impl GameState for Nim {
+//! #     type Action = Take;
+//! #     type Player = Side;
+//! #     fn get_legal_actions(&self) -> Vec<Take> { (1..=self.stones.min(3)).map(Take).collect() }
+//! #     fn apply_action(&self, take: &Take) -> Self { Nim { stones: self.stones - take.0, to_move: 1 - self.to_move } }
+//! #     fn is_terminal(&self) -> bool { self.stones == 0 }
+//! #     fn get_result(&self, side: &Side) -> f64 { if side.0 == self.to_move { 0.0 } else { 1.0 } }
+//! #     fn get_current_player(&self) -> Side { Side(self.to_move) }
+//! # }
+//!
+//! let config = MCTSConfig::default().with_reward_perspective(RewardPerspective::PerPlayer);
+//! let nim = Nim { stones: 10, to_move: 0 };
+//!
+//! // Each peer would normally run in its own process
+//! let mut peers: Vec<_> = ChannelTransport::mesh(2)
+//!     .into_iter()
+//!     .map(|transport| {
+//!         DistributedSearch::new(MCTS::new(nim.clone(), config.clone()), transport)
+//!             .with_sync_interval(100)
+//!     })
+//!     .collect();
+//! for peer in &mut peers {
+//!     peer.search(500).unwrap();
+//! }
+//! peers[0].sync().unwrap();
+//!
+//! // Both peers now hold the root statistics of all 1000 iterations
+//! assert_eq!(peers[0].mcts().root().visits(), 1000);
+//! assert_eq!(peers[1].mcts().root().visits(), 1000);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use crate::{
+    config::Budget,
+    game_state::{Action, ActionKey, GameState},
+    mcts::MCTS,
+    Result,
+};
+
+/// Iterations a distributed search runs between synchronizations
+pub const DEFAULT_SYNC_INTERVAL: usize = 1_000;
+
+/// Moves encoded root deltas between the processes of a distributed search
+///
+/// Implementations decide how peers are found and how bytes travel, e.g.
+/// over TCP, a message queue or MPI. [`ChannelTransport`] connects searches
+/// within one process.
+pub trait Transport {
+    /// Sends an encoded [`RootDelta`] to every peer
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::Transport` if the message could not be sent.
+    fn send(&mut self, message: &[u8]) -> Result<()>;
+
+    /// Returns the messages received from peers since the last call
+    ///
+    /// Must not block; returns an empty list if nothing arrived.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::Transport` if receiving failed.
+    fn receive(&mut self) -> Result<Vec<Vec<u8>>>;
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn send(&mut self, message: &[u8]) -> Result<()> {
+        (**self).send(message)
+    }
+
+    fn receive(&mut self) -> Result<Vec<Vec<u8>>> {
+        (**self).receive()
+    }
+}
+
+/// Statistics a root child gained since the last synchronization
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeDelta {
+    /// Key of the root action
+    pub key: ActionKey,
+
+    /// New visits
+    pub visits: u64,
+
+    /// Total reward of the new visits
+    pub total_reward: f64,
+}
+
+/// Root statistics one process gained since it last synchronized
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RootDelta {
+    /// [`GameState::hash`] of the root the statistics belong to
+    pub root_hash: u64,
+
+    /// Statistics gained by each root child that was visited
+    pub edges: Vec<EdgeDelta>,
+}
+
+/// Bytes per encoded edge: kind, id, visits and total reward
+const EDGE_BYTES: usize = 4 + 8 + 8 + 8;
+
+impl RootDelta {
+    /// Encodes the delta as little-endian bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(12 + self.edges.len() * EDGE_BYTES);
+        bytes.extend_from_slice(&self.root_hash.to_le_bytes());
+        bytes.extend_from_slice(&(self.edges.len() as u32).to_le_bytes());
+        for edge in &self.edges {
+            bytes.extend_from_slice(&edge.key.kind.to_le_bytes());
+            bytes.extend_from_slice(&edge.key.id.to_le_bytes());
+            bytes.extend_from_slice(&edge.visits.to_le_bytes());
+            bytes.extend_from_slice(&edge.total_reward.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a delta encoded with [`to_bytes`](Self::to_bytes)
+    ///
+    /// Returns `None` if the bytes are truncated or have trailing data.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let root_hash = u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?);
+        let count = u32::from_le_bytes(bytes.get(8..12)?.try_into().ok()?) as usize;
+        let body = &bytes[12..];
+        if body.len() != count.checked_mul(EDGE_BYTES)? {
+            return None;
+        }
+
+        let edges = body
+            .chunks_exact(EDGE_BYTES)
+            .map(|chunk| EdgeDelta {
+                key: ActionKey::new(
+                    u32::from_le_bytes(chunk[0..4].try_into().unwrap()),
+                    u64::from_le_bytes(chunk[4..12].try_into().unwrap()),
+                ),
+                visits: u64::from_le_bytes(chunk[12..20].try_into().unwrap()),
+                total_reward: f64::from_le_bytes(chunk[20..28].try_into().unwrap()),
+            })
+            .collect();
+        Some(RootDelta { root_hash, edges })
+    }
+}
+
+/// A search sharing its root statistics with peers
+///
+/// See the [module documentation](self).
+pub struct DistributedSearch<S: GameState + 'static, T: Transport> {
+    /// The local search
+    mcts: MCTS<S>,
+
+    /// Connection to the peers
+    transport: T,
+
+    /// Iterations between synchronizations
+    sync_interval: usize,
+
+    /// Statistics of local visits already sent, by root action key
+    sent: HashMap<ActionKey, (u64, f64)>,
+
+    /// Statistics grafted from peers, by root action key
+    received: HashMap<ActionKey, (u64, f64)>,
+
+    /// Messages dropped as undecodable or about another root
+    dropped_messages: usize,
+}
+
+impl<S: GameState + 'static, T: Transport> DistributedSearch<S, T> {
+    /// Wraps a search instance, synchronizing through `transport`
+    pub fn new(mcts: MCTS<S>, transport: T) -> Self {
+        DistributedSearch {
+            mcts,
+            transport,
+            sync_interval: DEFAULT_SYNC_INTERVAL,
+            sent: HashMap::new(),
+            received: HashMap::new(),
+            dropped_messages: 0,
+        }
+    }
+
+    /// Sets how many iterations run between synchronizations (at least 1)
+    ///
+    /// Shorter intervals share information sooner at the cost of more
+    /// messages.
+    pub fn with_sync_interval(mut self, iterations: usize) -> Self {
+        self.sync_interval = iterations.max(1);
+        self
+    }
+
+    /// Searches for `iterations` local iterations, synchronizing after every slice
+    ///
+    /// The search continues on the same tree across calls. Returns the best
+    /// root action by the configured criteria once the last slice has been
+    /// synchronized.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the search or the transport.
+    pub fn search(&mut self, iterations: usize) -> Result<S::Action> {
+        let mut remaining = iterations;
+        while remaining > 0 {
+            let slice = remaining.min(self.sync_interval);
+            self.mcts.continue_search(Budget::Iterations(slice))?;
+            remaining -= slice;
+            self.sync()?;
+        }
+        self.mcts
+            .best_action(self.mcts.config().best_child_criteria)
+    }
+
+    /// Sends local root statistics gained since the last call and merges the peers'
+    ///
+    /// Received statistics are grafted onto the root children with
+    /// [`MCTS::graft`], expanding children the local search has not tried
+    /// yet. Edges for actions that are not legal here are ignored. Returns
+    /// how many edges were merged.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the transport.
+    pub fn sync(&mut self) -> Result<usize> {
+        let delta = self.local_delta();
+        if !delta.edges.is_empty() {
+            self.transport.send(&delta.to_bytes())?;
+        }
+
+        let root_hash = self.mcts.root().state.hash();
+        let mut merged = 0;
+        for message in self.transport.receive()? {
+            let Some(delta) = RootDelta::from_bytes(&message) else {
+                self.dropped_messages += 1;
+                continue;
+            };
+            if delta.root_hash != root_hash {
+                self.dropped_messages += 1;
+                continue;
+            }
+            for edge in delta.edges.iter().filter(|edge| edge.visits > 0) {
+                if self.merge_edge(edge) {
+                    merged += 1;
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Collects the local statistics not sent yet and marks them sent
+    fn local_delta(&mut self) -> RootDelta {
+        let root = self.mcts.root();
+        let mut delta = RootDelta {
+            root_hash: root.state.hash(),
+            edges: Vec::new(),
+        };
+        for child in &root.children {
+            let Some(key) = child.action.as_ref().map(Action::key) else {
+                continue;
+            };
+            let (remote_visits, remote_reward) =
+                self.received.get(&key).copied().unwrap_or_default();
+            let sent = self.sent.entry(key).or_default();
+            let visits = child
+                .visits()
+                .saturating_sub(remote_visits)
+                .saturating_sub(sent.0);
+            if visits == 0 {
+                continue;
+            }
+            let total_reward = child.total_reward() - remote_reward - sent.1;
+            sent.0 += visits;
+            sent.1 += total_reward;
+            delta.edges.push(EdgeDelta {
+                key,
+                visits,
+                total_reward,
+            });
+        }
+        delta
+    }
+
+    /// Grafts a peer's edge statistics onto the matching root child
+    fn merge_edge(&mut self, edge: &EdgeDelta) -> bool {
+        let root = self.mcts.root();
+        let action = root
+            .children
+            .iter()
+            .filter_map(|child| child.action.as_ref())
+            .chain(&root.unexpanded_actions)
+            .find(|action| action.key() == edge.key)
+            .cloned();
+        let Some(action) = action else {
+            return false;
+        };
+
+        let value = edge.total_reward / edge.visits as f64;
+        if self.mcts.graft(&[action], edge.visits, value).is_err() {
+            return false;
+        }
+        let received = self.received.entry(edge.key).or_default();
+        received.0 += edge.visits;
+        received.1 += value * edge.visits as f64;
+        true
+    }
+
+    /// Returns how many messages were dropped as undecodable or about another root
+    pub fn dropped_messages(&self) -> usize {
+        self.dropped_messages
+    }
+
+    /// Returns the local search instance
+    pub fn mcts(&self) -> &MCTS<S> {
+        &self.mcts
+    }
+
+    /// Returns the local search instance mutably
+    ///
+    /// Searching it directly is fine; the next [`sync`](Self::sync) shares
+    /// the visits it added. Changing its root, e.g. with
+    /// [`MCTS::apply_move`], leaves the bookkeeping of sent and received
+    /// statistics stale; start a new distributed search instead.
+    pub fn mcts_mut(&mut self) -> &mut MCTS<S> {
+        &mut self.mcts
+    }
+
+    /// Returns the search instance and the transport
+    pub fn into_inner(self) -> (MCTS<S>, T) {
+        (self.mcts, self.transport)
+    }
+}
+
+impl<S: GameState + 'static, T: Transport> std::fmt::Debug for DistributedSearch<S, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DistributedSearch")
+            .field("sync_interval", &self.sync_interval)
+            .field("dropped_messages", &self.dropped_messages)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A transport between searches in the same process, built on channels
+///
+/// Useful for tests and for trying out a distributed setup before writing a
+/// network transport. Sending never fails; peers that have gone away are
+/// skipped from then on.
+#[derive(Debug)]
+pub struct ChannelTransport {
+    /// Senders to every other peer
+    peers: Vec<Sender<Vec<u8>>>,
+
+    /// Messages from the other peers
+    inbox: Receiver<Vec<u8>>,
+}
+
+impl ChannelTransport {
+    /// Creates `peers` transports, each connected to all the others
+    pub fn mesh(peers: usize) -> Vec<ChannelTransport> {
+        let (senders, inboxes): (Vec<_>, Vec<_>) = (0..peers).map(|_| mpsc::channel()).unzip();
+        inboxes
+            .into_iter()
+            .enumerate()
+            .map(|(index, inbox)| ChannelTransport {
+                peers: senders
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other, _)| other != index)
+                    .map(|(_, sender)| sender.clone())
+                    .collect(),
+                inbox,
+            })
+            .collect()
+    }
+}
+
+impl Transport for ChannelTransport {
+    fn send(&mut self, message: &[u8]) -> Result<()> {
+        // Peers that finished and dropped their transport are forgotten
+        self.peers
+            .retain(|peer| peer.send(message.to_vec()).is_ok());
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Vec<Vec<u8>>> {
+        Ok(self.inbox.try_iter().collect())
+    }
+}
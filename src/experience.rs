@@ -0,0 +1,199 @@
+//! Knowledge carried over from one game to the next
+//!
+//! Engines that play many games in a row, e.g. against the same opponent,
+//! keep rediscovering which moves tend to work. [`Experience`] aggregates the
+//! root statistics of every search in a game by [`Action::key`], folds them
+//! into what earlier games taught when the game ends, fading older games out,
+//! and seeds the root of later searches with the result.
+//!
+//! The statistics are coarse: an action is credited wherever it was a
+//! root action, whatever the position. Seeded visits are capped so the
+//! search soon overrules stale knowledge.
+//!
+//! # Example
+//!
+//! ```
+//! # use arboriter_mcts::{GameState, Action, Player, MCTS, MCTSConfig};
+//! use arboriter_mcts::experience::Experience;
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct A(usize);
+//! # impl Action for A { fn id(&self) -> usize { self.0 } }
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct P;
+//! # impl Player for P {}
+//! # #[derive(Clone)]
+//! # struct G(usize);
+//! # impl GameState for G {
+//! #     type Action = A;
+//! #     type Player = P;
+//! #     fn get_legal_actions(&self) -> Vec<A> { if self.0 < 2 { vec![A(0), A(1)] } else { vec![] } }
+//! #     fn apply_action(&self, a: &A) -> Self { G(self.0 + 1 + a.0) }
+//! #     fn is_terminal(&self) -> bool { self.0 >= 2 }
+//! #     fn get_result(&self, _: &P) -> f64 { if self.0 > 2 { 1.0 } else { 0.0 } }
+//! #     fn get_current_player(&self) -> P { P }
+//! # }
+//! let mut experience = Experience::new(0.8);
+//! let config = MCTSConfig::default().with_max_iterations(100);
+//!
+//! for _game in 0..3 {
+//!     let mut mcts = MCTS::new(G(0), config.clone());
+//!     experience.warm_start(&mut mcts, 20).unwrap();
+//!     while !mcts.root().state.is_terminal() {
+//!         let action = mcts.search().unwrap();
+//!         experience.record(&mcts.root_snapshot());
+//!         mcts.apply_move(&action);
+//!     }
+//!     experience.finish_game();
+//! }
+//! assert_eq!(experience.len(), 2);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    game_state::{Action, ActionKey, GameState},
+    history::HistoryEntry,
+    mcts::MCTS,
+    stats::RootSnapshot,
+    Result,
+};
+
+/// Per-action statistics aggregated over past games
+#[derive(Debug, Clone)]
+pub struct Experience {
+    /// Knowledge from finished games, keyed by action key
+    entries: HashMap<ActionKey, HistoryEntry>,
+
+    /// Statistics recorded in the current game
+    game: HashMap<ActionKey, HistoryEntry>,
+
+    /// Weight finished games keep each time another game ends
+    decay: f64,
+}
+
+impl Experience {
+    /// Creates empty experience
+    ///
+    /// `decay` in `[0, 1]` is the weight earlier games keep each time a game
+    /// ends: 1.0 remembers everything, 0.0 only the last game.
+    pub fn new(decay: f64) -> Self {
+        Experience {
+            entries: HashMap::new(),
+            game: HashMap::new(),
+            decay: decay.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Restores experience exported with [`entries`](Self::entries)
+    pub fn from_entries(
+        decay: f64,
+        entries: impl IntoIterator<Item = (ActionKey, HistoryEntry)>,
+    ) -> Self {
+        let mut experience = Self::new(decay);
+        experience.entries = entries.into_iter().collect();
+        experience
+    }
+
+    /// Records the root statistics of one search in the current game
+    pub fn record<A: Action>(&mut self, snapshot: &RootSnapshot<A>) {
+        for stats in &snapshot.actions {
+            if stats.visits == 0 {
+                continue;
+            }
+            let entry = self.game.entry(stats.action.key()).or_default();
+            entry.visits += stats.visits as f64;
+            entry.total_reward += stats.visits as f64 * stats.value;
+        }
+    }
+
+    /// Ends the current game, fading out earlier games and keeping this one
+    pub fn finish_game(&mut self) {
+        for entry in self.entries.values_mut() {
+            entry.visits *= self.decay;
+            entry.total_reward *= self.decay;
+        }
+        for (key, game) in self.game.drain() {
+            let entry = self.entries.entry(key).or_default();
+            entry.visits += game.visits;
+            entry.total_reward += game.total_reward;
+        }
+        self.entries.retain(|_, entry| entry.visits >= 1e-3);
+    }
+
+    /// Returns the statistics of an action key from finished games
+    pub fn get(&self, key: ActionKey) -> Option<HistoryEntry> {
+        self.entries.get(&key).copied()
+    }
+
+    /// Returns the average value of an action key from finished games
+    pub fn value(&self, key: ActionKey) -> Option<f64> {
+        self.get(key).map(|entry| entry.value())
+    }
+
+    /// Returns the statistics of finished games for export, in no particular order
+    pub fn entries(&self) -> Vec<(ActionKey, HistoryEntry)> {
+        self.entries
+            .iter()
+            .map(|(key, entry)| (*key, *entry))
+            .collect()
+    }
+
+    /// Returns the number of action keys known from finished games
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no finished game left any knowledge
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns warm-start entries for the known actions among `actions`
+    ///
+    /// Each known action gets its average value and its visits, capped at
+    /// `max_visits`. Actions seen too rarely to round to a visit are left out.
+    pub fn priors<A: Action>(&self, actions: &[A], max_visits: u64) -> Vec<(A, f64, u64)> {
+        actions
+            .iter()
+            .filter_map(|action| {
+                let entry = self.get(action.key())?;
+                let visits = (entry.visits.round() as u64).min(max_visits);
+                (visits > 0).then(|| (action.clone(), entry.value(), visits))
+            })
+            .collect()
+    }
+
+    /// Seeds the root of `mcts` with this experience as soft priors
+    ///
+    /// Calls [`MCTS::warm_start`] with [`priors`](Self::priors) for the
+    /// root's legal actions, so the next search starts from them. Returns the
+    /// number of root actions seeded.
+    pub fn warm_start<S: GameState + 'static>(
+        &self,
+        mcts: &mut MCTS<S>,
+        max_visits: u64,
+    ) -> Result<usize> {
+        let root = mcts.root();
+        let actions: Vec<S::Action> = root
+            .state
+            .get_legal_actions()
+            .into_iter()
+            .filter(|action| {
+                root.find_child(action).is_some() || root.find_unexpanded(action).is_some()
+            })
+            .collect();
+        let priors = self.priors(&actions, max_visits);
+        if priors.is_empty() {
+            return Ok(0);
+        }
+        mcts.warm_start(&priors)?;
+        Ok(priors.len())
+    }
+}
+
+impl Default for Experience {
+    /// Experience that halves the weight of earlier games after every game
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
@@ -3,7 +3,12 @@
 //! The GameState trait is the primary interface that must be implemented for any
 //! game or decision process that will be used with the MCTS algorithm.
 
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+
+use crate::{config::PlayoutLimits, utils::search_rng};
 
 /// Trait for actions that can be taken in a game
 ///
@@ -11,6 +16,66 @@ use std::fmt::Debug;
 pub trait Action: Clone + Debug + Send + Sync {
     /// Returns a unique identifier for this action
     fn id(&self) -> usize;
+
+    /// Returns the key that identifies this action across the search
+    ///
+    /// Everything that matches or aggregates actions by identity uses this
+    /// key: finding children, RAVE, history tables and MAST, experience,
+    /// deduplication and root constraints. The default wraps
+    /// [`id`](Self::id); override it when ids of different kinds of action
+    /// collide, e.g. with [`ActionKey::new`] for a kind and an id or
+    /// [`ActionKey::hashed`] for richer actions.
+    fn key(&self) -> ActionKey {
+        ActionKey::from(self.id())
+    }
+}
+
+/// Identity of an action, as returned by [`Action::key`]
+///
+/// A key is an id within a kind of action, so e.g. a piece move and a piece
+/// drop with the same id stay apart. Plain ids have kind 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct ActionKey {
+    /// Kind of action the id belongs to
+    pub kind: u32,
+
+    /// Identifier within the kind
+    pub id: u64,
+}
+
+impl ActionKey {
+    /// Creates a key for an id within a kind of action
+    pub const fn new(kind: u32, id: u64) -> Self {
+        ActionKey { kind, id }
+    }
+
+    /// Creates a key by hashing any hashable description of an action
+    ///
+    /// The hash is stable within a build but not across Rust versions, so
+    /// keys that are persisted, e.g. in exported
+    /// [`Experience`](crate::experience::Experience), should be built with
+    /// [`new`](Self::new) instead.
+    pub fn hashed<T: Hash + ?Sized>(kind: u32, value: &T) -> Self {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        ActionKey::new(kind, hasher.finish())
+    }
+}
+
+impl From<usize> for ActionKey {
+    fn from(id: usize) -> Self {
+        ActionKey::new(0, id as u64)
+    }
+}
+
+/// Feature vector describing an action, for sharing statistics between similar actions
+///
+/// Implement this for continuous or parametric actions to use
+/// [`KernelRegressionPolicy`](crate::policy::selection::KernelRegressionPolicy),
+/// which compares actions by a kernel over their features.
+pub trait ActionFeatures {
+    /// Returns the action's coordinates in feature space
+    fn features(&self) -> Vec<f64>;
 }
 
 /// Trait for players in a game
@@ -127,12 +192,27 @@ pub trait GameState: Clone + Send + Sync {
     fn simulate_random_playout(&self, for_player: &Self::Player) -> (f64, Vec<Self::Action>) {
         use rand::seq::SliceRandom;
 
-        let mut rng = rand::thread_rng();
+        let mut rng = search_rng();
         let mut current_state = self.clone();
         let mut trace = Vec::new();
 
-        // Play random moves until the game is over
+        // Resolve forced moves first, then play random moves until the game is over
+        let mut forcing = true;
         while !current_state.is_terminal() {
+            if let Some(state) = current_state.sample_chance_outcome(&mut rng) {
+                current_state = state;
+                continue;
+            }
+
+            if forcing {
+                if let Some(action) = current_state.forced_continuation() {
+                    current_state = current_state.apply_action(&action);
+                    trace.push(action);
+                    continue;
+                }
+                forcing = false;
+            }
+
             let legal_actions = current_state.get_legal_actions();
             if legal_actions.is_empty() {
                 break;
@@ -148,6 +228,116 @@ pub trait GameState: Clone + Send + Sync {
         (current_state.get_result(for_player), trace)
     }
 
+    /// Performs a random simulation that respects `limits`
+    ///
+    /// Behaves like [`simulate_random_playout`](Self::simulate_random_playout)
+    /// but stops after `limits.max_length` moves, scoring the unfinished game
+    /// with `limits.unfinished`, and, if cycle detection is on, stops with
+    /// `limits.cycle_reward` as soon as a state [`hash`](Self::hash) repeats.
+    fn simulate_random_playout_limited(
+        &self,
+        for_player: &Self::Player,
+        limits: &PlayoutLimits,
+    ) -> (f64, Vec<Self::Action>) {
+        use rand::seq::SliceRandom;
+
+        let mut rng = search_rng();
+        let mut current_state = self.clone();
+        let mut trace = Vec::new();
+        let mut seen = HashSet::new();
+        let mut forcing = true;
+
+        while !current_state.is_terminal() {
+            if let Some(reward) = limits.cycle_reward {
+                let hash = current_state.hash();
+                if hash != 0 && !seen.insert(hash) {
+                    return (reward, trace);
+                }
+            }
+
+            if limits.max_length.is_some_and(|max| trace.len() >= max) {
+                return (limits.unfinished.score(&current_state, for_player), trace);
+            }
+
+            if let Some(state) = current_state.sample_chance_outcome(&mut rng) {
+                current_state = state;
+                continue;
+            }
+
+            if forcing {
+                if let Some(action) = current_state.forced_continuation() {
+                    current_state = current_state.apply_action(&action);
+                    trace.push(action);
+                    continue;
+                }
+                forcing = false;
+            }
+
+            let legal_actions = current_state.get_legal_actions();
+            if legal_actions.is_empty() {
+                break;
+            }
+
+            let action = legal_actions.choose(&mut rng).unwrap();
+            trace.push(action.clone());
+            current_state = current_state.apply_action(action);
+        }
+
+        (current_state.get_result(for_player), trace)
+    }
+
+    /// Returns the move that must be played next before a playout can start
+    ///
+    /// Random playouts from tactically unstable positions, e.g. in the middle
+    /// of an exchange of pieces, say little about the position. Playouts
+    /// first follow this method for as long as it returns a move, resolving
+    /// the forced or capturing sequence deterministically, and only then
+    /// start playing random moves. Forced moves are part of the playout's
+    /// trace and count towards the
+    /// [playout length cap](crate::MCTSConfig::with_max_playout_length).
+    /// The sequence must end: return `None` once the position is quiet.
+    ///
+    /// Returns `None`, the default, so playouts are random from the start.
+    fn forced_continuation(&self) -> Option<Self::Action> {
+        None
+    }
+
+    /// Returns the outcomes of a pending chance event with their probabilities
+    ///
+    /// Games with dice rolls or card draws can model them as afterstates:
+    /// [`apply_action`](Self::apply_action) returns the state after the
+    /// player's move but before the chance event, and this method lists the
+    /// states the event can lead to. The search then inserts a chance node
+    /// for the afterstate whose children are the outcomes, sampled in
+    /// proportion to their probabilities, so decision values are not mixed
+    /// up with the variance of the dice. Probabilities need not be normalized.
+    ///
+    /// Returns `None`, the default, for states without a pending chance event.
+    fn chance_outcomes(&self) -> Option<Vec<(Self, f64)>> {
+        None
+    }
+
+    /// Returns true if this state is waiting for a chance event
+    ///
+    /// Defaults to whether [`chance_outcomes`](Self::chance_outcomes) lists
+    /// the outcomes. Override it together with
+    /// [`sample_chance_outcome`](Self::sample_chance_outcome) for chance
+    /// events with too many outcomes to list, such as continuous noise.
+    fn is_chance_event(&self) -> bool {
+        self.chance_outcomes().is_some()
+    }
+
+    /// Draws one outcome of the pending chance event
+    ///
+    /// The default samples [`chance_outcomes`](Self::chance_outcomes) by
+    /// probability. Chance nodes whose outcomes are not listed are grown by
+    /// calling this, limited by
+    /// [`MCTSConfig::with_outcome_widening`](crate::MCTSConfig::with_outcome_widening),
+    /// and playouts use it to resolve chance events.
+    fn sample_chance_outcome(&self, rng: &mut dyn rand::RngCore) -> Option<Self> {
+        sample_chance_outcome(self, rng)
+    }
+
     /// Returns a hash representing this state, used for transposition tables
     ///
     /// Default implementation returns a constant, effectively disabling
@@ -157,6 +347,28 @@ pub trait GameState: Clone + Send + Sync {
     }
 }
 
+/// Samples one of the outcomes listed by [`GameState::chance_outcomes`]
+///
+/// Outcomes are drawn in proportion to their probabilities. Returns `None`
+/// for states without listed outcomes or with an empty outcome list.
+pub fn sample_chance_outcome<S: GameState, R: rand::Rng + ?Sized>(
+    state: &S,
+    rng: &mut R,
+) -> Option<S> {
+    let mut outcomes = state.chance_outcomes()?;
+    let total: f64 = outcomes.iter().map(|(_, p)| p.max(0.0)).sum();
+    let mut threshold = rng.gen::<f64>() * total;
+    let mut chosen = outcomes.len().checked_sub(1)?;
+    for (index, (_, probability)) in outcomes.iter().enumerate() {
+        threshold -= probability.max(0.0);
+        if threshold < 0.0 {
+            chosen = index;
+            break;
+        }
+    }
+    Some(outcomes.swap_remove(chosen).0)
+}
+
 /// Simplified imlementation of Player trait for common types
 impl Player for usize {}
 impl Player for i32 {}
@@ -0,0 +1,133 @@
+//! History heuristic shared across the whole search
+//!
+//! A [`HistoryTable`] keeps the average reward of every action key, no matter
+//! where in the tree or in which playout the action was played. Attach one
+//! with [`MCTS::with_history`](crate::MCTS::with_history) and the search
+//! records every action of every iteration in it. Selection policies read it
+//! through [`SelectionContext::history`](crate::policy::SelectionContext::history)
+//! (see [`ProgressiveHistoryPolicy`](crate::policy::selection::ProgressiveHistoryPolicy)),
+//! and rollout policies hold their own handle to it (see
+//! [`MastPolicy`](crate::policy::simulation::MastPolicy)).
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//! use arboriter_mcts::{history::HistoryTable, ActionKey};
+//!
+//! let history = Arc::new(HistoryTable::new(0.5));
+//! let key = ActionKey::from(3);
+//! history.record(key, 1.0);
+//! history.record(key, 0.0);
+//! assert_eq!(history.value(&key), Some(0.5));
+//!
+//! // Between moves, old evidence counts for half as much
+//! history.decay();
+//! assert_eq!(history.get(&key).unwrap().visits, 1.0);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::RwLock;
+
+use crate::game_state::ActionKey;
+
+/// Accumulated statistics for one key of a [`HistoryTable`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HistoryEntry {
+    /// Number of times the key was recorded, reduced by decay
+    pub visits: f64,
+
+    /// Total reward of those records, reduced by decay
+    pub total_reward: f64,
+}
+
+impl HistoryEntry {
+    /// Returns the average reward, or 0.0 if nothing was recorded
+    pub fn value(&self) -> f64 {
+        if self.visits <= 0.0 {
+            return 0.0;
+        }
+        self.total_reward / self.visits
+    }
+}
+
+/// Thread-safe table of average rewards indexed by [`Action::key`](crate::Action::key)
+///
+/// The key type can be changed to track other things than single actions,
+/// e.g. `Vec<ActionKey>` for sequences of actions as in
+/// [`NstPolicy`](crate::policy::simulation::NstPolicy).
+#[derive(Debug)]
+pub struct HistoryTable<K = ActionKey> {
+    entries: RwLock<HashMap<K, HistoryEntry>>,
+    decay: f64,
+}
+
+impl<K: Eq + Hash> HistoryTable<K> {
+    /// Creates an empty table
+    ///
+    /// `decay` in `[0, 1]` is the weight old evidence keeps each time
+    /// [`decay`](Self::decay) is called, which the search does whenever a move
+    /// is played with [`MCTS::apply_move`](crate::MCTS::apply_move). Use 1.0 to
+    /// keep everything and 0.0 to start afresh on every move.
+    pub fn new(decay: f64) -> Self {
+        HistoryTable {
+            entries: RwLock::new(HashMap::new()),
+            decay: decay.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Records one reward for a key
+    pub fn record(&self, key: K, reward: f64) {
+        let mut entries = self.entries.write().unwrap();
+        let entry = entries.entry(key).or_default();
+        entry.visits += 1.0;
+        entry.total_reward += reward;
+    }
+
+    /// Returns the statistics of a key, if it was ever recorded
+    pub fn get(&self, key: &K) -> Option<HistoryEntry> {
+        self.entries.read().unwrap().get(key).copied()
+    }
+
+    /// Returns the average reward of a key, if it was ever recorded
+    pub fn value(&self, key: &K) -> Option<f64> {
+        self.get(key).map(|entry| entry.value())
+    }
+
+    /// Scales every entry by the decay factor, dropping entries that fade out
+    pub fn decay(&self) {
+        let mut entries = self.entries.write().unwrap();
+        if self.decay == 0.0 {
+            entries.clear();
+            return;
+        }
+        for entry in entries.values_mut() {
+            entry.visits *= self.decay;
+            entry.total_reward *= self.decay;
+        }
+        entries.retain(|_, entry| entry.visits >= 1e-3);
+    }
+
+    /// Removes every entry
+    pub fn clear(&self) {
+        self.entries.write().unwrap().clear();
+    }
+
+    /// Returns the number of keys in the table
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    /// Returns true if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Eq + Hash> Default for HistoryTable<K> {
+    /// A table that halves old evidence on every move
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
@@ -226,22 +226,40 @@
 //! ```bash
 //! cargo run --example tic_tac_toe
 //! cargo run --example connect_four
+//! cargo run --release --example perf_report
 //! ```
 
+pub mod adapters;
+pub mod analysis;
+#[cfg(feature = "bench-util")]
+pub mod bench;
+pub mod book;
 pub mod config;
+pub mod determinization;
+pub mod distributed;
+pub mod experience;
 pub mod game_state;
+pub mod history;
+pub mod macro_action;
 pub mod mcts;
 pub mod policy;
+pub mod policy_codec;
+pub mod result_cache;
+pub mod sanity;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod stats;
 pub mod tree;
+pub mod tuning;
 pub mod utils;
 
+pub use analysis::AnalysisSession;
 pub use config::MCTSConfig;
-pub use game_state::{Action, GameState, Player};
-pub use mcts::MCTS;
+pub use game_state::{Action, ActionKey, GameState, Player};
+pub use mcts::{SearchHandle, SearchId, SearchResult, StopToken, MCTS};
 pub use policy::{BackpropagationPolicy, SelectionPolicy, SimulationPolicy};
-pub use stats::SearchStatistics;
-pub use tree::{MCTSNode, NodePath};
+pub use stats::{AnalysisLine, IterationEvent, PlanStep, SearchOutcome, SearchStatistics};
+pub use tree::{MCTSNode, NodeHandle, NodePath};
 
 /// Error types for the MCTS algorithm
 #[derive(thiserror::Error, Debug)]
@@ -251,12 +269,46 @@ pub enum MCTSError {
     NoLegalActions,
 
     /// Search was stopped before completion
+    ///
+    /// Never returned by the search itself: a search stopped by time or by the
+    /// caller still returns its best action, with the reason recorded in
+    /// [`SearchStatistics::outcome`](crate::SearchStatistics::outcome).
+    #[deprecated(note = "check `SearchStatistics::outcome` instead")]
     #[error("Search stopped: {0}")]
     SearchStopped(String),
 
     /// Invalid configuration
     #[error("Invalid configuration: {0}")]
     InvalidConfiguration(String),
+
+    /// The game state or a policy contradicted what the tree recorded earlier
+    ///
+    /// Only returned when [`MCTSConfig::strict_mode`] is enabled, e.g. when
+    /// `get_legal_actions()` no longer matches the actions a node was created
+    /// with, or a policy picks an index that does not exist.
+    #[error("Domain inconsistency: {0}")]
+    DomainInconsistency(String),
+
+    /// The result validator rejected every legal root action
+    ///
+    /// Holds the reason given for the last rejected action. See
+    /// [`MCTS::with_result_validator`].
+    #[error("All actions rejected: {0}")]
+    ActionsRejected(String),
+
+    /// The search tree is corrupt
+    ///
+    /// Returned by [`MCTS::check_invariants`] with a description of the
+    /// first broken invariant found.
+    #[error("Tree invariant violated: {0}")]
+    InvariantViolation(String),
+
+    /// A transport between distributed searches failed
+    ///
+    /// Returned by [`Transport`](crate::distributed::Transport)
+    /// implementations, with a description of the failure.
+    #[error("Transport failed: {0}")]
+    Transport(String),
 }
 
 /// Result type for MCTS operations
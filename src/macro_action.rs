@@ -0,0 +1,246 @@
+//! Temporally extended actions (macro-actions)
+//!
+//! Some problems are easier to plan at a coarser granularity than raw moves:
+//! "walk to the door" instead of twenty single steps. A [`MacroAction`] is a
+//! sequence of primitive actions, and [`MacroState`] wraps any game so that
+//! its actions are macros produced by a generator you supply. Expansion and
+//! playouts apply each macro as one atomic step, so the tree's statistics
+//! are attributed to the macro as a whole.
+//!
+//! # Example
+//!
+//! ```
+//! # use arboriter_mcts::{GameState, Action, Player};
+//! use arboriter_mcts::macro_action::{MacroAction, MacroState};
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct Step(i32);
+//! # impl Action for Step { fn id(&self) -> usize { (self.0 + 1) as usize } }
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct P;
+//! # impl Player for P {}
+//! # #[derive(Clone)]
+//! # struct Corridor { position: i32 }
+//! # impl GameState for Corridor {
+//! #     type Action = Step;
+//! #     type Player = P;
+//! #     fn get_legal_actions(&self) -> Vec<Step> { vec![Step(-1), Step(1)] }
+//! #     fn apply_action(&self, a: &Step) -> Self { Corridor { position: self.position + a.0 } }
+//! #     fn is_terminal(&self) -> bool { self.position.abs() >= 10 }
+//! #     fn get_result(&self, _: &P) -> f64 { if self.position >= 10 { 1.0 } else { 0.0 } }
+//! #     fn get_current_player(&self) -> P { P }
+//! # }
+//!
+//! // Offer single steps and runs of five steps in either direction
+//! let state = MacroState::new(Corridor { position: 0 }, |state: &Corridor| {
+//!     let mut macros: Vec<_> = state
+//!         .get_legal_actions()
+//!         .into_iter()
+//!         .map(MacroAction::primitive)
+//!         .collect();
+//!     macros.push(MacroAction::new(vec![Step(1); 5]));
+//!     macros.push(MacroAction::new(vec![Step(-1); 5]));
+//!     macros
+//! });
+//!
+//! let after = state.apply_action(&MacroAction::new(vec![Step(1); 5]));
+//! assert_eq!(after.state().position, 5);
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::game_state::{Action, ActionKey, GameState};
+
+/// Kind of the [`ActionKey`] of a macro with more than one primitive
+pub const MACRO_KEY_KIND: u32 = u32::MAX;
+
+/// A sequence of primitive actions applied as one step
+///
+/// Its [`id`](Action::id) and [`key`](Action::key) are derived from those
+/// of its primitives, so two macros with the same primitives are the same
+/// action. A macro of one primitive keeps that primitive's id and key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroAction<A: Action> {
+    actions: Vec<A>,
+    id: usize,
+    key: ActionKey,
+}
+
+impl<A: Action> MacroAction<A> {
+    /// Creates a macro that applies `actions` in order
+    pub fn new(actions: Vec<A>) -> Self {
+        let id = match actions.as_slice() {
+            [single] => single.id(),
+            _ => {
+                let mut hasher = DefaultHasher::new();
+                for action in &actions {
+                    action.id().hash(&mut hasher);
+                }
+                hasher.finish() as usize
+            }
+        };
+        let key = match actions.as_slice() {
+            [single] => single.key(),
+            _ => {
+                let keys: Vec<ActionKey> = actions.iter().map(Action::key).collect();
+                ActionKey::hashed(MACRO_KEY_KIND, &keys)
+            }
+        };
+        MacroAction { actions, id, key }
+    }
+
+    /// Creates a macro consisting of a single primitive action
+    pub fn primitive(action: A) -> Self {
+        Self::new(vec![action])
+    }
+
+    /// Returns the primitive actions in the order they are applied
+    pub fn actions(&self) -> &[A] {
+        &self.actions
+    }
+
+    /// Returns the number of primitive actions
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns true if the macro has no primitive actions
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+impl<A: Action> Action for MacroAction<A> {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn key(&self) -> ActionKey {
+        self.key
+    }
+}
+
+/// Generates the macro-actions available in a state
+type MacroGenerator<S> =
+    Arc<dyn Fn(&S) -> Vec<MacroAction<<S as GameState>::Action>> + Send + Sync>;
+
+/// A game whose actions are macro-actions over another game's primitives
+///
+/// The legal actions of a `MacroState` are whatever the generator returns
+/// for the wrapped state; include [`MacroAction::primitive`] macros to keep
+/// single moves available. Applying a macro applies its primitives in order
+/// and stops early if the game ends or reaches a chance event, so a macro
+/// never skips over a terminal state. The generator is responsible for only
+/// offering macros whose primitives are legal in sequence.
+///
+/// Terminal checks, results, players, hashes and chance events are those of
+/// the wrapped state.
+pub struct MacroState<S: GameState> {
+    state: S,
+    generator: MacroGenerator<S>,
+}
+
+impl<S: GameState> MacroState<S> {
+    /// Wraps `state`, taking its actions from `generator`
+    pub fn new<F>(state: S, generator: F) -> Self
+    where
+        F: Fn(&S) -> Vec<MacroAction<S::Action>> + Send + Sync + 'static,
+    {
+        MacroState {
+            state,
+            generator: Arc::new(generator),
+        }
+    }
+
+    /// Returns the wrapped state
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Unwraps the state
+    pub fn into_inner(self) -> S {
+        self.state
+    }
+
+    /// Wraps another state with the same generator
+    pub fn with_state(&self, state: S) -> Self {
+        MacroState {
+            state,
+            generator: Arc::clone(&self.generator),
+        }
+    }
+}
+
+impl<S: GameState> Clone for MacroState<S> {
+    fn clone(&self) -> Self {
+        self.with_state(self.state.clone())
+    }
+}
+
+impl<S: GameState + fmt::Debug> fmt::Debug for MacroState<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MacroState")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: GameState> GameState for MacroState<S> {
+    type Action = MacroAction<S::Action>;
+    type Player = S::Player;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.state.is_terminal() {
+            return Vec::new();
+        }
+        (self.generator)(&self.state)
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut state = self.state.clone();
+        for primitive in action.actions() {
+            if state.is_terminal() || state.is_chance_event() {
+                break;
+            }
+            state = state.apply_action(primitive);
+        }
+        self.with_state(state)
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.state.is_terminal()
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        self.state.get_result(for_player)
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.state.get_current_player()
+    }
+
+    fn chance_outcomes(&self) -> Option<Vec<(Self, f64)>> {
+        let outcomes = self.state.chance_outcomes()?;
+        Some(
+            outcomes
+                .into_iter()
+                .map(|(state, probability)| (self.with_state(state), probability))
+                .collect(),
+        )
+    }
+
+    fn is_chance_event(&self) -> bool {
+        self.state.is_chance_event()
+    }
+
+    fn sample_chance_outcome(&self, rng: &mut dyn rand::RngCore) -> Option<Self> {
+        let state = self.state.sample_chance_outcome(rng)?;
+        Some(self.with_state(state))
+    }
+
+    fn hash(&self) -> u64 {
+        self.state.hash()
+    }
+}
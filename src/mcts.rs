@@ -3,39 +3,666 @@
 //! This module contains the core MCTS implementation, orchestrating the
 //! four phases of selection, expansion, simulation, and backpropagation.
 
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
 use crate::{
-    config::MCTSConfig,
-    game_state::GameState,
+    config::{
+        BestChildCriteria, Budget, GumbelRoot, MCTSConfig, MoveHysteresis, Perspective,
+        PlayoutLimits, PreSearch, ProgressiveWidening, RewardPerspective, RewardTransform,
+        RootPriorFilter, SubtreeGc, TieBreak, VirtualLoss, VirtualLossMode,
+    },
+    game_state::{Action, ActionKey, GameState},
+    history::HistoryTable,
     policy::{
-        backpropagation::{BackpropagationPolicy, StandardPolicy},
-        expansion::{ExpansionPolicy, RandomExpansionPolicy},
-        selection::{SelectionPolicy, UCB1Policy},
+        backpropagation::{BackpropContext, BackpropagationPolicy, StandardPolicy},
+        expansion::{ActionSampler, ExpansionPolicy, RandomExpansionPolicy},
+        opponent::{select_opponent_child, OpponentModel},
+        oracle::Oracle,
+        selection::{ChildScore, SelectionContext, SelectionPolicy, UCB1Policy},
         simulation::{RandomPolicy, SimulationPolicy},
     },
-    stats::SearchStatistics,
-    tree::{MCTSNode, NodePath},
+    policy_codec::PolicyCodec,
+    result_cache::ResultCache,
+    stats::{
+        ActionEvaluation, AnalysisLine, IterationEvent, PlanStep, ResultCacheStats,
+        RootActionStats, RootSnapshot, SearchOutcome, SearchStatistics,
+    },
+    tree::{check_subtree, visit_clock_millis, MCTSNode, NodePath, NodePool},
+    utils::with_seeded_rng,
     MCTSError, Result,
 };
 
-/// Standalone helper function to recursively recycle a subtree
+/// The nodes traversed by one iteration, as a list of path segments
+///
+/// Each entry is an absolute path from the root and the number of its leading
+/// indices that were *not* traversed. The first segment starts at the root
+/// (offset 0); in DAG mode, following a transposition link starts a new
+/// segment at the canonical node, so everything above it is skipped.
+type Route = Vec<(NodePath, usize)>;
+
+/// A leaf chosen by selection and expansion, waiting for its result
+struct Leaf<S: GameState> {
+    /// State the playout starts from
+    state: S,
+    /// Player the result is scored for
+    player: S::Player,
+    /// Result known without a playout, e.g. for a solved or terminal leaf
+    known: Option<f64>,
+}
+
+/// Callback that may veto the action a search is about to return
+type ResultValidator<S> =
+    Box<dyn Fn(&S, &<S as GameState>::Action) -> std::result::Result<(), String> + Send + Sync>;
+
+/// Callback run after every completed search
+type SearchCallback<S> = Box<dyn FnMut(&SearchResult<'_, S>) + Send>;
+
+/// Sequential halving plan of a search with a Gumbel root
+struct GumbelSchedule {
+    /// Remaining candidates: action key, Gumbel noise plus log prior, and
+    /// the child's visits when the search started
+    candidates: Vec<(ActionKey, f64, u64)>,
+    /// Visits each remaining candidate is due in this search so far
+    allotted: u64,
+    /// Iteration budget of the search
+    budget: usize,
+    /// Number of halving phases the budget is split into
+    phases: usize,
+}
+
+impl GumbelSchedule {
+    /// Visits each remaining candidate gets in one phase
+    fn phase_visits(&self) -> u64 {
+        (self.budget / (self.phases * self.candidates.len().max(1))).max(1) as u64
+    }
+}
+
+/// Enters a `tracing` span until the end of the enclosing block
 ///
-/// This needs to be outside the MCTS impl to avoid borrow checker issues
-fn recycle_subtree_recursive<S: GameState>(
-    mut node: MCTSNode<S>,
-    pool: &mut crate::tree::NodePool<S>,
+/// Expands to nothing unless the `tracing` feature is enabled.
+macro_rules! enter_span {
+    ($level:ident, $name:literal $(, $field:ident)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $name $(, $field)*).entered();
+    };
+}
+
+/// Applies `update` to every node on a path below `node`, deepest first
+fn update_path<S: GameState>(
+    node: &mut MCTSNode<S>,
+    indices: &[usize],
+    update: &dyn Fn(&mut MCTSNode<S>),
+) {
+    if let Some((&index, rest)) = indices.split_first() {
+        update_path(&mut node.children[index], rest, update);
+    }
+    update(node);
+}
+
+/// Applies `update` to every node traversed by a route, deepest first
+fn update_route<S: GameState>(
+    root: &mut MCTSNode<S>,
+    route: &Route,
+    update: &dyn Fn(&mut MCTSNode<S>),
 ) {
-    // First take all children
-    let mut children = std::mem::take(&mut node.children);
+    for (path, start) in route.iter().rev() {
+        // Navigate to the first traversed node of this segment
+        let mut node = &mut *root;
+        for &index in &path.indices[..*start] {
+            node = &mut node.children[index];
+        }
+        update_path(node, &path.indices[*start..], update);
+    }
+}
+
+/// Plies below the root searched by [`MCTS::advance_root`] for the new position
+pub const REUSE_DEPTH: usize = 2;
+
+/// Returns the path to the shallowest node within `depth` plies whose state
+/// has `hash`, skipping chance nodes
+fn find_position<S: GameState>(root: &MCTSNode<S>, hash: u64, depth: usize) -> Option<NodePath> {
+    let mut frontier = vec![(root, NodePath::new())];
+    for ply in 0..=depth {
+        if let Some((_, path)) = frontier
+            .iter()
+            .find(|(node, _)| !node.chance && node.state.hash() == hash)
+        {
+            return Some(path.clone());
+        }
+        if ply == depth {
+            break;
+        }
+        frontier = frontier
+            .into_iter()
+            .flat_map(|(node, path)| {
+                node.children.iter().enumerate().map(move |(index, child)| {
+                    let mut path = path.clone();
+                    path.push(index);
+                    (child, path)
+                })
+            })
+            .collect();
+    }
+    None
+}
+
+/// Lowers the depth of every node in a subtree moved `plies` levels up
+fn rebase_depths<S: GameState>(node: &mut MCTSNode<S>, plies: usize) {
+    node.depth -= plies;
+    for child in &mut node.children {
+        rebase_depths(child, plies);
+    }
+}
+
+/// Follows the most visited child from `node` until an unvisited node or a leaf
+fn continuation<S: GameState>(node: &MCTSNode<S>) -> Vec<S::Action> {
+    let mut moves = Vec::new();
+    let mut node = node;
+    while let Some(child) = most_visited_child(node) {
+        let Some(action) = &child.action else {
+            break;
+        };
+        moves.push(action.clone());
+        node = child;
+    }
+    moves
+}
+
+/// Returns the most visited child of `node`, if any child was visited
+fn most_visited_child<S: GameState>(node: &MCTSNode<S>) -> Option<&MCTSNode<S>> {
+    node.children
+        .iter()
+        .filter(|child| child.visits() > 0)
+        .max_by_key(|child| child.visits())
+}
+
+/// Records that every node traversed by a route was selected through at `now`
+fn touch_route<S: GameState>(root: &MCTSNode<S>, route: &Route, now: u64) {
+    for (path, start) in route {
+        let mut node = root;
+        for (depth, &index) in path.indices.iter().enumerate() {
+            if depth >= *start {
+                node.touch(now);
+            }
+            node = &node.children[index];
+        }
+        node.touch(now);
+    }
+}
+
+/// Collapses every stale node below `node` into a leaf
+///
+/// A collapsed node's descendants go to the pool, if there is one, and the
+/// actions of its children become untried again. Chance nodes are searched
+/// through but never collapsed, as their outcomes could not be expanded
+/// again. Returns the number of nodes removed.
+fn collapse_stale<S: GameState>(
+    node: &mut MCTSNode<S>,
+    is_stale: &dyn Fn(&MCTSNode<S>) -> bool,
+    pool: &mut Option<NodePool<S>>,
+) -> usize {
+    let mut removed = 0;
+    for child in &mut node.children {
+        if child.children.is_empty() {
+            continue;
+        }
+        if !child.chance && is_stale(child) {
+            for grandchild in std::mem::take(&mut child.children) {
+                removed += grandchild.subtree_size();
+                if let Some(action) = &grandchild.action {
+                    child.unexpanded_actions.push(action.clone());
+                }
+                if let Some(pool) = pool.as_mut() {
+                    pool.recycle_tree(grandchild);
+                }
+            }
+            child.exhausted = false;
+        } else {
+            removed += collapse_stale(child, is_stale, pool);
+        }
+    }
+    // A node with untried actions below it is no longer exhausted
+    if removed > 0 {
+        node.exhausted = false;
+    }
+    removed
+}
+
+/// Applies `update` to every node traversed by a route through shared references
+///
+/// Walks the route like [`update_route`], deepest first, but only needs `&`
+/// access to the tree: the nodes are gathered into `buffer` first, so each
+/// worker brings a path buffer of its own instead of the tree storing parent
+/// pointers. Stops at the first node `update` declines and returns false.
+#[cfg(feature = "parallel")]
+fn update_route_shared<'t, S: GameState>(
+    root: &'t MCTSNode<S>,
+    route: &Route,
+    buffer: &mut Vec<&'t MCTSNode<S>>,
+    update: &dyn Fn(&MCTSNode<S>) -> bool,
+) -> bool {
+    buffer.clear();
+    for (path, start) in route {
+        let mut node = root;
+        for (depth, &index) in path.indices.iter().enumerate() {
+            if depth >= *start {
+                buffer.push(node);
+            }
+            node = &node.children[index];
+        }
+        buffer.push(node);
+    }
+    buffer.iter().rev().all(|node| update(node))
+}
+
+/// Plays out `state` with `policy`, scoring the result for `player`
+///
+/// Policies that cannot score for another player than the one to move
+/// have their result flipped, as in a two-player zero-sum game.
+fn playout<S: GameState>(
+    policy: &dyn SimulationPolicy<S>,
+    limits: &PlayoutLimits,
+    state: &S,
+    player: &S::Player,
+) -> (f64, Vec<S::Action>) {
+    let simulate = || {
+        if limits.is_unlimited() {
+            policy.simulate(state)
+        } else {
+            policy.simulate_limited(state, limits)
+        }
+    };
+    if state.get_current_player() == *player {
+        return simulate();
+    }
+    match policy.simulate_for(state, player, limits) {
+        Some(outcome) => outcome,
+        None => {
+            let (result, trace) = simulate();
+            (1.0 - result, trace)
+        }
+    }
+}
+
+/// Returns true if selection should descend below `node` instead of growing it
+///
+/// A node grows while it has unexpanded actions, or can sample new ones, and
+/// a sampled chance node while it may draw more outcomes. Progressive
+/// widening and the branching cap let selection descend as soon as a node has
+/// as many children as they allow.
+fn is_widened<S: GameState>(node: &MCTSNode<S>, config: &MCTSConfig, sampling: bool) -> bool {
+    let at_limit = |widening: Option<ProgressiveWidening>| {
+        widening.is_some_and(|widening| {
+            !node.children.is_empty() && node.children.len() >= widening.max_children(node.visits())
+        })
+    };
+    if node.sampled_outcomes {
+        return at_limit(config.outcome_widening);
+    }
+    let at_cap = config
+        .max_children
+        .is_some_and(|max| node.children.len() >= max);
+    (node.is_fully_expanded() && !sampling) || at_cap || at_limit(config.action_widening)
+}
+
+/// Asks the oracle for the exact value of a node's state and marks it solved
+///
+/// The value is asked for `perspective`, or the player to move if that is
+/// `None`. Returns true if the oracle knew the value. Terminal states are
+/// left to their own result.
+fn consult_oracle<S: GameState>(
+    oracle: Option<&dyn Oracle<S>>,
+    node: &mut MCTSNode<S>,
+    perspective: Option<&S::Player>,
+) -> bool {
+    let Some(oracle) = oracle else {
+        return false;
+    };
+    if node.state.is_terminal() || node.chance {
+        return false;
+    }
+    let player = perspective
+        .cloned()
+        .unwrap_or_else(|| node.state.get_current_player());
+    match oracle.exact_value(&node.state, &player) {
+        Some(value) => {
+            node.solved = Some(value);
+            node.unexpanded_actions.clear();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Describes the first child a selection policy scored as NaN or worse
+///
+/// Reports the score's terms together with the child's and the node's
+/// statistics, which usually shows whether the NaN came from the policy,
+/// a prior or a corrupted value. Returns `None` if every score is usable.
+fn non_finite_score<S: GameState>(node: &MCTSNode<S>, scores: &[ChildScore]) -> Option<String> {
+    let (index, score) = scores
+        .iter()
+        .enumerate()
+        .find(|(_, score)| !score.is_finite())?;
+    let child = node.children.get(index)?;
+    Some(format!(
+        "selection policy scored child {} at depth {} as {} \
+         (exploitation {}, exploration {}, prior {}); \
+         child has {} visits, total reward {}, value {}; parent has {} visits",
+        index,
+        node.depth,
+        score.score,
+        score.exploitation,
+        score.exploration,
+        score.prior,
+        child.visits(),
+        child.total_reward(),
+        child.value(),
+        node.visits()
+    ))
+}
+
+/// Counts legal actions dropped as duplicates, warning about the first ones
+fn count_duplicates(statistics: &mut SearchStatistics, removed: usize) {
+    if removed == 0 {
+        return;
+    }
+    if statistics.duplicate_actions == 0 {
+        log::warn!(
+            "Dropped {} legal actions with duplicate keys; later ones are only counted",
+            removed
+        );
+    }
+    statistics.duplicate_actions += removed;
+}
+
+/// Replaces a node's value by the expectimax value of its visited children
+///
+/// Leaves keep their averaged results.
+fn back_up_expectimax<S: GameState>(node: &MCTSNode<S>) {
+    if node.children.iter().any(|child| child.visits() > 0) {
+        node.set_total_reward(node.expectimax_value() * node.visits() as f64);
+    }
+}
+
+/// Recomputes a node's `exhausted` flag during a deepest-first route walk
+///
+/// A node's subtree is exhausted when it has no unexpanded, non-terminal
+/// states left. Its children on the route were updated just before it, and
+/// `below` holds the flag of the node updated last, which for a followed
+/// transposition link is its canonical node.
+fn refresh_exhausted<S: GameState>(node: &mut MCTSNode<S>, sampling: bool, below: &Cell<bool>) {
+    node.exhausted = if node.state.is_terminal() || node.closes_cycle || node.solved.is_some() {
+        true
+    } else if node.sampled_outcomes || (sampling && !node.chance) {
+        false
+    } else if node.transposition.is_some() {
+        below.get()
+    } else {
+        node.is_fully_expanded()
+            && !node.children.is_empty()
+            && node.children.iter().all(|child| child.exhausted)
+    };
+    below.set(node.exhausted);
+}
+
+/// Widens a node's value bounds during a deepest-first route walk
+///
+/// `below` holds the value of the node updated last, the node's child on
+/// the route, and is handed this node's value for its parent.
+fn widen_with_child<S: GameState>(node: &MCTSNode<S>, below: &Cell<Option<f64>>) {
+    if let Some(value) = below.get() {
+        node.widen_value_bounds(value);
+    }
+    below.set((node.visits() > 0).then(|| node.value()));
+}
+
+/// Draws of an iteration made while selecting and expanding its leaf
+const SELECTION_STREAM: u64 = 0;
+/// Draws of an iteration made while playing out its leaf
+const PLAYOUT_STREAM: u64 = 1;
+/// Draws of the playouts spread over the root actions before a search
+const PRE_SEARCH_STREAM: u64 = 2;
+
+/// Returns the seed of one stream of an iteration's draws in a seeded search
+fn iteration_seed(seed: Option<u64>, iteration: usize, stream: u64) -> Option<u64> {
+    let mix = (iteration as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ stream.wrapping_mul(0xD1B5_4A32_D192_ED03);
+    seed.map(|seed| seed ^ mix)
+}
+
+/// Returns true if `target` is one of the nodes traversed in a route segment
+fn segment_contains(segment: &(NodePath, usize), target: &NodePath) -> bool {
+    let (path, start) = segment;
+    target.len() >= *start
+        && target.len() <= path.len()
+        && path.indices[..target.len()] == target.indices[..]
+}
+
+/// Handle for stopping a running search from another thread
+///
+/// Obtain one with [`MCTS::stop_token`] before starting the search. Calling
+/// [`stop`](StopToken::stop) makes the search finish its current iteration
+/// and return the best action found so far, with
+/// [`SearchOutcome::Stopped`] recorded in the statistics.
+#[derive(Debug, Clone, Default)]
+pub struct StopToken {
+    stopped: Arc<AtomicBool>,
+}
+
+impl StopToken {
+    /// Creates a new token that has not been triggered
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests that the search stop as soon as possible
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if a stop has been requested
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+
+    /// Clears a pending stop request
+    pub fn reset(&self) {
+        self.stopped.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Source of search identifiers, shared by every instance in the process
+static NEXT_SEARCH_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Identifies one search
+///
+/// Every search, on any instance, gets a new id when it starts; ids only
+/// grow within a process. Training loops use them to associate searches with
+/// episodes and telemetry, either from [`SearchResult::id`] in a
+/// [completion callback](MCTS::on_search_complete) or from
+/// [`MCTS::last_search_id`] after the search returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SearchId(u64);
+
+impl SearchId {
+    /// Takes the next unused id
+    fn next() -> Self {
+        SearchId(NEXT_SEARCH_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Returns the id as a number, e.g. to store in a log or database
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for SearchId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "search-{}", self.0)
+    }
+}
+
+/// A finished search, as passed to [`MCTS::on_search_complete`] callbacks
+///
+/// Everything is borrowed from the search instance; copy out what the
+/// callback needs to keep.
+pub struct SearchResult<'a, S: GameState> {
+    /// Identifies the search
+    pub id: SearchId,
+
+    /// The root of the searched tree
+    pub root: &'a MCTSNode<S>,
+
+    /// The action the search returns, or `None` if it returns an error
+    pub action: Option<&'a S::Action>,
+
+    /// How far the best root child leads the runner-up under the configured
+    /// criteria; see [`MCTS::runner_up_margin`]
+    pub margin: Option<f64>,
+
+    /// Statistics of the search
+    pub statistics: &'a SearchStatistics,
+}
+
+impl<S: GameState> SearchResult<'_, S> {
+    /// Returns each root child's action with its share of the root children's visits
+    ///
+    /// This is the usual policy target when training a network on search
+    /// results. The shares are all zero if no child was visited.
+    pub fn visit_distribution(&self) -> Vec<(&S::Action, f64)> {
+        let total: u64 = self.root.children.iter().map(|child| child.visits()).sum();
+        self.root
+            .children
+            .iter()
+            .filter_map(|child| {
+                let share = if total > 0 {
+                    child.visits() as f64 / total as f64
+                } else {
+                    0.0
+                };
+                child.action.as_ref().map(|action| (action, share))
+            })
+            .collect()
+    }
+
+    /// Returns the [`visit_distribution`](Self::visit_distribution) as a policy vector
+    ///
+    /// Each root child's share lands at the index `codec` gives its action,
+    /// ready to store as a network's training target. Actions that were not
+    /// expanded get 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::InvalidConfiguration` if the codec cannot place a
+    /// root child's action; see [`PolicyCodec::indices`].
+    pub fn policy_target<C: PolicyCodec<S::Action> + ?Sized>(&self, codec: &C) -> Result<Vec<f64>> {
+        codec.scatter(&self.visit_distribution())
+    }
+
+    /// Measures how far the search moved away from the root children's priors
+    ///
+    /// Returns the total variation distance between the priors, normalized
+    /// over the root children, and the [`visit_distribution`](Self::visit_distribution):
+    /// 0 when the search spread its visits exactly as the priors suggested,
+    /// approaching 1 when it visited only moves the priors dismissed. A high
+    /// value is a sign the prior network is wrong about the position, e.g.
+    /// to give such positions a larger budget in a curriculum. Returns 0 if
+    /// no child was visited.
+    pub fn prior_disagreement(&self) -> f64 {
+        let visits: u64 = self.root.children.iter().map(|child| child.visits()).sum();
+        let priors: f64 = self.root.children.iter().map(|child| child.prior()).sum();
+        if visits == 0 || priors <= 0.0 {
+            return 0.0;
+        }
+        let distance: f64 = self
+            .root
+            .children
+            .iter()
+            .map(|child| (child.prior() / priors - child.visits() as f64 / visits as f64).abs())
+            .sum();
+        distance / 2.0
+    }
+}
+
+impl<S: GameState> std::fmt::Debug for SearchResult<'_, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchResult")
+            .field("id", &self.id)
+            .field("action", &self.action)
+            .field("margin", &self.margin)
+            .field("iterations", &self.statistics.iterations)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Iterations between progress events when [`MCTS::spawn_search`] has to enable them
+const HANDLE_EVENT_INTERVAL: usize = 100;
+
+/// A search running on a background thread
+///
+/// Returned by [`MCTS::spawn_search`]. The handle can stop the search early,
+/// report the best action found so far, and wait for the final result. The
+/// search instance is handed back by [`join`](SearchHandle::join) so it can
+/// be reused, e.g. with [`MCTS::apply_move`].
+pub struct SearchHandle<S: GameState + 'static> {
+    stop_token: StopToken,
+    events: Receiver<IterationEvent<S::Action>>,
+    best_action: RefCell<Option<S::Action>>,
+    thread: JoinHandle<(MCTS<S>, Result<S::Action>)>,
+}
+
+impl<S: GameState + 'static> SearchHandle<S> {
+    /// Requests that the search stop after its current iteration
+    pub fn stop(&self) {
+        self.stop_token.stop();
+    }
+
+    /// Returns the best action as of the latest progress event
+    ///
+    /// This is `None` until the first event arrives. How fresh the answer is
+    /// depends on [`MCTSConfig::event_interval`].
+    pub fn best_action_now(&self) -> Option<S::Action> {
+        for event in self.events.try_iter() {
+            if event.best_action_so_far.is_some() {
+                *self.best_action.borrow_mut() = event.best_action_so_far;
+            }
+        }
+        self.best_action.borrow().clone()
+    }
 
-    // Recursively recycle each child
-    for child in children.drain(..) {
-        recycle_subtree_recursive(child, pool);
+    /// Returns true if the search thread has finished
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Waits for the search to finish
+    ///
+    /// Returns the search instance together with the search result. Panics in
+    /// the search thread are propagated to the caller.
+    pub fn join(self) -> (MCTS<S>, Result<S::Action>) {
+        match self.thread.join() {
+            Ok(finished) => finished,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
     }
+}
 
-    // Now recycle the node itself
-    pool.recycle_node(node);
+impl<S: GameState + 'static> std::fmt::Debug for SearchHandle<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchHandle")
+            .field("stop_token", &self.stop_token)
+            .field("best_action", &self.best_action)
+            .finish()
+    }
 }
 
 /// The main Monte Carlo Tree Search implementation
@@ -64,8 +691,80 @@ pub struct MCTS<S: GameState + 'static> {
     /// Policy for expansion (PUCT fix)
     pub expansion_policy: Box<dyn ExpansionPolicy<S>>,
 
+    /// Model of the opponent's replies and its weight in selection, if any
+    opponent_model: Option<(Box<dyn OpponentModel<S>>, f64)>,
+
+    /// Source of exact values for some states, if any
+    oracle: Option<Box<dyn Oracle<S>>>,
+
+    /// Player every result is computed for, overriding the configured perspective
+    perspective_player: Option<S::Player>,
+
+    /// Player that must be to move at the root whenever a search starts
+    expected_root_player: Option<S::Player>,
+
+    /// Source of new actions once a node's listed actions are used up, if any
+    action_sampler: Option<Box<dyn ActionSampler<S>>>,
+
     /// Node pool for efficient node allocation
     node_pool: Option<crate::tree::NodePool<S>>,
+
+    /// Results of terminal states, if the result cache is enabled
+    result_cache: Option<ResultCache<S::Player>>,
+
+    /// Random number generator handed to policies through their contexts
+    rng: StdRng,
+
+    /// Canonical node path for each state hash seen (DAG mode only)
+    transpositions: HashMap<u64, NodePath>,
+
+    /// Token the caller can use to stop a running search
+    stop_token: StopToken,
+
+    /// Channel for periodic progress events, if anyone subscribed
+    event_sender: Option<Sender<IterationEvent<S::Action>>>,
+
+    /// History table shared by all phases, if one was attached
+    history: Option<Arc<HistoryTable>>,
+
+    /// Whether the root was warm-started since the last search
+    ///
+    /// The next search then keeps the existing tree instead of recycling it.
+    warm_started: bool,
+
+    /// Root actions guaranteed a minimum fraction of the root's visits
+    root_constraints: Vec<(S::Action, f64)>,
+
+    /// Line of play the last search expected from the current root
+    ///
+    /// Moves that follow it advance it; any other change of root clears it.
+    plan: Vec<S::Action>,
+
+    /// Sequential halving plan of the running search, with a Gumbel root
+    gumbel: Option<GumbelSchedule>,
+
+    /// Callback that may veto the best action, if one was attached
+    result_validator: Option<ResultValidator<S>>,
+
+    /// Callbacks run after every completed search
+    search_callbacks: Vec<SearchCallback<S>>,
+
+    /// Identifies the latest search, once one has started
+    last_search_id: Option<SearchId>,
+
+    /// Route buffer reused by every iteration, so descending the tree does
+    /// not allocate once its paths have grown to the tree's depth
+    scratch_route: Route,
+
+    /// Nodes below the root when the current search started, counted only
+    /// when a memory limit is configured
+    nodes_before_search: usize,
+
+    /// Whether the tree is at the memory limit, so expansion is paused
+    expansion_frozen: bool,
+
+    /// When stale subtrees were last collected
+    last_gc: Instant,
 }
 
 impl<S: GameState + 'static> MCTS<S> {
@@ -88,6 +787,10 @@ impl<S: GameState + 'static> MCTS<S> {
         // Create an initial node pool - disabled by default
         let node_pool = None;
 
+        let rng = config
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+
         MCTS {
             root,
             config,
@@ -96,7 +799,29 @@ impl<S: GameState + 'static> MCTS<S> {
             simulation_policy,
             backpropagation_policy,
             expansion_policy,
+            action_sampler: None,
+            opponent_model: None,
+            oracle: None,
+            perspective_player: None,
+            expected_root_player: None,
             node_pool,
+            result_cache: None,
+            rng,
+            transpositions: HashMap::new(),
+            stop_token: StopToken::new(),
+            event_sender: None,
+            history: None,
+            warm_started: false,
+            root_constraints: Vec::new(),
+            plan: Vec::new(),
+            gumbel: None,
+            result_validator: None,
+            search_callbacks: Vec::new(),
+            last_search_id: None,
+            scratch_route: Route::new(),
+            nodes_before_search: 0,
+            expansion_frozen: false,
+            last_gc: Instant::now(),
         }
     }
 
@@ -109,12 +834,13 @@ impl<S: GameState + 'static> MCTS<S> {
     ///
     /// * `initial_state` - The initial game state
     /// * `config` - Configuration for the search
-    /// * `initial_pool_size` - Initial number of nodes to pre-allocate
+    /// * `initial_pool_size` - Number of free nodes to reserve room for
     pub fn with_node_pool(initial_state: S, config: MCTSConfig, initial_pool_size: usize) -> Self {
-        let mut mcts = Self::new(initial_state.clone(), config);
+        let mut mcts = Self::new(initial_state, config);
 
         // Initialize the regular node pool
-        let pool = crate::tree::NodePool::new(initial_state, initial_pool_size);
+        let mut pool = crate::tree::NodePool::new(initial_pool_size);
+        pool.set_max_free(mcts.config.node_pool_max_free);
         mcts.node_pool = Some(pool);
 
         mcts
@@ -128,11 +854,103 @@ impl<S: GameState + 'static> MCTS<S> {
         self
     }
 
+    /// Attaches a history table that every iteration records its actions in
+    ///
+    /// The search records the reward of every action on the tree path and in
+    /// the playout, exposes the table to selection policies through
+    /// [`SelectionContext::history`], and decays it whenever a move is played
+    /// with [`apply_move`](Self::apply_move). Rollout policies that use it,
+    /// like [`MastPolicy`](crate::policy::simulation::MastPolicy), are given
+    /// their own handle to the same table.
+    pub fn with_history(mut self, history: Arc<HistoryTable>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Attaches a callback that must accept the action a search returns
+    ///
+    /// The validator receives the root state and a candidate action and
+    /// returns `Err(reason)` to reject it. Rejected candidates are skipped in
+    /// favour of the next-best root child, then the unexpanded root actions,
+    /// so engines can honor rules the `GameState` does not encode, such as
+    /// anti-repetition rules or tournament adjudication. Applies wherever a
+    /// best action is chosen, including [`best_action`](Self::best_action).
+    pub fn with_result_validator<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&S, &S::Action) -> std::result::Result<(), String> + Send + Sync + 'static,
+    {
+        self.result_validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Registers a callback to run after every completed search
+    ///
+    /// Callbacks run in registration order once the best action has been
+    /// chosen, on the thread that ran the search, and receive a
+    /// [`SearchResult`] with the search's [`SearchId`], tree and statistics.
+    /// Searches that fail before their first iteration, e.g. for a root
+    /// without legal actions, do not complete and run no callbacks. Use them
+    /// to log telemetry or feed a training loop without wrapping every call
+    /// to [`search`](Self::search).
+    pub fn on_search_complete<F>(&mut self, callback: F)
+    where
+        F: FnMut(&SearchResult<'_, S>) + Send + 'static,
+    {
+        self.search_callbacks.push(Box::new(callback));
+    }
+
+    /// Removes every callback registered with [`on_search_complete`](Self::on_search_complete)
+    pub fn clear_search_callbacks(&mut self) {
+        self.search_callbacks.clear();
+    }
+
+    /// Returns the id of the latest search, or `None` before the first one
+    ///
+    /// The id is assigned when a search starts, so it is also available
+    /// while a [spawned search](Self::spawn_search) runs its callbacks.
+    pub fn last_search_id(&self) -> Option<SearchId> {
+        self.last_search_id
+    }
+
+    /// Returns the attached history table, if any
+    pub fn history(&self) -> Option<&Arc<HistoryTable>> {
+        self.history.as_ref()
+    }
+
     /// Returns a reference to the root node (useful for inspection/testing)
     pub fn root(&self) -> &MCTSNode<S> {
         &self.root
     }
 
+    /// Breaks down how the selection policy scores the children of a node
+    ///
+    /// Returns one [`ChildScore`] per child of the node at `path`, in child
+    /// order, as the configured selection policy would score them for the
+    /// next iteration. Returns `None` if `path` does not address a node or
+    /// the policy does not report scores. Nodes where an
+    /// [opponent model](Self::with_opponent_model) takes over are still
+    /// scored by the selection policy.
+    pub fn child_scores(&self, path: &NodePath) -> Option<Vec<ChildScore>> {
+        let node = self.root.descendant(path)?;
+        // Scoring never draws from it; keep the search's RNG untouched
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut context = SelectionContext {
+            depth: node.depth,
+            path: &path.indices,
+            iteration: self.statistics.iterations,
+            rng: &mut rng,
+            history: self.history.as_deref(),
+        };
+        self.selection_policy.child_scores(node, &mut context)
+    }
+
+    /// Returns the player to move at the root
+    ///
+    /// This is the player the search's best action is chosen for.
+    pub fn root_player(&self) -> &S::Player {
+        &self.root.player
+    }
+
     /// Sets the simulation policy to use
     pub fn with_simulation_policy<P: SimulationPolicy<S> + 'static>(mut self, policy: P) -> Self {
         self.simulation_policy = Box::new(policy);
@@ -157,21 +975,85 @@ impl<S: GameState + 'static> MCTS<S> {
         self
     }
 
+    /// Biases selection at the opponent's nodes towards the moves `model` predicts
+    ///
+    /// Wherever a player other than the root's is to move, the selection
+    /// policy is replaced by UCB1 mixed with the model's predicted
+    /// probabilities, `weight` in `[0, 1]` controlling how much the
+    /// prediction counts (see
+    /// [`select_opponent_child`](crate::policy::opponent::select_opponent_child)).
+    /// Combine it with [`RewardPerspective::PerPlayer`](crate::config::RewardPerspective::PerPlayer)
+    /// so that UCB1 at those nodes models an opponent playing for itself.
+    pub fn with_opponent_model<M: OpponentModel<S> + 'static>(
+        mut self,
+        model: M,
+        weight: f64,
+    ) -> Self {
+        self.opponent_model = Some((Box::new(model), weight.clamp(0.0, 1.0)));
+        self
+    }
+
+    /// Checks newly expanded states against `oracle` and scores known ones exactly
+    ///
+    /// A state the oracle knows becomes a solved leaf: it is never expanded,
+    /// and its exact value is backpropagated instead of a playout result
+    /// whenever the search reaches it. See [`Oracle`].
+    pub fn with_oracle<O: Oracle<S> + 'static>(mut self, oracle: O) -> Self {
+        self.oracle = Some(Box::new(oracle));
+        self
+    }
+
+    /// Computes every simulation result for `player`
+    ///
+    /// Overrides the configured [`Perspective`](crate::config::Perspective):
+    /// playouts, terminal states and oracles are scored for `player`
+    /// wherever the search is, e.g. to plan for one side of a game with more
+    /// than two players.
+    pub fn with_perspective_player(mut self, player: S::Player) -> Self {
+        self.perspective_player = Some(player);
+        self
+    }
+
+    /// Requires `player` to be the player to move at the root of every search
+    ///
+    /// A search started from a root where another player is to move fails
+    /// with `MCTSError::InvalidConfiguration` instead of quietly optimizing
+    /// for the wrong side, e.g. after [`reset_root`](Self::reset_root) is
+    /// called with a state one ply off.
+    pub fn with_expected_root_player(mut self, player: S::Player) -> Self {
+        self.expected_root_player = Some(player);
+        self
+    }
+
+    /// Draws new actions from `sampler` once a node's listed actions are used up
+    ///
+    /// For continuous or huge action spaces; see [`ActionSampler`]. Without
+    /// [progressive widening](MCTSConfig::with_progressive_widening) or a
+    /// [branching cap](MCTSConfig::with_max_children) every visit to a node
+    /// draws a new action, so the tree never gets deeper than one level.
+    /// Strict mode does not check sampled actions against the legal ones.
+    pub fn with_action_sampler<A: ActionSampler<S> + 'static>(mut self, sampler: A) -> Self {
+        self.action_sampler = Some(Box::new(sampler));
+        self
+    }
+
     /// Runs the search algorithm and returns the best action
     pub fn search(&mut self) -> Result<S::Action> {
         // Initialize node pool if it's enabled in the config but not created yet
         if self.node_pool.is_none() && self.config.node_pool_size > 0 {
-            self.node_pool = Some(crate::tree::NodePool::new(
-                self.root.state.clone(),
-                self.config.node_pool_size,
-            ));
+            let mut pool = crate::tree::NodePool::new(self.config.node_pool_size);
+            pool.set_max_free(self.config.node_pool_max_free);
+            self.node_pool = Some(pool);
         }
 
-        // First recycle the previous search tree if we have one
-        self.recycle_tree();
+        // First recycle the previous search tree if we have one, unless the
+        // caller seeded the root for this search
+        if !std::mem::take(&mut self.warm_started) {
+            self.recycle_tree();
+        }
 
         // Perform the search
-        let result = self.search_for_iterations(self.config.max_iterations);
+        let result = self.search_within(self.config.budget().iterations());
 
         // If using node pooling, we need to select the best action before recycling
         let best_action = if let Ok(action) = &result {
@@ -188,58 +1070,265 @@ impl<S: GameState + 'static> MCTS<S> {
     }
 
     /// Runs the search for the specified number of iterations
+    ///
+    /// The configured [`max_time`](MCTSConfig::max_time) still applies.
     pub fn search_for_iterations(&mut self, iterations: usize) -> Result<S::Action> {
-        // Reset statistics
-        self.statistics = SearchStatistics::new();
+        self.search_within(Some(iterations))
+    }
 
-        // Check if we have any legal actions
-        if self.root.unexpanded_actions.is_empty() && self.root.children.is_empty() {
-            return Err(MCTSError::NoLegalActions);
-        }
+    /// Continues searching the current tree within `budget`
+    ///
+    /// Unlike [`search`](Self::search), this never recycles the tree, and the
+    /// budget replaces the configured limits for this search only. Call it
+    /// repeatedly to deepen the analysis of one position in steps.
+    pub fn continue_search(&mut self, budget: Budget) -> Result<S::Action> {
+        let max_time = std::mem::replace(&mut self.config.max_time, budget.time());
+        let result = self.search_within(budget.iterations());
+        self.config.max_time = max_time;
+        result
+    }
 
-        let start_time = Instant::now();
-        let max_time = self.config.max_time;
+    /// Runs the search for at most `iterations` iterations, or without an
+    /// iteration limit if `None`
+    fn search_within(&mut self, iterations: Option<usize>) -> Result<S::Action> {
+        // Unlimited searches show up as usize::MAX iterations in traces
+        #[cfg(feature = "tracing")]
+        let max_iterations = iterations.unwrap_or(usize::MAX);
+        enter_span!(DEBUG, "search", max_iterations);
+
+        // Reset statistics, keeping the reward range the tree's values were
+        // normalized with
+        let reward_range = self.statistics.reward_range;
+        self.statistics = SearchStatistics::new();
+        self.statistics.reward_range = reward_range;
+        self.statistics.budget = Budget::from_limits(iterations, self.config.max_time);
+        let search_id = SearchId::next();
+        self.last_search_id = Some(search_id);
+
+        // A reused tree counts against the memory limit from the start
+        self.nodes_before_search = match self.config.max_memory_bytes {
+            Some(_) => self.root.subtree_size() - 1,
+            None => 0,
+        };
+        self.expansion_frozen = false;
+
+        // The result cache outlives searches; its counters are per search
+        if self.config.result_cache_size > 0 {
+            let size = self.config.result_cache_size;
+            let cache = self
+                .result_cache
+                .get_or_insert_with(|| ResultCache::new(size));
+            self.statistics.result_cache_stats = Some(ResultCacheStats {
+                capacity: cache.capacity(),
+                entries: cache.len(),
+                ..ResultCacheStats::default()
+            });
+        }
 
-        // Main search loop
-        for i in 0..iterations {
-            // Check time constraints if set
-            if let Some(max_duration) = max_time {
-                if start_time.elapsed() >= max_duration {
-                    self.statistics.stopped_early = true;
-                    println!("Search stopped early due to time limit");
-                    break;
-                }
+        if let Some(expected) = &self.expected_root_player {
+            if *expected != self.root.player {
+                return Err(MCTSError::InvalidConfiguration(format!(
+                    "expected {:?} to move at the root, but {:?} is",
+                    expected, self.root.player
+                )));
             }
+        }
 
-            // Execute one iteration of MCTS
-            self.execute_iteration()?;
+        if self.config.expectimax && self.config.reward_perspective != RewardPerspective::Shared {
+            return Err(MCTSError::InvalidConfiguration(
+                "expectimax backups need RewardPerspective::Shared".to_string(),
+            ));
+        }
+        if self.config.gumbel_root.is_some() && iterations.is_none() {
+            return Err(MCTSError::InvalidConfiguration(
+                "Gumbel root selection needs an iteration budget".to_string(),
+            ));
+        }
+
+        // Check if we have any legal actions
+        if self.root.chance {
+            return Err(MCTSError::InvalidConfiguration(
+                "the root is waiting for a chance event; reset it to the realized outcome"
+                    .to_string(),
+            ));
+        }
+        if self.config.deduplicate_actions {
+            let removed = self.root.dedup_unexpanded_actions();
+            count_duplicates(&mut self.statistics, removed);
+        }
+        if let Some(filter) = self.config.root_prior_filter {
+            self.filter_root_actions(filter);
+        }
+        let sampling = self.action_sampler.is_some() && !self.root.state.is_terminal();
+        if self.root.unexpanded_actions.is_empty() && self.root.children.is_empty() && !sampling {
+            return Err(MCTSError::NoLegalActions);
+        }
+
+        // A fresh root may first be ordered and pruned by a shallow pre-search,
+        // which needs an iteration budget to take its share of
+        let mut iterations = iterations;
+        if let (Some(pre_search), Some(budget)) = (self.config.pre_search, iterations) {
+            iterations = Some(budget - self.pre_search(pre_search, budget)?);
+        }
+
+        // Constrained root actions need a child before their budget can be enforced
+        for index in 0..self.root_constraints.len() {
+            let action = self.root_constraints[index].0.clone();
+            if self.root.find_child(&action).is_none() && self.expand_root_action(&action).is_some()
+            {
+                self.statistics.tree_size += 1;
+            }
+        }
+
+        // A Gumbel root samples and expands its candidates up front
+        self.gumbel = None;
+        if let (Some(gumbel), Some(budget)) = (self.config.gumbel_root, iterations) {
+            self.gumbel = self.plan_gumbel(gumbel, budget);
+        }
+
+        // Register the root so transpositions back to it are merged
+        if self.config.dag_mode && self.transpositions.is_empty() {
+            let hash = self.root.state.hash();
+            if hash != 0 {
+                self.transpositions.insert(hash, NodePath::new());
+            }
+        }
+
+        self.notify_search_start();
+
+        let start_time = Instant::now();
+        let max_time = self.config.max_time;
+        let time_check_interval = self.config.time_check_interval.max(1);
+        let batch_size = self.config.rollout_batch_size.max(1);
+        let limit = iterations.unwrap_or(usize::MAX);
+        let mut next_time_check = 0;
+        let mut next_gc_check = 0;
+
+        // Main search loop
+        let mut i = 0;
+        while i < limit {
+            // Check for a stop request from the caller
+            if self.stop_token.is_stopped() {
+                self.statistics.outcome = SearchOutcome::Stopped;
+                log::debug!("Search stopped by caller after {} iterations", i);
+                break;
+            }
+
+            // Check time constraints if set, every few iterations
+            if let Some(max_duration) = max_time.filter(|_| i >= next_time_check) {
+                next_time_check = i + time_check_interval;
+                if start_time.elapsed() >= max_duration {
+                    self.statistics.stopped_early = true;
+                    self.statistics.outcome = SearchOutcome::TimeLimit;
+                    log::debug!("Search stopped early due to time limit");
+                    break;
+                }
+            }
+
+            // Collect stale subtrees on the same cadence as the time checks
+            if let Some(gc) = self.config.subtree_gc.filter(|_| i >= next_gc_check) {
+                next_gc_check = i + time_check_interval;
+                if self.last_gc.elapsed() >= gc.interval {
+                    self.collect_garbage();
+                }
+            }
+
+            // Execute one iteration of MCTS, or a batch of them
+            let completed = if batch_size > 1 {
+                self.execute_batch(i, batch_size.min(limit - i))?
+            } else {
+                self.execute_iteration(i)?;
+                1
+            };
 
             // Update stats
-            self.statistics.iterations = i + 1;
+            let interval = self.config.event_interval;
+            for done in i + 1..=i + completed {
+                self.statistics.iterations = done;
+                if interval > 0 && done % interval == 0 {
+                    self.send_event();
+                }
+            }
+            i += completed;
+
+            if self.root.exhausted {
+                self.statistics.tree_exhausted = true;
+                if self.config.stop_when_exhausted {
+                    self.statistics.outcome = SearchOutcome::TreeExhausted;
+                    log::debug!("Search tree exhausted after {} iterations", i);
+                    break;
+                }
+            }
         }
 
         self.statistics.total_time = start_time.elapsed();
 
         // Collect node pool statistics if available
-        if let Some(pool) = &self.node_pool {
-            let stats = pool.get_stats();
-            self.statistics.update_node_pool_stats(
-                stats.total_created,
-                pool.available_nodes(),
-                stats.total_allocations,
-                stats.total_recycled,
-            );
+        self.record_pool_stats();
+        self.debug_check_invariants();
+
+        // A stop request only applies to the search it interrupted
+        self.stop_token.reset();
+
+        self.notify_search_end();
+
+        #[cfg(feature = "metrics")]
+        self.statistics.record_metrics();
+
+        // Select the best action based on configured criteria, or the
+        // Gumbel candidate that survived the halving
+        let schedule = self.gumbel.take();
+        let result = match schedule.and_then(|schedule| self.gumbel_choice(&schedule)) {
+            Some(result) => result,
+            None => self.best_action(self.config.best_child_criteria),
+        };
+        if let Ok(action) = &result {
+            self.plan = self.line_from_root(action);
+        }
+        self.notify_search_complete(search_id, result.as_ref().ok());
+        result
+    }
+
+    /// Runs every completion callback on the finished search
+    fn notify_search_complete(&mut self, id: SearchId, action: Option<&S::Action>) {
+        if self.search_callbacks.is_empty() {
+            return;
+        }
+        let finished = SearchResult {
+            id,
+            root: &self.root,
+            action,
+            margin: self.runner_up_margin(self.config.best_child_criteria),
+            statistics: &self.statistics,
+        };
+        for callback in &mut self.search_callbacks {
+            callback(&finished);
         }
+    }
+
+    /// Signals the start of a search to every policy
+    fn notify_search_start(&mut self) {
+        let root = &self.root.state;
+        self.selection_policy.on_search_start(root);
+        self.expansion_policy.on_search_start(root);
+        self.simulation_policy.on_search_start(root);
+        self.backpropagation_policy.on_search_start(root);
+    }
 
-        // Select the best action based on configured criteria
-        self.select_best_action()
+    /// Signals the end of a search to every policy
+    fn notify_search_end(&mut self) {
+        let root = &self.root;
+        self.selection_policy.on_search_end(root);
+        self.expansion_policy.on_search_end(root);
+        self.simulation_policy.on_search_end(root);
+        self.backpropagation_policy.on_search_end(root);
     }
 
     /// Runs the search for the specified duration
     ///
-    /// Creates a new MCTS search with the given time limit and runs it.
-    /// This is useful when you want to run a time-limited search without
-    /// changing the original configuration.
+    /// Runs a search with the given time limit, temporarily overriding the
+    /// configured `max_time`. This is useful when you want to run a
+    /// time-limited search without changing the original configuration.
     ///
     /// # Arguments
     ///
@@ -250,87 +1339,517 @@ impl<S: GameState + 'static> MCTS<S> {
     /// * `Ok(action)` - The best action found during the search
     /// * `Err(MCTSError)` - If the search couldn't complete successfully
     pub fn search_for_time(&mut self, duration: Duration) -> Result<S::Action> {
-        // First recycle the previous search tree
-        self.recycle_tree();
+        let original_config = self.config.clone();
+        self.config.max_time = Some(duration);
 
-        let mut config = self.config.clone();
-        config.max_time = Some(duration);
+        // Search in place so the node pool and any policy state carry over
+        let result = self.search();
 
-        // Keep a reasonable max iterations to prevent runaway search
-        // if time checking fails for some reason
-        if config.max_iterations == usize::MAX {
-            config.max_iterations = 1_000_000;
-        }
+        self.config = original_config;
+        result
+    }
+
+    /// Execute a single iteration of the MCTS algorithm
+    fn execute_iteration(&mut self, iteration: usize) -> Result<()> {
+        enter_span!(TRACE, "iteration", iteration);
+
+        // Borrow the scratch route for this iteration and hand it back even
+        // if a phase fails, so its allocations survive into the next one
+        let mut route = std::mem::take(&mut self.scratch_route);
+        let outcome = self.run_phases(&mut route, iteration);
+        self.scratch_route = route;
+        outcome
+    }
 
-        // Create a new MCTS instance with appropriate node pool based on configuration
-        let mut mcts = if config.node_pool_size > 0 {
-            // We need to extract the values before moving config
-            let node_pool_size = config.node_pool_size;
+    /// Runs the four phases of one iteration, building the route in `route`
+    fn run_phases(&mut self, route: &mut Route, iteration: usize) -> Result<()> {
+        let seed = self.config.seed;
+
+        // 1. Selection and 2. expansion phases
+        let leaf = with_seeded_rng(iteration_seed(seed, iteration, SELECTION_STREAM), || {
+            self.select_leaf(route, iteration)
+        })?;
+
+        // 3. Simulation phase, unless the leaf's result is already known
+        let simulation_start = Instant::now();
+        let (result, trace) = match leaf.known {
+            Some(result) => (result, Vec::new()),
+            None => with_seeded_rng(iteration_seed(seed, iteration, PLAYOUT_STREAM), || {
+                self.simulation_for(&leaf.state, &leaf.player)
+            }),
+        };
+        let simulated = Instant::now();
 
-            // If we already have a regular node pool, create a similar one
-            if self.node_pool.is_some() {
-                // Create a new instance using the clone of the state
-                let mut new_mcts = MCTS::new(self.root.state.clone(), config);
+        // 4. Backpropagation phase
+        self.complete_iteration(route, &leaf, result, &trace, iteration)?;
 
-                // Create a new node pool with the same size
-                new_mcts.node_pool = Some(crate::tree::NodePool::new(
-                    self.root.state.clone(),
-                    node_pool_size,
-                ));
+        let phase_times = &mut self.statistics.phase_times;
+        phase_times.simulation += simulated - simulation_start;
+        phase_times.backpropagation += simulated.elapsed();
 
-                new_mcts
-            } else {
-                // Create a fresh instance with a new pool
-                MCTS::with_node_pool(self.root.state.clone(), config, node_pool_size)
+        Ok(())
+    }
+
+    /// Runs up to `size` iterations as one batch, returning how many ran
+    ///
+    /// Distinct leaves are selected one after another with a virtual loss on
+    /// the route to each, their playouts run together (in parallel with the
+    /// `parallel` feature), and the results are backpropagated in the order
+    /// the leaves were selected. The batch ends early if selection returns to
+    /// a leaf that is already in it. See [`MCTSConfig::rollout_batch_size`].
+    fn execute_batch(&mut self, first_iteration: usize, size: usize) -> Result<usize> {
+        enter_span!(TRACE, "batch", first_iteration, size);
+
+        let seed = self.config.seed;
+        let mut batch: Vec<(Route, Leaf<S>)> = Vec::with_capacity(size);
+        while batch.len() < size {
+            let mut route = Route::new();
+            let iteration = first_iteration + batch.len();
+            let selected =
+                with_seeded_rng(iteration_seed(seed, iteration, SELECTION_STREAM), || {
+                    self.select_leaf(&mut route, iteration)
+                });
+            let leaf = match selected {
+                Ok(leaf) => leaf,
+                Err(error) => {
+                    for (route, _) in &batch {
+                        self.set_virtual_loss(route, false);
+                    }
+                    return Err(error);
+                }
+            };
+            let leaf_path = &route[route.len() - 1].0;
+            if batch
+                .iter()
+                .any(|(other, _)| other[other.len() - 1].0.indices == leaf_path.indices)
+            {
+                self.statistics.batch_collisions += 1;
+                break;
+            }
+            self.set_virtual_loss(&route, true);
+            batch.push((route, leaf));
+        }
+        self.statistics.rollout_batches += 1;
+
+        // Playouts only read their own leaf state, so they can run side by
+        // side; each draws from its own iteration's seed in a seeded search
+        let simulation_start = Instant::now();
+        let policy = &*self.simulation_policy;
+        let limits = self.config.playout_limits();
+        let play = |(offset, (_, leaf)): (usize, &(Route, Leaf<S>))| match leaf.known {
+            Some(result) => (result, Vec::new()),
+            None => {
+                let seed = iteration_seed(seed, first_iteration + offset, PLAYOUT_STREAM);
+                with_seeded_rng(seed, || playout(policy, &limits, &leaf.state, &leaf.player))
+            }
+        };
+        #[cfg(feature = "parallel")]
+        let outcomes: Vec<(f64, Vec<S::Action>)> = {
+            use rayon::prelude::*;
+            batch.par_iter().enumerate().map(play).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let outcomes: Vec<(f64, Vec<S::Action>)> = batch.iter().enumerate().map(play).collect();
+        let simulated = Instant::now();
+
+        let mut results: Vec<Option<f64>> = Vec::with_capacity(outcomes.len());
+        for (result, _) in &outcomes {
+            match self.accept_reward(*result) {
+                Ok(result) => results.push(result),
+                Err(error) => {
+                    for (route, _) in &batch {
+                        self.set_virtual_loss(route, false);
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        let revert_each = self.config.virtual_loss.revert_on_backpropagation;
+        if !revert_each {
+            for (route, _) in &batch {
+                self.set_virtual_loss(route, false);
             }
+        }
+
+        // Results are backpropagated side by side where the policy allows it,
+        // and one by one in selection order otherwise. Seeded searches go one
+        // by one, as concurrent updates would sum the rewards in varying
+        // order, and so do expectimax backups, which rewrite a node's total
+        // from its children instead of adding to it
+        #[cfg(feature = "parallel")]
+        let shared = if revert_each
+            || self.config.cache_selection_stats
+            || self.config.expectimax
+            || seed.is_some()
+        {
+            vec![false; batch.len()]
         } else {
-            MCTS::new(self.root.state.clone(), config)
+            self.backpropagate_shared(&batch, &outcomes, &results, first_iteration)
         };
+        #[cfg(not(feature = "parallel"))]
+        let shared = vec![false; batch.len()];
+
+        for (offset, ((route, leaf), (_, trace))) in batch.iter().zip(&outcomes).enumerate() {
+            if shared[offset] {
+                continue;
+            }
+            if revert_each {
+                self.set_virtual_loss(route, false);
+            }
+            match results[offset] {
+                Some(result) => {
+                    self.backpropagate_leaf(route, leaf, result, trace, first_iteration + offset)
+                }
+                None => self.count_dropped_visit(route),
+            }
+        }
 
-        // Set policies
-        mcts = mcts
-            .with_selection_policy(self.selection_policy.clone_box())
-            .with_simulation_policy(self.simulation_policy.clone_box())
-            .with_backpropagation_policy(self.backpropagation_policy.clone_box());
+        let phase_times = &mut self.statistics.phase_times;
+        phase_times.simulation += simulated - simulation_start;
+        phase_times.backpropagation += simulated.elapsed();
 
-        let result = mcts.search();
+        Ok(batch.len())
+    }
 
-        // If the search was successful, update our statistics
-        if result.is_ok() {
-            self.statistics = mcts.statistics.clone();
+    /// Backpropagates a batch's results in parallel, without exclusive access to the tree
+    ///
+    /// Every leaf's route is walked by a rayon worker with its own path
+    /// buffer, updating the statistics through their atomics with
+    /// [`BackpropagationPolicy::update_shared`]. Returns which leaves were
+    /// backpropagated: none, if the policy needs exclusive access.
+    #[cfg(feature = "parallel")]
+    fn backpropagate_shared(
+        &mut self,
+        batch: &[(Route, Leaf<S>)],
+        outcomes: &[(f64, Vec<S::Action>)],
+        results: &[Option<f64>],
+        first_iteration: usize,
+    ) -> Vec<bool> {
+        use rayon::prelude::*;
+        use std::sync::atomic::AtomicUsize;
+
+        let root = &self.root;
+        let policy = &*self.backpropagation_policy;
+        let perspective = self.config.reward_perspective;
+        let normalize = self.config.normalize_values;
+        let history = self.history.as_deref();
+        let saturated = AtomicUsize::new(0);
+        let backed_up: Vec<bool> = batch
+            .par_iter()
+            .zip(outcomes)
+            .zip(results)
+            .enumerate()
+            .map_init(
+                Vec::new,
+                |buffer, (offset, (((route, leaf), (_, trace)), &result))| {
+                    // Dropped results are counted one by one afterwards
+                    let Some(result) = result else {
+                        return false;
+                    };
+                    let context = BackpropContext {
+                        leaf_state: &leaf.state,
+                        path_depth: route[route.len() - 1].0.len(),
+                        iteration: first_iteration + offset,
+                        trace: Some(trace),
+                    };
+                    let child_value = Cell::new(None);
+                    let updated = update_route_shared(root, route, buffer, &|node| {
+                        let reward = perspective.reward_for(result, &leaf.player, &node.player);
+                        if !policy.update_shared(node, reward, &context) {
+                            return false;
+                        }
+                        node.add_outcome(reward);
+                        if normalize {
+                            widen_with_child(node, &child_value);
+                        }
+                        if node.take_saturated() {
+                            saturated.fetch_add(1, Ordering::Relaxed);
+                        }
+                        if let (Some(history), Some(action)) = (history, &node.action) {
+                            history.record(action.key(), reward);
+                        }
+                        true
+                    });
+                    if let (true, Some(history)) = (updated, history) {
+                        for action in trace {
+                            history.record(action.key(), result);
+                        }
+                    }
+                    updated
+                },
+            )
+            .collect();
+        self.count_saturated(saturated.into_inner());
+
+        for (((route, _), (_, _trace)), _) in batch
+            .iter()
+            .zip(outcomes)
+            .zip(&backed_up)
+            .filter(|(_, &updated)| updated)
+        {
+            #[cfg(feature = "metrics")]
+            metrics::histogram!(crate::stats::metric_names::ROLLOUT_LENGTH)
+                .record(_trace.len() as f64);
+            self.update_exhausted(route);
         }
+        backed_up
+    }
 
-        result
+    /// Adds (`applied`) or removes the configured virtual loss on a route
+    fn set_virtual_loss(&mut self, route: &Route, applied: bool) {
+        let VirtualLoss {
+            magnitude, mode, ..
+        } = self.config.virtual_loss;
+        if magnitude == 0 {
+            return;
+        }
+        let cache_stats = self.config.cache_selection_stats;
+        let per_edge = mode == VirtualLossMode::PerEdge;
+        let root_depth = self.root.depth;
+        update_route(&mut self.root, route, &|node: &mut MCTSNode<S>| {
+            // The root is the only node on a route without an incoming edge
+            if per_edge && node.depth == root_depth {
+                return;
+            }
+            if applied {
+                node.add_virtual_loss(magnitude);
+            } else {
+                node.remove_virtual_loss(magnitude);
+            }
+            if cache_stats {
+                node.refresh_cached_stats();
+            }
+        });
     }
 
-    /// Execute a single iteration of the MCTS algorithm
-    fn execute_iteration(&mut self) -> Result<()> {
+    /// Selects and expands a leaf, building the route to it in `route`
+    ///
+    /// Also settles dead ends and works out whether the leaf's result is
+    /// known without a playout: a leaf that repeats an ancestor, is solved,
+    /// or is terminal with the result cache enabled.
+    fn select_leaf(&mut self, route: &mut Route, iteration: usize) -> Result<Leaf<S>> {
+        self.check_memory_limit();
+
         // 1. Selection phase
-        let selected_path = self.selection();
+        let phase_start = Instant::now();
+        self.selection(route, iteration)?;
+        let selected = Instant::now();
+
+        // 2. Expansion phase, optionally continuing below the new node
+        let mut state = self.expansion(route)?;
+        for _ in 0..self.config.greedy_depth {
+            match self.deepen(route)? {
+                Some(deeper) => state = deeper,
+                None => break,
+            }
+        }
+        self.resolve_dead_end(route)?;
+        if self.config.subtree_gc.is_some() {
+            touch_route(&self.root, route, visit_clock_millis());
+        }
 
-        // 2. Expansion phase
-        let (_expanded_node, expanded_state) = self.expansion(&selected_path)?;
+        let player = self.perspective_for(&state);
+        let known = match self
+            .closed_cycle(route)
+            .or_else(|| self.solved_value(route))
+        {
+            Some(reward) => Some(reward),
+            None if self.result_cache.is_some() && state.is_terminal() => {
+                Some(self.terminal_result(&state, &player))
+            }
+            None => None,
+        };
 
-        // 3. Simulation phase
-        let (result, trace) = self.simulation(&expanded_state);
+        let phase_times = &mut self.statistics.phase_times;
+        phase_times.selection += selected - phase_start;
+        phase_times.expansion += selected.elapsed();
 
-        // 4. Backpropagation phase
-        self.backpropagation(&selected_path, result, Some(&trace));
+        Ok(Leaf {
+            state,
+            player,
+            known,
+        })
+    }
 
+    /// Backpropagates the result of a leaf's playout along its route
+    fn complete_iteration(
+        &mut self,
+        route: &Route,
+        leaf: &Leaf<S>,
+        result: f64,
+        trace: &[S::Action],
+        iteration: usize,
+    ) -> Result<()> {
+        match self.accept_reward(result)? {
+            Some(result) => self.backpropagate_leaf(route, leaf, result, trace, iteration),
+            None => self.count_dropped_visit(route),
+        }
         Ok(())
     }
 
+    /// Counts a visit without reward on every node of a route
+    ///
+    /// Used for dropped results, so a leaf that keeps producing them looks
+    /// like a loss instead of an unvisited node that selection returns to
+    /// forever.
+    fn count_dropped_visit(&mut self, route: &Route) {
+        let cache_stats = self.config.cache_selection_stats;
+        update_route(&mut self.root, route, &|node: &mut MCTSNode<S>| {
+            node.increment_visits();
+            if cache_stats {
+                node.refresh_cached_stats();
+            }
+        });
+    }
+
+    /// Backpropagates a leaf's transformed result along its route
+    fn backpropagate_leaf(
+        &mut self,
+        route: &Route,
+        leaf: &Leaf<S>,
+        result: f64,
+        trace: &[S::Action],
+        iteration: usize,
+    ) {
+        #[cfg(feature = "metrics")]
+        metrics::histogram!(crate::stats::metric_names::ROLLOUT_LENGTH).record(trace.len() as f64);
+
+        let context = BackpropContext {
+            leaf_state: &leaf.state,
+            path_depth: route[route.len() - 1].0.len(),
+            iteration,
+            trace: Some(trace),
+        };
+        self.backpropagation(route, result, &leaf.player, &context);
+        if let Some(history) = &self.history {
+            // Playout moves carry no player, so they are credited from the
+            // point of view the result was computed for
+            for action in trace {
+                history.record(action.key(), result);
+            }
+        }
+    }
+
     /// Selection phase: Find a promising node to expand
-    fn selection(&mut self) -> NodePath {
-        let path = std::cell::RefCell::new(NodePath::new());
+    ///
+    /// The route is rebuilt in place: its first segment is emptied and any
+    /// transposition segments from the previous iteration are dropped.
+    fn selection(&mut self, route: &mut Route, iteration: usize) -> Result<()> {
+        enter_span!(TRACE, "selection");
+        route.truncate(1);
+        match route.first_mut() {
+            Some((path, start)) => {
+                path.indices.clear();
+                *start = 0;
+            }
+            None => route.push((NodePath::new(), 0)),
+        }
+
+        // A constrained root action behind on its budget is searched first,
+        // then any root child still below the exploration floor, then the
+        // Gumbel candidate furthest behind its share
+        let forced = self
+            .constrained_root_child()
+            .or_else(|| self.under_visited_root_child())
+            .or_else(|| self.gumbel_root_child());
+        if let Some(index) = forced {
+            route[0].0.push(index);
+        }
+
+        let route = RefCell::new(route);
+        let rng = RefCell::new(&mut self.rng);
+        let error = RefCell::new(None);
+        let root = &self.root;
+        let start = forced.map_or(root, |index| &root.children[index]);
+        let strict = self.config.strict_mode;
+        let config = &self.config;
+        let exploration_constant = self.config.exploration_constant;
+        let sampling = self.action_sampler.is_some();
+        // At the memory limit every node with children counts as widened, so
+        // selection descends to the existing leaves instead of stopping to grow
+        let frozen = self.expansion_frozen;
+
+        // A transposition link is only followed if its canonical node is not
+        // already on the route, otherwise the descent could cycle forever
+        let can_follow = |node: &MCTSNode<S>| match &node.transposition {
+            Some(target) => !route
+                .borrow()
+                .iter()
+                .any(|segment| segment_contains(segment, target)),
+            None => false,
+        };
 
         arboriter::for_tree!(
-            node = &self.root;
-            !node.state.is_terminal() && node.is_fully_expanded() && !node.children.is_empty();
+            node = start;
+            !node.state.is_terminal()
+                && ((frozen && !node.children.is_empty()) || is_widened(node, config, sampling))
+                && (!node.children.is_empty() || can_follow(node));
             {
-                // Branch function: select the best child
-                let best_child_idx = self.selection_policy.select_child(node);
-                path.borrow_mut().push(best_child_idx);
+                // Transposition link: continue from the canonical node
+                if let Some(target) = &node.transposition {
+                    route.borrow_mut().push((target.clone(), target.len()));
+                    return vec![root.descendant(target).expect("transposition target exists")];
+                }
+
+                // Chance node: draw an outcome by probability
+                if node.chance {
+                    let outcome = node
+                        .sample_outcome(&mut **rng.borrow_mut())
+                        .expect("chance nodes have outcomes");
+                    if let Some(last) = route.borrow_mut().last_mut() {
+                        last.0.push(outcome);
+                    }
+                    return vec![&node.children[outcome]];
+                }
+
+                // Branch function: select the best child, at the opponent's
+                // nodes with the opponent model if there is one
+                let opponent_model = self
+                    .opponent_model
+                    .as_ref()
+                    .filter(|_| node.state.get_current_player() != root.player);
+                let best_child_idx = if let Some((model, weight)) = opponent_model {
+                    select_opponent_child(&**model, node, *weight, exploration_constant)
+                } else {
+                    let current_route = route.borrow();
+                    let mut rng = rng.borrow_mut();
+                    let mut context = SelectionContext {
+                        depth: node.depth,
+                        path: &current_route[current_route.len() - 1].0.indices,
+                        iteration,
+                        rng: &mut **rng,
+                        history: self.history.as_deref(),
+                    };
+                    let index = self.selection_policy.select_child(node, &mut context);
+                    // Scoring again costs an allocation per step, so release
+                    // builds only pay for it in strict mode
+                    if strict || cfg!(debug_assertions) {
+                        let problem = self
+                            .selection_policy
+                            .child_scores(node, &mut context)
+                            .and_then(|scores| non_finite_score(node, &scores));
+                        if let Some(problem) = problem {
+                            if strict {
+                                *error.borrow_mut() =
+                                    Some(MCTSError::DomainInconsistency(problem));
+                                return vec![];
+                            }
+                            panic!("{}", problem);
+                        }
+                    }
+                    index
+                };
+                if strict && best_child_idx >= node.children.len() {
+                    *error.borrow_mut() = Some(MCTSError::DomainInconsistency(format!(
+                        "selection policy chose child {} of a node with {} children",
+                        best_child_idx,
+                        node.children.len()
+                    )));
+                    return vec![];
+                }
+                if let Some(last) = route.borrow_mut().last_mut() {
+                    last.0.push(best_child_idx);
+                }
                 // Return a single branch to follow
                 vec![&node.children[best_child_idx]]
             }
@@ -340,29 +1859,139 @@ impl<S: GameState + 'static> MCTS<S> {
             }
         );
 
-        path.into_inner()
+        match error.into_inner() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
     }
 
     /// Expansion phase: Create a new child node for the selected node
-    fn expansion(&mut self, path: &NodePath) -> Result<(NodePath, S)> {
+    ///
+    /// A newly expanded child is appended to the last segment of the route.
+    /// Returns the state of the node the playout starts from.
+    fn expansion(&mut self, route: &mut Route) -> Result<S> {
+        enter_span!(TRACE, "expansion");
+        let fixed = self.fixed_perspective();
         // Navigate to the selected node
+        let last = route.len() - 1;
+        let expanded_path = &mut route[last].0;
         let mut node = &mut self.root;
-        let mut expanded_path = path.clone();
 
         // Follow the path to get to the selected node
-        for &index in &path.indices {
+        for &index in &expanded_path.indices {
             node = &mut node.children[index];
         }
 
-        // If the node is terminal, we can't expand it
-        if node.state.is_terminal() {
-            return Ok((expanded_path, node.state.clone()));
+        // If the node is terminal, closes a cycle or is solved, we can't expand it
+        if node.state.is_terminal() || node.closes_cycle || node.solved.is_some() {
+            return Ok(node.state.clone());
+        }
+
+        // At the memory limit the leaf is simulated from as it is
+        if self.expansion_frozen {
+            return Ok(node.state.clone());
+        }
+
+        // A sampled chance node grows by drawing an outcome
+        if node.sampled_outcomes {
+            let known = node.children.len();
+            return Ok(match node.add_sampled_outcome(&mut self.rng) {
+                Some(index) => {
+                    self.statistics.tree_size += node.children.len() - known;
+                    expanded_path.push(index);
+                    let outcome = &mut node.children[index];
+                    if outcome.visits() == 0
+                        && consult_oracle(self.oracle.as_deref(), outcome, fixed.as_ref())
+                    {
+                        self.statistics.oracle_hits += 1;
+                    }
+                    outcome.state.clone()
+                }
+                None => node.state.clone(),
+            });
+        }
+
+        // A node's actions are deduplicated before its first child is added
+        let dedup = self.config.deduplicate_actions;
+        if dedup && node.children.is_empty() {
+            let removed = node.dedup_unexpanded_actions();
+            count_duplicates(&mut self.statistics, removed);
+        }
+
+        // Re-query the domain so a changed action set is reported, not expanded
+        let legal_actions = if self.config.strict_mode && self.action_sampler.is_none() {
+            let mut legal_actions = node.state.get_legal_actions();
+            if dedup {
+                let mut seen = HashSet::new();
+                legal_actions.retain(|action| seen.insert(action.key()));
+            }
+            let known_actions = node.children.len() + node.unexpanded_actions.len();
+            // Dead ends with a configured reward are settled after expansion
+            if legal_actions.is_empty() && self.config.dead_end_reward.is_none() {
+                return Err(MCTSError::DomainInconsistency(format!(
+                    "non-terminal state at {} has no legal actions",
+                    expanded_path
+                )));
+            }
+            // A filtered root knows fewer actions than are legal on purpose
+            let filtered = expanded_path.is_empty() && self.config.root_prior_filter.is_some();
+            let changed = if filtered {
+                legal_actions.len() < known_actions
+            } else {
+                legal_actions.len() != known_actions
+            };
+            if node.transposition.is_none() && changed {
+                return Err(MCTSError::DomainInconsistency(format!(
+                    "state at {} now has {} legal actions but was created with {}",
+                    expanded_path,
+                    legal_actions.len(),
+                    known_actions
+                )));
+            }
+            Some(legal_actions)
+        } else {
+            None
+        };
+
+        // Once the listed actions are used up, a sampler supplies a new one
+        if let Some(sampler) = &self.action_sampler {
+            let at_cap = self
+                .config
+                .max_children
+                .is_some_and(|max| node.children.len() >= max);
+            if node.unexpanded_actions.is_empty() && !at_cap {
+                let action = sampler.sample_action(&node.state, &mut self.rng);
+                if let Some(index) = node.find_child(&action) {
+                    expanded_path.push(index);
+                    return Ok(node.children[index].state.clone());
+                }
+                node.unexpanded_actions.push(action);
+            }
         }
 
         // If there are unexpanded actions, use the expansion policy to choose one
         if !node.unexpanded_actions.is_empty() {
             if let Some((action_index, prior)) = self.expansion_policy.select_action_to_expand(node)
             {
+                if let Some(legal_actions) = &legal_actions {
+                    let action = node.unexpanded_actions.get(action_index).ok_or_else(|| {
+                        MCTSError::DomainInconsistency(format!(
+                            "expansion policy chose action {} of {} unexpanded actions",
+                            action_index,
+                            node.unexpanded_actions.len()
+                        ))
+                    })?;
+                    if !legal_actions
+                        .iter()
+                        .any(|legal| legal.key() == action.key())
+                    {
+                        return Err(MCTSError::DomainInconsistency(format!(
+                            "action {:?} at {} is no longer legal",
+                            action, expanded_path
+                        )));
+                    }
+                }
+
                 // The index of the new child will be the current length (since expand pushes to children)
                 let new_child_index = node.children.len();
 
@@ -383,18 +2012,30 @@ impl<S: GameState + 'static> MCTS<S> {
                     // Add the expanded node to the path
                     expanded_path.push(new_child_index);
 
+                    // In DAG mode, link transposed states to their canonical node.
+                    // Afterstates are skipped: their outcomes are what matters
+                    if self.config.dag_mode && !new_child.chance {
+                        let hash = new_child.state.hash();
+                        if hash != 0 {
+                            match self.transpositions.get(&hash) {
+                                Some(canonical) => {
+                                    new_child.transposition = Some(canonical.clone());
+                                    new_child.unexpanded_actions.clear();
+                                    self.statistics.transposition_hits += 1;
+                                }
+                                None => {
+                                    self.transpositions.insert(hash, expanded_path.clone());
+                                }
+                            }
+                        }
+                    }
+
                     // Update statistics
-                    self.statistics.tree_size += 1;
+                    self.statistics.tree_size += 1 + new_child.children.len();
 
                     // Update node pool statistics if available
                     if let Some(pool) = &self.node_pool {
-                        let pool_stats = pool.get_stats();
-                        self.statistics.node_pool_stats = Some(crate::stats::NodePoolStats {
-                            capacity: pool_stats.total_created,
-                            available: pool.available_nodes(),
-                            total_allocated: pool_stats.total_allocations,
-                            total_returned: pool_stats.total_recycled,
-                        });
+                        self.statistics.node_pool_stats = Some(pool.search_stats());
                     }
 
                     // Access the state after expansion is complete
@@ -402,156 +2043,1740 @@ impl<S: GameState + 'static> MCTS<S> {
                     // Actually we can't easily reborrow node.children.
                     // But we know expanded node is at new_child_index.
                     // Wait, new_child is &mut MCTSNode. We can just clone its state.
-                    let expanded_state = new_child.state.clone();
+                    // An afterstate is played out from one of its outcomes
+                    let mut leaf = new_child;
+                    loop {
+                        let outcome = if leaf.sampled_outcomes {
+                            self.statistics.tree_size += 1;
+                            leaf.add_sampled_outcome(&mut self.rng)
+                        } else {
+                            leaf.sample_outcome(&mut self.rng)
+                        };
+                        let Some(outcome) = outcome else {
+                            break;
+                        };
+                        expanded_path.push(outcome);
+                        leaf = &mut leaf.children[outcome];
+                    }
+                    if leaf.visits() == 0
+                        && consult_oracle(self.oracle.as_deref(), leaf, fixed.as_ref())
+                    {
+                        self.statistics.oracle_hits += 1;
+                    }
+                    let expanded_state = leaf.state.clone();
+
+                    // At the branching cap the remaining actions are dropped
+                    if self
+                        .config
+                        .max_children
+                        .is_some_and(|max| node.children.len() >= max)
+                    {
+                        node.unexpanded_actions = Vec::new();
+                    }
 
-                    return Ok((expanded_path, expanded_state));
+                    return Ok(expanded_state);
                 }
             }
         }
 
         // If we couldn't expand, just return the original node
-        Ok((expanded_path, node.state.clone()))
+        Ok(node.state.clone())
     }
 
-    /// Simulation phase: Play out the game from the expanded node
-    fn simulation(&self, state: &S) -> (f64, Vec<S::Action>) {
-        self.simulation_policy.simulate(state)
+    /// Expands one more ply below a node created in this iteration
+    ///
+    /// Returns the state of the new leaf, or `None` if the last node on the
+    /// route is not new or cannot be expanded further.
+    fn deepen(&mut self, route: &mut Route) -> Result<Option<S>> {
+        let last = route.len() - 1;
+        let depth = route[last].0.len();
+        let fresh = self
+            .root
+            .descendant(&route[last].0)
+            .is_some_and(|node| node.visits() == 0 && node.transposition.is_none());
+        if !fresh || self.closed_cycle(route).is_some() {
+            return Ok(None);
+        }
+
+        let state = self.expansion(route)?;
+        Ok((route[last].0.len() > depth).then_some(state))
     }
 
-    /// Backpropagation phase: Update statistics in all nodes along the path
-    fn backpropagation(&mut self, path: &NodePath, result: f64, trace: Option<&[S::Action]>) {
-        // First, update the root node
-        self.backpropagation_policy
-            .update_stats(&mut self.root, result, trace);
+    /// Estimated size of the tree in bytes, as compared against
+    /// [`MCTSConfig::max_memory_bytes`]
+    ///
+    /// Every node is counted at the size of an [`MCTSNode`] plus an action
+    /// list as long as the root's, the room its untried actions start with.
+    fn estimated_tree_bytes(&self) -> usize {
+        let actions = (self.root.children.len() + self.root.unexpanded_actions.len()).max(1);
+        let per_node =
+            std::mem::size_of::<MCTSNode<S>>() + actions * std::mem::size_of::<S::Action>();
+        self.tree_nodes().saturating_mul(per_node)
+    }
 
-        // Then update all nodes along the path
-        let mut node = &mut self.root;
+    /// Number of nodes in the tree, as tracked during a search
+    fn tree_nodes(&self) -> usize {
+        (self.nodes_before_search + self.statistics.tree_size)
+            .saturating_sub(self.statistics.pruned_nodes)
+    }
 
-        for &index in &path.indices {
-            node = &mut node.children[index];
-            self.backpropagation_policy
-                .update_stats(node, result, trace);
+    /// Pauses expansion while the tree's estimated size is at the memory limit
+    ///
+    /// Expansion resumes once a [`SubtreeGc`] has pruned the tree below it.
+    fn check_memory_limit(&mut self) {
+        let Some(limit) = self.config.max_memory_bytes else {
+            return;
+        };
+        let frozen = self.estimated_tree_bytes() >= limit;
+        if frozen && !self.expansion_frozen {
+            log::debug!(
+                "Memory limit of {} bytes reached at {} nodes; expansion paused",
+                limit,
+                self.tree_nodes()
+            );
         }
+        self.expansion_frozen = frozen;
+        self.statistics.memory_limited |= frozen;
     }
 
-    /// Selects the best action based on configured criteria
-    fn select_best_action(&self) -> Result<S::Action> {
-        // If there are no children, we need to make a first-play move
-        if self.root.children.is_empty() {
-            if self.root.unexpanded_actions.is_empty() {
-                return Err(MCTSError::NoLegalActions);
-            }
+    /// Collapses stale subtrees by the configured [`SubtreeGc`] criteria
+    ///
+    /// Runs on its own during a search every [`SubtreeGc::interval`]; call
+    /// it directly to prune between searches. Returns the number of nodes
+    /// removed, which is also added to
+    /// [`SearchStatistics::pruned_nodes`]. Does nothing without a configured
+    /// collector, or in DAG mode.
+    pub fn collect_garbage(&mut self) -> usize {
+        self.last_gc = Instant::now();
+        let Some(gc) = self.config.subtree_gc else {
+            return 0;
+        };
+        if self.config.dag_mode {
+            return 0;
+        }
+        enter_span!(DEBUG, "gc");
+
+        let SubtreeGc {
+            max_idle,
+            min_visit_fraction,
+            ..
+        } = gc;
+        let now = visit_clock_millis();
+        let min_visits = min_visit_fraction.map(|fraction| fraction * self.root.visits() as f64);
+        let is_stale = |node: &MCTSNode<S>| {
+            let idle = max_idle.is_some_and(|max_idle| {
+                let visited = node.last_visited.load(Ordering::Relaxed);
+                now.saturating_sub(visited) > max_idle.as_millis() as u64
+            });
+            idle || min_visits.is_some_and(|min| (node.visits() as f64) < min)
+        };
+        let removed = collapse_stale(&mut self.root, &is_stale, &mut self.node_pool);
 
-            // Choose the first unexpanded action
-            return Ok(self.root.unexpanded_actions[0].clone());
+        if removed > 0 {
+            log::debug!("Subtree GC removed {} nodes", removed);
+            self.statistics.pruned_nodes += removed;
+            self.record_pool_stats();
+            self.debug_check_invariants();
         }
+        removed
+    }
 
-        // Depending on the best child criteria in config
-        match self.config.best_child_criteria {
-            // Most visits (robust choice)
-            crate::config::BestChildCriteria::MostVisits => {
-                let mut best_visits = 0;
-                let mut best_index = 0;
-
-                for (i, child) in self.root.children.iter().enumerate() {
-                    let visits = child.visits();
-                    if visits > best_visits {
-                        best_visits = visits;
-                        best_index = i;
-                    }
+    /// Returns the root child of the constrained action furthest behind its budget
+    ///
+    /// A constrained action is behind while its child has fewer than
+    /// `min_fraction` of the root's visits, counting the current iteration.
+    fn constrained_root_child(&self) -> Option<usize> {
+        let budget = (self.root.visits() + 1) as f64;
+        self.root_constraints
+            .iter()
+            .filter_map(|(action, min_fraction)| {
+                let index = self.root.find_child(action)?;
+                let deficit = min_fraction * budget - self.root.children[index].visits() as f64;
+                (deficit > 0.0).then_some((index, deficit))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// Samples and expands the Gumbel candidates and plans their halving
+    ///
+    /// Returns `None` if no candidate could be expanded.
+    fn plan_gumbel(&mut self, gumbel: GumbelRoot, budget: usize) -> Option<GumbelSchedule> {
+        let unexpanded = &self.root.unexpanded_actions;
+        let total_actions = self.root.children.len() + unexpanded.len();
+        let priors = self
+            .expansion_policy
+            .action_priors(&self.root)
+            .filter(|priors| priors.len() == unexpanded.len());
+        let actions: Vec<(S::Action, f64)> = self
+            .root
+            .children
+            .iter()
+            .filter_map(|child| child.action.clone().map(|action| (action, child.prior())))
+            .chain(unexpanded.iter().enumerate().map(|(index, action)| {
+                let prior = priors
+                    .as_ref()
+                    .map_or(1.0 / total_actions as f64, |priors| priors[index]);
+                (action.clone(), prior)
+            }))
+            .collect();
+
+        // Gumbel-Top-k: the highest noise plus log prior, without replacement
+        let mut scored: Vec<(S::Action, f64, f64)> = actions
+            .into_iter()
+            .map(|(action, prior)| {
+                let uniform: f64 = self.rng.gen_range(f64::MIN_POSITIVE..1.0);
+                let noise = -(-uniform.ln()).ln();
+                (action, prior, noise + prior.max(f64::MIN_POSITIVE).ln())
+            })
+            .collect();
+        scored.sort_by(|a, b| b.2.total_cmp(&a.2));
+        scored.truncate(gumbel.considered);
+
+        let mut candidates = Vec::with_capacity(scored.len());
+        for (action, prior, score) in scored {
+            let index = match self.root.find_child(&action) {
+                Some(index) => index,
+                None => {
+                    let Some(index) = self.expand_root_action(&action) else {
+                        continue;
+                    };
+                    self.root.children[index].set_prior(prior);
+                    self.statistics.tree_size += 1;
+                    index
                 }
+            };
+            let start = self.root.children[index].visits();
+            candidates.push((action.key(), score, start));
+        }
+        if candidates.is_empty() {
+            return None;
+        }
 
-                // Get the action that led to this child
-                let action = self.root.children[best_index]
-                    .action
-                    .clone()
-                    .ok_or(MCTSError::NoLegalActions)?;
+        let phases = (candidates.len() as f64).log2().ceil().max(1.0) as usize;
+        let mut schedule = GumbelSchedule {
+            candidates,
+            allotted: 0,
+            budget,
+            phases,
+        };
+        schedule.allotted = schedule.phase_visits();
+        Some(schedule)
+    }
 
-                Ok(action)
-            }
+    /// Returns the index of the root child with action key `key`
+    fn root_child_by_key(&self, key: ActionKey) -> Option<usize> {
+        self.root.children.iter().position(|child| {
+            child
+                .action
+                .as_ref()
+                .is_some_and(|action| action.key() == key)
+        })
+    }
 
-            // Highest value (can be more exploitative)
-            crate::config::BestChildCriteria::HighestValue => {
-                let mut best_value = f64::NEG_INFINITY;
-                let mut best_index = 0;
-
-                for (i, child) in self.root.children.iter().enumerate() {
-                    let value = child.value();
-                    if value > best_value {
-                        best_value = value;
-                        best_index = i;
-                    }
-                }
+    /// Ranks a Gumbel candidate by noise, log prior and scaled completed value
+    fn gumbel_score(&self, gumbel: GumbelRoot, index: usize, noise_and_prior: f64) -> f64 {
+        let max_visits = self
+            .root
+            .children
+            .iter()
+            .map(|child| child.visits())
+            .max()
+            .unwrap_or(0);
+        // An unvisited candidate's value is completed with the mean of the
+        // visited ones, which unlike the root's own value is from the point
+        // of view of the player choosing between them
+        let child = &self.root.children[index];
+        let value = if child.visits() > 0 {
+            child.value()
+        } else {
+            self.root.mean_child_value().unwrap_or(0.0)
+        };
+        noise_and_prior + gumbel.sigma(value, max_visits)
+    }
 
-                // Get the action that led to this child
-                let action = self.root.children[best_index]
-                    .action
-                    .clone()
-                    .ok_or(MCTSError::NoLegalActions)?;
+    /// Returns the Gumbel candidate furthest behind its share of the current phase
+    ///
+    /// Once every candidate has had its share, the better half survives and
+    /// the next phase starts; the last candidate left gets every remaining
+    /// iteration.
+    fn gumbel_root_child(&mut self) -> Option<usize> {
+        let gumbel = self.config.gumbel_root?;
+        let mut schedule = self.gumbel.take()?;
+        let mut forced = None;
+        for _ in 0..=schedule.phases + 1 {
+            let behind = schedule
+                .candidates
+                .iter()
+                .filter_map(|&(key, _, start)| {
+                    let index = self.root_child_by_key(key)?;
+                    let gained = self.root.children[index].visits().saturating_sub(start);
+                    (gained < schedule.allotted).then_some((index, gained))
+                })
+                .min_by_key(|&(_, gained)| gained);
+            if let Some((index, _)) = behind {
+                forced = Some(index);
+                break;
+            }
 
-                Ok(action)
+            // Everyone had their share: keep the better half
+            if schedule.candidates.len() > 1 {
+                let mut ranked: Vec<(f64, (ActionKey, f64, u64))> = schedule
+                    .candidates
+                    .iter()
+                    .filter_map(|&candidate| {
+                        let index = self.root_child_by_key(candidate.0)?;
+                        Some((self.gumbel_score(gumbel, index, candidate.1), candidate))
+                    })
+                    .collect();
+                ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+                ranked.truncate(ranked.len().div_ceil(2));
+                schedule.candidates = ranked.into_iter().map(|(_, candidate)| candidate).collect();
             }
+            schedule.allotted += schedule.phase_visits();
         }
+        self.gumbel = Some(schedule);
+        forced
     }
 
-    /// Returns the search statistics
-    pub fn get_statistics(&self) -> &SearchStatistics {
-        &self.statistics
-    }
-    /// Resets the root node with a new state
-    ///
-    /// This is useful for sequential searches where you want to keep
-    /// the same MCTS instance (and its node pool) but start a fresh search
-    /// with a new root state.
+    /// Returns the action of the best remaining Gumbel candidate the result validator accepts
     ///
-    /// # Arguments
-    ///
-    /// * `state` - The new root state
-    pub fn reset_root(&mut self, state: S) {
-        // First recycle the current tree to return all nodes to the pool
-        self.recycle_tree();
+    /// Candidates are tried by Gumbel score, after [move
+    /// hysteresis](MCTSConfig::with_move_hysteresis) had its say with the
+    /// value margin, then the other root children and untried actions as in
+    /// [`best_action`](Self::best_action). Returns `None` if no candidate
+    /// is left.
+    fn gumbel_choice(&self, schedule: &GumbelSchedule) -> Option<Result<S::Action>> {
+        let gumbel = self.config.gumbel_root?;
+        let mut ranked: Vec<(f64, &MCTSNode<S>)> = schedule
+            .candidates
+            .iter()
+            .filter_map(|&(key, noise_and_prior, _)| {
+                let index = self.root_child_by_key(key)?;
+                let score = self.gumbel_score(gumbel, index, noise_and_prior);
+                Some((score, &self.root.children[index]))
+            })
+            .collect();
+        if ranked.is_empty() {
+            return None;
+        }
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+        if let Some(hysteresis) = self.config.move_hysteresis {
+            self.stick_to_plan(&mut ranked, hysteresis, BestChildCriteria::HighestValue);
+        }
 
-        // Then create a new root node
-        self.root = MCTSNode::new(state, None, None, 0);
+        let others = self
+            .ranked_children(self.config.best_child_criteria)
+            .into_iter()
+            .filter(|(_, child)| {
+                !ranked
+                    .iter()
+                    .any(|(_, ranked)| std::ptr::eq(*ranked, *child))
+            });
+        let candidates = ranked
+            .iter()
+            .copied()
+            .chain(others)
+            .filter_map(|(_, child)| child.action.as_ref())
+            .chain(&self.root.unexpanded_actions);
+        Some(self.first_accepted(candidates))
+    }
 
-        // Reset statistics
-        self.statistics = SearchStatistics::new();
+    /// Returns the least visited root child below the exploration floor, if any
+    ///
+    /// A root that can still grow is left to expand first.
+    fn under_visited_root_child(&self) -> Option<usize> {
+        let floor = self.config.root_exploration_floor;
+        let sampling = self.action_sampler.is_some();
+        if floor == 0 || !(self.expansion_frozen || is_widened(&self.root, &self.config, sampling))
+        {
+            return None;
+        }
+        self.root
+            .children
+            .iter()
+            .enumerate()
+            .map(|(index, child)| (index, child.visits()))
+            .filter(|&(_, visits)| visits < floor)
+            .min_by_key(|&(_, visits)| visits)
+            .map(|(index, _)| index)
     }
 
-    /// Recycles the entire search tree back to the node pool
+    /// Creates the root child for an unexpanded action, returning its index
     ///
-    /// This releases all nodes (except the root) back to the pool for reuse in
-    /// future searches. This can significantly improve performance when
-    /// running multiple consecutive searches.
-    pub fn recycle_tree(&mut self) {
-        // Recycle using the regular node pool
-        if let Some(pool) = &mut self.node_pool {
-            // Take all children from the root
-            let mut children = std::mem::take(&mut self.root.children);
+    /// The child gets a uniform prior. Respects the branching cap: reaching it
+    /// drops the root's remaining actions.
+    fn expand_root_action(&mut self, action: &S::Action) -> Option<usize> {
+        let action_index = self.root.find_unexpanded(action)?;
+        let total_actions = self.root.children.len() + self.root.unexpanded_actions.len();
+        let child = match &mut self.node_pool {
+            Some(pool) => self.root.expand_with_pool(action_index, pool),
+            None => self.root.expand(action_index),
+        }?;
+        child.set_prior(1.0 / total_actions as f64);
+
+        if self
+            .config
+            .max_children
+            .is_some_and(|max| self.root.children.len() >= max)
+        {
+            self.root.unexpanded_actions = Vec::new();
+        }
+        Some(self.root.children.len() - 1)
+    }
 
-            // Recycle each child tree (using a standalone function to avoid borrow issues)
-            for child in children.drain(..) {
-                recycle_subtree_recursive(child, pool);
+    /// Drops the root's unexpanded actions whose prior is below the filter's minimum
+    ///
+    /// The actions with the highest priors and constrained actions are kept.
+    /// Does nothing if the expansion policy does not know the priors.
+    fn filter_root_actions(&mut self, filter: RootPriorFilter) {
+        let actions = &self.root.unexpanded_actions;
+        if actions.is_empty() {
+            return;
+        }
+        let Some(priors) = self.expansion_policy.action_priors(&self.root) else {
+            return;
+        };
+        if priors.len() != actions.len() {
+            log::warn!(
+                "Expansion policy returned {} priors for {} root actions; not filtering",
+                priors.len(),
+                actions.len()
+            );
+            return;
+        }
+
+        let mut ranked: Vec<usize> = (0..priors.len()).collect();
+        ranked.sort_by(|&a, &b| priors[b].total_cmp(&priors[a]));
+        let mut keep: Vec<bool> = priors
+            .iter()
+            .map(|&prior| prior >= filter.min_prior)
+            .collect();
+        for &index in ranked.iter().take(filter.keep_at_least) {
+            keep[index] = true;
+        }
+        for (index, action) in actions.iter().enumerate() {
+            if self
+                .root_constraints
+                .iter()
+                .any(|(constrained, _)| constrained.key() == action.key())
+            {
+                keep[index] = true;
             }
+        }
 
-            // Update statistics
-            let stats = pool.get_stats();
-            self.statistics.update_node_pool_stats(
-                stats.total_created,
-                pool.available_nodes(),
-                stats.total_allocations,
-                stats.total_recycled,
+        let before = actions.len();
+        let mut keep = keep.into_iter();
+        self.root
+            .unexpanded_actions
+            .retain(|_| keep.next().unwrap_or(true));
+        let removed = before - self.root.unexpanded_actions.len();
+        if removed > 0 {
+            log::debug!(
+                "Filtered {} of {} root actions with priors below {}",
+                removed,
+                before,
+                filter.min_prior
             );
         }
+        self.statistics.filtered_root_actions += removed;
+    }
+
+    /// Spreads a share of `budget` over the root actions as playouts
+    ///
+    /// Seeds the root children with the results and prunes the actions that
+    /// fall more than the margin behind the best. Only runs on a root without
+    /// statistics or children. Returns the number of playouts spent, at most
+    /// `budget`.
+    fn pre_search(&mut self, settings: PreSearch, budget: usize) -> Result<usize> {
+        if self.action_sampler.is_some() || self.root.visits() > 0 || !self.root.children.is_empty()
+        {
+            return Ok(0);
+        }
+        let actions = self.root.unexpanded_actions.clone();
+        let playouts = settings.playouts_per_action(budget, actions.len());
+        if playouts == 0 {
+            return Ok(0);
+        }
+
+        // Score each action from the point of view of the player to move
+        let perspective = self.config.reward_perspective;
+        let mover = self.root.state.get_current_player();
+        let seed = iteration_seed(
+            self.config.seed,
+            self.statistics.iterations,
+            PRE_SEARCH_STREAM,
+        );
+        let mut evaluations = with_seeded_rng(seed, || {
+            let mut evaluations = Vec::with_capacity(actions.len());
+            for action in actions {
+                let state = self.root.state.apply_action(&action);
+                let leaf_player = self.perspective_for(&state);
+                let mut total = 0.0;
+                let mut accepted = 0;
+                for _ in 0..playouts {
+                    let (result, _) = self.simulation_for(&state, &leaf_player);
+                    if let Some(reward) = self.accept_reward(result)? {
+                        total += perspective.reward_for(reward, &leaf_player, &mover);
+                        accepted += 1;
+                    }
+                }
+                evaluations.push((action, total / accepted.max(1) as f64));
+            }
+            Ok::<_, MCTSError>(evaluations)
+        })?;
+        evaluations.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let best = evaluations[0].1;
+        let constrained = |action: &S::Action| {
+            self.root_constraints
+                .iter()
+                .any(|(constrained, _)| constrained.key() == action.key())
+        };
+        let kept: Vec<(S::Action, f64)> = evaluations
+            .iter()
+            .filter(|(action, value)| *value >= best - settings.prune_margin || constrained(action))
+            .cloned()
+            .collect();
+        let value_sum: f64 = kept.iter().map(|(_, value)| value.max(0.0)).sum();
+
+        // Best first, so a branching cap keeps the most promising actions
+        for (action, value) in &kept {
+            let Some(index) = self.expand_root_action(action) else {
+                continue;
+            };
+            let child = &self.root.children[index];
+            child.set_prior(if value_sum > 0.0 {
+                value.max(0.0) / value_sum
+            } else {
+                1.0 / kept.len() as f64
+            });
+            child.inject_stats(playouts as u64, *value);
+            self.root.inject_stats(playouts as u64, *value);
+            self.statistics.tree_size += 1;
+        }
+        self.root.unexpanded_actions.clear();
+
+        let spent = playouts * evaluations.len();
+        self.statistics.pre_search_playouts = spent;
+        log::debug!(
+            "Pre-search spent {} playouts and pruned {} of {} root actions",
+            spent,
+            evaluations.len() - kept.len(),
+            evaluations.len()
+        );
+        Ok(spent)
+    }
+
+    /// Checks whether the last node on a route repeats a state above it
+    ///
+    /// Only active with cycle detection enabled. A repeating node is cut off
+    /// from further expansion, and the configured cycle reward is returned to
+    /// be backpropagated in place of a playout.
+    fn closed_cycle(&mut self, route: &Route) -> Option<f64> {
+        let reward = self.config.cycle_reward?;
+        let last = &route[route.len() - 1].0;
+        let node = self.root.descendant(last)?;
+        if node.closes_cycle {
+            return Some(reward);
+        }
+        let hash = node.state.hash();
+        if hash == 0 {
+            return None;
+        }
+
+        // Walk each segment down from the root instead of looking up every
+        // prefix, which would allocate a path per node
+        let repeats = route.iter().any(|(path, start)| {
+            let mut node = &self.root;
+            for depth in 0..=path.len() {
+                if depth > 0 {
+                    node = &node.children[path.indices[depth - 1]];
+                }
+                if depth >= *start
+                    && path.indices[..depth] != last.indices[..]
+                    && node.state.hash() == hash
+                {
+                    return true;
+                }
+            }
+            false
+        });
+        if !repeats {
+            return None;
+        }
+
+        if let Some(node) = self.root.descendant_mut(last) {
+            node.closes_cycle = true;
+            node.unexpanded_actions.clear();
+        }
+        Some(reward)
+    }
+
+    /// Returns the exact value of the last node on a route, if it is solved
+    fn solved_value(&self, route: &Route) -> Option<f64> {
+        self.root.descendant(&route[route.len() - 1].0)?.solved
+    }
+
+    /// Simulation phase: Play out the game from the expanded node
+    fn simulation(&self, state: &S) -> (f64, Vec<S::Action>) {
+        enter_span!(TRACE, "simulation");
+        let limits = self.config.playout_limits();
+        if limits.is_unlimited() {
+            self.simulation_policy.simulate(state)
+        } else {
+            self.simulation_policy.simulate_limited(state, &limits)
+        }
+    }
+
+    /// Returns the player results are computed for, if it is the same for every state
+    fn fixed_perspective(&self) -> Option<S::Player> {
+        match (&self.perspective_player, self.config.perspective) {
+            (Some(player), _) => Some(player.clone()),
+            (None, Perspective::RootPlayer) => Some(self.root.player.clone()),
+            (None, Perspective::NodePlayer) => None,
+        }
+    }
+
+    /// Returns the player a simulation from `state` is scored for
+    fn perspective_for(&self, state: &S) -> S::Player {
+        self.fixed_perspective()
+            .unwrap_or_else(|| state.get_current_player())
+    }
+
+    /// Simulates from `state`, scoring the result for `player`
+    ///
+    /// Policies that cannot score for another player than the one to move
+    /// have their result flipped, as in a two-player zero-sum game.
+    fn simulation_for(&self, state: &S, player: &S::Player) -> (f64, Vec<S::Action>) {
+        enter_span!(TRACE, "simulation");
+        let limits = self.config.playout_limits();
+        playout(&*self.simulation_policy, &limits, state, player)
+    }
+
+    /// Scores a terminal leaf for `player`, through the result cache
+    fn terminal_result(&mut self, state: &S, player: &S::Player) -> f64 {
+        let hash = state.hash();
+        let Some(cache) = &mut self.result_cache else {
+            return state.get_result(player);
+        };
+        if hash == 0 {
+            return state.get_result(player);
+        }
+
+        let cached = cache.get(hash, player);
+        let result = match cached {
+            Some(result) => result,
+            None => {
+                let result = state.get_result(player);
+                cache.insert(hash, player, result);
+                result
+            }
+        };
+        if let Some(stats) = &mut self.statistics.result_cache_stats {
+            stats.entries = cache.len();
+            if cached.is_some() {
+                stats.hits += 1;
+            } else {
+                stats.misses += 1;
+            }
+        }
+        result
+    }
+
+    /// Guards and transforms a simulation result before it is backpropagated
+    ///
+    /// Clips the result to the configured range, then applies the reward
+    /// transform. Returns `None` if the result or its transform is NaN or
+    /// infinite, so it is dropped instead of corrupting node statistics; the
+    /// caller then counts the visit without a reward.
+    ///
+    /// # Errors
+    ///
+    /// In strict mode, a NaN or infinite result fails the search with
+    /// `MCTSError::DomainInconsistency` instead of being dropped.
+    fn accept_reward(&mut self, result: f64) -> Result<Option<f64>> {
+        let mut reward = result;
+        if let Some((min, max)) = self.config.reward_clip.filter(|_| !result.is_nan()) {
+            if !(min..=max).contains(&reward) {
+                reward = reward.clamp(min, max);
+                self.statistics.clipped_rewards += 1;
+            }
+        }
+        if reward.is_finite() {
+            reward = self.transform_reward(reward);
+        }
+        if reward.is_finite() {
+            return Ok(Some(reward));
+        }
+
+        let problem = format!(
+            "non-finite reward {} from simulation result {}",
+            reward, result
+        );
+        if self.config.strict_mode {
+            return Err(MCTSError::DomainInconsistency(problem));
+        }
+        if self.statistics.dropped_rewards == 0 {
+            log::warn!("Dropping {}; later ones are only counted", problem);
+        }
+        self.statistics.dropped_rewards += 1;
+        Ok(None)
+    }
+
+    /// Applies the configured reward transform to a simulation result
+    fn transform_reward(&mut self, result: f64) -> f64 {
+        let transform = self.config.reward_transform;
+        if matches!(transform, RewardTransform::Identity) {
+            return result;
+        }
+        self.statistics.observe_reward(result);
+        transform.apply(result, self.statistics.reward_range)
+    }
+
+    /// Backpropagation phase: Update statistics in all nodes along the route
+    ///
+    /// `result` is from the point of view of `result_player`.
+    fn backpropagation(
+        &mut self,
+        route: &Route,
+        result: f64,
+        result_player: &S::Player,
+        context: &BackpropContext<'_, S>,
+    ) {
+        enter_span!(TRACE, "backpropagation");
+        let cache_stats = self.config.cache_selection_stats;
+        let perspective = self.config.reward_perspective;
+        let expectimax = self.config.expectimax;
+        let policy = &self.backpropagation_policy;
+        let history = self.history.as_deref();
+        let saturated = Cell::new(0);
+        let normalize = self.config.normalize_values;
+        let sampling = self.action_sampler.is_some();
+        let below = Cell::new(self.leaf_canonical_exhausted(route));
+        let child_value = Cell::new(None);
+
+        // Deepest segment first, so updates run from the leaf to the root
+        update_route(&mut self.root, route, &|node: &mut MCTSNode<S>| {
+            let reward = perspective.reward_for(result, result_player, &node.player);
+            policy.update_with_context(node, reward, context);
+            node.add_outcome(reward);
+            if expectimax {
+                back_up_expectimax(node);
+            }
+            if normalize {
+                widen_with_child(node, &child_value);
+            }
+            if node.take_saturated() {
+                saturated.set(saturated.get() + 1);
+            }
+            if let (Some(history), Some(action)) = (history, &node.action) {
+                history.record(action.key(), reward);
+            }
+            if cache_stats {
+                node.refresh_cached_stats();
+            }
+            refresh_exhausted(node, sampling, &below);
+        });
+        self.count_saturated(saturated.get());
+    }
+
+    /// Records node updates whose reward accumulators saturated
+    fn count_saturated(&mut self, updates: usize) {
+        if updates == 0 {
+            return;
+        }
+        if self.statistics.saturated_updates == 0 {
+            log::warn!(
+                "Node reward totals saturated at {:e}; values are no longer exact",
+                crate::tree::MAX_ACCUMULATED_REWARD
+            );
+        }
+        self.statistics.saturated_updates += updates;
+    }
+
+    /// Settles a leaf whose state is not terminal but has no legal actions
+    ///
+    /// Such a node could be selected forever without ever being expanded.
+    /// With a [dead-end reward](MCTSConfig::dead_end_reward) it is marked
+    /// solved with that reward, otherwise the search fails.
+    fn resolve_dead_end(&mut self, route: &Route) -> Result<()> {
+        let path = &route[route.len() - 1].0;
+        let sampling = self.action_sampler.is_some();
+        let node = self.root.descendant_mut(path).expect("route nodes exist");
+        let dead_end = node.unexpanded_actions.is_empty()
+            && node.children.is_empty()
+            && node.transposition.is_none()
+            && node.solved.is_none()
+            && !node.chance
+            && !node.closes_cycle
+            && !sampling
+            && !node.state.is_terminal();
+        if !dead_end {
+            return Ok(());
+        }
+
+        match self.config.dead_end_reward {
+            Some(reward) => {
+                node.solved = Some(reward);
+                Ok(())
+            }
+            None => Err(MCTSError::DomainInconsistency(format!(
+                "non-terminal state at {} has no legal actions",
+                path
+            ))),
+        }
+    }
+
+    /// Recomputes the `exhausted` flags of the nodes on a route, deepest first
+    ///
+    /// Only nodes on the route can have changed, so this is enough to keep the
+    /// flags of the whole tree up to date. Sequential backpropagation does
+    /// this as it goes; batches updated through shared references catch up
+    /// here.
+    #[cfg(feature = "parallel")]
+    fn update_exhausted(&mut self, route: &Route) {
+        let sampling = self.action_sampler.is_some();
+        let below = Cell::new(self.leaf_canonical_exhausted(route));
+        update_route(&mut self.root, route, &|node: &mut MCTSNode<S>| {
+            refresh_exhausted(node, sampling, &below);
+        });
+    }
+
+    /// Returns the `exhausted` flag of the canonical node of a route's last
+    /// node, if that is a transposition link the route stops at
+    fn leaf_canonical_exhausted(&self, route: &Route) -> bool {
+        self.root
+            .descendant(&route[route.len() - 1].0)
+            .and_then(|leaf| leaf.transposition.as_ref())
+            .and_then(|target| self.root.descendant(target))
+            .is_some_and(|canonical| canonical.exhausted)
+    }
+
+    /// Returns the best root action under `criteria`
+    ///
+    /// This only reads the current tree, so it can be called after a search
+    /// to compare criteria without searching again, or between calls to
+    /// [`search_for_iterations`](Self::search_for_iterations) for an anytime
+    /// recommendation. [`search`](Self::search) returns the action chosen by
+    /// the configured [`best_child_criteria`](MCTSConfig::best_child_criteria).
+    ///
+    /// Before any child has been expanded, the first legal action is returned.
+    /// A [result validator](Self::with_result_validator) can veto candidates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::NoLegalActions` if the root has no legal actions,
+    /// and `MCTSError::ActionsRejected` if the validator rejects all of them.
+    pub fn best_action(&self, criteria: BestChildCriteria) -> Result<S::Action> {
+        let mut ranked = self.ranked_children(criteria);
+        if let Some(hysteresis) = self.config.move_hysteresis {
+            self.stick_to_plan(&mut ranked, hysteresis, criteria);
+        }
+
+        // Try the children best first, then fall back to first-play moves
+        let candidates = ranked
+            .into_iter()
+            .filter_map(|(_, child)| child.action.as_ref())
+            .chain(&self.root.unexpanded_actions);
+        self.first_accepted(candidates)
+    }
+
+    /// Returns the first of `candidates` the result validator accepts
+    fn first_accepted<'a>(
+        &'a self,
+        mut candidates: impl Iterator<Item = &'a S::Action>,
+    ) -> Result<S::Action> {
+        let Some(validator) = &self.result_validator else {
+            return candidates.next().cloned().ok_or(MCTSError::NoLegalActions);
+        };
+
+        let mut rejection = None;
+        for action in candidates {
+            match validator(&self.root.state, action) {
+                Ok(()) => return Ok(action.clone()),
+                Err(reason) => {
+                    log::debug!("Result validator rejected {:?}: {}", action, reason);
+                    rejection = Some(reason);
+                }
+            }
+        }
+        Err(match rejection {
+            Some(reason) => MCTSError::ActionsRejected(reason),
+            None => MCTSError::NoLegalActions,
+        })
+    }
+
+    /// Moves the planned root child to the front unless the best one leads it by the margin
+    fn stick_to_plan(
+        &self,
+        ranked: &mut Vec<(f64, &MCTSNode<S>)>,
+        hysteresis: MoveHysteresis,
+        criteria: BestChildCriteria,
+    ) {
+        let Some(planned) = self.plan.first() else {
+            return;
+        };
+        let Some(position) = ranked.iter().position(|(_, child)| {
+            child
+                .action
+                .as_ref()
+                .is_some_and(|action| action.key() == planned.key())
+        }) else {
+            return;
+        };
+        if position == 0 {
+            return;
+        }
+
+        let margin = match criteria {
+            BestChildCriteria::MostVisits => hysteresis.visit_margin * self.root.visits() as f64,
+            _ => hysteresis.value_margin,
+        };
+        if ranked[0].0 - ranked[position].0 <= margin {
+            let entry = ranked.remove(position);
+            ranked.insert(0, entry);
+        }
+    }
+
+    /// Returns the line of play starting with `action` that the tree expects
+    fn line_from_root(&self, action: &S::Action) -> Vec<S::Action> {
+        let mut line = vec![action.clone()];
+        if let Some(index) = self.root.find_child(action) {
+            line.extend(continuation(&self.root.children[index]));
+        }
+        line
+    }
+
+    /// Returns the line of play the last search expected from the current root
+    ///
+    /// The first action is the move that search chose, or with
+    /// [`move hysteresis`](MCTSConfig::with_move_hysteresis) the move the
+    /// next search will stick to. Playing moves along the line with
+    /// [`apply_move`](Self::apply_move) or [`advance_root`](Self::advance_root)
+    /// shortens it; playing any other move or calling
+    /// [`reset_root`](Self::reset_root) clears it.
+    pub fn planned_line(&self) -> &[S::Action] {
+        &self.plan
+    }
+
+    /// Returns how far the best root child leads the runner-up under `criteria`
+    ///
+    /// The margin is the difference of their scores: visits for
+    /// [`MostVisits`](BestChildCriteria::MostVisits), the configured
+    /// [value estimate](MCTSConfig::with_value_estimate) for
+    /// [`HighestValue`](BestChildCriteria::HighestValue) and lower bounds
+    /// for [`SecureChild`](BestChildCriteria::SecureChild). A small margin
+    /// means the search has not settled on a move, which time management and
+    /// instability detection use to decide whether to keep searching.
+    /// Returns `None` with fewer than two root children. A [result
+    /// validator](Self::with_result_validator) is not consulted.
+    pub fn runner_up_margin(&self, criteria: BestChildCriteria) -> Option<f64> {
+        match self.ranked_children(criteria)[..] {
+            [(best, _), (runner_up, _), ..] => Some(best - runner_up),
+            _ => None,
+        }
+    }
+
+    /// Scores the root children under `criteria` and sorts them best first
+    ///
+    /// Ties are broken by the configured [`TieBreak`](crate::config::TieBreak).
+    pub(crate) fn ranked_children(&self, criteria: BestChildCriteria) -> Vec<(f64, &MCTSNode<S>)> {
+        let parent_visits = self.root.visits().max(1) as f64;
+        let exploration = self.config.exploration_constant;
+        let score = |child: &MCTSNode<S>| match criteria {
+            // Most visits (robust choice)
+            BestChildCriteria::MostVisits => child.visits() as f64,
+
+            // Highest value (can be more exploitative)
+            BestChildCriteria::HighestValue => {
+                child.estimated_value(self.config.value_estimate, self.config.reward_perspective)
+            }
+
+            // Lower confidence bound (penalizes values backed by few visits)
+            BestChildCriteria::SecureChild => {
+                let visits = child.visits();
+                if visits == 0 {
+                    return f64::NEG_INFINITY;
+                }
+                child.value() - exploration * (parent_visits.ln() / visits as f64).sqrt()
+            }
+        };
+
+        // A seeded draw per child settles ties the statistics leave open
+        let mut draws = match self.config.tie_break {
+            TieBreak::Random(seed) => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                self.root
+                    .children
+                    .iter()
+                    .map(|_| rng.gen::<u64>())
+                    .collect()
+            }
+            _ => vec![0; self.root.children.len()],
+        }
+        .into_iter();
+
+        let mut ranked: Vec<(f64, u64, &MCTSNode<S>)> = self
+            .root
+            .children
+            .iter()
+            .map(|child| (score(child), draws.next().unwrap_or(0), child))
+            .collect();
+        // The sort is stable, so children still tied keep their order
+        ranked.sort_by(|a, b| {
+            let order = b.0.total_cmp(&a.0);
+            if self.config.tie_break == TieBreak::FirstChild {
+                return order;
+            }
+            order
+                .then_with(|| b.2.value().total_cmp(&a.2.value()))
+                .then_with(|| b.2.visits().cmp(&a.2.visits()))
+                .then_with(|| b.1.cmp(&a.1))
+        });
+        ranked
+            .into_iter()
+            .map(|(score, _, child)| (score, child))
+            .collect()
+    }
+
+    /// Returns a token that can stop a running search from another thread
+    ///
+    /// The token stays valid across searches. A stop requested while no search
+    /// is running applies to the next one.
+    pub fn stop_token(&self) -> StopToken {
+        self.stop_token.clone()
+    }
+
+    /// Runs [`search`](Self::search) on a background thread
+    ///
+    /// The returned [`SearchHandle`] can stop the search, report the best
+    /// action so far, and join the thread to get the instance and result back.
+    /// The handle takes over the [`iteration_events`](Self::iteration_events)
+    /// channel; if events are disabled they are enabled every 100 iterations
+    /// for the duration of this search.
+    pub fn spawn_search(mut self) -> SearchHandle<S> {
+        let event_interval = self.config.event_interval;
+        if event_interval == 0 {
+            self.config.event_interval = HANDLE_EVENT_INTERVAL;
+        }
+        let stop_token = self.stop_token();
+        let events = self.iteration_events();
+
+        let thread = std::thread::spawn(move || {
+            let result = self.search();
+            self.config.event_interval = event_interval;
+            self.event_sender = None;
+            (self, result)
+        });
+
+        SearchHandle {
+            stop_token,
+            events,
+            best_action: RefCell::new(None),
+            thread,
+        }
+    }
+
+    /// Returns a receiver for periodic [`IterationEvent`]s
+    ///
+    /// Events are sent every [`MCTSConfig::event_interval`] iterations; nothing
+    /// is sent while the interval is zero. Only the most recently returned
+    /// receiver gets events. Once it is dropped, events stop until this is
+    /// called again.
+    pub fn iteration_events(&mut self) -> Receiver<IterationEvent<S::Action>> {
+        let (sender, receiver) = mpsc::channel();
+        self.event_sender = Some(sender);
+        receiver
+    }
+
+    /// Sends a progress event, dropping the sender if nobody is listening
+    fn send_event(&mut self) {
+        let Some(sender) = &self.event_sender else {
+            return;
+        };
+
+        let event = IterationEvent {
+            iteration: self.statistics.iterations,
+            tree_size: self.statistics.tree_size,
+            best_action_so_far: self.best_action(self.config.best_child_criteria).ok(),
+        };
+        if sender.send(event).is_err() {
+            self.event_sender = None;
+        }
+    }
+
+    /// Returns the search statistics
+    pub fn get_statistics(&self) -> &SearchStatistics {
+        &self.statistics
+    }
+
+    /// Returns the search configuration
+    pub fn config(&self) -> &MCTSConfig {
+        &self.config
+    }
+
+    /// Resets the root node with a new state
+    ///
+    /// This is useful for sequential searches where you want to keep
+    /// the same MCTS instance (and its node pool) but start a fresh search
+    /// with a new root state. The old tree, root included, is returned to the
+    /// node pool if one is in use, the root's legal actions are generated
+    /// from `state`, and search statistics are cleared. Policies and the
+    /// configuration are kept as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The new root state
+    pub fn reset_root(&mut self, state: S) {
+        self.replace_root(state);
+        self.warm_started = false;
+        self.root_constraints.clear();
+        self.plan.clear();
+
+        // Reset statistics, keeping the pool's lifetime counters visible
+        self.statistics = SearchStatistics::new();
+        self.record_pool_stats();
+    }
+
+    /// Seeds root children with statistics from outside the search
+    ///
+    /// Each entry is `(action, value, visits)`: the root child for `action` is
+    /// created if needed and credited with `visits` visits averaging `value`,
+    /// as is the root itself. Use this to start from a shallow pre-search, an
+    /// opening book, or the previous turn when full tree reuse isn't possible.
+    /// The next call to [`search`](Self::search) builds on these statistics
+    /// instead of recycling the tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::InvalidConfiguration` if an action is not legal at
+    /// the root. No statistics are injected in that case.
+    pub fn warm_start(&mut self, root_priors: &[(S::Action, f64, u64)]) -> Result<()> {
+        if let Some((action, _, _)) = root_priors.iter().find(|(action, _, _)| {
+            self.root.find_child(action).is_none() && self.root.find_unexpanded(action).is_none()
+        }) {
+            return Err(MCTSError::InvalidConfiguration(format!(
+                "warm-start action {:?} is not legal at the root",
+                action
+            )));
+        }
+
+        for (action, value, visits) in root_priors {
+            let child_index = match self.root.find_child(action) {
+                Some(index) => index,
+                // Validated above, so only the branching cap can refuse it
+                None => match self.expand_root_action(action) {
+                    Some(index) => index,
+                    None => continue,
+                },
+            };
+
+            let child = &mut self.root.children[child_index];
+            child.inject_stats(*visits, *value);
+            if self.config.cache_selection_stats {
+                child.refresh_cached_stats();
+            }
+            self.root.inject_stats(*visits, *value);
+        }
+        if self.config.cache_selection_stats {
+            self.root.refresh_cached_stats();
+        }
+
+        self.warm_started = true;
+        Ok(())
+    }
+
+    /// Adds externally computed statistics for the node at the end of `path`
+    ///
+    /// `path` is a sequence of actions from the root; missing nodes along it
+    /// are expanded with a uniform prior, ignoring the branching cap. Every
+    /// node on the path, the root included, gets `visits` visits whose
+    /// average is `value`, the result for the player who moved into the last
+    /// node, as its [`value`](MCTSNode::value) reports it. Nodes above it get
+    /// the value converted with the configured
+    /// [reward perspective](MCTSConfig::reward_perspective). Like
+    /// [`warm_start`](Self::warm_start), this keeps the next search from
+    /// recycling the tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::InvalidConfiguration` if an action on the path is
+    /// not legal where it is played, including actions played from a chance
+    /// node. The tree is left untouched in that case.
+    pub fn graft(&mut self, path: &[S::Action], visits: u64, value: f64) -> Result<()> {
+        // Validate the whole path first, following states past the tree's edge
+        let mut node = Some(&self.root);
+        let mut state: Option<S> = None;
+        for (depth, action) in path.iter().enumerate() {
+            if let Some(current) = node {
+                if let Some(index) = current.find_child(action) {
+                    node = Some(&current.children[index]);
+                    continue;
+                }
+                if current.find_unexpanded(action).is_some() {
+                    state = Some(current.state.apply_action(action));
+                    node = None;
+                    continue;
+                }
+            } else if let Some(current) = &state {
+                if !current.is_terminal()
+                    && current
+                        .get_legal_actions()
+                        .iter()
+                        .any(|legal| legal.key() == action.key())
+                {
+                    state = Some(current.apply_action(action));
+                    continue;
+                }
+            }
+            return Err(MCTSError::InvalidConfiguration(format!(
+                "grafted action {:?} at depth {} is not legal",
+                action, depth
+            )));
+        }
+
+        let mut route = Vec::with_capacity(path.len());
+        let mut node = &mut self.root;
+        for action in path {
+            let index = match node.find_child(action) {
+                Some(index) => index,
+                None => {
+                    let illegal = || {
+                        MCTSError::InvalidConfiguration(format!(
+                            "grafted action {:?} is not legal",
+                            action
+                        ))
+                    };
+                    let action_index = node.find_unexpanded(action).ok_or_else(illegal)?;
+                    let total_actions = node.children.len() + node.unexpanded_actions.len();
+                    let child = match &mut self.node_pool {
+                        Some(pool) => node.expand_with_pool(action_index, pool),
+                        None => node.expand(action_index),
+                    }
+                    .ok_or_else(illegal)?;
+                    child.set_prior(1.0 / total_actions as f64);
+                    self.statistics.tree_size += 1 + child.children.len();
+                    node.children.len() - 1
+                }
+            };
+            route.push(index);
+            node = &mut node.children[index];
+        }
+
+        let target = node.player.clone();
+        let perspective = self.config.reward_perspective;
+        let cache_stats = self.config.cache_selection_stats;
+        let inject = |node: &mut MCTSNode<S>| {
+            node.inject_stats(visits, perspective.reward_for(value, &target, &node.player));
+            if cache_stats {
+                node.refresh_cached_stats();
+            }
+        };
+        let mut node = &mut self.root;
+        inject(node);
+        for index in route {
+            node = &mut node.children[index];
+            inject(node);
+        }
+
+        self.warm_started = true;
+        Ok(())
+    }
+
+    /// Adds the statistics of another tree searched from the same root state
+    ///
+    /// Use this to combine the trees of workers that searched the same
+    /// position, e.g. `mcts.merge_tree(worker.root())`. Statistics are summed
+    /// node by node as described in [`MCTSNode::merge`]; nodes only `other`
+    /// has are created here. Subtrees that cannot be matched, because their
+    /// action is not available here or they hang below a sampled chance
+    /// event, are skipped and counted. Both trees should use the same
+    /// [reward perspective](MCTSConfig::reward_perspective). Like
+    /// [`warm_start`](Self::warm_start), this keeps the next search from
+    /// recycling the tree. Returns how many subtrees were skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::InvalidConfiguration` if `other` is not for the
+    /// root's state. States are compared by [`GameState::hash`] when either
+    /// is nonzero, and otherwise by player to move and legal actions.
+    pub fn merge_tree(&mut self, other: &MCTSNode<S>) -> Result<usize> {
+        let (ours, theirs) = (self.root.state.hash(), other.state.hash());
+        let same_root = if ours != 0 || theirs != 0 {
+            ours == theirs
+        } else {
+            let keys = |state: &S| -> HashSet<_> {
+                state.get_legal_actions().iter().map(Action::key).collect()
+            };
+            self.root.state.get_current_player() == other.state.get_current_player()
+                && keys(&self.root.state) == keys(&other.state)
+        };
+        if !same_root || self.root.chance != other.chance {
+            return Err(MCTSError::InvalidConfiguration(
+                "merged tree was searched from a different root state".to_string(),
+            ));
+        }
+
+        // Merged nodes refresh their snapshots below a root that has one
+        if self.config.cache_selection_stats {
+            self.root.refresh_cached_stats();
+        }
+        let (created, skipped) = self.root.merge(other);
+        self.statistics.tree_size += created;
+        if skipped > 0 {
+            log::debug!(
+                "Merge skipped {} subtrees that do not match this tree",
+                skipped
+            );
+        }
+
+        self.warm_started = true;
+        Ok(skipped)
+    }
+
+    /// Guarantees a root action at least a fraction of the search budget
+    ///
+    /// Whenever the root child for `action` has fewer than `min_fraction` of
+    /// the root's visits, the next iteration descends into it regardless of
+    /// what the selection policy prefers. This gives candidate moves that look
+    /// bad a fair share of analysis. Adding a constraint for an action that
+    /// already has one replaces it. Constraints last until the root changes
+    /// with [`reset_root`](Self::reset_root) or [`apply_move`](Self::apply_move).
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::InvalidConfiguration` if the action is not legal at
+    /// the root, `min_fraction` is not in `(0, 1]`, or the fractions of all
+    /// constraints would add up to more than 1.
+    pub fn add_root_constraint(&mut self, action: S::Action, min_fraction: f64) -> Result<()> {
+        if self.root.find_child(&action).is_none() && self.root.find_unexpanded(&action).is_none() {
+            return Err(MCTSError::InvalidConfiguration(format!(
+                "constrained action {:?} is not legal at the root",
+                action
+            )));
+        }
+        if !(min_fraction > 0.0 && min_fraction <= 1.0) {
+            return Err(MCTSError::InvalidConfiguration(format!(
+                "minimum fraction {} for {:?} is not in (0, 1]",
+                min_fraction, action
+            )));
+        }
+        let others: f64 = self
+            .root_constraints
+            .iter()
+            .filter(|(constrained, _)| constrained.key() != action.key())
+            .map(|(_, fraction)| fraction)
+            .sum();
+        if others + min_fraction > 1.0 + 1e-9 {
+            return Err(MCTSError::InvalidConfiguration(format!(
+                "root constraints would claim {} of the budget",
+                others + min_fraction
+            )));
+        }
+
+        self.root_constraints
+            .retain(|(constrained, _)| constrained.key() != action.key());
+        self.root_constraints.push((action, min_fraction));
+        Ok(())
+    }
+
+    /// Returns the root constraints as `(action, min_fraction)` pairs
+    pub fn root_constraints(&self) -> &[(S::Action, f64)] {
+        &self.root_constraints
+    }
+
+    /// Removes every root constraint
+    pub fn clear_root_constraints(&mut self) {
+        self.root_constraints.clear();
+    }
+
+    /// Plays a move in the game and moves the root to the resulting state
+    ///
+    /// The action is applied to the current root state, every policy is told
+    /// about the move via `on_move_played`, and the root is reset to the new
+    /// state. Use this instead of [`reset_root`](Self::reset_root) when playing
+    /// a game so stateful policies can carry knowledge between moves.
+    ///
+    /// If the move leads to an afterstate with a pending chance event, call
+    /// [`reset_root`](Self::reset_root) with the realized outcome before
+    /// searching again.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action that was played from the current root state
+    pub fn apply_move(&mut self, action: &S::Action) {
+        let state = self.root.state.apply_action(action);
+        self.notify_move_played(action, &state);
+        // The plan was advanced by the move, so it survives the reset
+        let plan = std::mem::take(&mut self.plan);
+        self.reset_root(state);
+        self.plan = plan;
+    }
+
+    /// Moves the root to `state`, keeping the matching subtree if there is one
+    ///
+    /// The tree is searched up to [`REUSE_DEPTH`] plies below the root for a
+    /// node whose state has the same [`GameState::hash`]. If one is found it
+    /// becomes the root with all its statistics, the rest of the tree goes
+    /// back to the node pool, and the policies are told about every move on
+    /// the way as with [`apply_move`](Self::apply_move). Otherwise this is
+    /// [`reset_root`](Self::reset_root). States that do not implement `hash`,
+    /// chance nodes, and trees in DAG mode are never reused.
+    ///
+    /// Returns true if the tree was kept.
+    pub fn advance_root(&mut self, state: S) -> bool {
+        let hash = state.hash();
+        let found = (hash != 0 && !self.config.dag_mode)
+            .then(|| find_position(&self.root, hash, REUSE_DEPTH))
+            .flatten();
+        let Some(path) = found else {
+            self.reset_root(state);
+            return false;
+        };
+        if path.is_empty() {
+            return true;
+        }
+
+        // Tell the policies about the moves leading to the new root
+        let mut node = &self.root;
+        let mut moves = Vec::with_capacity(path.len());
+        for &index in &path.indices {
+            node = &node.children[index];
+            if let Some(action) = &node.action {
+                moves.push((action.clone(), node.state.clone()));
+            }
+        }
+        for (action, state) in &moves {
+            self.notify_move_played(action, state);
+        }
+
+        // Detach the subtree and make it the root
+        let (last, parent_path) = path.indices.split_last().expect("path is not empty");
+        let mut parent = &mut self.root;
+        for &index in parent_path {
+            parent = &mut parent.children[index];
+        }
+        let mut subtree = parent.children.swap_remove(*last);
+        rebase_depths(&mut subtree, path.len());
+        subtree.player = subtree.state.get_current_player();
+        let old_root = std::mem::replace(&mut self.root, subtree);
+        if let Some(pool) = &mut self.node_pool {
+            pool.recycle_tree(old_root);
+        }
+
+        self.transpositions.clear();
+        self.warm_started = false;
+        self.root_constraints.clear();
+        self.statistics = SearchStatistics::new();
+        self.record_pool_stats();
+        self.debug_check_invariants();
+        true
+    }
+
+    /// Tells every policy that `action` was played, leading to `state`
+    fn notify_move_played(&mut self, action: &S::Action, state: &S) {
+        if self
+            .plan
+            .first()
+            .is_some_and(|planned| planned.key() == action.key())
+        {
+            self.plan.remove(0);
+        } else {
+            self.plan.clear();
+        }
+        self.selection_policy.on_move_played(action, state);
+        self.expansion_policy.on_move_played(action, state);
+        self.simulation_policy.on_move_played(action, state);
+        self.backpropagation_policy.on_move_played(action, state);
+        if let Some(history) = &self.history {
+            history.decay();
+        }
+    }
+
+    /// Recycles the entire search tree back to the node pool
+    ///
+    /// This releases all nodes (except the root) back to the pool for reuse in
+    /// future searches and leaves a fresh, unvisited root for the same state.
+    /// This can significantly improve performance when running multiple
+    /// consecutive searches. Without a node pool the tree is kept as is.
+    pub fn recycle_tree(&mut self) {
+        if self.node_pool.is_some() {
+            let state = self.root.state.clone();
+            self.replace_root(state);
+            self.record_pool_stats();
+        }
+    }
+
+    /// Releases free nodes until at most `count` remain in the node pool
+    ///
+    /// Useful after recycling an unusually large tree. Does nothing without a
+    /// node pool.
+    pub fn shrink_node_pool(&mut self, count: usize) {
+        if let Some(pool) = &mut self.node_pool {
+            pool.shrink_to(count);
+            self.record_pool_stats();
+        }
+    }
+
+    /// Replaces the root with a fresh node for `state`
+    ///
+    /// The old tree is returned to the node pool if there is one. The new root
+    /// is taken from the pool too, so its legal actions and statistics are
+    /// regenerated either way.
+    fn replace_root(&mut self, state: S) {
+        match &mut self.node_pool {
+            Some(pool) => {
+                let root = pool.create_node(state, None, None, 0);
+                let old_root = std::mem::replace(&mut self.root, root);
+                pool.recycle_tree(old_root);
+            }
+            None => self.root = MCTSNode::new(state, None, None, 0),
+        }
+        self.transpositions.clear();
+        self.debug_check_invariants();
+    }
+
+    /// Verifies that the search tree and node pool are consistent
+    ///
+    /// Checks that every node's depth matches its position, that every child
+    /// except a chance outcome has an action that is no longer unexpanded
+    /// (with [deduplication](MCTSConfig::deduplicate_actions) on; otherwise
+    /// a duplicated action can be both), that no node has fewer visits than
+    /// its children together (outside DAG mode, where links reach nodes
+    /// directly), that transposition links resolve, that no two nodes share
+    /// a [generation](MCTSNode::generation), and that the node pool never
+    /// handed out or took back more nodes than it could have. This walks the
+    /// whole tree, so it is meant for tests and debugging; the
+    /// `debug-invariants` feature runs it after every search and root change
+    /// in debug builds.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::InvariantViolation` describing the first problem found.
+    pub fn check_invariants(&self) -> Result<()> {
+        check_subtree(
+            &self.root,
+            &self.root,
+            &mut NodePath::new(),
+            self.config.dag_mode,
+            self.config.deduplicate_actions,
+            &mut HashSet::new(),
+        )
+        .map_err(MCTSError::InvariantViolation)?;
+        if let Some(pool) = &self.node_pool {
+            pool.check_accounting()
+                .map_err(MCTSError::InvariantViolation)?;
+        }
+        Ok(())
+    }
+
+    /// Panics on a broken invariant in debug builds with `debug-invariants`
+    fn debug_check_invariants(&self) {
+        #[cfg(all(debug_assertions, feature = "debug-invariants"))]
+        if let Err(error) = self.check_invariants() {
+            panic!("{}", error);
+        }
+    }
+
+    /// Copies the node pool's counters into the search statistics
+    fn record_pool_stats(&mut self) {
+        if let Some(pool) = &self.node_pool {
+            self.statistics.node_pool_stats = Some(pool.search_stats());
+        }
+    }
+
+    /// Evaluates every legal root action with playouts alone, without a tree
+    ///
+    /// Runs the simulation policy `samples` times from the state after each
+    /// legal action, honoring the configured playout limits, and reports the
+    /// mean and standard deviation of the raw results in the order of
+    /// `get_legal_actions()`. The tree and the search statistics are left
+    /// untouched. Use it to sanity-check a `GameState` implementation or to
+    /// seed the root with [`warm_start`](Self::warm_start).
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::NoLegalActions` if the root has no legal actions,
+    /// and `MCTSError::InvalidConfiguration` if `samples` is zero.
+    pub fn static_evaluation(&self, samples: usize) -> Result<Vec<ActionEvaluation<S::Action>>> {
+        if samples == 0 {
+            return Err(MCTSError::InvalidConfiguration(
+                "static evaluation needs at least one sample".to_string(),
+            ));
+        }
+        let actions = self.root.state.get_legal_actions();
+        if actions.is_empty() {
+            return Err(MCTSError::NoLegalActions);
+        }
+
+        Ok(actions
+            .into_iter()
+            .map(|action| {
+                let state = self.root.state.apply_action(&action);
+                let results: Vec<f64> = (0..samples).map(|_| self.simulation(&state).0).collect();
+                let mean = results.iter().sum::<f64>() / samples as f64;
+                let variance = results
+                    .iter()
+                    .map(|result| (result - mean).powi(2))
+                    .sum::<f64>()
+                    / samples as f64;
+                ActionEvaluation {
+                    action,
+                    samples,
+                    mean,
+                    std_dev: variance.sqrt(),
+                }
+            })
+            .collect())
+    }
+
+    /// Captures the root's statistics for a later [`RootSnapshot::diff`]
+    ///
+    /// The best action is chosen with the configured
+    /// [`best_child_criteria`](MCTSConfig::best_child_criteria). Continue a
+    /// search with [`search_for_iterations`](Self::search_for_iterations) to
+    /// compare snapshots of the same tree; [`search`](Self::search) starts
+    /// from a fresh tree.
+    pub fn root_snapshot(&self) -> RootSnapshot<S::Action> {
+        let actions = self
+            .root
+            .children
+            .iter()
+            .filter_map(|child| {
+                Some(RootActionStats {
+                    action: child.action.clone()?,
+                    visits: child.visits(),
+                    value: child.value(),
+                })
+            })
+            .collect();
+
+        RootSnapshot {
+            root_visits: self.root.visits(),
+            actions,
+            best_action: self.best_action(self.config.best_child_criteria).ok(),
+        }
+    }
+
+    /// Returns the `k` most visited root actions with their expected continuations
+    ///
+    /// Lines are ordered by visits, most first. Each follows the most visited
+    /// child down the tree until it reaches an unvisited node or a leaf.
+    pub fn top_lines(&self, k: usize) -> Vec<AnalysisLine<S::Action>> {
+        let mut children: Vec<_> = self
+            .root
+            .children
+            .iter()
+            .filter(|child| child.action.is_some())
+            .collect();
+        children.sort_by_key(|child| std::cmp::Reverse(child.visits()));
+
+        children
+            .into_iter()
+            .take(k)
+            .filter_map(|child| {
+                let mut moves = vec![child.action.clone()?];
+                moves.extend(continuation(child));
+                Some(AnalysisLine {
+                    action: moves[0].clone(),
+                    moves,
+                    visits: child.visits(),
+                    value: child.value(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the expected line of play from the root, most visited child first
+    ///
+    /// Empty if the root has no visited children.
+    pub fn principal_variation(&self) -> Vec<S::Action> {
+        continuation(&self.root)
+    }
+
+    /// Returns up to `max_len` steps of the principal variation, each with its confidence
+    ///
+    /// The plan follows the most visited child like
+    /// [`principal_variation`](Self::principal_variation) and stops at the
+    /// first child with fewer than `min_visits` visits, or at a chance node,
+    /// whose outcome the plan cannot choose. Each step's
+    /// [`confidence`](PlanStep::confidence) multiplies the shares of the
+    /// parents' visits along the way, so it decays where the search was
+    /// less sure of the continuation.
+    pub fn extract_plan(&self, max_len: usize, min_visits: u64) -> Vec<PlanStep<S::Action>> {
+        let mut plan = Vec::new();
+        let mut confidence = 1.0;
+        let mut node = &self.root;
+        while plan.len() < max_len {
+            let Some(child) = most_visited_child(node) else {
+                break;
+            };
+            let Some(action) = &child.action else {
+                break;
+            };
+            let visits = child.visits();
+            if visits < min_visits {
+                break;
+            }
+
+            let share = (visits as f64 / node.visits().max(1) as f64).min(1.0);
+            confidence *= share;
+            plan.push(PlanStep {
+                action: action.clone(),
+                visits,
+                value: child.value(),
+                share,
+                confidence,
+            });
+            node = child;
+        }
+        plan
+    }
+
+    /// Returns a human-readable report of the last search
+    ///
+    /// This is the statistics [`summary`](SearchStatistics::summary) followed
+    /// by a table of root actions with their visits, mean value, and prior,
+    /// most visited first.
+    pub fn report(&self) -> String {
+        let mut children: Vec<_> = self.root.children.iter().collect();
+        children.sort_by_key(|child| std::cmp::Reverse(child.visits()));
+
+        let labels: Vec<String> = children
+            .iter()
+            .map(|child| match &child.action {
+                Some(action) => format!("{:?}", action),
+                None => "-".to_string(),
+            })
+            .collect();
+        let width = labels.iter().map(|label| label.len()).max().unwrap_or(0);
+        let width = width.max("Action".len());
+
+        let mut report = self.statistics.summary();
+        report.push_str(&format!(
+            "\n\nRoot Actions:\n  {:<width$}  {:>8}  {:>7}  {:>7}",
+            "Action", "Visits", "Value", "Prior"
+        ));
+        for (label, child) in labels.iter().zip(&children) {
+            report.push_str(&format!(
+                "\n  {:<width$}  {:>8}  {:>7.3}  {:>7.3}",
+                label,
+                child.visits(),
+                child.value(),
+                child.prior()
+            ));
+        }
+        report
+    }
+
+    /// Returns a visualization of the search tree
+    pub fn visualize_tree(&self) -> String {
+        let mut result = String::new();
+        Self::visualize_node(&self.root, 0, &mut result);
+        result
     }
 
-    /// Returns a visualization of the search tree
-    pub fn visualize_tree(&self) -> String {
-        let mut result = String::new();
-        Self::visualize_node(&self.root, 0, &mut result);
-        result
-    }
-
     /// Helper method to visualize a node and its children
     fn visualize_node(node: &MCTSNode<S>, depth: usize, output: &mut String) {
         let indent = "  ".repeat(depth);
@@ -561,12 +3786,16 @@ impl<S: GameState + 'static> MCTS<S> {
         };
 
         output.push_str(&format!(
-            "{}{} (visits: {}, value: {:.3})\n",
+            "{}{} (visits: {}, value: {:.3})",
             indent,
             action_str,
             node.visits(),
             node.value()
         ));
+        if let Some(target) = &node.transposition {
+            output.push_str(&format!(" -> {}", target));
+        }
+        output.push('\n');
 
         for child in &node.children {
             Self::visualize_node(child, depth + 1, output);
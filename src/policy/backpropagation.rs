@@ -8,13 +8,113 @@ use crate::{
     tree::MCTSNode,
 };
 
+/// Information about the whole iteration passed to backpropagation policies
+///
+/// The same context is used for every node updated in one iteration, so
+/// policies can depend on where the playout started, how deep the path went,
+/// or how far into the search they are.
+pub struct BackpropContext<'a, S: GameState> {
+    /// State of the node the simulation started from
+    pub leaf_state: &'a S,
+
+    /// Depth of that node (root = 0)
+    pub path_depth: usize,
+
+    /// Zero-based index of the current search iteration
+    pub iteration: usize,
+
+    /// Actions played during the simulation, if available
+    pub trace: Option<&'a [S::Action]>,
+}
+
+impl<'a, S: GameState> BackpropContext<'a, S> {
+    /// Creates a context for a leaf at the root on the first iteration
+    ///
+    /// Useful when calling a policy directly, e.g. in tests.
+    pub fn new(leaf_state: &'a S) -> Self {
+        BackpropContext {
+            leaf_state,
+            path_depth: 0,
+            iteration: 0,
+            trace: None,
+        }
+    }
+}
+
+impl<S: GameState> std::fmt::Debug for BackpropContext<'_, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackpropContext")
+            .field("path_depth", &self.path_depth)
+            .field("iteration", &self.iteration)
+            .field("trace", &self.trace)
+            .finish()
+    }
+}
+
 /// Trait for policies that backpropagate simulation results
+///
+/// The search updates the nodes of an iteration from the leaf up to the root,
+/// so when a node is updated its children already include the current result.
 pub trait BackpropagationPolicy<S: GameState>: Send + Sync {
     /// Updates statistics for a node based on a simulation result
     fn update_stats(&self, node: &mut MCTSNode<S>, result: f64, trace: Option<&[S::Action]>);
 
+    /// Updates statistics for a node with access to the whole iteration
+    ///
+    /// This is what the search calls. The default forwards to
+    /// [`update_stats`](Self::update_stats), so simple policies only need to
+    /// implement that; override this when the update depends on the leaf
+    /// state, the path depth, or the iteration.
+    fn update_with_context(
+        &self,
+        node: &mut MCTSNode<S>,
+        result: f64,
+        context: &BackpropContext<'_, S>,
+    ) {
+        self.update_stats(node, result, context.trace)
+    }
+
+    /// Updates statistics for a node through a shared reference
+    ///
+    /// Parallel workers backpropagating along their own paths at the same
+    /// time only hold `&MCTSNode`s, so this may only use the node's atomic
+    /// statistics (`increment_visits`, `add_reward`, ...). Returns false,
+    /// leaving the node untouched, if the policy needs exclusive access; the
+    /// search then backpropagates the path with
+    /// [`update_with_context`](Self::update_with_context) instead. This is the
+    /// default. A policy must give the same answer for every node.
+    fn update_shared(
+        &self,
+        _node: &MCTSNode<S>,
+        _result: f64,
+        _context: &BackpropContext<'_, S>,
+    ) -> bool {
+        false
+    }
+
     /// Create a boxed clone of this policy
     fn clone_box(&self) -> Box<dyn BackpropagationPolicy<S>>;
+
+    /// Called before a search starts, with the root state
+    ///
+    /// Stateful policies can use this to reset per-search data.
+    fn on_search_start(&mut self, _root: &S) {}
+
+    /// Called after a search finishes, with the final root node
+    fn on_search_end(&mut self, _root: &MCTSNode<S>) {}
+
+    /// Called when a move is played in the game via [`MCTS::apply_move`](crate::MCTS::apply_move)
+    ///
+    /// `state` is the position after the move. Policies that keep state across
+    /// moves (e.g. reply tables) can update or prune it here.
+    fn on_move_played(&mut self, _action: &S::Action, _state: &S) {}
+}
+
+/// Adds a visit with `result` to a node's reward and squared reward totals
+fn record_result<S: GameState>(node: &MCTSNode<S>, result: f64) {
+    node.increment_visits();
+    node.add_reward(result);
+    node.add_squared_reward(result);
 }
 
 /// Standard backpropagation policy
@@ -39,9 +139,17 @@ impl Default for StandardPolicy {
 
 impl<S: GameState> BackpropagationPolicy<S> for StandardPolicy {
     fn update_stats(&self, node: &mut MCTSNode<S>, result: f64, _trace: Option<&[S::Action]>) {
-        node.increment_visits();
-        node.add_reward(result);
-        node.add_squared_reward(result);
+        record_result(node, result);
+    }
+
+    fn update_shared(
+        &self,
+        node: &MCTSNode<S>,
+        result: f64,
+        _context: &BackpropContext<'_, S>,
+    ) -> bool {
+        record_result(node, result);
+        true
     }
 
     fn clone_box(&self) -> Box<dyn BackpropagationPolicy<S>> {
@@ -55,9 +163,39 @@ impl<S: GameState> BackpropagationPolicy<S> for Box<dyn BackpropagationPolicy<S>
         (**self).update_stats(node, result, trace)
     }
 
+    fn update_with_context(
+        &self,
+        node: &mut MCTSNode<S>,
+        result: f64,
+        context: &BackpropContext<'_, S>,
+    ) {
+        (**self).update_with_context(node, result, context)
+    }
+
+    fn update_shared(
+        &self,
+        node: &MCTSNode<S>,
+        result: f64,
+        context: &BackpropContext<'_, S>,
+    ) -> bool {
+        (**self).update_shared(node, result, context)
+    }
+
     fn clone_box(&self) -> Box<dyn BackpropagationPolicy<S>> {
         (**self).clone_box()
     }
+
+    fn on_search_start(&mut self, root: &S) {
+        (**self).on_search_start(root)
+    }
+
+    fn on_search_end(&mut self, root: &MCTSNode<S>) {
+        (**self).on_search_end(root)
+    }
+
+    fn on_move_played(&mut self, action: &S::Action, state: &S) {
+        (**self).on_move_played(action, state)
+    }
 }
 
 /// Weighted backpropagation policy
@@ -77,17 +215,30 @@ impl WeightedPolicy {
     pub fn new(depth_factor: f64) -> Self {
         WeightedPolicy { depth_factor }
     }
-}
 
-impl<S: GameState> BackpropagationPolicy<S> for WeightedPolicy {
-    fn update_stats(&self, node: &mut MCTSNode<S>, result: f64, _trace: Option<&[S::Action]>) {
+    /// Adds a visit with `result`, weighted by the node's depth
+    fn record<S: GameState>(&self, node: &MCTSNode<S>, result: f64) {
         // Calculate weight based on depth
         // Higher depth means lower weight if depth_factor is positive
         let weight = 1.0 / (1.0 + self.depth_factor * node.depth as f64);
 
-        node.increment_visits();
-        node.add_reward(result * weight);
-        node.add_squared_reward(result * weight);
+        record_result(node, result * weight);
+    }
+}
+
+impl<S: GameState> BackpropagationPolicy<S> for WeightedPolicy {
+    fn update_stats(&self, node: &mut MCTSNode<S>, result: f64, _trace: Option<&[S::Action]>) {
+        self.record(node, result);
+    }
+
+    fn update_shared(
+        &self,
+        node: &MCTSNode<S>,
+        result: f64,
+        _context: &BackpropContext<'_, S>,
+    ) -> bool {
+        self.record(node, result);
+        true
     }
 
     fn clone_box(&self) -> Box<dyn BackpropagationPolicy<S>> {
@@ -114,20 +265,17 @@ impl RavePolicy {
             rave_weight: rave_weight.clamp(0.0, 1.0),
         }
     }
-}
 
-impl<S: GameState> BackpropagationPolicy<S> for RavePolicy {
-    fn update_stats(&self, node: &mut MCTSNode<S>, result: f64, trace: Option<&[S::Action]>) {
+    /// Adds a visit with `result`, and a RAVE visit if the node's action was played later
+    fn record<S: GameState>(node: &MCTSNode<S>, result: f64, trace: Option<&[S::Action]>) {
         // Standard update
-        node.increment_visits();
-        node.add_reward(result);
-        node.add_squared_reward(result);
+        record_result(node, result);
 
         // RAVE (AMAF) update
         if let (Some(trace), Some(node_action)) = (trace, &node.action) {
             // Check if the action leading to this node appears in the action trace
             // (i.e., if this action was played later in the simulation)
-            let action_in_trace = trace.iter().any(|a| a.id() == node_action.id());
+            let action_in_trace = trace.iter().any(|a| a.key() == node_action.key());
 
             if action_in_trace {
                 node.increment_rave_visits();
@@ -135,6 +283,140 @@ impl<S: GameState> BackpropagationPolicy<S> for RavePolicy {
             }
         }
     }
+}
+
+impl<S: GameState> BackpropagationPolicy<S> for RavePolicy {
+    fn update_stats(&self, node: &mut MCTSNode<S>, result: f64, trace: Option<&[S::Action]>) {
+        Self::record(node, result, trace);
+    }
+
+    fn update_shared(
+        &self,
+        node: &MCTSNode<S>,
+        result: f64,
+        context: &BackpropContext<'_, S>,
+    ) -> bool {
+        Self::record(node, result, context.trace);
+        true
+    }
+
+    fn clone_box(&self) -> Box<dyn BackpropagationPolicy<S>> {
+        Box::new(self.clone())
+    }
+}
+
+/// Power-mean backpropagation policy
+///
+/// Instead of averaging every result seen below a node, an internal node's
+/// value is backed up from its children as their visit-weighted power mean:
+///
+/// ```text
+/// V(s) = (sum_i n_i * V(s_i)^p / sum_i n_i)^(1/p)
+/// ```
+///
+/// With `p = 1` this is close to the plain average; larger exponents move the
+/// estimate towards the best child (MaxMCTS in the limit), so a node with one
+/// strong child is not underestimated. Leaves keep the plain average. The
+/// value is stored in the node's total reward, so every selection policy sees
+/// it through [`MCTSNode::value`].
+#[derive(Debug, Clone)]
+pub struct PowerMeanPolicy {
+    /// Exponent of the power mean; must be at least 1
+    pub exponent: f64,
+}
+
+impl PowerMeanPolicy {
+    /// Creates a new power-mean policy with the given exponent
+    ///
+    /// Exponents below 1 are raised to 1.
+    pub fn new(exponent: f64) -> Self {
+        PowerMeanPolicy {
+            exponent: exponent.max(1.0),
+        }
+    }
+}
+
+impl<S: GameState> BackpropagationPolicy<S> for PowerMeanPolicy {
+    fn update_stats(&self, node: &mut MCTSNode<S>, result: f64, _trace: Option<&[S::Action]>) {
+        node.increment_visits();
+        node.add_squared_reward(result);
+
+        if node.children.iter().any(|child| child.visits() > 0) {
+            let value = node.power_mean_value(self.exponent);
+            node.set_total_reward(value * node.visits() as f64);
+        } else {
+            node.add_reward(result);
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn BackpropagationPolicy<S>> {
+        Box::new(self.clone())
+    }
+}
+
+/// Bayesian backpropagation policy with a Gaussian model of each node's value
+///
+/// Every node keeps a Gaussian posterior over its value, starting from the
+/// prior `N(prior_mean, 1 / prior_precision)`. Each result is treated as a
+/// noisy observation with precision `noise_precision`:
+///
+/// ```text
+/// τ' = τ + τ_noise
+/// μ' = (τ * μ + τ_noise * result) / τ'
+/// ```
+///
+/// With tiny budgets the prior keeps barely visited nodes from looking
+/// certain. Pair it with [`PosteriorQuantilePolicy`](crate::policy::selection::PosteriorQuantilePolicy)
+/// to select on the posterior. Visits and rewards are updated as usual.
+#[derive(Debug, Clone)]
+pub struct GaussianPolicy {
+    /// Mean of the prior over node values
+    pub prior_mean: f64,
+
+    /// Precision of the prior over node values
+    pub prior_precision: f64,
+
+    /// Precision of a single simulation result
+    pub noise_precision: f64,
+}
+
+impl GaussianPolicy {
+    /// Creates a new Gaussian policy
+    ///
+    /// Precisions are clamped to a small positive value.
+    pub fn new(prior_mean: f64, prior_precision: f64, noise_precision: f64) -> Self {
+        GaussianPolicy {
+            prior_mean,
+            prior_precision: prior_precision.max(1e-6),
+            noise_precision: noise_precision.max(1e-6),
+        }
+    }
+}
+
+impl Default for GaussianPolicy {
+    /// A weak prior centred on a draw, with results in `[0, 1]` treated as
+    /// having a standard deviation of 0.5
+    fn default() -> Self {
+        Self::new(0.5, 1.0, 4.0)
+    }
+}
+
+impl<S: GameState> BackpropagationPolicy<S> for GaussianPolicy {
+    fn update_stats(&self, node: &mut MCTSNode<S>, result: f64, _trace: Option<&[S::Action]>) {
+        node.increment_visits();
+        node.add_reward(result);
+        node.add_squared_reward(result);
+
+        let (mean, precision) = match node.posterior_precision() {
+            p if p > 0.0 => (node.posterior_mean(), p),
+            _ => (self.prior_mean, self.prior_precision),
+        };
+        let updated = precision + self.noise_precision;
+        node.set_posterior(
+            (precision * mean + self.noise_precision * result) / updated,
+            updated,
+        );
+    }
 
     fn clone_box(&self) -> Box<dyn BackpropagationPolicy<S>> {
         Box::new(self.clone())
@@ -1,8 +1,8 @@
 //! Expansion policies determine which unexpanded action to choose
 //! when expanding a leaf node.
 
-use crate::{game_state::GameState, tree::MCTSNode};
-use rand::prelude::IteratorRandom;
+use crate::{game_state::GameState, tree::MCTSNode, utils::search_rng};
+use rand::{prelude::IteratorRandom, RngCore};
 
 /// Trait for policies that select which action to expand
 pub trait ExpansionPolicy<S: GameState>: Send + Sync {
@@ -10,8 +10,33 @@ pub trait ExpansionPolicy<S: GameState>: Send + Sync {
     /// Returns the index of the action in `node.unexpanded_actions` and the prior probability to assign.
     fn select_action_to_expand(&self, node: &MCTSNode<S>) -> Option<(usize, f64)>;
 
+    /// Returns the prior of each of `node.unexpanded_actions`, in order
+    ///
+    /// Policies backed by an evaluator or heuristic that scores every action
+    /// at once can return the priors here, which lets
+    /// [`MCTSConfig::with_root_prior_filter`](crate::MCTSConfig::with_root_prior_filter)
+    /// drop hopeless root actions before the search spends anything on them.
+    /// The default returns `None`: priors are not known up front.
+    fn action_priors(&self, _node: &MCTSNode<S>) -> Option<Vec<f64>> {
+        None
+    }
+
     /// Create a boxed clone of this policy
     fn clone_box(&self) -> Box<dyn ExpansionPolicy<S>>;
+
+    /// Called before a search starts, with the root state
+    ///
+    /// Stateful policies can use this to reset per-search data.
+    fn on_search_start(&mut self, _root: &S) {}
+
+    /// Called after a search finishes, with the final root node
+    fn on_search_end(&mut self, _root: &MCTSNode<S>) {}
+
+    /// Called when a move is played in the game via [`MCTS::apply_move`](crate::MCTS::apply_move)
+    ///
+    /// `state` is the position after the move. Policies that keep state across
+    /// moves (e.g. reply tables) can update or prune it here.
+    fn on_move_played(&mut self, _action: &S::Action, _state: &S) {}
 }
 
 /// Random expansion policy
@@ -40,7 +65,7 @@ impl<S: GameState> ExpansionPolicy<S> for RandomExpansionPolicy {
             return None;
         }
 
-        let mut rng = rand::thread_rng();
+        let mut rng = search_rng();
         let index = (0..node.unexpanded_actions.len()).choose(&mut rng)?;
 
         // Calculate uniform prior: 1.0 / number of legal actions (at creation time)
@@ -61,13 +86,248 @@ impl<S: GameState> ExpansionPolicy<S> for RandomExpansionPolicy {
     }
 }
 
+/// Heuristic expansion policy
+///
+/// Expands the unexpanded action with the highest heuristic score first, so
+/// with [`MCTSConfig::with_max_children`](crate::MCTSConfig::with_max_children)
+/// a node keeps its best-scoring actions. Assigns a uniform prior (1/N)
+/// unless [`with_score_priors`](Self::with_score_priors) is set.
+#[derive(Debug, Clone)]
+pub struct HeuristicExpansionPolicy<F, S>
+where
+    F: Fn(&S, &S::Action) -> f64 + Clone + Send + Sync + 'static,
+    S: GameState + 'static,
+{
+    /// Scores an action in a state; higher is expanded first
+    heuristic: F,
+    /// Share of each prior spread uniformly, if priors follow the scores
+    score_priors: Option<f64>,
+    _phantom: std::marker::PhantomData<S>,
+}
+
+impl<F, S> HeuristicExpansionPolicy<F, S>
+where
+    F: Fn(&S, &S::Action) -> f64 + Clone + Send + Sync + 'static,
+    S: GameState + 'static,
+{
+    /// Creates a new heuristic expansion policy with the given function
+    pub fn new(heuristic: F) -> Self {
+        HeuristicExpansionPolicy {
+            heuristic,
+            score_priors: None,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Assigns priors proportional to the scores
+    ///
+    /// Lets [`PUCTPolicy`](crate::policy::selection::PUCTPolicy) and
+    /// [`MCTSConfig::with_root_prior_filter`](crate::MCTSConfig::with_root_prior_filter)
+    /// use the heuristic. Priors are normalized over all of the node's
+    /// actions, expanded or not, and negative and non-finite scores count as
+    /// 0. A `floor` share of the priors, clamped to `[0, 1]`, is spread
+    /// uniformly so actions scoring 0 can still be explored; 0.05 is a
+    /// reasonable start. If no action scores above 0, the priors are uniform.
+    pub fn with_score_priors(mut self, floor: f64) -> Self {
+        self.score_priors = Some(if floor.is_nan() {
+            0.0
+        } else {
+            floor.clamp(0.0, 1.0)
+        });
+        self
+    }
+
+    /// Returns an action's score, with negative and non-finite scores as 0
+    fn weight(&self, state: &S, action: &S::Action) -> f64 {
+        let score = (self.heuristic)(state, action);
+        if score.is_finite() {
+            score.max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the score-proportional priors of the unexpanded actions
+    fn score_priors(&self, node: &MCTSNode<S>, floor: f64) -> Vec<f64> {
+        let expanded: f64 = node
+            .children
+            .iter()
+            .filter_map(|child| child.action.as_ref())
+            .map(|action| self.weight(&node.state, action))
+            .sum();
+        let weights: Vec<f64> = node
+            .unexpanded_actions
+            .iter()
+            .map(|action| self.weight(&node.state, action))
+            .collect();
+        let total = expanded + weights.iter().sum::<f64>();
+        let uniform = 1.0 / (node.children.len() + node.unexpanded_actions.len()) as f64;
+        if !(total > 0.0 && total.is_finite()) {
+            return vec![uniform; weights.len()];
+        }
+        weights
+            .into_iter()
+            .map(|weight| (1.0 - floor) * weight / total + floor * uniform)
+            .collect()
+    }
+}
+
+impl<F, S> ExpansionPolicy<S> for HeuristicExpansionPolicy<F, S>
+where
+    F: Fn(&S, &S::Action) -> f64 + Clone + Send + Sync + 'static,
+    S: GameState + 'static,
+{
+    fn select_action_to_expand(&self, node: &MCTSNode<S>) -> Option<(usize, f64)> {
+        if let Some(priors) = self.action_priors(node) {
+            let mut best_index = 0;
+            for (index, prior) in priors.iter().enumerate() {
+                if *prior > priors[best_index] {
+                    best_index = index;
+                }
+            }
+            return Some((best_index, priors[best_index]));
+        }
+
+        let mut best_index = None;
+        let mut best_score = f64::NEG_INFINITY;
+        for (index, action) in node.unexpanded_actions.iter().enumerate() {
+            let score = (self.heuristic)(&node.state, action);
+            if best_index.is_none() || score > best_score {
+                best_index = Some(index);
+                best_score = score;
+            }
+        }
+        let index = best_index?;
+
+        let total_actions = node.children.len() + node.unexpanded_actions.len();
+        Some((index, 1.0 / total_actions as f64))
+    }
+
+    fn action_priors(&self, node: &MCTSNode<S>) -> Option<Vec<f64>> {
+        let floor = self.score_priors?;
+        if node.unexpanded_actions.is_empty() {
+            return None;
+        }
+        Some(self.score_priors(node, floor))
+    }
+
+    fn clone_box(&self) -> Box<dyn ExpansionPolicy<S>> {
+        Box::new(self.clone())
+    }
+}
+
+/// Share of the priors a [`GreedyExpansionPolicy`] spreads uniformly by default
+pub const DEFAULT_PRIOR_FLOOR: f64 = 0.05;
+
+/// Greedy expansion policy
+///
+/// A [`HeuristicExpansionPolicy`] with
+/// [score priors](HeuristicExpansionPolicy::with_score_priors): expands the
+/// highest-scoring action first and assigns priors proportional to the
+/// scores, so [`PUCTPolicy`](crate::policy::selection::PUCTPolicy) and
+/// [`MCTSConfig::with_root_prior_filter`](crate::MCTSConfig::with_root_prior_filter)
+/// can use the heuristic. A [`DEFAULT_PRIOR_FLOOR`] share of the priors is
+/// spread uniformly so actions scoring 0 can still be explored.
+#[derive(Debug, Clone)]
+pub struct GreedyExpansionPolicy<F, S>
+where
+    F: Fn(&S, &S::Action) -> f64 + Clone + Send + Sync + 'static,
+    S: GameState + 'static,
+{
+    /// The heuristic policy with score priors enabled
+    inner: HeuristicExpansionPolicy<F, S>,
+}
+
+impl<F, S> GreedyExpansionPolicy<F, S>
+where
+    F: Fn(&S, &S::Action) -> f64 + Clone + Send + Sync + 'static,
+    S: GameState + 'static,
+{
+    /// Creates a new greedy expansion policy with the given function
+    pub fn new(heuristic: F) -> Self {
+        GreedyExpansionPolicy {
+            inner: HeuristicExpansionPolicy::new(heuristic).with_score_priors(DEFAULT_PRIOR_FLOOR),
+        }
+    }
+
+    /// Sets the share of the priors spread uniformly, clamped to `[0, 1]`
+    pub fn with_prior_floor(mut self, floor: f64) -> Self {
+        self.inner = self.inner.with_score_priors(floor);
+        self
+    }
+}
+
+impl<F, S> ExpansionPolicy<S> for GreedyExpansionPolicy<F, S>
+where
+    F: Fn(&S, &S::Action) -> f64 + Clone + Send + Sync + 'static,
+    S: GameState + 'static,
+{
+    fn select_action_to_expand(&self, node: &MCTSNode<S>) -> Option<(usize, f64)> {
+        self.inner.select_action_to_expand(node)
+    }
+
+    fn action_priors(&self, node: &MCTSNode<S>) -> Option<Vec<f64>> {
+        self.inner.action_priors(node)
+    }
+
+    fn clone_box(&self) -> Box<dyn ExpansionPolicy<S>> {
+        Box::new(self.clone())
+    }
+}
+
 // Implement ExpansionPolicy for Box<dyn ExpansionPolicy>
 impl<S: GameState> ExpansionPolicy<S> for Box<dyn ExpansionPolicy<S>> {
     fn select_action_to_expand(&self, node: &MCTSNode<S>) -> Option<(usize, f64)> {
         (**self).select_action_to_expand(node)
     }
 
+    fn action_priors(&self, node: &MCTSNode<S>) -> Option<Vec<f64>> {
+        (**self).action_priors(node)
+    }
+
     fn clone_box(&self) -> Box<dyn ExpansionPolicy<S>> {
         (**self).clone_box()
     }
+
+    fn on_search_start(&mut self, root: &S) {
+        (**self).on_search_start(root)
+    }
+
+    fn on_search_end(&mut self, root: &MCTSNode<S>) {
+        (**self).on_search_end(root)
+    }
+
+    fn on_move_played(&mut self, action: &S::Action, state: &S) {
+        (**self).on_move_played(action, state)
+    }
+}
+
+/// Draws candidate actions in domains whose actions cannot be listed
+///
+/// Robotics, control and parameter tuning problems often have continuous or
+/// astronomically large action spaces. Attach a sampler with
+/// [`MCTS::with_action_sampler`](crate::MCTS::with_action_sampler) and a node
+/// draws a new action from it whenever its listed actions are used up, so
+/// [`get_legal_actions`](GameState::get_legal_actions) may return an empty
+/// list for non-terminal states. Combine it with
+/// [`MCTSConfig::with_progressive_widening`](crate::MCTSConfig::with_progressive_widening)
+/// so each node's candidate set grows with its visits.
+///
+/// Sampled actions are told apart by [`key`](crate::Action::key): drawing the
+/// key of an existing child revisits that child instead of adding a new one.
+///
+/// Closures `Fn(&S, &mut dyn RngCore) -> S::Action` implement this trait.
+pub trait ActionSampler<S: GameState>: Send + Sync {
+    /// Draws an action to try in the non-terminal `state`
+    fn sample_action(&self, state: &S, rng: &mut dyn RngCore) -> S::Action;
+}
+
+impl<S, F> ActionSampler<S> for F
+where
+    S: GameState,
+    F: Fn(&S, &mut dyn RngCore) -> S::Action + Send + Sync,
+{
+    fn sample_action(&self, state: &S, rng: &mut dyn RngCore) -> S::Action {
+        self(state, rng)
+    }
 }
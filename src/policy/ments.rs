@@ -0,0 +1,220 @@
+//! Maximum-entropy MCTS (MENTS)
+//!
+//! MENTS replaces averaged backups with softmax ("soft") value backups and
+//! UCT-style selection with E2W (empirical exponential weights) sampling.
+//! The two halves rely on each other, so a single [`MentsPolicy`] implements
+//! both [`SelectionPolicy`] and [`BackpropagationPolicy`] with a shared
+//! temperature:
+//!
+//! ```
+//! # use arboriter_mcts::{GameState, Action, Player, MCTS, MCTSConfig};
+//! # use arboriter_mcts::policy::ments::MentsPolicy;
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct A(usize);
+//! # impl Action for A { fn id(&self) -> usize { self.0 } }
+//! # #[derive(Debug, Clone, PartialEq)]
+//! # struct P;
+//! # impl Player for P {}
+//! # #[derive(Clone)]
+//! # struct G(usize);
+//! # impl GameState for G {
+//! #     type Action = A;
+//! #     type Player = P;
+//! #     fn get_legal_actions(&self) -> Vec<A> { if self.0 < 3 { vec![A(0), A(1)] } else { vec![] } }
+//! #     fn apply_action(&self, _: &A) -> Self { G(self.0 + 1) }
+//! #     fn is_terminal(&self) -> bool { self.0 >= 3 }
+//! #     fn get_result(&self, _: &P) -> f64 { 0.5 }
+//! #     fn get_current_player(&self) -> P { P }
+//! # }
+//! let ments = MentsPolicy::new(0.1, 1.0);
+//! let mut mcts = MCTS::new(G(0), MCTSConfig::default().with_max_iterations(100))
+//!     .with_selection_policy(ments.clone())
+//!     .with_backpropagation_policy(ments);
+//! assert!(mcts.search().is_ok());
+//! ```
+//!
+//! See Xiao et al., "Maximum Entropy Monte-Carlo Planning" (NeurIPS 2019).
+
+use rand::Rng;
+
+use crate::{
+    game_state::GameState,
+    policy::{
+        backpropagation::BackpropagationPolicy,
+        selection::{ChildScore, SelectionContext, SelectionPolicy},
+    },
+    tree::MCTSNode,
+};
+
+/// Matched E2W selection and softmax backup policy
+///
+/// Backup stores the soft value of every internal node,
+///
+/// ```text
+/// V(s) = τ * ln(sum_a exp(Q(s, a) / τ))
+/// ```
+///
+/// over its visited children, in the node's total reward; leaves keep the
+/// plain average of their results. Selection samples a child from
+///
+/// ```text
+/// π(a) = (1 - λ) * softmax(Q / τ)(a) + λ / K,    λ = min(1, ε * K / ln(N + 1))
+/// ```
+///
+/// where `K` is the number of children and `N` the parent's visits.
+///
+/// Soft values exceed the best child's value by up to `τ * ln(K)`, so keep
+/// the default [`BestChildCriteria::MostVisits`](crate::config::BestChildCriteria::MostVisits)
+/// when choosing the final move.
+#[derive(Debug, Clone)]
+pub struct MentsPolicy {
+    /// Temperature τ of the softmax; smaller values approach a hard max
+    pub temperature: f64,
+
+    /// Exploration coefficient ε of the uniform mixing weight λ
+    pub exploration: f64,
+}
+
+impl MentsPolicy {
+    /// Creates a MENTS policy with the given temperature and exploration coefficient
+    ///
+    /// The temperature is clamped to a small positive value.
+    pub fn new(temperature: f64, exploration: f64) -> Self {
+        MentsPolicy {
+            temperature: temperature.max(1e-6),
+            exploration,
+        }
+    }
+
+    /// Returns the soft value of a node's visited children, if it has any
+    pub fn soft_value<S: GameState>(&self, node: &MCTSNode<S>) -> Option<f64> {
+        let values: Vec<f64> = node
+            .children
+            .iter()
+            .filter(|child| child.visits() > 0)
+            .map(|child| child.value())
+            .collect();
+
+        let max = values.iter().copied().reduce(f64::max)?;
+        let sum: f64 = values
+            .iter()
+            .map(|value| ((value - max) / self.temperature).exp())
+            .sum();
+        Some(max + self.temperature * sum.ln())
+    }
+
+    /// Returns the E2W sampling distribution over a node's children
+    pub fn e2w_probabilities<S: GameState>(&self, node: &MCTSNode<S>) -> Vec<f64> {
+        let count = node.children.len();
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let values: Vec<f64> = node
+            .children
+            .iter()
+            .map(|child| child.selection_stats().1)
+            .collect();
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = values
+            .iter()
+            .map(|value| ((value - max) / self.temperature).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        let lambda = self.mixing_weight(node);
+
+        weights
+            .iter()
+            .map(|weight| (1.0 - lambda) * weight / total + lambda / count as f64)
+            .collect()
+    }
+
+    /// Returns the weight `λ` of the uniform distribution in the E2W mix
+    fn mixing_weight<S: GameState>(&self, node: &MCTSNode<S>) -> f64 {
+        let count = node.children.len() as f64;
+        let parent_visits = node.selection_stats().0 as f64;
+        let lambda = (self.exploration * count / (parent_visits + 1.0).ln()).min(1.0);
+        // Before the first visit ln(1) = 0, which makes the mix uniform
+        if lambda.is_nan() {
+            1.0
+        } else {
+            lambda
+        }
+    }
+
+    /// Returns the probability every child gets from the uniform part of the mix
+    fn uniform_share<S: GameState>(&self, node: &MCTSNode<S>) -> f64 {
+        match node.children.len() {
+            0 => 0.0,
+            count => self.mixing_weight(node) / count as f64,
+        }
+    }
+}
+
+impl Default for MentsPolicy {
+    fn default() -> Self {
+        Self::new(0.1, 1.0)
+    }
+}
+
+impl<S: GameState> SelectionPolicy<S> for MentsPolicy {
+    fn select_child(&self, node: &MCTSNode<S>, context: &mut SelectionContext<'_>) -> usize {
+        let probabilities = self.e2w_probabilities(node);
+        if probabilities.is_empty() {
+            return 0;
+        }
+
+        let mut remaining: f64 = context.rng.gen();
+        for (i, probability) in probabilities.iter().enumerate() {
+            remaining -= probability;
+            if remaining <= 0.0 {
+                return i;
+            }
+        }
+        probabilities.len() - 1
+    }
+
+    // Scores are the E2W probabilities: the softmax share as exploitation
+    // and the uniform share as exploration
+    fn child_scores(
+        &self,
+        node: &MCTSNode<S>,
+        _context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        let probabilities = self.e2w_probabilities(node);
+        let uniform = self.uniform_share(node);
+        let scores = probabilities
+            .iter()
+            .zip(&node.children)
+            .map(|(probability, child)| {
+                ChildScore::new(probability - uniform, uniform, child.prior())
+            })
+            .collect();
+        Some(scores)
+    }
+
+    fn clone_box(&self) -> Box<dyn SelectionPolicy<S>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<S: GameState> BackpropagationPolicy<S> for MentsPolicy {
+    fn update_stats(&self, node: &mut MCTSNode<S>, result: f64, _trace: Option<&[S::Action]>) {
+        node.increment_visits();
+        node.add_squared_reward(result);
+
+        match self.soft_value(node) {
+            Some(value) => node.set_total_reward(value * node.visits() as f64),
+            None => node.add_reward(result),
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn BackpropagationPolicy<S>> {
+        Box::new(self.clone())
+    }
+}
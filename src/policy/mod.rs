@@ -5,12 +5,18 @@
 //! - Simulation policies: How to play out games from a node
 //! - Backpropagation policies: How to update node statistics
 //! - Expansion policies: How to create new nodes
+//! - MENTS: A matched selection and backpropagation pair using softmax backups
+//! - Opponent models: Predicted replies mixed into selection at the opponent's nodes
+//! - Oracles: Exact values of known states, such as endgame tablebases
 
 pub mod backpropagation;
 pub mod expansion;
+pub mod ments;
+pub mod opponent;
+pub mod oracle;
 pub mod selection;
 pub mod simulation;
 
-pub use backpropagation::{BackpropagationPolicy, StandardPolicy};
-pub use selection::{SelectionPolicy, UCB1Policy};
+pub use backpropagation::{BackpropContext, BackpropagationPolicy, StandardPolicy};
+pub use selection::{SelectionContext, SelectionPolicy, UCB1Policy};
 pub use simulation::{RandomPolicy, SimulationPolicy};
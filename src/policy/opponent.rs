@@ -0,0 +1,71 @@
+//! Opponent models for playing against predictable opponents
+//!
+//! Plain MCTS assumes the opponent finds its best reply. Against humans or
+//! scripted bots with known habits that is needlessly pessimistic. An
+//! [`OpponentModel`] predicts how likely the opponent is to play each move,
+//! and [`MCTS::with_opponent_model`](crate::MCTS::with_opponent_model) mixes
+//! that prediction into selection wherever the opponent is to move.
+
+use crate::{game_state::GameState, tree::MCTSNode, utils};
+
+/// Predicts the opponent's moves
+///
+/// Closures `Fn(&S, &S::Action) -> f64` implement this trait.
+pub trait OpponentModel<S: GameState>: Send + Sync {
+    /// Returns the probability, in `[0, 1]`, that the opponent plays `action` in `state`
+    fn probability(&self, state: &S, action: &S::Action) -> f64;
+}
+
+impl<S, F> OpponentModel<S> for F
+where
+    S: GameState,
+    F: Fn(&S, &S::Action) -> f64 + Send + Sync,
+{
+    fn probability(&self, state: &S, action: &S::Action) -> f64 {
+        self(state, action)
+    }
+}
+
+/// Selects the opponent's reply by mixing UCB1 with the model's prediction
+///
+/// Scores each child with
+///
+/// ```text
+/// (1 - w) * (value + C * sqrt(ln(N) / n)) + w * p(action)
+/// ```
+///
+/// after first trying every unvisited child, as UCB1 does. A weight of 0 is
+/// plain UCB1, the worst case for the searching player; a weight of 1
+/// always follows the most likely predicted move.
+pub fn select_opponent_child<S: GameState>(
+    model: &dyn OpponentModel<S>,
+    node: &MCTSNode<S>,
+    weight: f64,
+    exploration_constant: f64,
+) -> usize {
+    let (parent_visits, _) = node.selection_stats();
+    let mut best_value = f64::NEG_INFINITY;
+    let mut best_index = 0;
+
+    for (i, child) in node.children.iter().enumerate() {
+        let (child_visits, child_value) = child.selection_stats();
+        if child_visits == 0 {
+            return i; // Always explore nodes that have never been visited
+        }
+
+        let ucb = child_value
+            + utils::exploration_term(parent_visits, child_visits, exploration_constant);
+        let prediction = child
+            .action
+            .as_ref()
+            .map_or(0.0, |action| model.probability(&node.state, action));
+        let score = (1.0 - weight) * ucb + weight * prediction;
+
+        if score > best_value {
+            best_value = score;
+            best_index = i;
+        }
+    }
+
+    best_index
+}
@@ -0,0 +1,31 @@
+//! Oracles with exact knowledge of some states
+//!
+//! Chess endgame tablebases, solved puzzle positions and closed-form
+//! evaluations tell the exact value of a state without any search. Attach an
+//! [`Oracle`] with [`MCTS::with_oracle`](crate::MCTS::with_oracle) and every
+//! newly expanded node is checked against it: a state with a known value
+//! becomes a solved leaf that is never expanded, and its value replaces the
+//! playout whenever the search reaches it.
+
+use crate::game_state::GameState;
+
+/// Knows the exact value of some states
+///
+/// Closures `Fn(&S, &S::Player) -> Option<f64>` implement this trait.
+pub trait Oracle<S: GameState>: Send + Sync {
+    /// Returns the exact result of `state` for `for_player`, if known
+    ///
+    /// Values use the same scale as [`GameState::get_result`]: the result
+    /// with perfect play from `state` on.
+    fn exact_value(&self, state: &S, for_player: &S::Player) -> Option<f64>;
+}
+
+impl<S, F> Oracle<S> for F
+where
+    S: GameState,
+    F: Fn(&S, &S::Player) -> Option<f64> + Send + Sync,
+{
+    fn exact_value(&self, state: &S, for_player: &S::Player) -> Option<f64> {
+        self(state, for_player)
+    }
+}
@@ -5,18 +5,149 @@
 
 use std::f64;
 
-use crate::{game_state::GameState, tree::MCTSNode};
+use rand::RngCore;
+
+use crate::{
+    game_state::{Action, ActionFeatures, GameState},
+    history::HistoryTable,
+    tree::MCTSNode,
+    utils,
+};
+
+/// Number of children at which [`UCB1Policy`] switches to batched scoring
+///
+/// Below this width the scalar loop is as fast as gathering statistics into
+/// contiguous arrays first.
+pub const WIDE_NODE_THRESHOLD: usize = 32;
+
+/// Information about the current descent passed to selection policies
+///
+/// This lets policies depend on more than the node itself, e.g. exploration
+/// that decays with depth, schedules driven by the iteration number, or
+/// randomized tie-breaking using the search's RNG.
+pub struct SelectionContext<'a> {
+    /// Depth of the node whose child is being selected (root = 0)
+    pub depth: usize,
+
+    /// Child indices followed from the root to reach the node
+    pub path: &'a [usize],
+
+    /// Zero-based index of the current search iteration
+    pub iteration: usize,
+
+    /// Random number generator owned by the search
+    pub rng: &'a mut dyn RngCore,
+
+    /// History table attached with [`MCTS::with_history`](crate::MCTS::with_history), if any
+    pub history: Option<&'a HistoryTable>,
+}
+
+impl<'a> SelectionContext<'a> {
+    /// Creates a context for selecting a root child on the first iteration
+    ///
+    /// Useful when calling a policy directly, e.g. in tests.
+    pub fn new(rng: &'a mut dyn RngCore) -> Self {
+        SelectionContext {
+            depth: 0,
+            path: &[],
+            iteration: 0,
+            rng,
+            history: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for SelectionContext<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SelectionContext")
+            .field("depth", &self.depth)
+            .field("path", &self.path)
+            .field("iteration", &self.iteration)
+            .field("history", &self.history.is_some())
+            .finish()
+    }
+}
+
+/// How a selection policy scored one child, for debugging
+///
+/// See [`SelectionPolicy::child_scores`] and
+/// [`MCTS::child_scores`](crate::MCTS::child_scores).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChildScore {
+    /// The value estimate the policy starts from
+    pub exploitation: f64,
+
+    /// Every bonus the policy adds on top, infinite for children it always tries first
+    pub exploration: f64,
+
+    /// The child's prior
+    pub prior: f64,
+
+    /// The score selection compares, `exploitation + exploration`
+    pub score: f64,
+}
+
+impl ChildScore {
+    /// Creates a score from its two terms
+    pub fn new(exploitation: f64, exploration: f64, prior: f64) -> Self {
+        ChildScore {
+            exploitation,
+            exploration,
+            prior,
+            score: exploitation + exploration,
+        }
+    }
+
+    /// Returns whether selection can compare this score
+    ///
+    /// False if the score is NaN or negative infinity, or if the
+    /// exploitation term or prior is not finite. An infinite exploration
+    /// bonus only marks a child the policy always tries first.
+    pub fn is_finite(&self) -> bool {
+        !self.score.is_nan()
+            && self.score != f64::NEG_INFINITY
+            && self.exploitation.is_finite()
+            && self.prior.is_finite()
+    }
+}
 
 /// Trait for policies that select nodes to explore
 pub trait SelectionPolicy<S: GameState>: Send + Sync {
     /// Selects a child index based on the policy
-    fn select_child(&self, node: &MCTSNode<S>) -> usize;
+    fn select_child(&self, node: &MCTSNode<S>, context: &mut SelectionContext<'_>) -> usize;
+
+    /// Scores every child of `node` the way [`select_child`](Self::select_child) does
+    ///
+    /// Returns one entry per child, in child order, or `None` if the policy
+    /// does not break its scores down; the default. Deterministic policies
+    /// select the first child with the highest score.
+    fn child_scores(
+        &self,
+        _node: &MCTSNode<S>,
+        _context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        None
+    }
 
     /// Create a boxed clone of this policy
     fn clone_box(&self) -> Box<dyn SelectionPolicy<S>>;
 
     /// Returns the policy as Any to allow downcasting
     fn as_any(&self) -> &dyn std::any::Any;
+
+    /// Called before a search starts, with the root state
+    ///
+    /// Stateful policies can use this to reset per-search data.
+    fn on_search_start(&mut self, _root: &S) {}
+
+    /// Called after a search finishes, with the final root node
+    fn on_search_end(&mut self, _root: &MCTSNode<S>) {}
+
+    /// Called when a move is played in the game via [`MCTS::apply_move`](crate::MCTS::apply_move)
+    ///
+    /// `state` is the position after the move. Policies that keep state across
+    /// moves (e.g. reply tables) can update or prune it here.
+    fn on_move_played(&mut self, _action: &S::Action, _state: &S) {}
 }
 
 /// Upper Confidence Bound 1 (UCB1) selection policy
@@ -61,27 +192,72 @@ impl UCB1Policy {
         }
 
         // UCB1 formula: value + C * sqrt(ln(parent_visits) / child_visits)
-        let exploitation = child_value;
-        let exploration =
-            self.exploration_constant * ((parent_visits as f64).ln() / child_visits as f64).sqrt();
+        child_value
+            + utils::exploration_term(parent_visits, child_visits, self.exploration_constant)
+    }
 
-        exploitation + exploration
+    /// Selects a child by scoring children in fixed-size batches
+    ///
+    /// This is the fast path for wide nodes: child statistics are gathered into
+    /// stack arrays and scored with [`utils::ucb1_scores`], which computes
+    /// `ln(parent_visits)` once and lets the compiler vectorize the rest.
+    /// Returns the same index as the scalar loop, including tie-breaking.
+    pub fn select_child_batched<S: GameState>(&self, node: &MCTSNode<S>) -> usize {
+        const BATCH: usize = utils::SCORE_BATCH_SIZE;
+
+        let ln_parent_visits = (node.selection_stats().0.max(1) as f64).ln();
+        let mut values = [0.0; BATCH];
+        let mut visits = [0.0; BATCH];
+        let mut scores = [0.0; BATCH];
+
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+
+        for (chunk_index, chunk) in node.children.chunks(BATCH).enumerate() {
+            let len = chunk.len();
+            for (i, child) in chunk.iter().enumerate() {
+                let (child_visits, child_value) = child.selection_stats();
+                values[i] = child_value;
+                visits[i] = child_visits as f64;
+            }
+
+            utils::ucb1_scores(
+                &values[..len],
+                &visits[..len],
+                ln_parent_visits,
+                self.exploration_constant,
+                &mut scores[..len],
+            );
+
+            for (i, &score) in scores[..len].iter().enumerate() {
+                if score > best_value {
+                    best_value = score;
+                    best_index = chunk_index * BATCH + i;
+                }
+            }
+        }
+
+        best_index
     }
 }
 
 impl<S: GameState> SelectionPolicy<S> for UCB1Policy {
-    fn select_child(&self, node: &MCTSNode<S>) -> usize {
+    fn select_child(&self, node: &MCTSNode<S>, _context: &mut SelectionContext<'_>) -> usize {
         if node.children.is_empty() {
             return 0;
         }
 
-        let parent_visits = node.visits();
+        if node.children.len() >= WIDE_NODE_THRESHOLD {
+            return self.select_child_batched(node);
+        }
+
+        // Clamped like the batched path, so both score a parent without visits alike
+        let parent_visits = node.selection_stats().0.max(1);
         let mut best_value = f64::NEG_INFINITY;
         let mut best_index = 0;
 
         for (i, child) in node.children.iter().enumerate() {
-            let child_value = child.value();
-            let child_visits = child.visits();
+            let (child_visits, child_value) = child.selection_stats();
 
             let ucb_value = self.ucb1_value(child_value, child_visits, parent_visits);
 
@@ -94,6 +270,223 @@ impl<S: GameState> SelectionPolicy<S> for UCB1Policy {
         best_index
     }
 
+    fn child_scores(
+        &self,
+        node: &MCTSNode<S>,
+        _context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        let parent_visits = node.selection_stats().0.max(1);
+        let scores = node
+            .children
+            .iter()
+            .map(|child| {
+                let (child_visits, child_value) = child.selection_stats();
+                let exploration = if child_visits == 0 {
+                    f64::INFINITY
+                } else {
+                    utils::exploration_term(parent_visits, child_visits, self.exploration_constant)
+                };
+                ChildScore::new(child_value, exploration, child.prior())
+            })
+            .collect();
+        Some(scores)
+    }
+
+    fn clone_box(&self) -> Box<dyn SelectionPolicy<S>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// UCB1 with a progressive history bias
+///
+/// Adds a bias from the search's [`HistoryTable`] that fades as the child
+/// gathers its own statistics:
+///
+/// ```text
+/// value + C * sqrt(ln(N) / n) + W * H(a) / (n + 1)
+/// ```
+///
+/// where `H(a)` is the average reward of the child's action key anywhere in the
+/// search. Without a history table attached to the search this is plain UCB1.
+#[derive(Debug, Clone)]
+pub struct ProgressiveHistoryPolicy {
+    /// Exploration constant
+    pub exploration_constant: f64,
+
+    /// Weight `W` of the history bias
+    pub history_weight: f64,
+}
+
+impl ProgressiveHistoryPolicy {
+    /// Creates a new progressive history policy
+    pub fn new(exploration_constant: f64, history_weight: f64) -> Self {
+        ProgressiveHistoryPolicy {
+            exploration_constant,
+            history_weight,
+        }
+    }
+
+    /// Returns the exploration term plus the history bias of a visited child
+    fn bonus<S: GameState>(
+        &self,
+        child: &MCTSNode<S>,
+        parent_visits: u64,
+        context: &SelectionContext<'_>,
+    ) -> f64 {
+        let child_visits = child.selection_stats().0;
+        let history = match (context.history, &child.action) {
+            (Some(table), Some(action)) => table.value(&action.key()).unwrap_or(0.0),
+            _ => 0.0,
+        };
+        utils::exploration_term(parent_visits, child_visits, self.exploration_constant)
+            + self.history_weight * history / (child_visits as f64 + 1.0)
+    }
+}
+
+impl<S: GameState> SelectionPolicy<S> for ProgressiveHistoryPolicy {
+    fn select_child(&self, node: &MCTSNode<S>, context: &mut SelectionContext<'_>) -> usize {
+        let (parent_visits, _) = node.selection_stats();
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+
+        for (i, child) in node.children.iter().enumerate() {
+            let (child_visits, child_value) = child.selection_stats();
+
+            if child_visits == 0 {
+                return i; // Always explore nodes that have never been visited
+            }
+
+            let score = child_value + self.bonus(child, parent_visits, context);
+
+            if score > best_value {
+                best_value = score;
+                best_index = i;
+            }
+        }
+
+        best_index
+    }
+
+    fn child_scores(
+        &self,
+        node: &MCTSNode<S>,
+        context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        let (parent_visits, _) = node.selection_stats();
+        let scores = node
+            .children
+            .iter()
+            .map(|child| {
+                let (child_visits, child_value) = child.selection_stats();
+                let exploration = if child_visits == 0 {
+                    f64::INFINITY
+                } else {
+                    self.bonus(child, parent_visits, context)
+                };
+                ChildScore::new(child_value, exploration, child.prior())
+            })
+            .collect();
+        Some(scores)
+    }
+
+    fn clone_box(&self) -> Box<dyn SelectionPolicy<S>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// UCB1 over a weighted blend of win rate and expected score
+///
+/// Scores each child with
+///
+/// ```text
+/// w * win_rate + (1 - w) * value + C * sqrt(ln(N) / n)
+/// ```
+///
+/// where `win_rate` counts each result through the child as a win, draw or
+/// loss (see [`outcome_of`](crate::tree::outcome_of)) and `value` is the mean
+/// result. A weight close to 1 maximizes the chance of winning and uses the
+/// margin only to separate moves with similar win rates.
+#[derive(Debug, Clone)]
+pub struct BlendedValuePolicy {
+    /// Exploration constant
+    pub exploration_constant: f64,
+
+    /// Weight `w` of the win rate, in `[0, 1]`
+    pub win_weight: f64,
+}
+
+impl BlendedValuePolicy {
+    /// Creates a new blended policy
+    ///
+    /// The win weight is clamped to `[0, 1]`.
+    pub fn new(exploration_constant: f64, win_weight: f64) -> Self {
+        BlendedValuePolicy {
+            exploration_constant,
+            win_weight: win_weight.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the blended value of a node, without the exploration term
+    pub fn blended_value<S: GameState>(&self, node: &MCTSNode<S>) -> f64 {
+        self.win_weight * node.win_rate() + (1.0 - self.win_weight) * node.value()
+    }
+}
+
+impl<S: GameState> SelectionPolicy<S> for BlendedValuePolicy {
+    fn select_child(&self, node: &MCTSNode<S>, _context: &mut SelectionContext<'_>) -> usize {
+        let (parent_visits, _) = node.selection_stats();
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+
+        for (i, child) in node.children.iter().enumerate() {
+            let child_visits = child.visits();
+
+            if child_visits == 0 {
+                return i; // Always explore nodes that have never been visited
+            }
+
+            let score = self.blended_value(child)
+                + utils::exploration_term(parent_visits, child_visits, self.exploration_constant);
+
+            if score > best_value {
+                best_value = score;
+                best_index = i;
+            }
+        }
+
+        best_index
+    }
+
+    fn child_scores(
+        &self,
+        node: &MCTSNode<S>,
+        _context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        let (parent_visits, _) = node.selection_stats();
+        let scores = node
+            .children
+            .iter()
+            .map(|child| {
+                let child_visits = child.visits();
+                let exploration = if child_visits == 0 {
+                    f64::INFINITY
+                } else {
+                    utils::exploration_term(parent_visits, child_visits, self.exploration_constant)
+                };
+                ChildScore::new(self.blended_value(child), exploration, child.prior())
+            })
+            .collect();
+        Some(scores)
+    }
+
     fn clone_box(&self) -> Box<dyn SelectionPolicy<S>> {
         Box::new(self.clone())
     }
@@ -106,7 +499,15 @@ impl<S: GameState> SelectionPolicy<S> for UCB1Policy {
 /// Upper Confidence Bound 1 Tuned (UCB1-Tuned) selection policy
 ///
 /// An improved version of UCB1 that takes into account the variance
-/// of the rewards.
+/// of the rewards:
+///
+/// ```text
+/// UCB1-Tuned = average_reward + C * sqrt((ln(N) / n) * min(1/4, V + sqrt(2 ln(N) / n)))
+/// ```
+///
+/// Where `V` is the sample variance of the child's rewards. An exploration
+/// constant of 1.0 gives the original formulation; see
+/// [`utils::ucb1_tuned_value`](crate::utils::ucb1_tuned_value).
 #[derive(Debug, Clone)]
 pub struct UCB1TunedPolicy {
     /// Exploration constant
@@ -123,38 +524,29 @@ impl UCB1TunedPolicy {
 }
 
 impl<S: GameState> SelectionPolicy<S> for UCB1TunedPolicy {
-    fn select_child(&self, node: &MCTSNode<S>) -> usize {
+    fn select_child(&self, node: &MCTSNode<S>, _context: &mut SelectionContext<'_>) -> usize {
         if node.children.is_empty() {
             return 0;
         }
 
-        let parent_visits = node.visits();
+        let (parent_visits, _) = node.selection_stats();
         let mut best_value = f64::NEG_INFINITY;
         let mut best_index = 0;
 
         for (i, child) in node.children.iter().enumerate() {
-            let child_value = child.value();
-            let child_visits = child.visits();
+            let (child_visits, _) = child.selection_stats();
 
             if child_visits == 0 {
                 return i; // Always explore nodes that have never been visited
             }
 
-            // UCB1-Tuned variance calculation
-            // V_n = (sum(x^2) / n) - (sum(x) / n)^2 + sqrt(2ln(N)/n)
-            let avg_reward = child_value;
-            let sum_squared = child.sum_squared_reward();
-            let variance = (sum_squared / child_visits as f64) - (avg_reward * avg_reward);
-
-            let exploration_term = ((parent_visits as f64).ln() / child_visits as f64).sqrt();
-            let upper_bound_variance = variance + exploration_term;
-
-            // Allow variance to be at most 0.25 (since rewards are in [0,1])
-            let min_variance = f64::min(0.25, upper_bound_variance);
-
-            let exploration = self.exploration_constant * exploration_term * min_variance;
-
-            let ucb_value = child_value + exploration;
+            let ucb_value = utils::ucb1_tuned_value(
+                child.total_reward(),
+                child.sum_squared_reward(),
+                child_visits,
+                parent_visits,
+                self.exploration_constant,
+            );
 
             if ucb_value > best_value {
                 best_value = ucb_value;
@@ -165,6 +557,39 @@ impl<S: GameState> SelectionPolicy<S> for UCB1TunedPolicy {
         best_index
     }
 
+    fn child_scores(
+        &self,
+        node: &MCTSNode<S>,
+        _context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        let (parent_visits, _) = node.selection_stats();
+        let scores = node
+            .children
+            .iter()
+            .map(|child| {
+                let (child_visits, _) = child.selection_stats();
+                if child_visits == 0 {
+                    return ChildScore::new(0.0, f64::INFINITY, child.prior());
+                }
+                let mean = child.total_reward() / child_visits as f64;
+                let score = utils::ucb1_tuned_value(
+                    child.total_reward(),
+                    child.sum_squared_reward(),
+                    child_visits,
+                    parent_visits,
+                    self.exploration_constant,
+                );
+                ChildScore {
+                    exploitation: mean,
+                    exploration: score - mean,
+                    prior: child.prior(),
+                    score,
+                }
+            })
+            .collect();
+        Some(scores)
+    }
+
     fn clone_box(&self) -> Box<dyn SelectionPolicy<S>> {
         Box::new(self.clone())
     }
@@ -181,6 +606,12 @@ impl<S: GameState> SelectionPolicy<S> for UCB1TunedPolicy {
 ///
 /// Note: The priors are assigned during the expansion phase via the
 /// [`ExpansionPolicy`](crate::policy::expansion::ExpansionPolicy).
+///
+/// With [`MCTSConfig::with_value_normalization`](crate::MCTSConfig::with_value_normalization),
+/// child values are min-max normalized with the parent's
+/// [value bounds](MCTSNode::value_bounds), and unvisited children are scored
+/// with the [mean value](MCTSNode::mean_child_value) of their visited
+/// siblings instead of being tried first.
 #[derive(Debug, Clone)]
 pub struct PUCTPolicy {
     /// Exploration constant
@@ -197,20 +628,23 @@ impl PUCTPolicy {
 }
 
 impl<S: GameState> SelectionPolicy<S> for PUCTPolicy {
-    fn select_child(&self, node: &MCTSNode<S>) -> usize {
+    fn select_child(&self, node: &MCTSNode<S>, _context: &mut SelectionContext<'_>) -> usize {
         if node.children.is_empty() {
             return 0;
         }
 
-        let parent_visits = node.visits();
+        // sqrt(N) is shared by every child, so compute it once
+        let (parent_visits, _) = node.selection_stats();
+        let sqrt_parent_visits = (parent_visits as f64).sqrt();
+        let normalized = node.value_bounds().is_some();
+        let unvisited_value = unvisited_value(node, normalized);
         let mut best_value = f64::NEG_INFINITY;
         let mut best_index = 0;
 
         for (i, child) in node.children.iter().enumerate() {
-            let child_value = child.value();
-            let child_visits = child.visits();
+            let (child_visits, child_value) = child.selection_stats();
 
-            if child_visits == 0 {
+            if child_visits == 0 && !normalized {
                 return i; // Always explore nodes that have never been visited
             }
 
@@ -219,8 +653,8 @@ impl<S: GameState> SelectionPolicy<S> for PUCTPolicy {
 
             // PUCT formula from AlphaZero: Q(s,a) + U(s,a)
             // where U(s,a) = c_puct * P(s,a) * sqrt(sum_b N(s,b)) / (1 + N(s,a))
-            let exploitation = child_value;
-            let exploration = self.exploration_constant * prior * (parent_visits as f64).sqrt()
+            let exploitation = completed_value(node, child_visits, child_value, unvisited_value);
+            let exploration = self.exploration_constant * prior * sqrt_parent_visits
                 / (1.0 + child_visits as f64);
 
             let puct_value = exploitation + exploration;
@@ -234,6 +668,35 @@ impl<S: GameState> SelectionPolicy<S> for PUCTPolicy {
         best_index
     }
 
+    fn child_scores(
+        &self,
+        node: &MCTSNode<S>,
+        _context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        let (parent_visits, _) = node.selection_stats();
+        let sqrt_parent_visits = (parent_visits as f64).sqrt();
+        let normalized = node.value_bounds().is_some();
+        let unvisited_value = unvisited_value(node, normalized);
+        let scores = node
+            .children
+            .iter()
+            .map(|child| {
+                let (child_visits, child_value) = child.selection_stats();
+                let prior = child.prior();
+                let exploration = if child_visits == 0 && !normalized {
+                    f64::INFINITY
+                } else {
+                    self.exploration_constant * prior * sqrt_parent_visits
+                        / (1.0 + child_visits as f64)
+                };
+                let exploitation =
+                    completed_value(node, child_visits, child_value, unvisited_value);
+                ChildScore::new(exploitation, exploration, prior)
+            })
+            .collect();
+        Some(scores)
+    }
+
     fn clone_box(&self) -> Box<dyn SelectionPolicy<S>> {
         Box::new(self.clone())
     }
@@ -249,10 +712,318 @@ impl Default for PUCTPolicy {
     }
 }
 
+/// Returns the value PUCT completes unvisited children with
+///
+/// The mean of the visited children is from the point of view of the
+/// player choosing between them, which the parent's own value need not be.
+/// Only needed with value bounds; zero until a child has been visited.
+fn unvisited_value<S: GameState>(node: &MCTSNode<S>, normalized: bool) -> f64 {
+    if !normalized {
+        return 0.0;
+    }
+    node.mean_child_value().unwrap_or(0.0)
+}
+
+/// Returns a child's value for PUCT, normalized with the parent's value bounds
+///
+/// Unvisited children take `unvisited_value`. Without bounds the child's
+/// value is returned as is.
+fn completed_value<S: GameState>(
+    node: &MCTSNode<S>,
+    child_visits: u64,
+    child_value: f64,
+    unvisited_value: f64,
+) -> f64 {
+    if node.value_bounds().is_none() {
+        return child_value;
+    }
+    let value = if child_visits == 0 {
+        unvisited_value
+    } else {
+        child_value
+    };
+    node.normalize_value(value)
+}
+
+/// Bayesian selection policy that maximizes a posterior quantile
+///
+/// Scores each child by the `quantile` of its Gaussian value posterior,
+///
+/// ```text
+/// score = μ + Φ⁻¹(quantile) / sqrt(τ)
+/// ```
+///
+/// so quantiles above 0.5 are optimistic about uncertain children, much like
+/// Bayes-UCB. The posterior is maintained by
+/// [`GaussianPolicy`](crate::policy::backpropagation::GaussianPolicy); for
+/// children it has not updated, the sample mean with the visit count as
+/// precision is used instead.
+#[derive(Debug, Clone)]
+pub struct PosteriorQuantilePolicy {
+    /// Posterior quantile to maximize, in `(0, 1)`
+    pub quantile: f64,
+}
+
+impl PosteriorQuantilePolicy {
+    /// Creates a new posterior-quantile policy
+    ///
+    /// The quantile is clamped to `[0.5, 0.999999]`.
+    pub fn new(quantile: f64) -> Self {
+        PosteriorQuantilePolicy {
+            quantile: quantile.clamp(0.5, 0.999_999),
+        }
+    }
+}
+
+impl Default for PosteriorQuantilePolicy {
+    fn default() -> Self {
+        Self::new(0.9)
+    }
+}
+
+impl<S: GameState> SelectionPolicy<S> for PosteriorQuantilePolicy {
+    fn select_child(&self, node: &MCTSNode<S>, _context: &mut SelectionContext<'_>) -> usize {
+        let z = utils::normal_quantile(self.quantile);
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+
+        for (i, child) in node.children.iter().enumerate() {
+            let (child_visits, child_value) = child.selection_stats();
+
+            if child_visits == 0 {
+                return i; // Always explore nodes that have never been visited
+            }
+
+            let (mean, precision) = match child.posterior_precision() {
+                p if p > 0.0 => (child.posterior_mean(), p),
+                _ => (child_value, child_visits as f64),
+            };
+            let score = mean + z / precision.sqrt();
+
+            if score > best_value {
+                best_value = score;
+                best_index = i;
+            }
+        }
+
+        best_index
+    }
+
+    fn child_scores(
+        &self,
+        node: &MCTSNode<S>,
+        _context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        let z = utils::normal_quantile(self.quantile);
+        let scores = node
+            .children
+            .iter()
+            .map(|child| {
+                let (child_visits, child_value) = child.selection_stats();
+                if child_visits == 0 {
+                    return ChildScore::new(child_value, f64::INFINITY, child.prior());
+                }
+                let (mean, precision) = match child.posterior_precision() {
+                    p if p > 0.0 => (child.posterior_mean(), p),
+                    _ => (child_value, child_visits as f64),
+                };
+                ChildScore::new(mean, z / precision.sqrt(), child.prior())
+            })
+            .collect();
+        Some(scores)
+    }
+
+    fn clone_box(&self) -> Box<dyn SelectionPolicy<S>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Similarity between two action feature vectors, used by [`KernelRegressionPolicy`]
+#[derive(Debug, Clone, Copy)]
+pub enum SimilarityKernel {
+    /// `exp(-|a - b|^2 / (2 * bandwidth^2))`
+    Gaussian {
+        /// Distance at which similarity has dropped to about 0.6
+        bandwidth: f64,
+    },
+
+    /// A custom function returning the similarity of two feature vectors
+    ///
+    /// Should return 1 for identical actions and values in `[0, 1]`.
+    Custom(fn(&[f64], &[f64]) -> f64),
+}
+
+impl SimilarityKernel {
+    /// Returns the similarity of two feature vectors
+    pub fn similarity(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            SimilarityKernel::Gaussian { bandwidth } => {
+                let distance: f64 = a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum();
+                (-distance / (2.0 * bandwidth * bandwidth)).exp()
+            }
+            SimilarityKernel::Custom(kernel) => kernel(a, b),
+        }
+    }
+}
+
+/// Kernel regression UCT (KR-UCT) for continuous or parametric actions
+///
+/// Each child's statistics are pooled with those of similar children, weighted
+/// by a [`SimilarityKernel`] over the actions' [`ActionFeatures`]:
+///
+/// ```text
+/// W(i) = Σ_j K(i, j) * n_j
+/// E(i) = Σ_j K(i, j) * n_j * v_j / W(i)
+/// score = E(i) + C * sqrt(ln(Σ_j W(j)) / W(i))
+/// ```
+///
+/// so a freshly sampled action close to a good one starts out with a good
+/// estimate instead of from scratch. Children whose kernel-weighted visits
+/// are still zero are explored first. Pair it with an
+/// [`ActionSampler`](crate::policy::expansion::ActionSampler) and progressive
+/// widening. Scoring compares every pair of children, so it costs
+/// `O(children^2)` kernel evaluations per selection.
+#[derive(Debug, Clone)]
+pub struct KernelRegressionPolicy {
+    /// Exploration constant
+    pub exploration_constant: f64,
+
+    /// Similarity between actions
+    pub kernel: SimilarityKernel,
+}
+
+impl KernelRegressionPolicy {
+    /// Creates a KR-UCT policy with a Gaussian kernel of the given bandwidth
+    pub fn new(exploration_constant: f64, bandwidth: f64) -> Self {
+        Self::with_kernel(
+            exploration_constant,
+            SimilarityKernel::Gaussian { bandwidth },
+        )
+    }
+
+    /// Creates a KR-UCT policy with any kernel
+    pub fn with_kernel(exploration_constant: f64, kernel: SimilarityKernel) -> Self {
+        KernelRegressionPolicy {
+            exploration_constant,
+            kernel,
+        }
+    }
+
+    /// Returns the kernel-weighted `(visits, value)` of every child
+    pub fn kernel_stats<S>(&self, node: &MCTSNode<S>) -> Vec<(f64, f64)>
+    where
+        S: GameState,
+        S::Action: ActionFeatures,
+    {
+        let features: Vec<Option<Vec<f64>>> = node
+            .children
+            .iter()
+            .map(|child| child.action.as_ref().map(ActionFeatures::features))
+            .collect();
+        let stats: Vec<(u64, f64)> = node
+            .children
+            .iter()
+            .map(|child| child.selection_stats())
+            .collect();
+
+        (0..node.children.len())
+            .map(|i| {
+                let mut weight = 0.0;
+                let mut weighted_value = 0.0;
+                for (j, &(visits, value)) in stats.iter().enumerate() {
+                    let similarity = match (&features[i], &features[j]) {
+                        _ if i == j => 1.0,
+                        (Some(a), Some(b)) => self.kernel.similarity(a, b),
+                        _ => 0.0,
+                    };
+                    weight += similarity * visits as f64;
+                    weighted_value += similarity * visits as f64 * value;
+                }
+                let value = if weight > 0.0 {
+                    weighted_value / weight
+                } else {
+                    0.0
+                };
+                (weight, value)
+            })
+            .collect()
+    }
+}
+
+impl<S> SelectionPolicy<S> for KernelRegressionPolicy
+where
+    S: GameState,
+    S::Action: ActionFeatures,
+{
+    fn select_child(&self, node: &MCTSNode<S>, _context: &mut SelectionContext<'_>) -> usize {
+        let stats = self.kernel_stats(node);
+        let total_weight: f64 = stats.iter().map(|(weight, _)| weight).sum();
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+
+        for (i, &(weight, value)) in stats.iter().enumerate() {
+            if weight <= 0.0 {
+                return i; // Nothing similar has been visited yet
+            }
+
+            let score =
+                value + self.exploration_constant * (total_weight.max(1.0).ln() / weight).sqrt();
+            if score > best_value {
+                best_value = score;
+                best_index = i;
+            }
+        }
+
+        best_index
+    }
+
+    fn child_scores(
+        &self,
+        node: &MCTSNode<S>,
+        _context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        let stats = self.kernel_stats(node);
+        let total_weight: f64 = stats.iter().map(|(weight, _)| weight).sum();
+        let scores = stats
+            .iter()
+            .zip(&node.children)
+            .map(|(&(weight, value), child)| {
+                let exploration = if weight <= 0.0 {
+                    f64::INFINITY
+                } else {
+                    self.exploration_constant * (total_weight.max(1.0).ln() / weight).sqrt()
+                };
+                ChildScore::new(value, exploration, child.prior())
+            })
+            .collect();
+        Some(scores)
+    }
+
+    fn clone_box(&self) -> Box<dyn SelectionPolicy<S>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
 // Implement SelectionPolicy for Box<dyn SelectionPolicy>
 impl<S: GameState> SelectionPolicy<S> for Box<dyn SelectionPolicy<S>> {
-    fn select_child(&self, node: &MCTSNode<S>) -> usize {
-        (**self).select_child(node)
+    fn select_child(&self, node: &MCTSNode<S>, context: &mut SelectionContext<'_>) -> usize {
+        (**self).select_child(node, context)
+    }
+
+    fn child_scores(
+        &self,
+        node: &MCTSNode<S>,
+        context: &mut SelectionContext<'_>,
+    ) -> Option<Vec<ChildScore>> {
+        (**self).child_scores(node, context)
     }
 
     fn clone_box(&self) -> Box<dyn SelectionPolicy<S>> {
@@ -262,4 +1033,16 @@ impl<S: GameState> SelectionPolicy<S> for Box<dyn SelectionPolicy<S>> {
     fn as_any(&self) -> &dyn std::any::Any {
         (**self).as_any()
     }
+
+    fn on_search_start(&mut self, root: &S) {
+        (**self).on_search_start(root)
+    }
+
+    fn on_search_end(&mut self, root: &MCTSNode<S>) {
+        (**self).on_search_end(root)
+    }
+
+    fn on_move_played(&mut self, action: &S::Action, state: &S) {
+        (**self).on_move_played(action, state)
+    }
 }
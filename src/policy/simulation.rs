@@ -3,15 +3,72 @@
 //! Simulation policies determine how to play out a game from a given state
 //! to estimate the value of that state.
 
-use crate::game_state::GameState;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use rand::{Rng, RngCore};
+
+use crate::{
+    config::PlayoutLimits,
+    game_state::{Action, ActionKey, GameState},
+    history::HistoryTable,
+    policy::selection::UCB1Policy,
+    tree::MCTSNode,
+    utils::search_rng,
+};
 
 /// Trait for policies that simulate games
 pub trait SimulationPolicy<S: GameState>: Send + Sync {
     /// Simulates a game from the given state and returns the result and action trace
     fn simulate(&self, state: &S) -> (f64, Vec<S::Action>);
 
+    /// Simulates a game from the given state, respecting playout limits
+    ///
+    /// Called instead of [`simulate`](Self::simulate) when the configuration
+    /// sets a [`max_playout_length`](crate::MCTSConfig::max_playout_length)
+    /// or enables [cycle detection](crate::MCTSConfig::cycle_reward).
+    /// Policies that play moves should override this; the default ignores the
+    /// limits, which is only right for policies that never play moves.
+    fn simulate_limited(&self, state: &S, _limits: &PlayoutLimits) -> (f64, Vec<S::Action>) {
+        self.simulate(state)
+    }
+
+    /// Simulates a game from the given state and returns the result for `player`
+    ///
+    /// The search calls this instead of [`simulate`](Self::simulate) when the
+    /// configured [`Perspective`](crate::config::Perspective) asks for another
+    /// player than the one to move at `state`. Honor `limits` as in
+    /// [`simulate_limited`](Self::simulate_limited) unless they are
+    /// [unlimited](PlayoutLimits::is_unlimited).
+    ///
+    /// Returns `None`, the default, if the policy can only score for the
+    /// player to move; the search then uses `1 - result` of a regular
+    /// simulation, which is right for two-player zero-sum games.
+    fn simulate_for(
+        &self,
+        _state: &S,
+        _player: &S::Player,
+        _limits: &PlayoutLimits,
+    ) -> Option<(f64, Vec<S::Action>)> {
+        None
+    }
+
     /// Create a boxed clone of this policy
     fn clone_box(&self) -> Box<dyn SimulationPolicy<S>>;
+
+    /// Called before a search starts, with the root state
+    ///
+    /// Stateful policies can use this to reset per-search data.
+    fn on_search_start(&mut self, _root: &S) {}
+
+    /// Called after a search finishes, with the final root node
+    fn on_search_end(&mut self, _root: &MCTSNode<S>) {}
+
+    /// Called when a move is played in the game via [`MCTS::apply_move`](crate::MCTS::apply_move)
+    ///
+    /// `state` is the position after the move. Policies that keep state across
+    /// moves (e.g. reply tables) can update or prune it here.
+    fn on_move_played(&mut self, _action: &S::Action, _state: &S) {}
 }
 
 /// Random simulation policy
@@ -40,6 +97,24 @@ impl<S: GameState> SimulationPolicy<S> for RandomPolicy {
         state.simulate_random_playout(&player)
     }
 
+    fn simulate_limited(&self, state: &S, limits: &PlayoutLimits) -> (f64, Vec<S::Action>) {
+        let player = state.get_current_player();
+        state.simulate_random_playout_limited(&player, limits)
+    }
+
+    fn simulate_for(
+        &self,
+        state: &S,
+        player: &S::Player,
+        limits: &PlayoutLimits,
+    ) -> Option<(f64, Vec<S::Action>)> {
+        Some(if limits.is_unlimited() {
+            state.simulate_random_playout(player)
+        } else {
+            state.simulate_random_playout_limited(player, limits)
+        })
+    }
+
     fn clone_box(&self) -> Box<dyn SimulationPolicy<S>> {
         Box::new(self.clone())
     }
@@ -94,6 +169,168 @@ where
     }
 }
 
+/// Shallow minimax simulation policy
+///
+/// Instead of playing random moves, searches the game tree below the leaf to
+/// a fixed depth with alpha-beta pruning and scores the frontier with a
+/// heuristic. This hybrid catches short tactics that random playouts miss,
+/// at the cost of a more expensive evaluation.
+///
+/// Values are from the point of view of the player to move at the leaf, or
+/// the player asked for through
+/// [`simulate_for`](SimulationPolicy::simulate_for): that player maximizes
+/// and every other player minimizes. Terminal states are
+/// scored with [`GameState::get_result`], the heuristic `heuristic(state,
+/// player)` scores states at the depth limit, and chance events contribute the
+/// probability-weighted average of their listed outcomes (or one sampled
+/// outcome). Depth counts decisions, so chance events are free. The returned
+/// trace is the principal variation.
+#[derive(Debug, Clone)]
+pub struct MinimaxPolicy<F, S>
+where
+    F: Fn(&S, &S::Player) -> f64 + Clone + Send + Sync + 'static,
+    S: GameState + 'static,
+{
+    /// Number of decisions searched below the leaf
+    depth: usize,
+
+    /// Evaluation of states at the depth limit
+    heuristic: F,
+    _phantom: std::marker::PhantomData<S>,
+}
+
+impl<F, S> MinimaxPolicy<F, S>
+where
+    F: Fn(&S, &S::Player) -> f64 + Clone + Send + Sync + 'static,
+    S: GameState + 'static,
+{
+    /// Creates a policy searching `depth` decisions deep, scoring the frontier with `heuristic`
+    pub fn new(depth: usize, heuristic: F) -> Self {
+        MinimaxPolicy {
+            depth,
+            heuristic,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns the search depth in decisions
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Alpha-beta search returning the value for `player` and the principal variation
+    fn alpha_beta(
+        &self,
+        state: &S,
+        depth: usize,
+        player: &S::Player,
+        mut alpha: f64,
+        mut beta: f64,
+    ) -> (f64, Vec<S::Action>) {
+        if state.is_terminal() {
+            return (state.get_result(player), Vec::new());
+        }
+
+        if let Some(outcomes) = state.chance_outcomes() {
+            let total: f64 = outcomes.iter().map(|(_, p)| p.max(0.0)).sum();
+            if total > 0.0 {
+                let value = outcomes
+                    .iter()
+                    .map(|(outcome, probability)| {
+                        let (value, _) = self.alpha_beta(
+                            outcome,
+                            depth,
+                            player,
+                            f64::NEG_INFINITY,
+                            f64::INFINITY,
+                        );
+                        value * probability.max(0.0) / total
+                    })
+                    .sum();
+                return (value, Vec::new());
+            }
+        } else if let Some(outcome) = state.sample_chance_outcome(&mut search_rng()) {
+            return self.alpha_beta(&outcome, depth, player, alpha, beta);
+        }
+
+        let legal_actions = state.get_legal_actions();
+        if depth == 0 || legal_actions.is_empty() {
+            return ((self.heuristic)(state, player), Vec::new());
+        }
+
+        let maximizing = state.get_current_player() == *player;
+        let mut best = if maximizing {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+        let mut best_line = Vec::new();
+        for action in legal_actions {
+            let next = state.apply_action(&action);
+            let (value, line) = self.alpha_beta(&next, depth - 1, player, alpha, beta);
+            let improves = if maximizing {
+                value > best
+            } else {
+                value < best
+            };
+            if improves {
+                best = value;
+                best_line = line;
+                best_line.insert(0, action);
+            }
+            if maximizing {
+                alpha = alpha.max(best);
+            } else {
+                beta = beta.min(best);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        (best, best_line)
+    }
+
+    fn search(&self, state: &S, depth: usize, player: &S::Player) -> (f64, Vec<S::Action>) {
+        self.alpha_beta(state, depth, player, f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn limited_depth(&self, limits: &PlayoutLimits) -> usize {
+        limits
+            .max_length
+            .map_or(self.depth, |max| self.depth.min(max))
+    }
+}
+
+impl<F, S> SimulationPolicy<S> for MinimaxPolicy<F, S>
+where
+    F: Fn(&S, &S::Player) -> f64 + Clone + Send + Sync + 'static,
+    S: GameState + 'static,
+{
+    fn simulate(&self, state: &S) -> (f64, Vec<S::Action>) {
+        self.search(state, self.depth, &state.get_current_player())
+    }
+
+    /// Searches no deeper than the playout length cap
+    fn simulate_limited(&self, state: &S, limits: &PlayoutLimits) -> (f64, Vec<S::Action>) {
+        let depth = self.limited_depth(limits);
+        self.search(state, depth, &state.get_current_player())
+    }
+
+    /// Maximizes for `player` instead of the player to move
+    fn simulate_for(
+        &self,
+        state: &S,
+        player: &S::Player,
+        limits: &PlayoutLimits,
+    ) -> Option<(f64, Vec<S::Action>)> {
+        Some(self.search(state, self.limited_depth(limits), player))
+    }
+
+    fn clone_box(&self) -> Box<dyn SimulationPolicy<S>> {
+        Box::new(self.clone())
+    }
+}
+
 /// Mixture simulation policy
 ///
 /// This policy combines multiple simulation policies, using each with
@@ -138,35 +375,57 @@ impl<S: GameState> MixturePolicy<S> {
         self.policies.push((Box::new(policy), probability));
         self
     }
-}
 
-impl<S: GameState + 'static> SimulationPolicy<S> for MixturePolicy<S> {
-    fn simulate(&self, state: &S) -> (f64, Vec<S::Action>) {
-        use rand::Rng;
-
-        if self.policies.is_empty() {
-            // Fallback to random policy
-            let random_policy = RandomPolicy::new();
-            return random_policy.simulate(state);
-        }
+    /// Picks one of the policies according to their probabilities
+    fn choose_policy(&self) -> Option<&dyn SimulationPolicy<S>> {
+        let (last, _) = self.policies.last()?;
 
         // Calculate total probability
         let total: f64 = self.policies.iter().map(|(_, p)| *p).sum();
 
         // Select a policy based on probabilities
-        let mut rng = rand::thread_rng();
+        let mut rng = search_rng();
         let r: f64 = rng.gen_range(0.0..total);
 
         let mut cumulative = 0.0;
         for (policy, prob) in &self.policies {
             cumulative += prob;
             if r < cumulative {
-                return policy.simulate(state);
+                return Some(policy.as_ref());
             }
         }
 
         // Fallback to the last policy
-        self.policies.last().unwrap().0.simulate(state)
+        Some(last.as_ref())
+    }
+}
+
+impl<S: GameState + 'static> SimulationPolicy<S> for MixturePolicy<S> {
+    fn simulate(&self, state: &S) -> (f64, Vec<S::Action>) {
+        match self.choose_policy() {
+            Some(policy) => policy.simulate(state),
+            // Fallback to random policy
+            None => RandomPolicy::new().simulate(state),
+        }
+    }
+
+    fn simulate_limited(&self, state: &S, limits: &PlayoutLimits) -> (f64, Vec<S::Action>) {
+        match self.choose_policy() {
+            Some(policy) => policy.simulate_limited(state, limits),
+            None => RandomPolicy::new().simulate_limited(state, limits),
+        }
+    }
+
+    fn simulate_for(
+        &self,
+        state: &S,
+        player: &S::Player,
+        limits: &PlayoutLimits,
+    ) -> Option<(f64, Vec<S::Action>)> {
+        match self.choose_policy() {
+            Some(policy) => policy.simulate_for(state, player, limits),
+            None => RandomPolicy::new().simulate_for(state, player, limits),
+        }
     }
 
     fn clone_box(&self) -> Box<dyn SimulationPolicy<S>> {
@@ -179,6 +438,24 @@ impl<S: GameState + 'static> SimulationPolicy<S> for MixturePolicy<S> {
             policies: new_policies,
         })
     }
+
+    fn on_search_start(&mut self, root: &S) {
+        for (policy, _) in &mut self.policies {
+            policy.on_search_start(root);
+        }
+    }
+
+    fn on_search_end(&mut self, root: &MCTSNode<S>) {
+        for (policy, _) in &mut self.policies {
+            policy.on_search_end(root);
+        }
+    }
+
+    fn on_move_played(&mut self, action: &S::Action, state: &S) {
+        for (policy, _) in &mut self.policies {
+            policy.on_move_played(action, state);
+        }
+    }
 }
 
 impl<S: GameState> Default for MixturePolicy<S> {
@@ -186,13 +463,649 @@ impl<S: GameState> Default for MixturePolicy<S> {
         Self::new()
     }
 }
+
+/// Move-Average Sampling Technique (MAST) simulation policy
+///
+/// Plays rollouts that prefer actions with a good average reward anywhere in
+/// the search, as kept by a shared [`HistoryTable`]. Each move is sampled from
+/// a Gibbs distribution over the legal actions,
+///
+/// ```text
+/// P(a) ∝ exp(H(a) / τ)
+/// ```
+///
+/// where `H(a)` is the history value of the action's key, or `unseen_value` for
+/// actions the table has not seen. Attach the same table to the search with
+/// [`MCTS::with_history`](crate::MCTS::with_history) so it gets updated:
+///
+/// ```
+/// # use arboriter_mcts::{GameState, Action, Player, MCTS, MCTSConfig};
+/// # #[derive(Debug, Clone, PartialEq)]
+/// # struct A(usize);
+/// # impl Action for A { fn id(&self) -> usize { self.0 } }
+/// # #[derive(Debug, Clone, PartialEq)]
+/// # struct P;
+/// # impl Player for P {}
+/// # #[derive(Clone)]
+/// # struct G(usize);
+/// # impl GameState for G {
+/// #     type Action = A;
+/// #     type Player = P;
+/// #     fn get_legal_actions(&self) -> Vec<A> { if self.0 < 3 { vec![A(0), A(1)] } else { vec![] } }
+/// #     fn apply_action(&self, _: &A) -> Self { G(self.0 + 1) }
+/// #     fn is_terminal(&self) -> bool { self.0 >= 3 }
+/// #     fn get_result(&self, _: &P) -> f64 { 0.5 }
+/// #     fn get_current_player(&self) -> P { P }
+/// # }
+/// use std::sync::Arc;
+/// use arboriter_mcts::{history::HistoryTable, policy::simulation::MastPolicy};
+///
+/// let history = Arc::new(HistoryTable::new(0.5));
+/// let mut mcts = MCTS::new(G(0), MCTSConfig::default().with_max_iterations(50))
+///     .with_history(history.clone())
+///     .with_simulation_policy(MastPolicy::new(history, 0.5));
+/// assert!(mcts.search().is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MastPolicy {
+    /// Table of action values shared with the search
+    pub history: Arc<HistoryTable>,
+
+    /// Temperature τ of the Gibbs distribution; higher is more random
+    pub temperature: f64,
+
+    /// Value assumed for actions the table has not seen
+    ///
+    /// Defaults to 1.0, which makes untried actions attractive.
+    pub unseen_value: f64,
+}
+
+impl MastPolicy {
+    /// Creates a new MAST policy reading from `history`
+    ///
+    /// The temperature is clamped to a small positive value.
+    pub fn new(history: Arc<HistoryTable>, temperature: f64) -> Self {
+        MastPolicy {
+            history,
+            temperature: temperature.max(1e-6),
+            unseen_value: 1.0,
+        }
+    }
+
+    fn choose<A: Action>(&self, legal_actions: &[A], rng: &mut impl Rng) -> usize {
+        let values: Vec<f64> = legal_actions
+            .iter()
+            .map(|action| {
+                self.history
+                    .value(&action.key())
+                    .unwrap_or(self.unseen_value)
+            })
+            .collect();
+        sample_gibbs(&values, self.temperature, rng)
+    }
+}
+
+impl<S: GameState> SimulationPolicy<S> for MastPolicy {
+    fn simulate(&self, state: &S) -> (f64, Vec<S::Action>) {
+        let mut rng = search_rng();
+        let player = state.get_current_player();
+        guided_playout(state, &player, None, |_, legal, _| {
+            self.choose(legal, &mut rng)
+        })
+    }
+
+    fn simulate_limited(&self, state: &S, limits: &PlayoutLimits) -> (f64, Vec<S::Action>) {
+        let mut rng = search_rng();
+        let player = state.get_current_player();
+        guided_playout(state, &player, Some(limits), |_, legal, _| {
+            self.choose(legal, &mut rng)
+        })
+    }
+
+    fn simulate_for(
+        &self,
+        state: &S,
+        player: &S::Player,
+        limits: &PlayoutLimits,
+    ) -> Option<(f64, Vec<S::Action>)> {
+        let mut rng = search_rng();
+        let limits = (!limits.is_unlimited()).then_some(limits);
+        Some(guided_playout(state, player, limits, |_, legal, _| {
+            self.choose(legal, &mut rng)
+        }))
+    }
+
+    fn clone_box(&self) -> Box<dyn SimulationPolicy<S>> {
+        Box::new(self.clone())
+    }
+}
+
+/// N-gram Selection Technique (NST) simulation policy
+///
+/// Learns the average result of short sequences of consecutive action keys
+/// (n-grams) across playouts and biases rollouts towards continuations that
+/// worked before. A candidate action `a` after the moves `…, b, c` is scored
+/// by averaging the values of the n-grams `[a]`, `[c, a]` and `[b, c, a]`
+/// (up to `max_length` keys) that have been seen at least `min_visits` times,
+/// and moves are sampled from a Gibbs distribution over those scores as in
+/// [`MastPolicy`].
+///
+/// The policy updates its table itself at the end of every playout, crediting
+/// every n-gram of the playout with the result from the point of view of the
+/// player to move where the playout started. Moves made in the tree are not
+/// included. The table is behind an [`Arc`], so clones of the policy and the
+/// caller share it, and it is decayed whenever a move is played with
+/// [`MCTS::apply_move`](crate::MCTS::apply_move).
+#[derive(Debug, Clone)]
+pub struct NstPolicy {
+    /// N-gram values, keyed by sequences of action keys
+    pub ngrams: Arc<HistoryTable<Vec<ActionKey>>>,
+
+    /// Longest n-gram tracked, 1 to 3 in practice
+    pub max_length: usize,
+
+    /// Temperature τ of the Gibbs distribution; higher is more random
+    pub temperature: f64,
+
+    /// Number of visits an n-gram needs before it is trusted
+    pub min_visits: f64,
+
+    /// Value assumed for actions without a trusted n-gram
+    pub unseen_value: f64,
+}
+
+impl NstPolicy {
+    /// Creates an NST policy with its own table, tracking n-grams up to `max_length`
+    ///
+    /// `decay` is the weight old statistics keep on every move; see
+    /// [`HistoryTable::new`]. N-grams need one visit to be trusted and unseen
+    /// actions are valued at 1.0, which makes them attractive.
+    pub fn new(max_length: usize, temperature: f64, decay: f64) -> Self {
+        Self::with_table(Arc::new(HistoryTable::new(decay)), max_length, temperature)
+    }
+
+    /// Creates an NST policy that reads and updates an existing table
+    pub fn with_table(
+        ngrams: Arc<HistoryTable<Vec<ActionKey>>>,
+        max_length: usize,
+        temperature: f64,
+    ) -> Self {
+        NstPolicy {
+            ngrams,
+            max_length: max_length.max(1),
+            temperature: temperature.max(1e-6),
+            min_visits: 1.0,
+            unseen_value: 1.0,
+        }
+    }
+
+    /// Returns the averaged n-gram value of playing `action` after `previous`
+    pub fn score(&self, previous: &[ActionKey], action: ActionKey) -> f64 {
+        let mut total = 0.0;
+        let mut count = 0;
+
+        for length in 1..=self.max_length.min(previous.len() + 1) {
+            let mut key = previous[previous.len() + 1 - length..].to_vec();
+            key.push(action);
+            if let Some(entry) = self.ngrams.get(&key) {
+                if entry.visits >= self.min_visits {
+                    total += entry.value();
+                    count += 1;
+                }
+            }
+        }
+
+        if count == 0 {
+            return self.unseen_value;
+        }
+        total / count as f64
+    }
+
+    /// Credits every n-gram of a playout with its result
+    pub fn record_playout(&self, actions: &[ActionKey], result: f64) {
+        for end in 1..=actions.len() {
+            for length in 1..=self.max_length.min(end) {
+                self.ngrams
+                    .record(actions[end - length..end].to_vec(), result);
+            }
+        }
+    }
+
+    fn choose<A: Action>(&self, legal_actions: &[A], trace: &[A], rng: &mut impl Rng) -> usize {
+        let history_start = trace.len().saturating_sub(self.max_length - 1);
+        let previous: Vec<ActionKey> = trace[history_start..].iter().map(|a| a.key()).collect();
+        let values: Vec<f64> = legal_actions
+            .iter()
+            .map(|action| self.score(&previous, action.key()))
+            .collect();
+        sample_gibbs(&values, self.temperature, rng)
+    }
+
+    fn learn<A: Action>(&self, outcome: (f64, Vec<A>)) -> (f64, Vec<A>) {
+        let keys: Vec<ActionKey> = outcome.1.iter().map(|a| a.key()).collect();
+        self.record_playout(&keys, outcome.0);
+        outcome
+    }
+}
+
+impl<S: GameState> SimulationPolicy<S> for NstPolicy {
+    fn simulate(&self, state: &S) -> (f64, Vec<S::Action>) {
+        let mut rng = search_rng();
+        let player = state.get_current_player();
+        self.learn(guided_playout(state, &player, None, |_, legal, trace| {
+            self.choose(legal, trace, &mut rng)
+        }))
+    }
+
+    fn simulate_limited(&self, state: &S, limits: &PlayoutLimits) -> (f64, Vec<S::Action>) {
+        let mut rng = search_rng();
+        let player = state.get_current_player();
+        self.learn(guided_playout(
+            state,
+            &player,
+            Some(limits),
+            |_, legal, trace| self.choose(legal, trace, &mut rng),
+        ))
+    }
+
+    fn clone_box(&self) -> Box<dyn SimulationPolicy<S>> {
+        Box::new(self.clone())
+    }
+
+    fn on_move_played(&mut self, _action: &S::Action, _state: &S) {
+        self.ngrams.decay();
+    }
+}
+
+/// Information about the current rollout passed to rollout selectors
+///
+/// The rollout counterpart of
+/// [`SelectionContext`](crate::policy::selection::SelectionContext).
+pub struct RolloutContext<'a, A> {
+    /// Moves played so far in this rollout, forced moves included
+    pub trace: &'a [A],
+
+    /// Random number generator of the rollout
+    pub rng: &'a mut dyn RngCore,
+
+    /// History table shared through [`PolicyRollout::with_history`], if any
+    pub history: Option<&'a HistoryTable>,
+}
+
+impl<A> std::fmt::Debug for RolloutContext<'_, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RolloutContext")
+            .field("trace_len", &self.trace.len())
+            .field("history", &self.history.is_some())
+            .finish()
+    }
+}
+
+/// Lightweight move choice for rollouts
+///
+/// Where a [`SelectionPolicy`](crate::policy::selection::SelectionPolicy)
+/// scores the children of a tree node, a rollout selector picks one of a
+/// state's legal actions from knowledge that is cheap to look up, typically
+/// the statistics of a shared [`HistoryTable`]. Run rollouts with one
+/// through [`PolicyRollout`].
+///
+/// [`UCB1Policy`] implements this trait over the history table, as does
+/// [`SoftmaxSelector`], and so do closures `Fn(&S, &[S::Action], &mut RolloutContext<'_, S::Action>) -> usize`.
+pub trait RolloutSelector<S: GameState>: Send + Sync {
+    /// Returns the index in `legal_actions` of the move to play in `state`
+    ///
+    /// `legal_actions` is never empty. Indices past the end play the last action.
+    fn select_action(
+        &self,
+        state: &S,
+        legal_actions: &[S::Action],
+        context: &mut RolloutContext<'_, S::Action>,
+    ) -> usize;
+}
+
+impl<S, F> RolloutSelector<S> for F
+where
+    S: GameState,
+    F: Fn(&S, &[S::Action], &mut RolloutContext<'_, S::Action>) -> usize + Send + Sync,
+{
+    fn select_action(
+        &self,
+        state: &S,
+        legal_actions: &[S::Action],
+        context: &mut RolloutContext<'_, S::Action>,
+    ) -> usize {
+        self(state, legal_actions, context)
+    }
+}
+
+/// Plays UCB1 over the history table's action statistics
+///
+/// Each action is scored with [`UCB1Policy::ucb1_value`] from its history
+/// entry, the parent visits being the sum over the legal actions, so
+/// actions the table has not seen are tried first, in random order. Without
+/// a history table moves are uniformly random.
+impl<S: GameState> RolloutSelector<S> for UCB1Policy {
+    fn select_action(
+        &self,
+        _state: &S,
+        legal_actions: &[S::Action],
+        context: &mut RolloutContext<'_, S::Action>,
+    ) -> usize {
+        let Some(history) = context.history else {
+            return context.rng.gen_range(0..legal_actions.len());
+        };
+        let entries: Vec<_> = legal_actions
+            .iter()
+            .map(|action| history.get(&action.key()).unwrap_or_default())
+            .collect();
+        let parent_visits = entries
+            .iter()
+            .map(|entry| entry.visits)
+            .sum::<f64>()
+            .round() as u64;
+
+        let values: Vec<f64> = entries
+            .iter()
+            .map(|entry| self.ucb1_value(entry.value(), entry.visits.round() as u64, parent_visits))
+            .collect();
+
+        // Unseen actions all score infinity; try them in random order
+        let unseen: Vec<usize> = (0..values.len())
+            .filter(|&i| values[i] == f64::INFINITY)
+            .collect();
+        if !unseen.is_empty() {
+            return unseen[context.rng.gen_range(0..unseen.len())];
+        }
+
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+        for (i, value) in values.into_iter().enumerate() {
+            if value > best_value {
+                best_value = value;
+                best_index = i;
+            }
+        }
+        best_index
+    }
+}
+
+/// Samples moves from a Gibbs distribution over history values
+///
+/// Chooses moves as [`MastPolicy`] does: an action with average history
+/// value `v` is played with probability proportional to `exp(v / τ)`.
+/// Without a history table every action takes the unseen value, so moves
+/// are uniformly random.
+#[derive(Debug, Clone)]
+pub struct SoftmaxSelector {
+    /// Temperature τ of the Gibbs distribution; higher is more random
+    pub temperature: f64,
+
+    /// Value assumed for actions the table has not seen
+    ///
+    /// Defaults to 1.0, which makes untried actions attractive.
+    pub unseen_value: f64,
+}
+
+impl SoftmaxSelector {
+    /// Creates a softmax selector
+    ///
+    /// The temperature is clamped to a small positive value.
+    pub fn new(temperature: f64) -> Self {
+        SoftmaxSelector {
+            temperature: temperature.max(1e-6),
+            unseen_value: 1.0,
+        }
+    }
+}
+
+impl<S: GameState> RolloutSelector<S> for SoftmaxSelector {
+    fn select_action(
+        &self,
+        _state: &S,
+        legal_actions: &[S::Action],
+        context: &mut RolloutContext<'_, S::Action>,
+    ) -> usize {
+        let values: Vec<f64> = legal_actions
+            .iter()
+            .map(|action| {
+                context
+                    .history
+                    .and_then(|history| history.value(&action.key()))
+                    .unwrap_or(self.unseen_value)
+            })
+            .collect();
+        sample_gibbs(&values, self.temperature, &mut context.rng)
+    }
+}
+
+/// Simulation policy that plays rollouts with a [`RolloutSelector`]
+///
+/// Reuses the knowledge the search already gathers instead of playing
+/// uniformly random moves: share a table with
+/// [`MCTS::with_history`](crate::MCTS::with_history) and
+/// [`with_history`](Self::with_history), and the selector reads what every
+/// backpropagation recorded in it. Forced moves and playout limits are
+/// honored as by the other move-playing policies.
+///
+/// # Example
+///
+/// ```
+/// # use arboriter_mcts::{GameState, Action, Player, MCTS, MCTSConfig};
+/// # #[derive(Debug, Clone, PartialEq)]
+/// # struct A(usize);
+/// # impl Action for A { fn id(&self) -> usize { self.0 } }
+/// # #[derive(Debug, Clone, PartialEq)]
+/// # struct P;
+/// # impl Player for P {}
+/// # #[derive(Clone)]
+/// # struct G(usize);
+/// # impl GameState for G {
+/// #     type Action = A;
+/// #     type Player = P;
+/// #     fn get_legal_actions(&self) -> Vec<A> { if self.0 < 3 { vec![A(0), A(1)] } else { vec![] } }
+/// #     fn apply_action(&self, _: &A) -> Self { G(self.0 + 1) }
+/// #     fn is_terminal(&self) -> bool { self.0 >= 3 }
+/// #     fn get_result(&self, _: &P) -> f64 { 0.5 }
+/// #     fn get_current_player(&self) -> P { P }
+/// # }
+/// use std::sync::Arc;
+/// use arboriter_mcts::{
+///     history::HistoryTable,
+///     policy::{selection::UCB1Policy, simulation::PolicyRollout},
+/// };
+///
+/// let history = Arc::new(HistoryTable::new(0.5));
+/// let rollout = PolicyRollout::new(UCB1Policy::new(0.5)).with_history(history.clone());
+/// let mut mcts = MCTS::new(G(0), MCTSConfig::default().with_max_iterations(50))
+///     .with_history(history)
+///     .with_simulation_policy(rollout);
+/// assert!(mcts.search().is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolicyRollout<R> {
+    /// Chooses each move of a rollout
+    pub selector: R,
+
+    /// Table of action values shared with the search, if any
+    pub history: Option<Arc<HistoryTable>>,
+}
+
+impl<R> PolicyRollout<R> {
+    /// Creates a policy playing rollouts with `selector`
+    pub fn new(selector: R) -> Self {
+        PolicyRollout {
+            selector,
+            history: None,
+        }
+    }
+
+    /// Lets the selector read `history`
+    pub fn with_history(mut self, history: Arc<HistoryTable>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Plays one rollout for `player`
+    fn rollout<S>(
+        &self,
+        state: &S,
+        player: &S::Player,
+        limits: Option<&PlayoutLimits>,
+    ) -> (f64, Vec<S::Action>)
+    where
+        S: GameState,
+        R: RolloutSelector<S>,
+    {
+        let mut rng = search_rng();
+        let history = self.history.as_deref();
+        guided_playout(state, player, limits, |current, legal, trace| {
+            let mut context = RolloutContext {
+                trace,
+                rng: &mut rng,
+                history,
+            };
+            self.selector.select_action(current, legal, &mut context)
+        })
+    }
+}
+
+impl<S, R> SimulationPolicy<S> for PolicyRollout<R>
+where
+    S: GameState,
+    R: RolloutSelector<S> + Clone + 'static,
+{
+    fn simulate(&self, state: &S) -> (f64, Vec<S::Action>) {
+        self.rollout(state, &state.get_current_player(), None)
+    }
+
+    fn simulate_limited(&self, state: &S, limits: &PlayoutLimits) -> (f64, Vec<S::Action>) {
+        self.rollout(state, &state.get_current_player(), Some(limits))
+    }
+
+    fn simulate_for(
+        &self,
+        state: &S,
+        player: &S::Player,
+        limits: &PlayoutLimits,
+    ) -> Option<(f64, Vec<S::Action>)> {
+        let limits = (!limits.is_unlimited()).then_some(limits);
+        Some(self.rollout(state, player, limits))
+    }
+
+    fn clone_box(&self) -> Box<dyn SimulationPolicy<S>> {
+        Box::new(self.clone())
+    }
+}
+
+/// Samples an index with probability proportional to `exp(value / temperature)`
+fn sample_gibbs(values: &[f64], temperature: f64, rng: &mut impl Rng) -> usize {
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let weights: Vec<f64> = values
+        .iter()
+        .map(|value| ((value - max) / temperature).exp())
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    let mut remaining = rng.gen::<f64>() * total;
+    for (i, weight) in weights.iter().enumerate() {
+        remaining -= weight;
+        if remaining <= 0.0 {
+            return i;
+        }
+    }
+    weights.len() - 1
+}
+
+/// Plays a rollout where `choose` picks each move from the legal actions
+///
+/// `choose` also receives the current state and the moves played so far in
+/// this rollout. The result is from the point of view of `player`, and
+/// `limits` are honored like in
+/// [`GameState::simulate_random_playout_limited`]. Forced moves from
+/// [`GameState::forced_continuation`] are played before `choose` is first
+/// asked.
+fn guided_playout<S, F>(
+    state: &S,
+    player: &S::Player,
+    limits: Option<&PlayoutLimits>,
+    mut choose: F,
+) -> (f64, Vec<S::Action>)
+where
+    S: GameState,
+    F: FnMut(&S, &[S::Action], &[S::Action]) -> usize,
+{
+    let mut current_state = state.clone();
+    let mut trace = Vec::new();
+    let mut seen = HashSet::new();
+    let mut forcing = true;
+
+    while !current_state.is_terminal() {
+        if let Some(limits) = limits {
+            if let Some(reward) = limits.cycle_reward {
+                let hash = current_state.hash();
+                if hash != 0 && !seen.insert(hash) {
+                    return (reward, trace);
+                }
+            }
+            if limits.max_length.is_some_and(|max| trace.len() >= max) {
+                return (limits.unfinished.score(&current_state, player), trace);
+            }
+        }
+
+        if forcing {
+            if let Some(action) = current_state.forced_continuation() {
+                current_state = current_state.apply_action(&action);
+                trace.push(action);
+                continue;
+            }
+            forcing = false;
+        }
+
+        let legal_actions = current_state.get_legal_actions();
+        if legal_actions.is_empty() {
+            break;
+        }
+
+        let index = choose(&current_state, &legal_actions, &trace).min(legal_actions.len() - 1);
+        let action = legal_actions[index].clone();
+        current_state = current_state.apply_action(&action);
+        trace.push(action);
+    }
+
+    (current_state.get_result(player), trace)
+}
 // Implement SimulationPolicy for Box<dyn SimulationPolicy>
 impl<S: GameState> SimulationPolicy<S> for Box<dyn SimulationPolicy<S>> {
     fn simulate(&self, state: &S) -> (f64, Vec<S::Action>) {
         (**self).simulate(state)
     }
 
+    fn simulate_limited(&self, state: &S, limits: &PlayoutLimits) -> (f64, Vec<S::Action>) {
+        (**self).simulate_limited(state, limits)
+    }
+
+    fn simulate_for(
+        &self,
+        state: &S,
+        player: &S::Player,
+        limits: &PlayoutLimits,
+    ) -> Option<(f64, Vec<S::Action>)> {
+        (**self).simulate_for(state, player, limits)
+    }
+
     fn clone_box(&self) -> Box<dyn SimulationPolicy<S>> {
         (**self).clone_box()
     }
+
+    fn on_search_start(&mut self, root: &S) {
+        (**self).on_search_start(root)
+    }
+
+    fn on_search_end(&mut self, root: &MCTSNode<S>) {
+        (**self).on_search_end(root)
+    }
+
+    fn on_move_played(&mut self, action: &S::Action, state: &S) {
+        (**self).on_move_played(action, state)
+    }
 }
@@ -0,0 +1,363 @@
+//! Mapping between actions and the entries of a policy vector
+//!
+//! AlphaZero-style networks output a fixed-size policy vector, one entry per
+//! action the game could ever allow. Feeding it to the search means looking
+//! up each legal action's entry, masking out the illegal ones and
+//! renormalizing; training it on search results means scattering the visit
+//! distribution back into a vector of the same shape. A [`PolicyCodec`]
+//! states the mapping once, and its provided methods do the masking in one
+//! place so priors cannot silently end up on the wrong actions.
+//!
+//! [`PolicyHeadExpansionPolicy`] feeds a network's policy to the search as
+//! priors, and [`SearchResult::policy_target`](crate::SearchResult::policy_target)
+//! exports the training target.
+//!
+//! # Example
+//!
+//! ```
+//! use arboriter_mcts::policy_codec::{IdCodec, PolicyCodec};
+//! # use arboriter_mcts::Action;
+//! # #[derive(Clone, Debug, PartialEq)]
+//! # struct Cell(usize);
+//! # impl Action for Cell { fn id(&self) -> usize { self.0 } }
+//!
+//! // A 3x3 board: cell ids are the policy indices
+//! let codec = IdCodec::new(9);
+//! let legal = [Cell(0), Cell(4), Cell(8)];
+//! let policy = [0.1, 0.0, 0.0, 0.0, 0.2, 0.3, 0.0, 0.0, 0.1];
+//!
+//! // Cell 5 is illegal, so its share goes to the others
+//! let priors = codec.masked_priors(&policy, &legal).unwrap();
+//! assert_eq!(priors, vec![0.25, 0.5, 0.25]);
+//! ```
+
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{
+    game_state::{Action, GameState},
+    policy::expansion::ExpansionPolicy,
+    tree::MCTSNode,
+    MCTSError, Result,
+};
+
+/// Maps actions to indices of a fixed-size policy vector
+///
+/// Only [`policy_size`](Self::policy_size) and [`index_of`](Self::index_of)
+/// are required. Every legal action must map to its own index below the
+/// policy size; the provided methods return
+/// `MCTSError::InvalidConfiguration` when one does not.
+pub trait PolicyCodec<A: Action>: Send + Sync {
+    /// Returns the length of the policy vector
+    fn policy_size(&self) -> usize;
+
+    /// Returns the policy index of `action`, or `None` if it has none
+    fn index_of(&self, action: &A) -> Option<usize>;
+
+    /// Returns the policy indices of `actions`, in order
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::InvalidConfiguration` if an action has no index,
+    /// an index is out of range, or two actions share an index.
+    fn indices(&self, actions: &[A]) -> Result<Vec<usize>> {
+        let size = self.policy_size();
+        let mut seen = vec![false; size];
+        actions
+            .iter()
+            .map(|action| match self.index_of(action) {
+                Some(index) if index < size => {
+                    if std::mem::replace(&mut seen[index], true) {
+                        return Err(MCTSError::InvalidConfiguration(format!(
+                            "action {:?} shares policy index {} with another action",
+                            action, index
+                        )));
+                    }
+                    Ok(index)
+                }
+                Some(index) => Err(MCTSError::InvalidConfiguration(format!(
+                    "policy index {} of action {:?} is not below the policy size {}",
+                    index, action, size
+                ))),
+                None => Err(MCTSError::InvalidConfiguration(format!(
+                    "action {:?} has no policy index",
+                    action
+                ))),
+            })
+            .collect()
+    }
+
+    /// Returns a mask over the policy vector that is true at the legal actions
+    ///
+    /// # Errors
+    ///
+    /// As for [`indices`](Self::indices).
+    fn legal_mask(&self, legal: &[A]) -> Result<Vec<bool>> {
+        let mut mask = vec![false; self.policy_size()];
+        for index in self.indices(legal)? {
+            mask[index] = true;
+        }
+        Ok(mask)
+    }
+
+    /// Returns the priors of the legal actions, in order, from a probability vector
+    ///
+    /// The entries of illegal actions are dropped and the rest renormalized
+    /// to sum to 1. Negative and non-finite entries count as 0; if nothing is
+    /// left, the priors are uniform.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCTSError::InvalidConfiguration` if `policy` does not have
+    /// [`policy_size`](Self::policy_size) entries, or as for
+    /// [`indices`](Self::indices).
+    fn masked_priors(&self, policy: &[f64], legal: &[A]) -> Result<Vec<f64>> {
+        self.check_len(policy.len())?;
+        let weights = self
+            .indices(legal)?
+            .into_iter()
+            .map(|index| policy[index])
+            .map(|weight| {
+                if weight.is_finite() {
+                    weight.max(0.0)
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        Ok(normalize(weights))
+    }
+
+    /// Returns the priors of the legal actions, in order, from raw logits
+    ///
+    /// Takes a softmax over the legal actions' logits only, so illegal
+    /// actions get no share however large their logits are. Non-finite
+    /// logits are treated as minus infinity; if every legal logit is, the
+    /// priors are uniform.
+    ///
+    /// # Errors
+    ///
+    /// As for [`masked_priors`](Self::masked_priors).
+    fn masked_softmax(&self, logits: &[f64], legal: &[A]) -> Result<Vec<f64>> {
+        self.check_len(logits.len())?;
+        let logits: Vec<f64> = self
+            .indices(legal)?
+            .into_iter()
+            .map(|index| logits[index])
+            .collect();
+        let max = logits
+            .iter()
+            .copied()
+            .filter(|logit| logit.is_finite())
+            .fold(f64::NEG_INFINITY, f64::max);
+        let weights = logits
+            .into_iter()
+            .map(|logit| {
+                if logit.is_finite() {
+                    (logit - max).exp()
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        Ok(normalize(weights))
+    }
+
+    /// Scatters per-action weights, such as a visit distribution, into a policy vector
+    ///
+    /// Entries of actions not listed are 0.
+    ///
+    /// # Errors
+    ///
+    /// As for [`indices`](Self::indices).
+    fn scatter(&self, weights: &[(&A, f64)]) -> Result<Vec<f64>> {
+        let actions: Vec<A> = weights
+            .iter()
+            .map(|(action, _)| (*action).clone())
+            .collect();
+        let mut policy = vec![0.0; self.policy_size()];
+        for (index, (_, weight)) in self.indices(&actions)?.into_iter().zip(weights) {
+            policy[index] = *weight;
+        }
+        Ok(policy)
+    }
+
+    /// Checks that a vector has one entry per policy index
+    fn check_len(&self, len: usize) -> Result<()> {
+        if len != self.policy_size() {
+            return Err(MCTSError::InvalidConfiguration(format!(
+                "policy vector has {} entries, expected {}",
+                len,
+                self.policy_size()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Scales weights to sum to 1, or returns uniform weights if they sum to 0
+fn normalize(weights: Vec<f64>) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    if total > 0.0 && total.is_finite() {
+        weights.into_iter().map(|weight| weight / total).collect()
+    } else {
+        let uniform = 1.0 / weights.len().max(1) as f64;
+        vec![uniform; weights.len()]
+    }
+}
+
+/// Codec using each action's [`id`](Action::id) as its policy index
+///
+/// Suits games whose action ids already number the policy head, such as
+/// board cells or columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdCodec {
+    size: usize,
+}
+
+impl IdCodec {
+    /// Creates a codec for a policy vector of `size` entries
+    pub fn new(size: usize) -> Self {
+        IdCodec { size }
+    }
+}
+
+impl<A: Action> PolicyCodec<A> for IdCodec {
+    fn policy_size(&self) -> usize {
+        self.size
+    }
+
+    fn index_of(&self, action: &A) -> Option<usize> {
+        Some(action.id())
+    }
+}
+
+/// Expansion policy taking its priors from a network's policy head
+///
+/// `evaluate` returns the policy vector for a state, either probabilities
+/// or raw logits (see [`with_logits`](Self::with_logits)), and the codec
+/// maps it onto the node's legal actions. The action with the highest prior
+/// is expanded first, and [`action_priors`](ExpansionPolicy::action_priors)
+/// reports the priors so
+/// [`MCTSConfig::with_root_prior_filter`](crate::MCTSConfig::with_root_prior_filter)
+/// can use them. `evaluate` runs on every expansion; cache inside it if the
+/// network is expensive.
+///
+/// If the codec rejects the node's actions or the vector has the wrong
+/// length, a warning is logged once and the node gets uniform priors.
+pub struct PolicyHeadExpansionPolicy<S, C, F>
+where
+    S: GameState,
+    C: PolicyCodec<S::Action> + Clone + 'static,
+    F: Fn(&S) -> Vec<f64> + Clone + Send + Sync + 'static,
+{
+    /// Maps actions to policy indices
+    codec: C,
+
+    /// Returns the policy vector for a state
+    evaluate: F,
+
+    /// Whether `evaluate` returns logits instead of probabilities
+    logits: bool,
+
+    /// Whether a codec error has been logged
+    warned: AtomicBool,
+
+    _phantom: PhantomData<fn(&S)>,
+}
+
+impl<S, C, F> PolicyHeadExpansionPolicy<S, C, F>
+where
+    S: GameState,
+    C: PolicyCodec<S::Action> + Clone + 'static,
+    F: Fn(&S) -> Vec<f64> + Clone + Send + Sync + 'static,
+{
+    /// Creates a policy reading probabilities from `evaluate` through `codec`
+    pub fn new(codec: C, evaluate: F) -> Self {
+        PolicyHeadExpansionPolicy {
+            codec,
+            evaluate,
+            logits: false,
+            warned: AtomicBool::new(false),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Treats the vectors `evaluate` returns as logits, masked with a softmax
+    pub fn with_logits(mut self) -> Self {
+        self.logits = true;
+        self
+    }
+
+    /// Returns the priors of all of the node's actions: children first, then unexpanded
+    fn node_priors(&self, node: &MCTSNode<S>) -> Vec<f64> {
+        let actions: Vec<S::Action> = node
+            .children
+            .iter()
+            .filter_map(|child| child.action.clone())
+            .chain(node.unexpanded_actions.iter().cloned())
+            .collect();
+        let policy = (self.evaluate)(&node.state);
+        let priors = if self.logits {
+            self.codec.masked_softmax(&policy, &actions)
+        } else {
+            self.codec.masked_priors(&policy, &actions)
+        };
+        priors.unwrap_or_else(|error| {
+            if !self.warned.swap(true, Ordering::Relaxed) {
+                log::warn!("Falling back to uniform priors: {}", error);
+            }
+            vec![1.0 / actions.len().max(1) as f64; actions.len()]
+        })
+    }
+}
+
+impl<S, C, F> Clone for PolicyHeadExpansionPolicy<S, C, F>
+where
+    S: GameState,
+    C: PolicyCodec<S::Action> + Clone + 'static,
+    F: Fn(&S) -> Vec<f64> + Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        PolicyHeadExpansionPolicy {
+            codec: self.codec.clone(),
+            evaluate: self.evaluate.clone(),
+            logits: self.logits,
+            warned: AtomicBool::new(self.warned.load(Ordering::Relaxed)),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, C, F> ExpansionPolicy<S> for PolicyHeadExpansionPolicy<S, C, F>
+where
+    S: GameState + 'static,
+    C: PolicyCodec<S::Action> + Clone + 'static,
+    F: Fn(&S) -> Vec<f64> + Clone + Send + Sync + 'static,
+{
+    fn select_action_to_expand(&self, node: &MCTSNode<S>) -> Option<(usize, f64)> {
+        let priors = self.action_priors(node)?;
+        priors
+            .into_iter()
+            .enumerate()
+            .fold(
+                None,
+                |best: Option<(usize, f64)>, (index, prior)| match best {
+                    Some((_, best_prior)) if best_prior >= prior => best,
+                    _ => Some((index, prior)),
+                },
+            )
+    }
+
+    fn action_priors(&self, node: &MCTSNode<S>) -> Option<Vec<f64>> {
+        if node.unexpanded_actions.is_empty() {
+            return None;
+        }
+        let mut priors = self.node_priors(node);
+        Some(priors.split_off(priors.len() - node.unexpanded_actions.len()))
+    }
+
+    fn clone_box(&self) -> Box<dyn ExpansionPolicy<S>> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,76 @@
+//! Cache of terminal state results
+//!
+//! In small games many iterations end in the same few terminal states, and
+//! scoring them over and over can dominate the search. A [`ResultCache`]
+//! remembers [`GameState::get_result`](crate::GameState::get_result) per
+//! state [`hash`](crate::GameState::hash) and player. Enable it with
+//! [`MCTSConfig::with_result_cache`](crate::MCTSConfig::with_result_cache);
+//! hit rates are reported in
+//! [`SearchStatistics::result_cache_stats`](crate::SearchStatistics::result_cache_stats).
+
+use std::collections::HashMap;
+
+/// Size-bounded map from state hash and player to result
+///
+/// Once `capacity` states are stored, further results are computed but not
+/// kept. States hashing to `0` are never cached.
+#[derive(Debug, Clone)]
+pub struct ResultCache<P> {
+    results: HashMap<u64, Vec<(P, f64)>>,
+    capacity: usize,
+}
+
+impl<P: PartialEq + Clone> ResultCache<P> {
+    /// Creates an empty cache holding up to `capacity` states
+    pub fn new(capacity: usize) -> Self {
+        ResultCache {
+            results: HashMap::new(),
+            capacity,
+        }
+    }
+
+    /// Returns the cached result of the state with `hash` for `player`
+    pub fn get(&self, hash: u64, player: &P) -> Option<f64> {
+        self.results
+            .get(&hash)?
+            .iter()
+            .find(|(cached, _)| cached == player)
+            .map(|(_, result)| *result)
+    }
+
+    /// Stores a result, returning false if the cache had no room for it
+    pub fn insert(&mut self, hash: u64, player: &P, result: f64) -> bool {
+        if hash == 0 {
+            return false;
+        }
+        if !self.results.contains_key(&hash) && self.results.len() >= self.capacity {
+            return false;
+        }
+        let entries = self.results.entry(hash).or_default();
+        match entries.iter_mut().find(|(cached, _)| cached == player) {
+            Some(entry) => entry.1 = result,
+            None => entries.push((player.clone(), result)),
+        }
+        true
+    }
+
+    /// Returns the number of states cached
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns true if nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Returns the largest number of states the cache holds
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Removes every cached result
+    pub fn clear(&mut self) {
+        self.results.clear();
+    }
+}
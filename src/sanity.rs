@@ -0,0 +1,194 @@
+//! Smoke tests for game implementations
+//!
+//! A game with a subtle bug, such as results scored for the wrong player or
+//! a reward perspective that does not match the game, still searches without
+//! errors; it just plays badly. [`play_against_random`] checks that search
+//! actually helps: it plays a configuration against an agent that moves
+//! uniformly at random, and the report says whether the search scored
+//! significantly better than the even score random play would get.
+//!
+//! # Example
+//!
+//! ```
+//! use arboriter_mcts::{config::RewardPerspective, sanity::play_against_random, MCTSConfig};
+//! # use arboriter_mcts::{Action, GameState, Player};
+//! # #[derive(Clone, Copy, Debug, PartialEq)]
+//! # struct Side(u8);
+//! # impl Player for Side {}
+//! # #[derive(Clone, Debug, PartialEq)]
+//! # struct Take(u32);
+//! # impl Action for Take { fn id(&self) -> usize { self.0 as usize } }
+//! # /// Nim: take 1 to 3 stones, taking the last one wins
+//! # #[derive(Clone, Debug)]
+//! # struct Nim { stones: u32, to_move: u8 }
+//! # impl GameState for Nim {
+//! #     type Action = Take;
+//! #     type Player = Side;
+//! #     fn get_legal_actions(&self) -> Vec<Take> { (1..=self.stones.min(3)).map(Take).collect() }
+//! #     fn apply_action(&self, take: &Take) -> Self { Nim { stones: self.stones - take.0, to_move: 1 - self.to_move } }
+//! #     fn is_terminal(&self) -> bool { self.stones == 0 }
+//! #     fn get_result(&self, side: &Side) -> f64 { if side.0 == self.to_move { 0.0 } else { 1.0 } }
+//! #     fn get_current_player(&self) -> Side { Side(self.to_move) }
+//! # }
+//!
+//! let config = MCTSConfig::default()
+//!     .with_max_iterations(300)
+//!     .with_reward_perspective(RewardPerspective::PerPlayer);
+//! let report = play_against_random(&Nim { stones: 15, to_move: 0 }, &config, 20, 7).unwrap();
+//! assert!(report.beats_random(), "{:?}", report);
+//! ```
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{
+    config::MCTSConfig, game_state::GameState, mcts::MCTS, tree::outcome_of, MCTSError, Result,
+};
+
+/// z-score a search must reach to beat random play, a one-sided p-value of 0.01
+pub const SIGNIFICANT_Z: f64 = 2.326;
+
+/// Outcomes of a match against the random agent, from the search's side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RandomMatchReport {
+    /// Games played
+    pub games: usize,
+
+    /// Games the search won
+    pub wins: usize,
+
+    /// Games drawn
+    pub draws: usize,
+
+    /// Games the search lost
+    pub losses: usize,
+}
+
+impl RandomMatchReport {
+    /// Returns the search's average score, counting draws as half a win
+    ///
+    /// Returns 0.5 if no games were played.
+    pub fn score(&self) -> f64 {
+        if self.games == 0 {
+            return 0.5;
+        }
+        (self.wins as f64 + 0.5 * self.draws as f64) / self.games as f64
+    }
+
+    /// Returns how many standard errors the score lies above 0.5
+    ///
+    /// The standard error uses the largest variance a score in `[0, 1]` can
+    /// have, so the test stays conservative whatever the draw rate.
+    pub fn z_score(&self) -> f64 {
+        (self.score() - 0.5) * 2.0 * (self.games as f64).sqrt()
+    }
+
+    /// Returns true if the search scored significantly better than random play
+    ///
+    /// See [`SIGNIFICANT_Z`].
+    pub fn beats_random(&self) -> bool {
+        self.z_score() >= SIGNIFICANT_Z
+    }
+}
+
+/// Plays `games` games of search with `config` against a uniformly random agent
+///
+/// The search moves first in every other game, so random play against random
+/// play would average 0.5 even in games that favour one side. Each move is a
+/// fresh search from the current state. Results come from
+/// [`GameState::get_result`] for the player the search played and are
+/// classified with [`outcome_of`]; chance events are resolved with
+/// [`GameState::sample_chance_outcome`]. This suits two-player games where
+/// players alternate turns. `seed` makes the random agent's moves
+/// reproducible.
+///
+/// # Errors
+///
+/// Returns the first error of any search, and `MCTSError::NoLegalActions` if
+/// the random agent faces a non-terminal state without legal actions.
+pub fn play_against_random<S: GameState + 'static>(
+    initial: &S,
+    config: &MCTSConfig,
+    games: usize,
+    seed: u64,
+) -> Result<RandomMatchReport> {
+    play_against_random_with(initial, games, seed, |state| {
+        MCTS::new(state, config.clone())
+    })
+}
+
+/// Like [`play_against_random`], building each search with `build`
+///
+/// Use this to test a search with custom policies, oracles or other
+/// attachments; `build` is called once per move with the current state.
+///
+/// # Errors
+///
+/// Returns the first error of any search, and `MCTSError::NoLegalActions` if
+/// the random agent faces a non-terminal state without legal actions.
+pub fn play_against_random_with<S, F>(
+    initial: &S,
+    games: usize,
+    seed: u64,
+    mut build: F,
+) -> Result<RandomMatchReport>
+where
+    S: GameState + 'static,
+    F: FnMut(S) -> MCTS<S>,
+{
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut report = RandomMatchReport {
+        games,
+        ..RandomMatchReport::default()
+    };
+    for game in 0..games {
+        let score = play_game(initial, game % 2 == 0, &mut rng, &mut build)?;
+        match outcome_of(score) {
+            outcome if outcome > 0.5 => report.wins += 1,
+            outcome if outcome < 0.5 => report.losses += 1,
+            _ => report.draws += 1,
+        }
+    }
+    Ok(report)
+}
+
+/// Plays one game and returns the search's result
+fn play_game<S, F>(initial: &S, search_first: bool, rng: &mut StdRng, build: &mut F) -> Result<f64>
+where
+    S: GameState + 'static,
+    F: FnMut(S) -> MCTS<S>,
+{
+    let first_mover = initial.get_current_player();
+    let mut searcher = None;
+    let mut random = None;
+    let mut state = initial.clone();
+
+    while !state.is_terminal() {
+        if state.is_chance_event() {
+            state = state
+                .sample_chance_outcome(rng)
+                .ok_or(MCTSError::NoLegalActions)?;
+            continue;
+        }
+
+        let mover = state.get_current_player();
+        let action = if (mover == first_mover) == search_first {
+            searcher.get_or_insert_with(|| mover.clone());
+            build(state.clone()).search()?
+        } else {
+            random.get_or_insert_with(|| mover.clone());
+            state
+                .get_legal_actions()
+                .choose(rng)
+                .cloned()
+                .ok_or(MCTSError::NoLegalActions)?
+        };
+        state = state.apply_action(&action);
+    }
+
+    // A side that never moved is scored as the opponent's complement
+    Ok(match (searcher, random) {
+        (Some(player), _) => state.get_result(&player),
+        (None, Some(player)) => 1.0 - state.get_result(&player),
+        (None, None) => 0.5,
+    })
+}
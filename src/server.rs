@@ -0,0 +1,486 @@
+//! JSON-RPC search server for engines embedded in non-Rust backends
+//!
+//! Enabled with the `server` feature. A [`SearchServer`] answers JSON-RPC 2.0
+//! requests over plain HTTP/1.1 with a single method:
+//!
+//! ```text
+//! POST /
+//! {"jsonrpc": "2.0", "id": 1, "method": "search",
+//!  "params": {"state": <state>, "budget": {"iterations": 5000, "time_ms": 200}, "lines": 3}}
+//! ```
+//!
+//! `params` may also be positional, `[state, budget]`. The budget may give
+//! `iterations`, `time_ms` or both; without one the configured budget is
+//! used. The result holds the best action, the iterations searched, the tree
+//! size, and the top `lines` (default 1) with their expected continuations:
+//!
+//! ```text
+//! {"jsonrpc": "2.0", "id": 1, "result": {"action": <action>, "iterations": 5000,
+//!  "tree_size": 4817, "lines": [{"moves": [<action>, ...], "visits": 2210, "value": 0.61}]}}
+//! ```
+//!
+//! States are decoded and actions encoded by a [`StateCodec`]; [`SerdeCodec`]
+//! covers states that implement `serde::Deserialize` and actions that
+//! implement `serde::Serialize`. Every request gets a fresh search tree. The
+//! server is deliberately small: one request per connection, one thread per
+//! connection, and no TLS or authentication, so keep it behind your own
+//! network boundary. [`SearchServer::with_max_budget`] bounds what a client
+//! may ask for, per request or per batch.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::marker::PhantomData;
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use crate::{config::Budget, game_state::GameState, MCTSConfig, MCTS};
+
+/// Largest request body the server reads, in bytes
+pub const MAX_BODY_BYTES: usize = 1 << 20;
+
+/// Largest request line and headers the server reads, in bytes
+pub const MAX_HEADER_BYTES: usize = 16 << 10;
+
+/// JSON-RPC error code for a body that is not JSON
+pub const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code for JSON that is not a request
+pub const INVALID_REQUEST: i64 = -32600;
+/// JSON-RPC error code for an unknown method
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for parameters that cannot be used
+pub const INVALID_PARAMS: i64 = -32602;
+/// JSON-RPC error code for a search that failed
+pub const SEARCH_ERROR: i64 = -32000;
+
+/// Translates between JSON and a game's states and actions
+pub trait StateCodec<S: GameState>: Send + Sync {
+    /// Builds a state from the `state` parameter of a request
+    fn decode_state(&self, state: Value) -> Result<S, String>;
+
+    /// Encodes an action for a response
+    fn encode_action(&self, action: &S::Action) -> Value;
+}
+
+/// Codec for states and actions that implement serde's traits
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SerdeCodec;
+
+impl<S> StateCodec<S> for SerdeCodec
+where
+    S: GameState + DeserializeOwned,
+    S::Action: Serialize,
+{
+    fn decode_state(&self, state: Value) -> Result<S, String> {
+        serde_json::from_value(state).map_err(|error| error.to_string())
+    }
+
+    fn encode_action(&self, action: &S::Action) -> Value {
+        serde_json::to_value(action).unwrap_or(Value::Null)
+    }
+}
+
+/// Customizes the search built for each request, e.g. to attach policies
+pub type SearchSetup<S> = Box<dyn Fn(MCTS<S>) -> MCTS<S> + Send + Sync>;
+
+/// Serves `search` requests for one game over JSON-RPC
+///
+/// See the [module documentation](self).
+pub struct SearchServer<S: GameState + 'static, C: StateCodec<S>> {
+    codec: C,
+    config: MCTSConfig,
+    setup: Option<SearchSetup<S>>,
+    max_budget: Option<Budget>,
+    _state: PhantomData<fn() -> S>,
+}
+
+impl<S: GameState + 'static, C: StateCodec<S>> SearchServer<S, C> {
+    /// Creates a server that searches with `config`, translating through `codec`
+    pub fn new(codec: C, config: MCTSConfig) -> Self {
+        SearchServer {
+            codec,
+            config,
+            setup: None,
+            max_budget: None,
+            _state: PhantomData,
+        }
+    }
+
+    /// Applies `setup` to every search before it runs
+    pub fn with_setup<F>(mut self, setup: F) -> Self
+    where
+        F: Fn(MCTS<S>) -> MCTS<S> + Send + Sync + 'static,
+    {
+        self.setup = Some(Box::new(setup));
+        self
+    }
+
+    /// Caps the iterations and time a request may ask for
+    ///
+    /// Each limit of `max` applies on its own; requests without a limit of
+    /// their own get the cap. The requests of a batch share one cap: each
+    /// search gets what the earlier ones left, and once a limit is used up
+    /// the rest of the batch fails with [`SEARCH_ERROR`].
+    pub fn with_max_budget(mut self, max: Budget) -> Self {
+        self.max_budget = Some(max);
+        self
+    }
+
+    /// Listens on `addr` and serves requests until the listener fails
+    pub fn serve<A: ToSocketAddrs>(&self, addr: A) -> io::Result<()> {
+        self.serve_listener(TcpListener::bind(addr)?)
+    }
+
+    /// Serves requests arriving on `listener`, one thread per connection
+    pub fn serve_listener(&self, listener: TcpListener) -> io::Result<()> {
+        std::thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                scope.spawn(move || {
+                    if let Err(error) = self.handle_connection(stream) {
+                        log::debug!("Search server connection failed: {}", error);
+                    }
+                });
+            }
+            Ok(())
+        })
+    }
+
+    /// Reads one HTTP request from `stream` and writes the response
+    pub fn handle_connection(&self, mut stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+        let (status, body) = match read_request(&mut stream)? {
+            HttpRequest::Post(body) => match self.handle_request(&body) {
+                Some(response) => ("200 OK", response),
+                None => ("204 No Content", String::new()),
+            },
+            HttpRequest::WrongMethod => ("405 Method Not Allowed", String::new()),
+            HttpRequest::TooLarge => ("413 Payload Too Large", String::new()),
+            HttpRequest::HeadTooLarge => ("431 Request Header Fields Too Large", String::new()),
+            HttpRequest::Malformed => ("400 Bad Request", String::new()),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )?;
+        stream.flush()
+    }
+
+    /// Answers a JSON-RPC request body
+    ///
+    /// Batches are answered with an array. Returns `None` when nothing is to
+    /// be sent back, i.e. for notifications, which have no `id`.
+    pub fn handle_request(&self, body: &str) -> Option<String> {
+        let request: Value = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(error) => return Some(error_response(Value::Null, PARSE_ERROR, error).to_string()),
+        };
+        match request {
+            Value::Array(batch) if batch.is_empty() => {
+                Some(error_response(Value::Null, INVALID_REQUEST, "empty batch").to_string())
+            }
+            Value::Array(batch) => {
+                let mut allowance = self.max_budget.map(Allowance::new);
+                let responses: Vec<Value> = batch
+                    .into_iter()
+                    .filter_map(|request| self.answer(request, &mut allowance))
+                    .collect();
+                (!responses.is_empty()).then(|| Value::Array(responses).to_string())
+            }
+            request => {
+                let mut allowance = self.max_budget.map(Allowance::new);
+                self.answer(request, &mut allowance)
+                    .map(|response| response.to_string())
+            }
+        }
+    }
+
+    /// Answers a single request, or nothing for a notification
+    fn answer(&self, request: Value, allowance: &mut Option<Allowance>) -> Option<Value> {
+        let Value::Object(mut request) = request else {
+            return Some(error_response(
+                Value::Null,
+                INVALID_REQUEST,
+                "not an object",
+            ));
+        };
+        let id = request.remove("id");
+        let notification = id.is_none();
+        let id = id.unwrap_or(Value::Null);
+
+        if request.get("jsonrpc") != Some(&json!("2.0")) {
+            return Some(error_response(
+                id,
+                INVALID_REQUEST,
+                "jsonrpc must be \"2.0\"",
+            ));
+        }
+        let outcome = match request.get("method").and_then(Value::as_str) {
+            Some("search") => {
+                self.search(request.remove("params").unwrap_or(Value::Null), allowance)
+            }
+            Some(method) => Err((METHOD_NOT_FOUND, format!("unknown method {:?}", method))),
+            None => Err((INVALID_REQUEST, "method must be a string".to_string())),
+        };
+
+        if notification {
+            return None;
+        }
+        Some(match outcome {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err((code, message)) => error_response(id, code, message),
+        })
+    }
+
+    /// Runs the `search` method within what is left of `allowance`
+    fn search(
+        &self,
+        params: Value,
+        allowance: &mut Option<Allowance>,
+    ) -> Result<Value, (i64, String)> {
+        let invalid = |message: String| (INVALID_PARAMS, message);
+        let (state, budget, lines) = match params {
+            Value::Object(mut params) => (
+                params.remove("state"),
+                params.remove("budget"),
+                params.remove("lines"),
+            ),
+            Value::Array(params) => {
+                let mut params = params.into_iter();
+                (params.next(), params.next(), None)
+            }
+            _ => return Err(invalid("params must be an object or an array".to_string())),
+        };
+
+        let state = self
+            .codec
+            .decode_state(state.ok_or_else(|| invalid("missing state".to_string()))?)
+            .map_err(|error| invalid(format!("cannot decode state: {}", error)))?;
+        let cap = match allowance {
+            None => None,
+            Some(allowance) => Some(allowance.remaining().ok_or_else(|| {
+                (
+                    SEARCH_ERROR,
+                    "the batch has used up the server's budget".to_string(),
+                )
+            })?),
+        };
+        let budget = self.budget(budget, cap).map_err(invalid)?;
+        let lines = match lines {
+            None => 1,
+            Some(lines) => lines
+                .as_u64()
+                .ok_or_else(|| invalid("lines must be a count".to_string()))?
+                as usize,
+        };
+
+        let mut mcts = MCTS::new(state, self.config.clone());
+        if let Some(setup) = &self.setup {
+            mcts = setup(mcts);
+        }
+        let action = mcts
+            .continue_search(budget)
+            .map_err(|error| (SEARCH_ERROR, error.to_string()))?;
+
+        let stats = mcts.get_statistics();
+        if let Some(allowance) = allowance {
+            allowance.spend(stats.iterations);
+        }
+        let lines: Vec<Value> = mcts
+            .top_lines(lines)
+            .iter()
+            .map(|line| {
+                let moves: Vec<Value> = line
+                    .moves
+                    .iter()
+                    .map(|action| self.codec.encode_action(action))
+                    .collect();
+                json!({"moves": moves, "visits": line.visits, "value": line.value})
+            })
+            .collect();
+        Ok(json!({
+            "action": self.codec.encode_action(&action),
+            "iterations": stats.iterations,
+            "tree_size": stats.tree_size,
+            "lines": lines,
+        }))
+    }
+
+    /// Works out the budget of a request, capped by `cap`
+    fn budget(&self, budget: Option<Value>, cap: Option<Budget>) -> Result<Budget, String> {
+        let requested = match budget {
+            None | Some(Value::Null) => self.config.budget(),
+            Some(Value::Object(budget)) => {
+                let limit = |key: &str| match budget.get(key) {
+                    None | Some(Value::Null) => Ok(None),
+                    Some(value) => value
+                        .as_u64()
+                        .map(Some)
+                        .ok_or_else(|| format!("budget {} must be a count", key)),
+                };
+                Budget::from_limits(
+                    limit("iterations")?.map(|iterations| iterations as usize),
+                    limit("time_ms")?.map(Duration::from_millis),
+                )
+            }
+            Some(_) => return Err("budget must be an object".to_string()),
+        };
+
+        let capped = match cap {
+            None => requested,
+            Some(max) => Budget::from_limits(
+                min_limit(requested.iterations(), max.iterations()),
+                min_limit(requested.time(), max.time()),
+            ),
+        };
+        if capped == Budget::Unlimited {
+            return Err("budget must limit iterations or time".to_string());
+        }
+        Ok(capped)
+    }
+}
+
+impl<S: GameState + 'static, C: StateCodec<S> + std::fmt::Debug> std::fmt::Debug
+    for SearchServer<S, C>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchServer")
+            .field("codec", &self.codec)
+            .field("config", &self.config)
+            .field("max_budget", &self.max_budget)
+            .finish()
+    }
+}
+
+/// What is left of the server's budget cap for the requests of one body
+///
+/// A batch shares one allowance, so it cannot search longer than the cap
+/// allows a single request.
+#[derive(Debug, Clone, Copy)]
+struct Allowance {
+    iterations: Option<usize>,
+    deadline: Option<Instant>,
+}
+
+impl Allowance {
+    /// Starts an allowance of `max` from now
+    fn new(max: Budget) -> Self {
+        Allowance {
+            iterations: max.iterations(),
+            deadline: max.time().map(|time| Instant::now() + time),
+        }
+    }
+
+    /// Returns the budget still available, or `None` once a limit is used up
+    fn remaining(&self) -> Option<Budget> {
+        let time = self
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        if self.iterations == Some(0) || time == Some(Duration::ZERO) {
+            return None;
+        }
+        Some(Budget::from_limits(self.iterations, time))
+    }
+
+    /// Deducts the iterations of a finished search
+    fn spend(&mut self, iterations: usize) {
+        if let Some(left) = &mut self.iterations {
+            *left = left.saturating_sub(iterations);
+        }
+    }
+}
+
+/// The tighter of two optional limits
+fn min_limit<T: Ord>(requested: Option<T>, max: Option<T>) -> Option<T> {
+    match (requested, max) {
+        (Some(requested), Some(max)) => Some(requested.min(max)),
+        (requested, max) => requested.or(max),
+    }
+}
+
+/// Builds a JSON-RPC error response
+fn error_response(id: Value, code: i64, message: impl ToString) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": code, "message": message.to_string()},
+    })
+}
+
+/// What [`read_request`] found on a connection
+enum HttpRequest {
+    Post(String),
+    WrongMethod,
+    TooLarge,
+    HeadTooLarge,
+    Malformed,
+}
+
+/// Reads the request line, headers and body of one HTTP request
+fn read_request(stream: &mut TcpStream) -> io::Result<HttpRequest> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    let mut head_left = MAX_HEADER_BYTES as u64;
+    if read_head_line(&mut reader, &mut line, &mut head_left)?.is_none() {
+        return Ok(HttpRequest::HeadTooLarge);
+    }
+    let post = line.split_whitespace().next() == Some("POST");
+
+    let mut content_length = None;
+    loop {
+        match read_head_line(&mut reader, &mut line, &mut head_left)? {
+            None => return Ok(HttpRequest::HeadTooLarge),
+            Some(0) => return Ok(HttpRequest::Malformed),
+            Some(_) => {}
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    if !post {
+        return Ok(HttpRequest::WrongMethod);
+    }
+    let Some(length) = content_length else {
+        return Ok(HttpRequest::Malformed);
+    };
+    if length > MAX_BODY_BYTES {
+        return Ok(HttpRequest::TooLarge);
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(match String::from_utf8(body) {
+        Ok(body) => HttpRequest::Post(body),
+        Err(_) => HttpRequest::Malformed,
+    })
+}
+
+/// Reads one line of the request head into `line`, from at most `left` bytes
+///
+/// Returns the bytes read, or `None` once the head outgrows the bytes left.
+fn read_head_line(
+    reader: &mut impl BufRead,
+    line: &mut String,
+    left: &mut u64,
+) -> io::Result<Option<usize>> {
+    line.clear();
+    if *left == 0 {
+        return Ok(None);
+    }
+    let read = reader.by_ref().take(*left).read_line(line)?;
+    *left -= read as u64;
+    if *left == 0 && !line.ends_with('\n') {
+        return Ok(None);
+    }
+    Ok(Some(read))
+}
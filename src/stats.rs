@@ -3,8 +3,333 @@
 //! This module provides structures for collecting and reporting statistics
 //! about MCTS search processes.
 
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
 use std::time::Duration;
 
+use crate::{
+    config::{BestChildCriteria, Budget},
+    game_state::{Action, GameState},
+    mcts::MCTS,
+};
+
+/// Names of the metrics recorded with the `metrics` feature
+///
+/// Every search records its totals once it finishes, and every iteration
+/// records the length of its playout. Install any `metrics` recorder, such as
+/// a Prometheus exporter, to collect them.
+#[cfg(feature = "metrics")]
+pub mod metric_names {
+    /// Counter of finished searches, labelled with their `outcome`
+    pub const SEARCHES: &str = "arboriter_mcts_searches_total";
+
+    /// Counter of iterations run
+    pub const ITERATIONS: &str = "arboriter_mcts_iterations_total";
+
+    /// Counter of nodes added to search trees
+    pub const NODES_ALLOCATED: &str = "arboriter_mcts_nodes_allocated_total";
+
+    /// Histogram of playout lengths in moves
+    pub const ROLLOUT_LENGTH: &str = "arboriter_mcts_rollout_length";
+
+    /// Histogram of search durations in seconds
+    pub const SEARCH_DURATION: &str = "arboriter_mcts_search_duration_seconds";
+}
+
+/// Why a search stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchOutcome {
+    /// The full iteration budget was used
+    #[default]
+    Completed,
+
+    /// The configured time limit was reached before the iteration budget
+    TimeLimit,
+
+    /// The caller requested a stop through a [`StopToken`](crate::mcts::StopToken)
+    Stopped,
+
+    /// The whole reachable tree was expanded, so further iterations were useless
+    ///
+    /// Only produced when [`MCTSConfig::stop_when_exhausted`](crate::MCTSConfig::stop_when_exhausted)
+    /// is enabled; otherwise exhaustion is reported through
+    /// [`SearchStatistics::tree_exhausted`] alone.
+    TreeExhausted,
+}
+
+impl SearchOutcome {
+    /// Returns true if the search ended before using its full budget
+    pub fn is_early(&self) -> bool {
+        *self != SearchOutcome::Completed
+    }
+}
+
+/// Progress report sent periodically while a search runs
+///
+/// Enable with [`MCTSConfig::with_event_interval`](crate::MCTSConfig::with_event_interval)
+/// and receive through [`MCTS::iteration_events`](crate::MCTS::iteration_events).
+/// Events travel over a channel, so the receiver can live on a different
+/// thread than the search.
+#[derive(Debug, Clone)]
+pub struct IterationEvent<A> {
+    /// Number of iterations completed so far
+    pub iteration: usize,
+
+    /// Number of nodes added to the tree so far
+    pub tree_size: usize,
+
+    /// The action the search would return if it stopped now
+    pub best_action_so_far: Option<A>,
+}
+
+/// Visits and mean value of one root action
+#[derive(Debug, Clone, PartialEq)]
+pub struct RootActionStats<A> {
+    /// The root action
+    pub action: A,
+
+    /// Visits of the child reached by the action
+    pub visits: u64,
+
+    /// Mean value of the child reached by the action
+    pub value: f64,
+}
+
+/// One line of play from the root, for analysis displays
+///
+/// Produced by [`MCTS::top_lines`](crate::MCTS::top_lines).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisLine<A> {
+    /// The root action the line starts with
+    pub action: A,
+
+    /// The expected continuation, starting with `action` and following the
+    /// most visited child at every step
+    pub moves: Vec<A>,
+
+    /// Visits of the child reached by `action`
+    pub visits: u64,
+
+    /// Mean value of the child reached by `action`
+    pub value: f64,
+}
+
+/// One step of a plan extracted from the tree
+///
+/// Produced by [`MCTS::extract_plan`](crate::MCTS::extract_plan).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanStep<A> {
+    /// The action to take at this step
+    pub action: A,
+
+    /// Visits of the child reached by the action
+    pub visits: u64,
+
+    /// Mean value of the child reached by the action
+    pub value: f64,
+
+    /// Share of the parent's visits that went to this action, in `[0, 1]`
+    pub share: f64,
+
+    /// Product of the shares of this step and every step before it
+    ///
+    /// Decays along the plan; a low confidence marks where the plan becomes
+    /// speculative.
+    pub confidence: f64,
+}
+
+/// Playout results of one root action in a static evaluation
+///
+/// Produced by [`MCTS::static_evaluation`](crate::MCTS::static_evaluation).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionEvaluation<A> {
+    /// The root action
+    pub action: A,
+
+    /// Number of playouts run after the action
+    pub samples: usize,
+
+    /// Mean playout result
+    pub mean: f64,
+
+    /// Standard deviation of the playout results
+    pub std_dev: f64,
+}
+
+/// Root statistics at one point in time
+///
+/// Take one with [`MCTS::root_snapshot`](crate::MCTS::root_snapshot) before
+/// and after continuing a search, then [`diff`](Self::diff) them to see how
+/// the search changed its mind.
+#[derive(Debug, Clone)]
+pub struct RootSnapshot<A> {
+    /// Visits of the root
+    pub root_visits: u64,
+
+    /// Statistics of every expanded root action
+    pub actions: Vec<RootActionStats<A>>,
+
+    /// The action the search would return at this point
+    pub best_action: Option<A>,
+}
+
+impl<A: Action> RootSnapshot<A> {
+    /// Compares this snapshot with a later one of the same root
+    ///
+    /// Actions are matched by [`Action::key`]. An action missing from one side
+    /// counts as unvisited there. The result lists actions by visits gained,
+    /// most first.
+    pub fn diff(&self, later: &RootSnapshot<A>) -> SnapshotDiff<A> {
+        let find = |snapshot: &RootSnapshot<A>, action: &A| {
+            snapshot
+                .actions
+                .iter()
+                .find(|stats| stats.action.key() == action.key())
+                .map_or((0, 0.0), |stats| (stats.visits, stats.value))
+        };
+
+        let mut actions: Vec<ActionDelta<A>> = later
+            .actions
+            .iter()
+            .map(|after| {
+                let (visits_before, value_before) = find(self, &after.action);
+                ActionDelta {
+                    action: after.action.clone(),
+                    visits_before,
+                    visits_after: after.visits,
+                    value_before,
+                    value_after: after.value,
+                }
+            })
+            .collect();
+        for before in &self.actions {
+            if actions
+                .iter()
+                .all(|delta| delta.action.key() != before.action.key())
+            {
+                actions.push(ActionDelta {
+                    action: before.action.clone(),
+                    visits_before: before.visits,
+                    visits_after: 0,
+                    value_before: before.value,
+                    value_after: 0.0,
+                });
+            }
+        }
+        actions.sort_by_key(|delta| std::cmp::Reverse(delta.visits_gained()));
+
+        SnapshotDiff {
+            root_visits_gained: later.root_visits.saturating_sub(self.root_visits),
+            actions,
+            best_before: self.best_action.clone(),
+            best_after: later.best_action.clone(),
+        }
+    }
+}
+
+/// Change of one root action between two snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionDelta<A> {
+    /// The root action
+    pub action: A,
+
+    /// Visits in the earlier snapshot
+    pub visits_before: u64,
+
+    /// Visits in the later snapshot
+    pub visits_after: u64,
+
+    /// Mean value in the earlier snapshot
+    pub value_before: f64,
+
+    /// Mean value in the later snapshot
+    pub value_after: f64,
+}
+
+impl<A> ActionDelta<A> {
+    /// Returns the number of visits gained, or 0 if the action lost visits
+    pub fn visits_gained(&self) -> u64 {
+        self.visits_after.saturating_sub(self.visits_before)
+    }
+
+    /// Returns the change in mean value
+    pub fn value_change(&self) -> f64 {
+        self.value_after - self.value_before
+    }
+}
+
+/// Difference between two [`RootSnapshot`]s
+#[derive(Debug, Clone)]
+pub struct SnapshotDiff<A> {
+    /// Visits the root gained
+    pub root_visits_gained: u64,
+
+    /// Change of every root action, most visits gained first
+    pub actions: Vec<ActionDelta<A>>,
+
+    /// Best action in the earlier snapshot
+    pub best_before: Option<A>,
+
+    /// Best action in the later snapshot
+    pub best_after: Option<A>,
+}
+
+impl<A: Action> SnapshotDiff<A> {
+    /// Returns true if the best action changed between the snapshots
+    ///
+    /// A flip late in a search is a sign of instability; time management can
+    /// use it to keep searching.
+    pub fn best_action_flipped(&self) -> bool {
+        match (&self.best_before, &self.best_after) {
+            (Some(before), Some(after)) => before.key() != after.key(),
+            (before, after) => before.is_some() != after.is_some(),
+        }
+    }
+
+    /// Returns the share of the new root visits that went to `action`
+    pub fn visit_share(&self, action: &A) -> f64 {
+        if self.root_visits_gained == 0 {
+            return 0.0;
+        }
+        self.actions
+            .iter()
+            .find(|delta| delta.action.key() == action.key())
+            .map_or(0.0, |delta| {
+                delta.visits_gained() as f64 / self.root_visits_gained as f64
+            })
+    }
+
+    /// Returns a human-readable report of the changes
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Root Changes:\n\
+             - Root visits gained: {}\n\
+             - Best action: {:?} -> {:?}{}",
+            self.root_visits_gained,
+            self.best_before,
+            self.best_after,
+            if self.best_action_flipped() {
+                " (flipped)"
+            } else {
+                ""
+            }
+        );
+        for delta in &self.actions {
+            summary.push_str(&format!(
+                "\n - {:?}: +{} visits ({} -> {}), value {:.3} -> {:.3}",
+                delta.action,
+                delta.visits_gained(),
+                delta.visits_before,
+                delta.visits_after,
+                delta.value_before,
+                delta.value_after
+            ));
+        }
+        summary
+    }
+}
+
 /// Statistics collected during an MCTS search
 #[derive(Debug, Clone)]
 pub struct SearchStatistics {
@@ -23,10 +348,153 @@ pub struct SearchStatistics {
     /// Whether the search was stopped early due to time constraints
     pub stopped_early: bool,
 
+    /// Why the search stopped
+    pub outcome: SearchOutcome,
+
+    /// The iteration and time limits the search ran with
+    pub budget: Budget,
+
+    /// Whether every reachable state was expanded down to terminal states
+    pub tree_exhausted: bool,
+
+    /// Number of expansions merged into an existing node (DAG mode only)
+    pub transposition_hits: usize,
+
+    /// Number of expanded states whose exact value an oracle knew
+    pub oracle_hits: usize,
+
+    /// Number of playouts spent by the root pre-search
+    pub pre_search_playouts: usize,
+
+    /// Number of rollout batches run
+    ///
+    /// Zero unless [`MCTSConfig::rollout_batch_size`](crate::MCTSConfig::rollout_batch_size)
+    /// is above 1.
+    pub rollout_batches: usize,
+
+    /// Number of times a batch selection reached a leaf already in the batch
+    ///
+    /// Each collision ends its batch early. Many collisions mean the
+    /// [virtual loss](crate::config::VirtualLoss) is too weak to spread a
+    /// batch over the tree, or the tree is nearly exhausted.
+    pub batch_collisions: usize,
+
+    /// Whether the search stopped growing the tree at the memory limit
+    ///
+    /// See [`MCTSConfig::max_memory_bytes`](crate::MCTSConfig::max_memory_bytes).
+    /// While at the limit, iterations keep selecting and simulating from the
+    /// existing leaves, but no node is added until a
+    /// [`SubtreeGc`](crate::config::SubtreeGc) makes room.
+    pub memory_limited: bool,
+
+    /// Number of nodes removed by the [`SubtreeGc`](crate::config::SubtreeGc)
+    pub pruned_nodes: usize,
+
+    /// Number of simulation results clipped to the configured range
+    ///
+    /// See [`MCTSConfig::reward_clip`](crate::MCTSConfig::reward_clip).
+    pub clipped_rewards: usize,
+
+    /// Number of NaN or infinite simulation results dropped instead of
+    /// backpropagated
+    ///
+    /// Each still counts as a visit without reward on its path.
+    ///
+    /// A non-zero count points at a bug in `get_result`, a playout policy or
+    /// a reward transform.
+    pub dropped_rewards: usize,
+
+    /// Number of node updates in which a reward accumulator saturated
+    ///
+    /// A saturated total stops growing instead of wrapping around, so the
+    /// node's value drifts low from then on. See
+    /// [`MAX_ACCUMULATED_REWARD`](crate::tree::MAX_ACCUMULATED_REWARD) for
+    /// how many visits a reward range allows; clipping rewards with
+    /// [`with_reward_clipping`](crate::MCTSConfig::with_reward_clipping)
+    /// keeps them in it.
+    pub saturated_updates: usize,
+
+    /// Number of legal actions dropped because their id was listed before
+    ///
+    /// Only counted with
+    /// [`MCTSConfig::deduplicate_actions`](crate::MCTSConfig::deduplicate_actions).
+    pub duplicate_actions: usize,
+
+    /// Number of root actions dropped for having a low prior
+    ///
+    /// Only counted with
+    /// [`MCTSConfig::root_prior_filter`](crate::MCTSConfig::root_prior_filter).
+    pub filtered_root_actions: usize,
+
+    /// Time spent in each phase of the search
+    pub phase_times: PhaseTimes,
+
+    /// Smallest and largest raw simulation results seen, before any transform
+    ///
+    /// Only tracked when a [`RewardTransform`](crate::config::RewardTransform)
+    /// other than `Identity` is configured. Unlike the other statistics this
+    /// carries over between searches on the same tree.
+    pub reward_range: Option<(f64, f64)>,
+
     /// Node pool metrics (if node pool is used)
     pub node_pool_stats: Option<NodePoolStats>,
+
+    /// Terminal result cache metrics (if the cache is enabled)
+    pub result_cache_stats: Option<ResultCacheStats>,
 }
 
+/// Time spent in each of the four MCTS phases
+#[derive(Debug, Clone, Default)]
+pub struct PhaseTimes {
+    /// Time spent descending the tree
+    pub selection: Duration,
+
+    /// Time spent creating new nodes
+    pub expansion: Duration,
+
+    /// Time spent in playouts
+    pub simulation: Duration,
+
+    /// Time spent updating node statistics
+    pub backpropagation: Duration,
+}
+
+/// Column names matching [`SearchStatistics::to_csv_row`]
+const CSV_COLUMNS: [&str; 32] = [
+    "iterations",
+    "total_time_s",
+    "tree_size",
+    "max_depth",
+    "iterations_per_second",
+    "outcome",
+    "stopped_early",
+    "tree_exhausted",
+    "transposition_hits",
+    "oracle_hits",
+    "pre_search_playouts",
+    "rollout_batches",
+    "batch_collisions",
+    "memory_limited",
+    "pruned_nodes",
+    "clipped_rewards",
+    "dropped_rewards",
+    "saturated_updates",
+    "duplicate_actions",
+    "filtered_root_actions",
+    "selection_s",
+    "expansion_s",
+    "simulation_s",
+    "backpropagation_s",
+    "pool_capacity",
+    "pool_available",
+    "pool_total_allocated",
+    "pool_total_returned",
+    "pool_retained_bytes",
+    "cache_entries",
+    "cache_hits",
+    "cache_misses",
+];
+
 /// Statistics about the node pool
 #[derive(Debug, Clone)]
 pub struct NodePoolStats {
@@ -41,6 +509,41 @@ pub struct NodePoolStats {
 
     /// Total nodes returned to the pool
     pub total_returned: usize,
+
+    /// Estimated memory held by free nodes, in bytes
+    ///
+    /// See [`NodePool::retained_bytes`](crate::tree::NodePool::retained_bytes).
+    pub retained_bytes: usize,
+}
+
+/// Statistics about the terminal result cache
+///
+/// See [`MCTSConfig::with_result_cache`](crate::MCTSConfig::with_result_cache).
+#[derive(Debug, Clone, Default)]
+pub struct ResultCacheStats {
+    /// Largest number of states the cache holds
+    pub capacity: usize,
+
+    /// Number of states cached at the end of the search
+    pub entries: usize,
+
+    /// Terminal leaves scored from the cache during this search
+    pub hits: usize,
+
+    /// Terminal leaves scored with `get_result` during this search
+    pub misses: usize,
+}
+
+impl ResultCacheStats {
+    /// Returns the share of lookups answered by the cache, 0 without lookups
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
 }
 
 impl SearchStatistics {
@@ -52,11 +555,32 @@ impl SearchStatistics {
             tree_size: 1, // Start with root node
             max_depth: 0,
             stopped_early: false,
+            outcome: SearchOutcome::Completed,
+            budget: Budget::Unlimited,
+            tree_exhausted: false,
+            transposition_hits: 0,
+            oracle_hits: 0,
+            pre_search_playouts: 0,
+            rollout_batches: 0,
+            batch_collisions: 0,
+            memory_limited: false,
+            pruned_nodes: 0,
+            clipped_rewards: 0,
+            dropped_rewards: 0,
+            saturated_updates: 0,
+            duplicate_actions: 0,
+            filtered_root_actions: 0,
+            phase_times: PhaseTimes::default(),
+            reward_range: None,
             node_pool_stats: None,
+            result_cache_stats: None,
         }
     }
 
     /// Update node pool statistics
+    ///
+    /// Leaves [`NodePoolStats::retained_bytes`] at zero; the search fills it in
+    /// from [`NodePool::search_stats`](crate::tree::NodePool::search_stats).
     pub fn update_node_pool_stats(
         &mut self,
         capacity: usize,
@@ -69,6 +593,15 @@ impl SearchStatistics {
             available,
             total_allocated: allocated,
             total_returned: returned,
+            retained_bytes: 0,
+        });
+    }
+
+    /// Widens [`reward_range`](Self::reward_range) to include a raw result
+    pub fn observe_reward(&mut self, result: f64) {
+        self.reward_range = Some(match self.reward_range {
+            Some((min, max)) => (min.min(result), max.max(result)),
+            None => (result, result),
         });
     }
 
@@ -88,6 +621,96 @@ impl SearchStatistics {
         self.iterations as f64 / self.total_time.as_secs_f64()
     }
 
+    /// Returns the CSV header line matching [`to_csv_row`](Self::to_csv_row)
+    ///
+    /// Write this once, then append one row per search to build a file that
+    /// loads directly into pandas, R, or a spreadsheet.
+    pub fn csv_header() -> String {
+        CSV_COLUMNS.join(",")
+    }
+
+    /// Returns the statistics as one CSV row, without a trailing newline
+    ///
+    /// Times are in seconds. Node pool columns are empty when no pool is used,
+    /// and result cache columns when no cache is used.
+    pub fn to_csv_row(&self) -> String {
+        self.csv_fields().join(",")
+    }
+
+    /// Returns the TSV header line matching [`to_tsv_row`](Self::to_tsv_row)
+    pub fn tsv_header() -> String {
+        CSV_COLUMNS.join("\t")
+    }
+
+    /// Returns the statistics as one tab-separated row
+    pub fn to_tsv_row(&self) -> String {
+        self.csv_fields().join("\t")
+    }
+
+    /// Formats each column of a CSV row; none of the values contain separators
+    fn csv_fields(&self) -> Vec<String> {
+        let pool = |field: fn(&NodePoolStats) -> usize| {
+            self.node_pool_stats
+                .as_ref()
+                .map(|stats| field(stats).to_string())
+                .unwrap_or_default()
+        };
+
+        let cache = |field: fn(&ResultCacheStats) -> usize| {
+            self.result_cache_stats
+                .as_ref()
+                .map(|stats| field(stats).to_string())
+                .unwrap_or_default()
+        };
+
+        vec![
+            self.iterations.to_string(),
+            self.total_time.as_secs_f64().to_string(),
+            self.tree_size.to_string(),
+            self.max_depth.to_string(),
+            format!("{:.1}", self.iterations_per_second()),
+            format!("{:?}", self.outcome),
+            self.stopped_early.to_string(),
+            self.tree_exhausted.to_string(),
+            self.transposition_hits.to_string(),
+            self.oracle_hits.to_string(),
+            self.pre_search_playouts.to_string(),
+            self.rollout_batches.to_string(),
+            self.batch_collisions.to_string(),
+            self.memory_limited.to_string(),
+            self.pruned_nodes.to_string(),
+            self.clipped_rewards.to_string(),
+            self.dropped_rewards.to_string(),
+            self.saturated_updates.to_string(),
+            self.duplicate_actions.to_string(),
+            self.filtered_root_actions.to_string(),
+            self.phase_times.selection.as_secs_f64().to_string(),
+            self.phase_times.expansion.as_secs_f64().to_string(),
+            self.phase_times.simulation.as_secs_f64().to_string(),
+            self.phase_times.backpropagation.as_secs_f64().to_string(),
+            pool(|stats| stats.capacity),
+            pool(|stats| stats.available),
+            pool(|stats| stats.total_allocated),
+            pool(|stats| stats.total_returned),
+            pool(|stats| stats.retained_bytes),
+            cache(|stats| stats.entries),
+            cache(|stats| stats.hits),
+            cache(|stats| stats.misses),
+        ]
+    }
+
+    /// Records the totals of a finished search through the `metrics` facade
+    ///
+    /// Called by the search itself; see [`metric_names`] for what is recorded.
+    #[cfg(feature = "metrics")]
+    pub fn record_metrics(&self) {
+        let outcome = format!("{:?}", self.outcome).to_lowercase();
+        metrics::counter!(metric_names::SEARCHES, "outcome" => outcome).increment(1);
+        metrics::counter!(metric_names::ITERATIONS).increment(self.iterations as u64);
+        metrics::counter!(metric_names::NODES_ALLOCATED).increment(self.tree_size as u64);
+        metrics::histogram!(metric_names::SEARCH_DURATION).record(self.total_time.as_secs_f64());
+    }
+
     /// Returns a summary of the statistics as a string
     pub fn summary(&self) -> String {
         let mut summary = format!(
@@ -98,16 +721,69 @@ impl SearchStatistics {
              - Max depth: {}\n\
              - Avg time per iteration: {:.3} µs\n\
              - Iterations per second: {:.1}\n\
-             - Stopped early: {}",
+             - Stopped early: {}\n\
+             - Outcome: {:?}\n\
+             - Budget: {:?}\n\
+             - Tree exhausted: {}",
             self.iterations,
             self.total_time.as_secs_f64(),
             self.tree_size,
             self.max_depth,
             self.avg_time_per_iteration_us(),
             self.iterations_per_second(),
-            self.stopped_early
+            self.stopped_early,
+            self.outcome,
+            self.budget,
+            self.tree_exhausted
         );
 
+        if self.rollout_batches > 0 {
+            summary.push_str(&format!(
+                "\n - Rollout batches: {} ({} collisions)",
+                self.rollout_batches, self.batch_collisions
+            ));
+        }
+
+        if self.memory_limited {
+            summary.push_str("\n - Memory limited: expansion paused at the memory limit");
+        }
+
+        if self.pruned_nodes > 0 {
+            summary.push_str(&format!("\n - Pruned nodes: {}", self.pruned_nodes));
+        }
+
+        if self.clipped_rewards > 0 || self.dropped_rewards > 0 {
+            summary.push_str(&format!(
+                "\n - Rewards clipped: {}, dropped as NaN or infinite: {}",
+                self.clipped_rewards, self.dropped_rewards
+            ));
+        }
+
+        if self.saturated_updates > 0 {
+            summary.push_str(&format!(
+                "\n - Saturated node updates: {}",
+                self.saturated_updates
+            ));
+        }
+
+        if self.duplicate_actions > 0 {
+            summary.push_str(&format!(
+                "\n - Duplicate actions dropped: {}",
+                self.duplicate_actions
+            ));
+        }
+
+        if self.filtered_root_actions > 0 {
+            summary.push_str(&format!(
+                "\n - Root actions filtered by prior: {}",
+                self.filtered_root_actions
+            ));
+        }
+
+        if let Some((min, max)) = self.reward_range {
+            summary.push_str(&format!("\n - Reward range: [{:.3}, {:.3}]", min, max));
+        }
+
         // Add node pool stats if available
         if let Some(pool_stats) = &self.node_pool_stats {
             summary.push_str(&format!(
@@ -116,11 +792,13 @@ impl SearchStatistics {
                  - Available nodes: {}\n\
                  - Total allocated: {}\n\
                  - Total returned: {}\n\
+                 - Retained: {} bytes\n\
                  - Reuse ratio: {:.2}%",
                 pool_stats.capacity,
                 pool_stats.available,
                 pool_stats.total_allocated,
                 pool_stats.total_returned,
+                pool_stats.retained_bytes,
                 if pool_stats.total_allocated > 0 {
                     (pool_stats.total_returned as f64 / pool_stats.total_allocated as f64) * 100.0
                 } else {
@@ -129,6 +807,21 @@ impl SearchStatistics {
             ));
         }
 
+        if let Some(cache_stats) = &self.result_cache_stats {
+            summary.push_str(&format!(
+                "\n\nResult Cache Statistics:\n\
+                 - Entries: {} of {}\n\
+                 - Hits: {}\n\
+                 - Misses: {}\n\
+                 - Hit rate: {:.2}%",
+                cache_stats.entries,
+                cache_stats.capacity,
+                cache_stats.hits,
+                cache_stats.misses,
+                cache_stats.hit_rate() * 100.0
+            ));
+        }
+
         summary
     }
 }
@@ -138,3 +831,183 @@ impl Default for SearchStatistics {
         Self::new()
     }
 }
+
+/// Appends one JSON object per search to a writer, one object per line
+///
+/// Each line records the search's sequence number, the configuration
+/// [fingerprint](crate::MCTSConfig::fingerprint), the best action, the most
+/// visited root actions and the search statistics, e.g.
+///
+/// ```text
+/// {"search":0,"config":"9c1f...","best_action":"Move(4)","root_visits":1000,
+///  "top_actions":[{"action":"Move(4)","visits":412,"value":0.61,"share":0.412},...],
+///  "statistics":{"iterations":1000,"total_time":0.012,...}}
+/// ```
+///
+/// Actions are written with their `Debug` representation.
+///
+/// # Example
+///
+/// ```no_run
+/// # use arboriter_mcts::{GameState, Action, Player, MCTS, MCTSConfig};
+/// # use arboriter_mcts::stats::SearchLogger;
+/// # #[derive(Debug, Clone, PartialEq)]
+/// # struct A(usize);
+/// # impl Action for A { fn id(&self) -> usize { self.0 } }
+/// # #[derive(Debug, Clone, PartialEq)]
+/// # struct P;
+/// # impl Player for P {}
+/// # #[derive(Clone)]
+/// # struct G(usize);
+/// # impl GameState for G {
+/// #     type Action = A;
+/// #     type Player = P;
+/// #     fn get_legal_actions(&self) -> Vec<A> { if self.0 < 3 { vec![A(0), A(1)] } else { vec![] } }
+/// #     fn apply_action(&self, _: &A) -> Self { G(self.0 + 1) }
+/// #     fn is_terminal(&self) -> bool { self.0 >= 3 }
+/// #     fn get_result(&self, _: &P) -> f64 { 0.5 }
+/// #     fn get_current_player(&self) -> P { P }
+/// # }
+/// let mut logger = SearchLogger::append_to("searches.jsonl", 5)?;
+/// let mut mcts = MCTS::new(G(0), MCTSConfig::default());
+/// mcts.search().unwrap();
+/// logger.log(&mcts)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct SearchLogger<W: Write> {
+    writer: W,
+    top_k: usize,
+    searches: usize,
+}
+
+impl<W: Write> SearchLogger<W> {
+    /// Creates a logger writing to `writer`, listing up to `top_k` root actions per search
+    pub fn new(writer: W, top_k: usize) -> Self {
+        SearchLogger {
+            writer,
+            top_k,
+            searches: 0,
+        }
+    }
+
+    /// Returns the number of searches logged so far
+    pub fn searches(&self) -> usize {
+        self.searches
+    }
+
+    /// Writes one line describing the last search of `mcts` and flushes it
+    pub fn log<S: GameState + 'static>(&mut self, mcts: &MCTS<S>) -> io::Result<()> {
+        let line = search_json(mcts, self.searches, self.top_k);
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()?;
+        self.searches += 1;
+        Ok(())
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl SearchLogger<BufWriter<File>> {
+    /// Creates a logger appending to the file at `path`, creating it if needed
+    pub fn append_to<P: AsRef<Path>>(path: P, top_k: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(BufWriter::new(file), top_k))
+    }
+}
+
+/// Formats the JSON object for one search
+fn search_json<S: GameState + 'static>(mcts: &MCTS<S>, search: usize, top_k: usize) -> String {
+    let snapshot = mcts.root_snapshot();
+
+    // Ranked like best_action, so visit ties follow the configured tie-break
+    let top_actions: Vec<String> = mcts
+        .ranked_children(BestChildCriteria::MostVisits)
+        .into_iter()
+        .filter_map(|(_, child)| Some((child.action.as_ref()?, child)))
+        .take(top_k)
+        .map(|(action, child)| {
+            let share = if snapshot.root_visits > 0 {
+                child.visits() as f64 / snapshot.root_visits as f64
+            } else {
+                0.0
+            };
+            format!(
+                "{{\"action\":{},\"visits\":{},\"value\":{},\"share\":{}}}",
+                json_string(&format!("{:?}", action)),
+                child.visits(),
+                json_number(child.value()),
+                json_number(share)
+            )
+        })
+        .collect();
+
+    let best_action = match &snapshot.best_action {
+        Some(action) => json_string(&format!("{:?}", action)),
+        None => "null".to_string(),
+    };
+
+    let stats = mcts.get_statistics();
+    let statistics = format!(
+        "{{\"iterations\":{},\"total_time\":{},\"tree_size\":{},\"max_depth\":{},\
+         \"iterations_per_second\":{},\"outcome\":{},\"stopped_early\":{},\
+         \"tree_exhausted\":{},\"transposition_hits\":{},\"oracle_hits\":{},\"pre_search_playouts\":{},\"cache_hit_rate\":{}}}",
+        stats.iterations,
+        json_number(stats.total_time.as_secs_f64()),
+        stats.tree_size,
+        stats.max_depth,
+        json_number(stats.iterations_per_second()),
+        json_string(&format!("{:?}", stats.outcome)),
+        stats.stopped_early,
+        stats.tree_exhausted,
+        stats.transposition_hits,
+        stats.oracle_hits,
+        stats.pre_search_playouts,
+        match &stats.result_cache_stats {
+            Some(cache) => json_number(cache.hit_rate()),
+            None => "null".to_string(),
+        }
+    );
+
+    format!(
+        "{{\"search\":{},\"config\":\"{:016x}\",\"best_action\":{},\"root_visits\":{},\
+         \"top_actions\":[{}],\"statistics\":{}}}",
+        search,
+        mcts.config().fingerprint(),
+        best_action,
+        snapshot.root_visits,
+        top_actions.join(","),
+        statistics
+    )
+}
+
+/// Quotes and escapes a string for JSON
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Formats a number for JSON, which has no infinities or NaN
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
@@ -1,8 +1,15 @@
 use rand::prelude::IteratorRandom;
+use std::collections::HashSet;
 use std::fmt;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
-use crate::game_state::GameState;
+use crate::config::{RewardPerspective, ValueEstimate};
+use crate::game_state::{Action, ActionKey, GameState};
+use crate::utils::search_rng;
+
+pub use storage::{AtomicReward, AtomicVisits, MAX_ACCUMULATED_REWARD};
 
 /// Represents a node in the MCTS tree
 ///
@@ -18,24 +25,102 @@ pub struct MCTSNode<S: GameState> {
 
     /// Number of times this node has been visited
     /// Uses atomic operations to support potential future parallelization
-    pub visits: AtomicU64,
+    pub visits: AtomicVisits,
 
     /// Total reward accumulated from simulations through this node
     /// Uses atomic operations and fixed-point representation internally
-    pub total_reward: AtomicU64,
+    pub total_reward: AtomicReward,
 
     /// Sum of squared rewards (for variance calculation in UCB1-Tuned)
-    pub sum_squared_reward: AtomicU64,
+    pub sum_squared_reward: AtomicReward,
 
     /// Number of RAVE visits (AMAF)
-    pub rave_visits: AtomicU64,
+    pub rave_visits: AtomicVisits,
 
     /// Total RAVE reward
-    pub rave_reward: AtomicU64,
+    pub rave_reward: AtomicReward,
+
+    /// Total of win/draw/loss outcomes (1, 0.5, 0) of results through this node
+    ///
+    /// Kept alongside [`total_reward`](Self::total_reward) so a node's win rate
+    /// and its expected score can be told apart; see [`outcome_of`].
+    pub outcome_total: AtomicReward,
+
+    /// Posterior mean of the node's value under a Gaussian model
+    pub posterior_mean: AtomicReward,
+
+    /// Posterior precision (inverse variance) of the node's value
+    ///
+    /// Zero until a Bayesian backpropagation policy has updated the node.
+    pub posterior_precision: AtomicReward,
+
+    /// Smallest value of any of this node's children, as `f64` bits
+    ///
+    /// Only maintained while [`MCTSConfig::normalize_values`](crate::MCTSConfig::normalize_values)
+    /// is enabled; positive infinity until then. See [`value_bounds`](Self::value_bounds).
+    pub value_min: AtomicU64,
+
+    /// Largest value of any of this node's children, as `f64` bits
+    ///
+    /// Negative infinity until the search records a value.
+    pub value_max: AtomicU64,
 
     /// Prior probability for this node (P(s,a))
     /// Used by PUCT policy. Defaults to 1.0 if not set.
-    pub prior: AtomicU64,
+    pub prior: AtomicReward,
+
+    /// Cached `(visits, value)` snapshot used by selection policies
+    ///
+    /// Only populated when [`MCTSConfig::cache_selection_stats`](crate::MCTSConfig::cache_selection_stats)
+    /// is enabled, in which case the search refreshes it after every
+    /// backpropagation. `None` means selection reads the atomics directly.
+    pub cached_stats: Option<(u64, f64)>,
+
+    /// Whether every node below this one has been expanded down to terminal states
+    ///
+    /// Maintained by the search during backpropagation. Once the root is
+    /// exhausted, further iterations cannot add new information.
+    pub exhausted: bool,
+
+    /// Exact value of this node's state, if an oracle knew it
+    ///
+    /// Set by the search when an [`Oracle`](crate::policy::oracle::Oracle)
+    /// is attached, or to the [dead-end reward](crate::MCTSConfig::dead_end_reward)
+    /// for a non-terminal state without legal actions. The value is the
+    /// result for the search's perspective player, like a playout result.
+    /// Solved nodes are never expanded, and the value is backpropagated in
+    /// place of a playout.
+    pub solved: Option<f64>,
+
+    /// Whether this node's state repeats one of its ancestors'
+    ///
+    /// Set by the search when cycle detection is enabled. Such nodes are never
+    /// expanded and are scored with the configured cycle reward.
+    pub closes_cycle: bool,
+
+    /// Canonical node this node merges into when searching in DAG mode
+    ///
+    /// Set when expansion reaches a state that already has a node elsewhere in
+    /// the tree. Such link nodes keep their own edge statistics but never get
+    /// children; selection continues from the canonical node instead.
+    pub transposition: Option<NodePath>,
+
+    /// Whether this node is an afterstate waiting for a chance event
+    ///
+    /// Chance nodes have no actions; their children are the outcomes listed
+    /// by [`GameState::chance_outcomes`], each with its normalized
+    /// probability stored as the child's prior. Selection samples a child by
+    /// probability instead of asking the selection policy.
+    pub chance: bool,
+
+    /// Whether this chance node draws its outcomes one at a time
+    ///
+    /// Set for chance events that [`GameState::sample_chance_outcome`] can
+    /// draw from but [`GameState::chance_outcomes`] does not list. Such
+    /// nodes start without children and gain one each time the search draws
+    /// an outcome it has not seen; revisits pick an existing outcome in
+    /// proportion to its visits.
+    pub sampled_outcomes: bool,
 
     /// Children nodes representing states reachable from this one
     pub children: Vec<MCTSNode<S>>,
@@ -50,22 +135,265 @@ pub struct MCTSNode<S: GameState> {
     /// Player who made the move to reach this state
     /// For the root node, this is the starting player
     pub player: S::Player,
+
+    /// Number identifying this use of the node
+    ///
+    /// Every node gets a fresh generation when it is created or handed out
+    /// again by a [`NodePool`], so no two nodes alive at once share one. A
+    /// [`NodeHandle`] remembers it to tell a recycled node from the one it
+    /// was taken from.
+    pub generation: u64,
+
+    /// When a search last selected through this node, in milliseconds on
+    /// the crate's visit clock, or zero if it never has
+    ///
+    /// Only kept up to date while a [`SubtreeGc`](crate::config::SubtreeGc)
+    /// is configured; see [`idle_time`](Self::idle_time).
+    pub last_visited: AtomicU64,
+
+    /// Whether a reward accumulator saturated since the search last checked
+    ///
+    /// Set instead of letting a total wrap around when it would exceed
+    /// [`MAX_ACCUMULATED_REWARD`]; the search clears it and counts the
+    /// update in [`SearchStatistics::saturated_updates`](crate::SearchStatistics::saturated_updates).
+    pub saturated: AtomicBool,
 }
 
-/// Internal representation of a fixed-point value for rewards
-/// This allows atomic operations on floating point rewards
-const REWARD_SCALE: f64 = 1_000_000.0;
+/// Source of node generations; zero is never handed out
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Returns a generation no other node has had
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Start of the clock node visit times are measured on
+static VISIT_CLOCK: OnceLock<Instant> = OnceLock::new();
+
+/// Returns the time on the visit clock in milliseconds, never zero
+pub(crate) fn visit_clock_millis() -> u64 {
+    let start = VISIT_CLOCK.get_or_init(Instant::now);
+    start.elapsed().as_millis() as u64 + 1
+}
+
+/// Classifies a result as a win (1.0), draw (0.5) or loss (0.0)
+///
+/// Results above 0.5 are wins and results below are losses, so a game whose
+/// [`get_result`](GameState::get_result) encodes the margin, e.g.
+/// `0.5 + margin / (2 * max_margin)`, gets both an expected score and a win
+/// rate out of one number.
+pub fn outcome_of(result: f64) -> f64 {
+    const DRAW_TOLERANCE: f64 = 1e-9;
+    if result > 0.5 + DRAW_TOLERANCE {
+        1.0
+    } else if result < 0.5 - DRAW_TOLERANCE {
+        0.0
+    } else {
+        0.5
+    }
+}
+
+/// Atomic storage for node statistics
+///
+/// By default visit counts are `u64` and rewards are stored as fixed-point
+/// `u64` values with six decimal places. With the `compact-stats` feature
+/// visit counts are `u32` and rewards are stored as `f32` bit patterns,
+/// roughly halving the size of the statistics in each node. With
+/// `f64-stats` rewards are stored as `f64` bit patterns instead, keeping
+/// reward differences below 1e-6 and negative rewards; `compact-stats` wins
+/// if both are enabled. The accessor API on [`MCTSNode`] is `f64`/`u64`
+/// either way; conversions happen here.
+#[cfg(not(any(feature = "compact-stats", feature = "f64-stats")))]
+mod storage {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Atomic type used for visit counters
+    pub type AtomicVisits = AtomicU64;
+
+    /// Atomic type used for rewards and priors
+    pub type AtomicReward = AtomicU64;
+
+    /// Internal representation of a fixed-point value for rewards
+    /// This allows atomic operations on floating point rewards
+    const REWARD_SCALE: f64 = 1_000_000.0;
+
+    /// Largest encoded value of a single reward
+    const MAX_ENCODED: u64 = u64::MAX / 2;
+
+    /// Largest total a reward accumulator holds before it saturates
+    ///
+    /// About 1.8e13. Squared rewards are accumulated as well, so with
+    /// rewards up to `r` a node saturates after roughly
+    /// `MAX_ACCUMULATED_REWARD / r²` visits: never in practice for rewards
+    /// in `[0, 1]`, but after about 1.8e5 visits for rewards near 1e4.
+    pub const MAX_ACCUMULATED_REWARD: f64 = u64::MAX as f64 / REWARD_SCALE;
+
+    /// Safely convert a floating point reward to a scaled integer
+    pub(super) fn encode_reward(value: f64) -> u64 {
+        ((value * REWARD_SCALE).max(0.0) as u64).min(MAX_ENCODED)
+    }
+
+    /// Safely convert a scaled integer back to a floating point reward
+    pub(super) fn decode_reward(value: u64) -> f64 {
+        value as f64 / REWARD_SCALE
+    }
+
+    /// Atomically adds a reward to an accumulator, saturating at `u64::MAX`
+    ///
+    /// Returns true if the reward or the new total did not fit.
+    pub(super) fn add_reward(cell: &AtomicReward, value: f64) -> bool {
+        let encoded = encode_reward(value);
+        let mut saturated = encoded == MAX_ENCODED;
+        let _ = cell.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |total| {
+            let (sum, overflowed) = total.overflowing_add(encoded);
+            saturated |= overflowed;
+            Some(if overflowed { u64::MAX } else { sum })
+        });
+        saturated
+    }
+
+    /// Loads a visit counter
+    pub(super) fn load_visits(cell: &AtomicVisits) -> u64 {
+        cell.load(Ordering::Relaxed)
+    }
+
+    /// Atomically adds to a visit counter
+    pub(super) fn add_visits(cell: &AtomicVisits, count: u64) {
+        cell.fetch_add(count, Ordering::Relaxed);
+    }
 
-/// Safely convert a floating point reward to a scaled integer
-fn float_to_scaled_u64(value: f64) -> u64 {
-    ((value * REWARD_SCALE).max(0.0) as u64).min(u64::MAX / 2)
+    /// Atomically subtracts from a visit counter, stopping at zero
+    pub(super) fn remove_visits(cell: &AtomicVisits, count: u64) {
+        let _ = cell.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |visits| {
+            Some(visits.saturating_sub(count))
+        });
+    }
 }
 
-/// Safely convert a scaled integer back to a floating point reward
-fn scaled_u64_to_float(value: u64) -> f64 {
-    value as f64 / REWARD_SCALE
+#[cfg(all(feature = "f64-stats", not(feature = "compact-stats")))]
+mod storage {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Atomic type used for visit counters
+    pub type AtomicVisits = AtomicU64;
+
+    /// Atomic type used for rewards and priors
+    pub type AtomicReward = AtomicU64;
+
+    /// Largest total a reward accumulator holds before it saturates
+    pub const MAX_ACCUMULATED_REWARD: f64 = f64::MAX;
+
+    /// Convert a floating point reward to its bit pattern
+    pub(super) fn encode_reward(value: f64) -> u64 {
+        value.to_bits()
+    }
+
+    /// Convert a bit pattern back to a floating point reward
+    pub(super) fn decode_reward(value: u64) -> f64 {
+        f64::from_bits(value)
+    }
+
+    /// Atomically adds a reward to an accumulator, saturating at `f64::MAX`
+    ///
+    /// Returns true if the new total did not fit.
+    pub(super) fn add_reward(cell: &AtomicReward, value: f64) -> bool {
+        let mut saturated = false;
+        let _ = cell.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+            let sum = f64::from_bits(bits) + value;
+            saturated = sum.is_infinite();
+            Some(sum.clamp(f64::MIN, f64::MAX).to_bits())
+        });
+        saturated
+    }
+
+    /// Loads a visit counter
+    pub(super) fn load_visits(cell: &AtomicVisits) -> u64 {
+        cell.load(Ordering::Relaxed)
+    }
+
+    /// Atomically adds to a visit counter
+    pub(super) fn add_visits(cell: &AtomicVisits, count: u64) {
+        cell.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Atomically subtracts from a visit counter, stopping at zero
+    pub(super) fn remove_visits(cell: &AtomicVisits, count: u64) {
+        let _ = cell.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |visits| {
+            Some(visits.saturating_sub(count))
+        });
+    }
 }
 
+#[cfg(feature = "compact-stats")]
+mod storage {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Atomic type used for visit counters
+    pub type AtomicVisits = AtomicU32;
+
+    /// Atomic type used for rewards and priors
+    pub type AtomicReward = AtomicU32;
+
+    /// Largest total a reward accumulator holds before it saturates
+    ///
+    /// `f32` accumulators run out of precision long before they overflow:
+    /// once a total passes about 1.7e7, adding a reward below 1 no longer
+    /// changes it, so values drift low in searches of tens of millions of
+    /// iterations.
+    pub const MAX_ACCUMULATED_REWARD: f64 = f32::MAX as f64;
+
+    /// Narrows a reward to `f32`, saturating instead of overflowing to infinity
+    fn saturate(value: f64) -> f32 {
+        (value as f32).clamp(f32::MIN, f32::MAX)
+    }
+
+    /// Convert a floating point reward to its `f32` bit pattern
+    pub(super) fn encode_reward(value: f64) -> u32 {
+        saturate(value).to_bits()
+    }
+
+    /// Convert an `f32` bit pattern back to a floating point reward
+    pub(super) fn decode_reward(value: u32) -> f64 {
+        f32::from_bits(value) as f64
+    }
+
+    /// Atomically adds a reward to an accumulator, saturating at `f32::MAX`
+    ///
+    /// Returns true if the new total did not fit.
+    pub(super) fn add_reward(cell: &AtomicReward, value: f64) -> bool {
+        let mut saturated = false;
+        let _ = cell.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+            let sum = f32::from_bits(bits) as f64 + value;
+            saturated = (sum as f32).is_infinite();
+            Some(saturate(sum).to_bits())
+        });
+        saturated
+    }
+
+    /// Loads a visit counter
+    pub(super) fn load_visits(cell: &AtomicVisits) -> u64 {
+        cell.load(Ordering::Relaxed) as u64
+    }
+
+    /// Atomically adds to a visit counter, saturating at `u32::MAX`
+    pub(super) fn add_visits(cell: &AtomicVisits, count: u64) {
+        let count = count.min(u32::MAX as u64) as u32;
+        let _ = cell.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |visits| {
+            Some(visits.saturating_add(count))
+        });
+    }
+
+    /// Atomically subtracts from a visit counter, stopping at zero
+    pub(super) fn remove_visits(cell: &AtomicVisits, count: u64) {
+        let count = count.min(u32::MAX as u64) as u32;
+        let _ = cell.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |visits| {
+            Some(visits.saturating_sub(count))
+        });
+    }
+}
+
+use storage::{decode_reward, encode_reward};
+
 impl<S: GameState> MCTSNode<S> {
     /// Creates a new node with the given state and action
     pub fn new(
@@ -75,43 +403,153 @@ impl<S: GameState> MCTSNode<S> {
         depth: usize,
     ) -> Self {
         let player = parent_player.unwrap_or_else(|| state.get_current_player());
-        let unexpanded_actions = state.get_legal_actions();
+        let chance = state.is_chance_event();
+        let outcomes = if chance {
+            state.chance_outcomes()
+        } else {
+            None
+        };
+        let unexpanded_actions = if chance {
+            Vec::new()
+        } else {
+            state.get_legal_actions()
+        };
 
-        MCTSNode {
+        let mut node = MCTSNode {
             state,
             action,
-            visits: AtomicU64::new(0),
-            total_reward: AtomicU64::new(0),
-            sum_squared_reward: AtomicU64::new(0),
-            rave_visits: AtomicU64::new(0),
-            rave_reward: AtomicU64::new(0),
-            prior: AtomicU64::new(float_to_scaled_u64(1.0)), // Default prior is 1.0
+            visits: AtomicVisits::new(0),
+            total_reward: AtomicReward::new(encode_reward(0.0)),
+            sum_squared_reward: AtomicReward::new(encode_reward(0.0)),
+            rave_visits: AtomicVisits::new(0),
+            rave_reward: AtomicReward::new(encode_reward(0.0)),
+            outcome_total: AtomicReward::new(encode_reward(0.0)),
+            posterior_mean: AtomicReward::new(encode_reward(0.0)),
+            posterior_precision: AtomicReward::new(encode_reward(0.0)),
+            value_min: AtomicU64::new(f64::INFINITY.to_bits()),
+            value_max: AtomicU64::new(f64::NEG_INFINITY.to_bits()),
+            prior: AtomicReward::new(encode_reward(1.0)), // Default prior is 1.0
+            cached_stats: None,
+            exhausted: false,
+            solved: None,
+            closes_cycle: false,
+            transposition: None,
+            chance,
+            sampled_outcomes: chance && outcomes.is_none(),
             children: Vec::new(),
             unexpanded_actions,
             depth,
             player,
+            generation: next_generation(),
+            last_visited: AtomicU64::new(0),
+            saturated: AtomicBool::new(false),
+        };
+        if let Some(outcomes) = outcomes {
+            node.add_chance_outcomes(outcomes);
         }
+        node
+    }
+
+    /// Turns this node into a chance node with a child for each outcome
+    ///
+    /// The outcome children keep this node's player, so their values are
+    /// seen from the point of view of the player whose move led here. An
+    /// empty outcome list leaves the node without actions or children.
+    fn add_chance_outcomes(&mut self, outcomes: Vec<(S, f64)>) {
+        let total: f64 = outcomes.iter().map(|(_, p)| p.max(0.0)).sum();
+        self.chance = !outcomes.is_empty();
+        for (state, probability) in outcomes {
+            let child = MCTSNode::new(state, None, Some(self.player.clone()), self.depth + 1);
+            child.set_prior(if total > 0.0 {
+                probability.max(0.0) / total
+            } else {
+                0.0
+            });
+            self.children.push(child);
+        }
+    }
+
+    /// Returns true if an oracle knew the exact value of this node's state
+    pub fn is_solved(&self) -> bool {
+        self.solved.is_some()
+    }
+
+    /// Returns true if this node is an afterstate waiting for a chance event
+    pub fn is_chance(&self) -> bool {
+        self.chance
+    }
+
+    /// Returns the index of an existing chance outcome drawn at random
+    ///
+    /// Listed outcomes are drawn by probability, sampled outcomes in
+    /// proportion to their visits. Returns `None` if this is not a chance
+    /// node or it has no outcome children yet.
+    pub fn sample_outcome<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Option<usize> {
+        if !self.chance {
+            return None;
+        }
+        let weight = |child: &MCTSNode<S>| {
+            if self.sampled_outcomes {
+                child.visits().max(1) as f64
+            } else {
+                child.prior()
+            }
+        };
+        let total: f64 = self.children.iter().map(weight).sum();
+        let mut threshold = rng.gen::<f64>() * total;
+        for (index, child) in self.children.iter().enumerate() {
+            threshold -= weight(child);
+            if threshold < 0.0 {
+                return Some(index);
+            }
+        }
+        self.children.len().checked_sub(1)
+    }
+
+    /// Draws a new outcome for a sampled chance node and returns its index
+    ///
+    /// An outcome whose [`hash`](GameState::hash) matches an existing child
+    /// is merged into that child instead of adding a duplicate. Returns
+    /// `None` if this is not a sampled chance node or no outcome was drawn.
+    pub fn add_sampled_outcome<R: rand::RngCore>(&mut self, rng: &mut R) -> Option<usize> {
+        if !self.sampled_outcomes {
+            return None;
+        }
+        let state = self.state.sample_chance_outcome(rng)?;
+        let hash = state.hash();
+        if hash != 0 {
+            if let Some(index) = self
+                .children
+                .iter()
+                .position(|child| child.state.hash() == hash)
+            {
+                return Some(index);
+            }
+        }
+
+        let child = MCTSNode::new(state, None, Some(self.player.clone()), self.depth + 1);
+        self.children.push(child);
+        Some(self.children.len() - 1)
     }
 
     /// Returns the number of visits to this node
     pub fn visits(&self) -> u64 {
-        self.visits.load(Ordering::Relaxed)
+        storage::load_visits(&self.visits)
     }
 
     /// Returns the total reward accumulated at this node
     pub fn total_reward(&self) -> f64 {
-        scaled_u64_to_float(self.total_reward.load(Ordering::Relaxed))
+        decode_reward(self.total_reward.load(Ordering::Relaxed))
     }
 
     /// Returns the prior probability of this node
     pub fn prior(&self) -> f64 {
-        scaled_u64_to_float(self.prior.load(Ordering::Relaxed))
+        decode_reward(self.prior.load(Ordering::Relaxed))
     }
 
     /// Sets the prior probability of this node
     pub fn set_prior(&self, prior: f64) {
-        self.prior
-            .store(float_to_scaled_u64(prior), Ordering::Relaxed);
+        self.prior.store(encode_reward(prior), Ordering::Relaxed);
     }
 
     /// Returns the average reward (value) of this node
@@ -123,42 +561,326 @@ impl<S: GameState> MCTSNode<S> {
         self.total_reward() / visits as f64
     }
 
+    /// Returns the power mean of the children's values, weighted by visits
+    ///
+    /// With `exponent = 1` this is the visit-weighted average of the children;
+    /// as the exponent grows it approaches the best child's value, so a single
+    /// strong child is not averaged away. Values are clamped at zero, matching
+    /// the crate's `[0, 1]` reward convention. Falls back to [`value`](Self::value)
+    /// when no child has been visited.
+    pub fn power_mean_value(&self, exponent: f64) -> f64 {
+        let mut weighted_sum = 0.0;
+        let mut total_visits = 0.0;
+        for child in &self.children {
+            let visits = child.visits() as f64;
+            if visits > 0.0 {
+                weighted_sum += visits * child.value().max(0.0).powf(exponent);
+                total_visits += visits;
+            }
+        }
+
+        if total_visits == 0.0 {
+            return self.value();
+        }
+        (weighted_sum / total_visits).powf(1.0 / exponent)
+    }
+
+    /// Returns the visit-weighted mean value of this node's visited children
+    ///
+    /// Each child's value is from the point of view of the player who moved
+    /// into it, so this stands for the player to move here even where
+    /// [`value`](Self::value) does not, as with
+    /// [`RewardPerspective::PerPlayer`]. Uses the children's
+    /// [`selection_stats`](Self::selection_stats). Returns `None` if no
+    /// child has been visited.
+    pub fn mean_child_value(&self) -> Option<f64> {
+        let mut visits = 0;
+        let mut weighted_sum = 0.0;
+        for (child_visits, child_value) in self.children.iter().map(|c| c.selection_stats()) {
+            visits += child_visits;
+            weighted_sum += child_visits as f64 * child_value;
+        }
+        (visits > 0).then(|| weighted_sum / visits as f64)
+    }
+
+    /// Returns the expectimax value of this node's visited children
+    ///
+    /// A chance node takes the expectation over its outcomes, weighting
+    /// listed outcomes by probability and sampled ones by visits, renormalized
+    /// over the outcomes visited so far. Any other node takes its best
+    /// child's value. Falls back to [`value`](Self::value) when no child has
+    /// been visited.
+    pub fn expectimax_value(&self) -> f64 {
+        let visited = self.children.iter().filter(|child| child.visits() > 0);
+        if !self.chance {
+            return visited
+                .map(|child| child.value())
+                .reduce(f64::max)
+                .unwrap_or_else(|| self.value());
+        }
+
+        let mut weighted_sum = 0.0;
+        let mut total_weight = 0.0;
+        for child in visited {
+            let weight = if self.sampled_outcomes {
+                child.visits() as f64
+            } else {
+                child.prior()
+            };
+            weighted_sum += weight * child.value();
+            total_weight += weight;
+        }
+        if total_weight > 0.0 {
+            weighted_sum / total_weight
+        } else {
+            self.value()
+        }
+    }
+
+    /// Returns the value of this node's best line
+    ///
+    /// Follows the visited child with the highest max value at every level,
+    /// down to a node without visited children, whose mean value is used.
+    /// The best child's value is converted to this node's point of view
+    /// with `perspective`. A chance node instead averages its outcomes' max
+    /// values, weighted as in [`expectimax_value`](Self::expectimax_value).
+    /// Where the mean averages every move tried, this reports what the
+    /// node is worth if the best moves found are played. The whole subtree
+    /// is walked on each call.
+    pub fn max_value(&self, perspective: RewardPerspective) -> f64 {
+        let visited = self.children.iter().filter(|child| child.visits() > 0);
+        if !self.chance {
+            // Children are valued by the player choosing among them
+            return visited
+                .map(|child| (child.max_value(perspective), &child.player))
+                .reduce(|best, next| if next.0 > best.0 { next } else { best })
+                .map_or_else(
+                    || self.value(),
+                    |(value, mover)| perspective.reward_for(value, mover, &self.player),
+                );
+        }
+
+        let mut weighted_sum = 0.0;
+        let mut total_weight = 0.0;
+        for child in visited {
+            let weight = if self.sampled_outcomes {
+                child.visits() as f64
+            } else {
+                child.prior()
+            };
+            let value =
+                perspective.reward_for(child.max_value(perspective), &child.player, &self.player);
+            weighted_sum += weight * value;
+            total_weight += weight;
+        }
+        if total_weight > 0.0 {
+            weighted_sum / total_weight
+        } else {
+            self.value()
+        }
+    }
+
+    /// Returns this node's mean or max value, as chosen by `estimate`
+    pub fn estimated_value(&self, estimate: ValueEstimate, perspective: RewardPerspective) -> f64 {
+        match estimate {
+            ValueEstimate::Mean => self.value(),
+            ValueEstimate::Max => self.max_value(perspective),
+        }
+    }
+
+    /// Returns the `(visits, value)` pair selection policies should use
+    ///
+    /// This is the cached snapshot when one is present, avoiding the atomic
+    /// loads and fixed-point conversion, and the live statistics otherwise.
+    pub fn selection_stats(&self) -> (u64, f64) {
+        match self.cached_stats {
+            Some(stats) => stats,
+            None => (self.visits(), self.value()),
+        }
+    }
+
+    /// Refreshes the cached selection snapshot from the live statistics
+    pub fn refresh_cached_stats(&mut self) {
+        self.cached_stats = Some((self.visits(), self.value()));
+    }
+
     /// Increments the visit count
+    ///
+    /// Saturates instead of wrapping around with `compact-stats`, whose
+    /// `u32` counters run out after about 4.3e9 visits.
     pub fn increment_visits(&self) {
-        self.visits.fetch_add(1, Ordering::Relaxed);
+        storage::add_visits(&self.visits, 1);
+    }
+
+    /// Adds a virtual loss of `visits` visits without reward
+    ///
+    /// Used while a batch of leaves is being selected, so that a node already
+    /// on the way to a pending playout looks worse to the next selection.
+    /// Must be undone with [`remove_virtual_loss`](Self::remove_virtual_loss)
+    /// before the playout's result is backpropagated.
+    pub(crate) fn add_virtual_loss(&self, visits: u64) {
+        storage::add_visits(&self.visits, visits);
+    }
+
+    /// Removes a virtual loss added by [`add_virtual_loss`](Self::add_virtual_loss)
+    pub(crate) fn remove_virtual_loss(&self, visits: u64) {
+        storage::remove_visits(&self.visits, visits);
+    }
+
+    /// Adds `visits` visits with an average reward of `value`
+    ///
+    /// This is equivalent to backpropagating `value` that many times, outcome
+    /// included, and is used to seed statistics from outside the search, e.g.
+    /// when warm-starting.
+    pub fn inject_stats(&self, visits: u64, value: f64) {
+        let count = visits as f64;
+        storage::add_visits(&self.visits, visits);
+        self.accumulate(&self.total_reward, value * count);
+        self.accumulate(&self.sum_squared_reward, value * value * count);
+        self.accumulate(&self.outcome_total, outcome_of(value) * count);
+    }
+
+    /// Adds the statistics of `other`, a node for the same state, to this subtree
+    ///
+    /// Visits, rewards, outcomes and RAVE statistics are summed node by node.
+    /// Children are matched by action key and the outcomes of listed chance
+    /// events by position. A child only `other` has is expanded here if its
+    /// action is still unexpanded; children whose action is not available
+    /// here, and the outcomes of sampled chance events, are skipped with their
+    /// whole subtree. An exact value is taken from `other` if this node has
+    /// none. A node holding a [cached selection snapshot](Self::selection_stats)
+    /// has it refreshed, and so does every node merged below it, created ones
+    /// included. Returns how many nodes were created and how many subtrees
+    /// were skipped.
+    pub fn merge(&mut self, other: &MCTSNode<S>) -> (usize, usize) {
+        storage::add_visits(&self.visits, other.visits());
+        self.accumulate(&self.total_reward, other.total_reward());
+        self.accumulate(&self.sum_squared_reward, other.sum_squared_reward());
+        self.accumulate(
+            &self.outcome_total,
+            decode_reward(other.outcome_total.load(Ordering::Relaxed)),
+        );
+        storage::add_visits(&self.rave_visits, other.rave_visits());
+        self.accumulate(
+            &self.rave_reward,
+            decode_reward(other.rave_reward.load(Ordering::Relaxed)),
+        );
+        if let Some((min, max)) = other.value_bounds() {
+            self.widen_value_bounds(min);
+            self.widen_value_bounds(max);
+        }
+        if self.solved.is_none() {
+            self.solved = other.solved;
+        }
+        let cached = self.cached_stats.is_some();
+        if cached {
+            self.refresh_cached_stats();
+        }
+
+        let (mut created, mut skipped) = (0, 0);
+        if self.chance || other.chance {
+            if self.chance != other.chance
+                || self.sampled_outcomes
+                || other.sampled_outcomes
+                || self.children.len() != other.children.len()
+            {
+                return (0, other.children.len());
+            }
+            for (outcome, theirs) in self.children.iter_mut().zip(&other.children) {
+                if cached {
+                    outcome.refresh_cached_stats();
+                }
+                let (more, less) = outcome.merge(theirs);
+                created += more;
+                skipped += less;
+            }
+            return (created, skipped);
+        }
+
+        for theirs in &other.children {
+            let Some(action) = theirs.action.as_ref() else {
+                skipped += 1;
+                continue;
+            };
+            let index = match self.find_child(action) {
+                Some(index) => index,
+                None => match self.find_unexpanded(action) {
+                    Some(action_index) => {
+                        let Some(child) = self.expand(action_index) else {
+                            skipped += 1;
+                            continue;
+                        };
+                        child.set_prior(theirs.prior());
+                        created += 1 + child.children.len();
+                        self.children.len() - 1
+                    }
+                    None => {
+                        skipped += 1;
+                        continue;
+                    }
+                },
+            };
+            let child = &mut self.children[index];
+            if cached {
+                child.refresh_cached_stats();
+            }
+            let (more, less) = child.merge(theirs);
+            created += more;
+            skipped += less;
+        }
+        (created, skipped)
+    }
+
+    /// Overwrites the total reward, e.g. to store a backed-up value
+    ///
+    /// Backup operators that do not average, like
+    /// [`PowerMeanPolicy`](crate::policy::backpropagation::PowerMeanPolicy),
+    /// use this so that [`value`](Self::value) reports their estimate.
+    pub fn set_total_reward(&self, total: f64) {
+        self.total_reward
+            .store(encode_reward(total), Ordering::Relaxed);
+    }
+
+    /// Adds reward to an accumulator, flagging the node if it saturates
+    fn accumulate(&self, cell: &AtomicReward, reward: f64) {
+        if storage::add_reward(cell, reward) {
+            self.saturated.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Clears the saturation flag, returning whether it was set
+    pub(crate) fn take_saturated(&self) -> bool {
+        self.saturated.swap(false, Ordering::Relaxed)
     }
 
     /// Adds reward to the total
     pub fn add_reward(&self, reward: f64) {
-        self.total_reward
-            .fetch_add(float_to_scaled_u64(reward), Ordering::Relaxed);
+        self.accumulate(&self.total_reward, reward);
     }
 
     /// Adds squared reward (for UCB1-Tuned)
     pub fn add_squared_reward(&self, reward: f64) {
-        self.sum_squared_reward
-            .fetch_add(float_to_scaled_u64(reward * reward), Ordering::Relaxed);
+        self.accumulate(&self.sum_squared_reward, reward * reward);
     }
 
     /// Returns the sum of squared rewards
     pub fn sum_squared_reward(&self) -> f64 {
-        scaled_u64_to_float(self.sum_squared_reward.load(Ordering::Relaxed))
+        decode_reward(self.sum_squared_reward.load(Ordering::Relaxed))
     }
 
     /// Increments the RAVE visit count
     pub fn increment_rave_visits(&self) {
-        self.rave_visits.fetch_add(1, Ordering::Relaxed);
+        storage::add_visits(&self.rave_visits, 1);
     }
 
     /// Adds RAVE reward
     pub fn add_rave_reward(&self, reward: f64) {
-        self.rave_reward
-            .fetch_add(float_to_scaled_u64(reward), Ordering::Relaxed);
+        self.accumulate(&self.rave_reward, reward);
     }
 
     /// Returns the number of RAVE visits
     pub fn rave_visits(&self) -> u64 {
-        self.rave_visits.load(Ordering::Relaxed)
+        storage::load_visits(&self.rave_visits)
     }
 
     /// Returns the RAVE value (average RAVE reward)
@@ -167,7 +889,75 @@ impl<S: GameState> MCTSNode<S> {
         if visits == 0 {
             return 0.0;
         }
-        scaled_u64_to_float(self.rave_reward.load(Ordering::Relaxed)) / visits as f64
+        decode_reward(self.rave_reward.load(Ordering::Relaxed)) / visits as f64
+    }
+
+    /// Adds the win/draw/loss outcome of a result
+    pub fn add_outcome(&self, result: f64) {
+        self.accumulate(&self.outcome_total, outcome_of(result));
+    }
+
+    /// Returns the fraction of results through this node that were wins,
+    /// counting draws as half a win
+    pub fn win_rate(&self) -> f64 {
+        let visits = self.visits();
+        if visits == 0 {
+            return 0.0;
+        }
+        decode_reward(self.outcome_total.load(Ordering::Relaxed)) / visits as f64
+    }
+
+    /// Returns the posterior mean of the node's value
+    pub fn posterior_mean(&self) -> f64 {
+        decode_reward(self.posterior_mean.load(Ordering::Relaxed))
+    }
+
+    /// Returns the posterior precision of the node's value
+    pub fn posterior_precision(&self) -> f64 {
+        decode_reward(self.posterior_precision.load(Ordering::Relaxed))
+    }
+
+    /// Stores a new Gaussian posterior for the node's value
+    pub fn set_posterior(&self, mean: f64, precision: f64) {
+        self.posterior_mean
+            .store(encode_reward(mean), Ordering::Relaxed);
+        self.posterior_precision
+            .store(encode_reward(precision), Ordering::Relaxed);
+    }
+
+    /// Returns the smallest and largest child values recorded at this node, if any
+    pub fn value_bounds(&self) -> Option<(f64, f64)> {
+        let min = f64::from_bits(self.value_min.load(Ordering::Relaxed));
+        let max = f64::from_bits(self.value_max.load(Ordering::Relaxed));
+        (min <= max).then_some((min, max))
+    }
+
+    /// Widens the value bounds to include `value`; non-finite values are ignored
+    pub fn widen_value_bounds(&self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        let _ = self
+            .value_min
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                (value < f64::from_bits(bits)).then_some(value.to_bits())
+            });
+        let _ = self
+            .value_max
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                (value > f64::from_bits(bits)).then_some(value.to_bits())
+            });
+    }
+
+    /// Maps `value` into `[0, 1]` using the value bounds of this node's children
+    ///
+    /// Returns `value` unchanged until the bounds span a nonzero range, so
+    /// rewards already in `[0, 1]` are not distorted early on.
+    pub fn normalize_value(&self, value: f64) -> f64 {
+        match self.value_bounds() {
+            Some((min, max)) if max > min => ((value - min) / (max - min)).clamp(0.0, 1.0),
+            _ => value,
+        }
     }
 
     /// Returns true if this node is fully expanded
@@ -175,11 +965,117 @@ impl<S: GameState> MCTSNode<S> {
         self.unexpanded_actions.is_empty()
     }
 
+    /// Records that a search selected through this node at `now` on the visit clock
+    pub(crate) fn touch(&self, now: u64) {
+        self.last_visited.store(now, Ordering::Relaxed);
+    }
+
+    /// Returns how long ago a search last selected through this node
+    ///
+    /// `None` if no search has since the node was created, or if no
+    /// [`SubtreeGc`](crate::config::SubtreeGc) was configured to track it.
+    pub fn idle_time(&self) -> Option<Duration> {
+        match self.last_visited.load(Ordering::Relaxed) {
+            0 => None,
+            visited => Some(Duration::from_millis(
+                visit_clock_millis().saturating_sub(visited),
+            )),
+        }
+    }
+
+    /// Returns the number of nodes in this node's subtree, itself included
+    pub fn subtree_size(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(|child| child.subtree_size())
+            .sum::<usize>()
+    }
+
     /// Returns true if this node is a leaf (has no children)
     pub fn is_leaf(&self) -> bool {
         self.children.is_empty()
     }
 
+    /// Returns the index of the child reached by `action`, matched by key
+    pub fn find_child(&self, action: &S::Action) -> Option<usize> {
+        self.children.iter().position(|child| {
+            child
+                .action
+                .as_ref()
+                .is_some_and(|child_action| child_action.key() == action.key())
+        })
+    }
+
+    /// Returns the index of `action` in the unexpanded actions, matched by key
+    pub fn find_unexpanded(&self, action: &S::Action) -> Option<usize> {
+        self.unexpanded_actions
+            .iter()
+            .position(|unexpanded| unexpanded.key() == action.key())
+    }
+
+    /// Removes unexpanded actions whose key repeats a child's or an earlier action's
+    ///
+    /// Keeps the first action with each [`key`](Action::key) and returns how
+    /// many were removed.
+    pub fn dedup_unexpanded_actions(&mut self) -> usize {
+        let mut seen: HashSet<ActionKey> = self
+            .children
+            .iter()
+            .filter_map(|child| child.action.as_ref().map(Action::key))
+            .collect();
+        let before = self.unexpanded_actions.len();
+        self.unexpanded_actions
+            .retain(|action| seen.insert(action.key()));
+        before - self.unexpanded_actions.len()
+    }
+
+    /// Returns true if this node links to a canonical node in DAG mode
+    pub fn is_transposition(&self) -> bool {
+        self.transposition.is_some()
+    }
+
+    /// Returns the descendant reached by following `path` from this node
+    ///
+    /// Returns `None` if any index along the path is out of bounds.
+    pub fn descendant(&self, path: &NodePath) -> Option<&MCTSNode<S>> {
+        let mut node = self;
+        for &index in &path.indices {
+            node = node.children.get(index)?;
+        }
+        Some(node)
+    }
+
+    /// Returns a handle to the descendant at `path`, or `None` if there is none
+    ///
+    /// Unlike the path alone, the handle stops resolving once the node is
+    /// recycled or replaced; see [`resolve`](Self::resolve).
+    pub fn handle(&self, path: &NodePath) -> Option<NodeHandle> {
+        self.descendant(path).map(|node| NodeHandle {
+            path: path.clone(),
+            generation: node.generation,
+        })
+    }
+
+    /// Returns the descendant a handle was taken from, if it is still there
+    ///
+    /// Returns `None` if the path no longer leads anywhere or leads to a
+    /// different node, e.g. after the tree was recycled into a node pool and
+    /// rebuilt.
+    pub fn resolve(&self, handle: &NodeHandle) -> Option<&MCTSNode<S>> {
+        self.descendant(&handle.path)
+            .filter(|node| node.generation == handle.generation)
+    }
+
+    /// Returns a mutable reference to the descendant reached by following `path`
+    pub fn descendant_mut(&mut self, path: &NodePath) -> Option<&mut MCTSNode<S>> {
+        let mut node = self;
+        for &index in &path.indices {
+            node = node.children.get_mut(index)?;
+        }
+        Some(node)
+    }
+
     /// Expands the node by creating a child for an unexpanded action
     ///
     /// This method takes an action from the unexpanded actions list,
@@ -256,7 +1152,7 @@ impl<S: GameState> MCTSNode<S> {
         }
 
         // Use IteratorRandom trait for choose method on range
-        let mut rng = rand::thread_rng();
+        let mut rng = search_rng();
         let index = (0..self.unexpanded_actions.len()).choose(&mut rng).unwrap();
 
         self.expand(index)
@@ -269,7 +1165,7 @@ impl<S: GameState> MCTSNode<S> {
         }
 
         // Use IteratorRandom trait for choose method on range
-        let mut rng = rand::thread_rng();
+        let mut rng = search_rng();
         let index = (0..self.unexpanded_actions.len()).choose(&mut rng).unwrap();
 
         self.expand_with_pool(index, pool)
@@ -282,12 +1178,12 @@ impl<S: GameState> MCTSNode<S> {
 /// instead of frequently allocating and deallocating them. This can significantly
 /// improve performance in large MCTS searches.
 pub struct NodePool<S: GameState> {
-    /// Template state used for creating new nodes
-    template_state: S,
-
-    /// Preallocated, reusable nodes for efficient reuse
+    /// Recycled nodes waiting to be reused
     free_nodes: Vec<MCTSNode<S>>,
 
+    /// Largest number of free nodes kept; extra recycled nodes are dropped
+    max_free: Option<usize>,
+
     /// Statistics about allocations
     stats: NodePoolStats,
 }
@@ -303,50 +1199,23 @@ pub struct NodePoolStats {
 
     /// Total nodes recycled back to the pool
     pub total_recycled: usize,
+
+    /// Total nodes dropped because the free list was full or shrunk
+    pub total_released: usize,
 }
 
 impl<S: GameState> NodePool<S> {
-    /// Creates a new node pool with the given template state
-    ///
-    /// # Arguments
+    /// Creates an empty node pool with room for `initial_size` free nodes
     ///
-    /// * `template_state` - A template state that can be cloned when creating new nodes
-    /// * `initial_size` - Number of nodes to preallocate
-    pub fn new(template_state: S, initial_size: usize) -> Self {
-        let mut pool = NodePool {
-            template_state,
+    /// No nodes are built up front, since that would need a state to put in
+    /// them. Reserving the free list means recycling up to `initial_size`
+    /// nodes never reallocates it; nodes are created on demand and reused
+    /// once a tree is recycled.
+    pub fn new(initial_size: usize) -> Self {
+        NodePool {
             free_nodes: Vec::with_capacity(initial_size),
+            max_free: None,
             stats: NodePoolStats::default(),
-        };
-
-        // Preallocate nodes if requested
-        if initial_size > 0 {
-            pool.preallocate(initial_size);
-        }
-
-        pool
-    }
-
-    /// Preallocate nodes to reduce allocation pressure during search
-    fn preallocate(&mut self, count: usize) {
-        for _ in 0..count {
-            let node = MCTSNode {
-                state: self.template_state.clone(),
-                action: None,
-                visits: AtomicU64::new(0),
-                total_reward: AtomicU64::new(0),
-                sum_squared_reward: AtomicU64::new(0),
-                rave_visits: AtomicU64::new(0),
-                rave_reward: AtomicU64::new(0),
-                prior: AtomicU64::new(float_to_scaled_u64(1.0)),
-                children: Vec::new(),
-                unexpanded_actions: Vec::new(),
-                depth: 0,
-                player: self.template_state.get_current_player(),
-            };
-
-            self.free_nodes.push(node);
-            self.stats.total_created += 1;
         }
     }
 
@@ -367,22 +1236,50 @@ impl<S: GameState> NodePool<S> {
                 None => state.get_current_player(),
             };
 
-            // Get legal actions before moving state
-            let legal_actions = state.get_legal_actions();
+            // Get legal actions or chance outcomes before moving state
+            let chance = state.is_chance_event();
+            let outcomes = if chance {
+                state.chance_outcomes()
+            } else {
+                None
+            };
+            let legal_actions = if chance {
+                Vec::new()
+            } else {
+                state.get_legal_actions()
+            };
 
             // Reuse an existing node
             node.state = state;
             node.action = action;
-            node.visits = AtomicU64::new(0);
-            node.total_reward = AtomicU64::new(0);
-            node.sum_squared_reward = AtomicU64::new(0);
-            node.rave_visits = AtomicU64::new(0);
-            node.rave_reward = AtomicU64::new(0);
-            node.prior = AtomicU64::new(float_to_scaled_u64(1.0));
+            node.visits = AtomicVisits::new(0);
+            node.total_reward = AtomicReward::new(encode_reward(0.0));
+            node.sum_squared_reward = AtomicReward::new(encode_reward(0.0));
+            node.rave_visits = AtomicVisits::new(0);
+            node.rave_reward = AtomicReward::new(encode_reward(0.0));
+            node.outcome_total = AtomicReward::new(encode_reward(0.0));
+            node.posterior_mean = AtomicReward::new(encode_reward(0.0));
+            node.posterior_precision = AtomicReward::new(encode_reward(0.0));
+            node.value_min = AtomicU64::new(f64::INFINITY.to_bits());
+            node.value_max = AtomicU64::new(f64::NEG_INFINITY.to_bits());
+            node.prior = AtomicReward::new(encode_reward(1.0));
+            node.cached_stats = None;
+            node.exhausted = false;
+            node.solved = None;
+            node.closes_cycle = false;
+            node.transposition = None;
+            node.chance = chance;
+            node.sampled_outcomes = chance && outcomes.is_none();
             node.children.clear();
             node.depth = depth;
             node.player = player;
+            node.generation = next_generation();
+            node.last_visited = AtomicU64::new(0);
+            node.saturated = AtomicBool::new(false);
             node.unexpanded_actions = legal_actions;
+            if let Some(outcomes) = outcomes {
+                node.add_chance_outcomes(outcomes);
+            }
 
             node
         } else {
@@ -392,8 +1289,109 @@ impl<S: GameState> NodePool<S> {
         }
     }
 
+    /// Limits how many free nodes the pool keeps
+    ///
+    /// Recycling a huge tree otherwise leaves every one of its nodes in the
+    /// free list for the rest of the pool's life. With a limit, nodes recycled
+    /// into a full free list are dropped instead, and an existing surplus is
+    /// released right away. `None` removes the limit.
+    pub fn set_max_free(&mut self, max_free: Option<usize>) {
+        self.max_free = max_free;
+        if let Some(max) = max_free {
+            self.shrink_to(max);
+        }
+    }
+
+    /// Returns the limit on free nodes, if any
+    pub fn max_free(&self) -> Option<usize> {
+        self.max_free
+    }
+
+    /// Drops free nodes until at most `count` remain and returns their memory
+    pub fn shrink_to(&mut self, count: usize) {
+        if self.free_nodes.len() > count {
+            self.stats.total_released += self.free_nodes.len() - count;
+            self.free_nodes.truncate(count);
+        }
+        self.free_nodes.shrink_to(count);
+    }
+
+    /// Returns the number of nodes the pool has created and not released
+    ///
+    /// Every such node is either free in the pool or part of a tree.
+    pub fn capacity(&self) -> usize {
+        self.stats
+            .total_created
+            .saturating_sub(self.stats.total_released)
+    }
+
+    /// Estimates the memory held by the free list, in bytes
+    ///
+    /// Counts the free list itself and the child and action vectors recycled
+    /// nodes still own. Heap memory owned by the states inside free nodes is
+    /// not included.
+    pub fn retained_bytes(&self) -> usize {
+        let node_size = std::mem::size_of::<MCTSNode<S>>();
+        let action_size = std::mem::size_of::<S::Action>();
+        let owned: usize = self
+            .free_nodes
+            .iter()
+            .map(|node| {
+                node.children.capacity() * node_size
+                    + node.unexpanded_actions.capacity() * action_size
+            })
+            .sum();
+        self.free_nodes.capacity() * node_size + owned
+    }
+
+    /// Checks that no node was handed out or recycled more often than possible
+    ///
+    /// A node reused twice, or recycled while still in a tree, breaks these
+    /// counts.
+    pub(crate) fn check_accounting(&self) -> std::result::Result<(), String> {
+        let reused = self
+            .stats
+            .total_allocations
+            .saturating_sub(self.stats.total_created);
+        if reused > self.stats.total_recycled {
+            return Err(format!(
+                "node pool reused {} nodes but only {} were recycled",
+                reused, self.stats.total_recycled
+            ));
+        }
+        if self.free_nodes.len() > self.stats.total_recycled - reused {
+            return Err(format!(
+                "node pool holds {} free nodes but only {} were recycled and not reused",
+                self.free_nodes.len(),
+                self.stats.total_recycled - reused
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the pool metrics reported in [`SearchStatistics`](crate::SearchStatistics)
+    pub fn search_stats(&self) -> crate::stats::NodePoolStats {
+        crate::stats::NodePoolStats {
+            capacity: self.capacity(),
+            available: self.available_nodes(),
+            total_allocated: self.stats.total_allocations,
+            total_returned: self.stats.total_recycled,
+            retained_bytes: self.retained_bytes(),
+        }
+    }
+
     /// Recycles a node back to the pool for future reuse
+    ///
+    /// If the free list is at its [limit](Self::set_max_free) the node is
+    /// dropped instead.
     pub fn recycle_node(&mut self, mut node: MCTSNode<S>) {
+        if self
+            .max_free
+            .is_some_and(|max| self.free_nodes.len() >= max)
+        {
+            self.stats.total_released += 1;
+            return;
+        }
         self.stats.total_recycled += 1;
 
         // Clear any large data structures to prevent memory bloat
@@ -430,12 +1428,11 @@ impl<S: GameState> NodePool<S> {
 // Manual Clone implementation for NodePool
 impl<S: GameState> Clone for NodePool<S> {
     fn clone(&self) -> Self {
-        // Create a new pool with the same template state and stats
         // We don't clone the free_nodes as they cannot be shared between instances
         // Instead, we'll create new nodes when needed
         NodePool {
-            template_state: self.template_state.clone(),
             free_nodes: Vec::new(), // Start with empty free_nodes
+            max_free: self.max_free,
             stats: self.stats.clone(),
         }
     }
@@ -499,6 +1496,105 @@ impl fmt::Display for NodePath {
     }
 }
 
+/// A path to a node together with the node's generation
+///
+/// Taken with [`MCTSNode::handle`] and checked with [`MCTSNode::resolve`]. A
+/// bare [`NodePath`] silently addresses whatever node sits at its position
+/// now; a handle notices when that is no longer the node it was taken from.
+#[derive(Debug, Clone)]
+pub struct NodeHandle {
+    path: NodePath,
+    generation: u64,
+}
+
+impl NodeHandle {
+    /// Returns the path the handle was taken at
+    pub fn path(&self) -> &NodePath {
+        &self.path
+    }
+
+    /// Returns the generation of the node the handle was taken from
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+/// Checks the structural invariants of a subtree, returning the first violation
+///
+/// `path` addresses `node` from the root. Visit counts are only compared in
+/// a tree: in DAG mode canonical nodes are also reached through links. An
+/// action may only be both expanded and unexpanded with `unique_actions`
+/// off, where a duplicated legal action gets a child per copy.
+pub(crate) fn check_subtree<S: GameState>(
+    root: &MCTSNode<S>,
+    node: &MCTSNode<S>,
+    path: &mut NodePath,
+    dag_mode: bool,
+    unique_actions: bool,
+    generations: &mut HashSet<u64>,
+) -> std::result::Result<(), String> {
+    if !generations.insert(node.generation) {
+        return Err(format!(
+            "node at {} shares generation {} with another node",
+            path, node.generation
+        ));
+    }
+    if node.depth != path.len() {
+        return Err(format!(
+            "node at {} has depth {} but sits at depth {}",
+            path,
+            node.depth,
+            path.len()
+        ));
+    }
+
+    if let Some(target) = &node.transposition {
+        match root.descendant(target) {
+            Some(canonical) if !canonical.is_transposition() => {}
+            Some(_) => return Err(format!("link at {} points to another link", path)),
+            None => return Err(format!("link at {} points to missing {}", path, target)),
+        }
+    }
+
+    if !dag_mode {
+        let child_visits: u64 = node.children.iter().map(|child| child.visits()).sum();
+        if child_visits > node.visits() {
+            return Err(format!(
+                "node at {} has {} visits but its children have {}",
+                path,
+                node.visits(),
+                child_visits
+            ));
+        }
+    }
+
+    for (index, child) in node.children.iter().enumerate() {
+        // Chance outcomes are reached without an action
+        match &child.action {
+            Some(action)
+                if unique_actions
+                    && node
+                        .unexpanded_actions
+                        .iter()
+                        .any(|a| a.key() == action.key()) =>
+            {
+                return Err(format!(
+                    "action {:?} at {} is both expanded and unexpanded",
+                    action, path
+                ));
+            }
+            Some(_) => {}
+            None if node.chance => {}
+            None => return Err(format!("child {} of {} has no action", index, path)),
+        }
+
+        path.push(index);
+        check_subtree(root, child, path, dag_mode, unique_actions, generations)?;
+        path.indices.pop();
+    }
+    Ok(())
+}
+
 /// Standalone helper function for tree recycling
 ///
 /// This needs to be outside the MCTS impl to avoid borrow checker issues
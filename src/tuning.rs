@@ -0,0 +1,237 @@
+//! Automatic tuning of the exploration constant
+//!
+//! Choosing the exploration constant is mostly trial and error. This module
+//! automates the trials: [`play_match`] plays two configurations against each
+//! other, and [`SpsaTuner`] uses the results to move the constant towards the
+//! stronger setting, one self-play match at a time (simultaneous perturbation
+//! stochastic approximation, SPSA). [`tune_exploration`] ties the two
+//! together for competitive two-player games.
+//!
+//! # Example
+//!
+//! ```
+//! use arboriter_mcts::tuning::SpsaTuner;
+//!
+//! // Any match result works; here the candidate closer to 0.8 always wins
+//! let mut tuner = SpsaTuner::new(2.0).with_seed(7);
+//! for _ in 0..200 {
+//!     tuner.step(|plus, minus| {
+//!         if (plus - 0.8).abs() < (minus - 0.8).abs() { 1.0 } else { 0.0 }
+//!     });
+//! }
+//! assert!((tuner.value() - 0.8).abs() < 0.2);
+//! ```
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{config::MCTSConfig, game_state::GameState, mcts::MCTS, Result};
+
+/// SPSA tuner for a single search parameter, usually the exploration constant
+///
+/// Each step proposes two candidates `θ ± c_k` around the current value `θ`,
+/// plays them against each other, and moves `θ` by `a_k * (2s - 1) / (2c_k)`
+/// towards the winner, where `s` is the score of the `+` candidate in `[0, 1]`.
+/// The gains shrink as `a_k = a / (k + 1 + A)^0.602` and
+/// `c_k = c / (k + 1)^0.101`, the standard SPSA schedules, so the value
+/// settles down as evidence accumulates.
+#[derive(Debug, Clone)]
+pub struct SpsaTuner {
+    value: f64,
+    min: f64,
+    max: f64,
+    step_size: f64,
+    perturbation: f64,
+    stability: f64,
+    iteration: usize,
+    direction: Option<f64>,
+    rng: StdRng,
+}
+
+impl SpsaTuner {
+    /// Creates a tuner starting from `initial`
+    ///
+    /// Defaults suit exploration constants: values stay within
+    /// `[0.01, 10.0]`, the step size `a` is 0.5 and the perturbation `c` 0.2.
+    pub fn new(initial: f64) -> Self {
+        SpsaTuner {
+            value: initial,
+            min: 0.01,
+            max: 10.0,
+            step_size: 0.5,
+            perturbation: 0.2,
+            stability: 10.0,
+            iteration: 0,
+            direction: None,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Keeps the value and the candidates within `[min, max]`
+    pub fn with_bounds(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max.max(min);
+        self.value = self.value.clamp(self.min, self.max);
+        self
+    }
+
+    /// Sets the step size `a`, scaling how far a decisive match moves the value
+    pub fn with_step_size(mut self, step_size: f64) -> Self {
+        self.step_size = step_size;
+        self
+    }
+
+    /// Sets the perturbation `c`, the initial distance of each candidate from the value
+    pub fn with_perturbation(mut self, perturbation: f64) -> Self {
+        self.perturbation = perturbation.max(f64::EPSILON);
+        self
+    }
+
+    /// Seeds the random directions for reproducible tuning runs
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Returns the current value
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the number of completed steps
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    /// Proposes the `(plus, minus)` candidates for the next match
+    ///
+    /// Which side of the value is called `plus` is random, so neither
+    /// candidate is systematically tied to the first-move advantage. Report
+    /// the plus candidate's score with [`update`](Self::update).
+    pub fn candidates(&mut self) -> (f64, f64) {
+        let direction = if self.rng.gen::<bool>() { 1.0 } else { -1.0 };
+        self.direction = Some(direction);
+        let offset = direction * self.current_perturbation();
+        (
+            (self.value + offset).clamp(self.min, self.max),
+            (self.value - offset).clamp(self.min, self.max),
+        )
+    }
+
+    /// Moves the value according to the plus candidate's score in `[0, 1]`
+    ///
+    /// Returns the new value. Does nothing unless
+    /// [`candidates`](Self::candidates) was called since the last update.
+    pub fn update(&mut self, plus_score: f64) -> f64 {
+        let Some(direction) = self.direction.take() else {
+            return self.value;
+        };
+
+        let k = self.iteration as f64;
+        let gain = self.step_size / (k + 1.0 + self.stability).powf(0.602);
+        let gradient = (2.0 * plus_score.clamp(0.0, 1.0) - 1.0)
+            / (2.0 * self.current_perturbation() * direction);
+        self.value = (self.value + gain * gradient).clamp(self.min, self.max);
+        self.iteration += 1;
+        self.value
+    }
+
+    /// Runs one step: proposes candidates, scores them, and updates the value
+    ///
+    /// `evaluate` receives `(plus, minus)` and returns the plus candidate's
+    /// score in `[0, 1]`, e.g. from [`play_match`].
+    pub fn step<F: FnOnce(f64, f64) -> f64>(&mut self, evaluate: F) -> f64 {
+        let (plus, minus) = self.candidates();
+        let score = evaluate(plus, minus);
+        self.update(score)
+    }
+
+    fn current_perturbation(&self) -> f64 {
+        self.perturbation / (self.iteration as f64 + 1.0).powf(0.101)
+    }
+}
+
+/// Plays `games` games between two configurations and returns the first one's average score
+///
+/// The configurations take turns moving first. Each move is a fresh search
+/// from the current state with the mover's configuration. Scores come from
+/// [`GameState::get_result`] for the player the first configuration played,
+/// so this suits two-player games where players alternate turns.
+///
+/// # Errors
+///
+/// Returns the first error of any search.
+pub fn play_match<S: GameState + 'static>(
+    initial: &S,
+    first: &MCTSConfig,
+    second: &MCTSConfig,
+    games: usize,
+) -> Result<f64> {
+    if games == 0 {
+        return Ok(0.5);
+    }
+
+    let configs = [first, second];
+    let mut total = 0.0;
+    for game in 0..games {
+        total += play_game(initial, configs, game % 2)?;
+    }
+    Ok(total / games as f64)
+}
+
+/// Plays one game and returns the score of `configs[0]`
+fn play_game<S: GameState + 'static>(
+    initial: &S,
+    configs: [&MCTSConfig; 2],
+    starter: usize,
+) -> Result<f64> {
+    let first_mover = initial.get_current_player();
+    let mut players: [Option<S::Player>; 2] = [None, None];
+    let mut state = initial.clone();
+
+    while !state.is_terminal() {
+        let mover = state.get_current_player();
+        let engine = if mover == first_mover {
+            starter
+        } else {
+            1 - starter
+        };
+        players[engine].get_or_insert_with(|| mover.clone());
+
+        let mut mcts = MCTS::new(state.clone(), configs[engine].clone());
+        let action = mcts.search()?;
+        state = state.apply_action(&action);
+    }
+
+    // A side that never moved is scored as the opponent's complement
+    Ok(match &players {
+        [Some(player), _] => state.get_result(player),
+        [None, Some(player)] => 1.0 - state.get_result(player),
+        [None, None] => 0.5,
+    })
+}
+
+/// Tunes the exploration constant of `base` by one SPSA step of self-play
+///
+/// Plays `games` games between the tuner's two candidates, each a copy of
+/// `base` with that exploration constant, and returns the updated constant.
+/// Call it between real games, or in a loop, and search with
+/// `base.with_exploration_constant(tuner.value())`.
+///
+/// # Errors
+///
+/// Returns the first error of any search.
+pub fn tune_exploration<S: GameState + 'static>(
+    tuner: &mut SpsaTuner,
+    initial: &S,
+    base: &MCTSConfig,
+    games: usize,
+) -> Result<f64> {
+    let (plus, minus) = tuner.candidates();
+    let score = play_match(
+        initial,
+        &base.clone().with_exploration_constant(plus),
+        &base.clone().with_exploration_constant(minus),
+        games,
+    )?;
+    Ok(tuner.update(score))
+}
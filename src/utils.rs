@@ -3,6 +3,69 @@
 //! This module contains various helper functions and utilities used
 //! throughout the MCTS implementation.
 
+use std::cell::RefCell;
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+thread_local! {
+    /// Generator installed for the playout or expansion running on this thread
+    static SEEDED_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Random number generator for policies and game implementations
+///
+/// Draws from the generator the search seeded for the current iteration when
+/// [`MCTSConfig::seed`](crate::MCTSConfig::seed) is set, and from
+/// `rand::thread_rng()` otherwise. Use it in place of `thread_rng` so
+/// seeded searches stay reproducible.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchRng;
+
+impl SearchRng {
+    fn draw<T>(&self, draw: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        SEEDED_RNG.with(|seeded| match seeded.borrow_mut().as_mut() {
+            Some(rng) => draw(rng),
+            None => draw(&mut rand::thread_rng()),
+        })
+    }
+}
+
+impl RngCore for SearchRng {
+    fn next_u32(&mut self) -> u32 {
+        self.draw(|rng| rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draw(|rng| rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.draw(|rng| rng.fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.draw(|rng| rng.try_fill_bytes(dest))
+    }
+}
+
+/// Returns the random number generator policies and games should draw from
+///
+/// See [`SearchRng`].
+pub fn search_rng() -> SearchRng {
+    SearchRng
+}
+
+/// Runs `f` with [`search_rng`] seeded from `seed` on this thread, if given
+pub(crate) fn with_seeded_rng<T>(seed: Option<u64>, f: impl FnOnce() -> T) -> T {
+    let Some(seed) = seed else {
+        return f();
+    };
+    let previous = SEEDED_RNG.with(|seeded| seeded.replace(Some(StdRng::seed_from_u64(seed))));
+    let result = f();
+    SEEDED_RNG.with(|seeded| seeded.replace(previous));
+    result
+}
+
 /// Calculates the exploitation term for UCB1
 ///
 /// This is simply the average reward for a node.
@@ -15,13 +78,15 @@ pub fn exploitation_term(total_reward: f64, visits: u64) -> f64 {
 
 /// Calculates the exploration term for UCB1
 ///
-/// This is the term that encourages exploration of less-visited nodes.
+/// This is the term that encourages exploration of less-visited nodes. A
+/// parent without visits, e.g. one only children carry virtual loss for,
+/// counts as visited once so the term is zero instead of NaN.
 pub fn exploration_term(parent_visits: u64, child_visits: u64, exploration_constant: f64) -> f64 {
     if child_visits == 0 {
         return f64::INFINITY;
     }
 
-    exploration_constant * ((parent_visits as f64).ln() / child_visits as f64).sqrt()
+    exploration_constant * ((parent_visits.max(1) as f64).ln() / child_visits as f64).sqrt()
 }
 
 /// Calculates the UCB1 value for a node
@@ -44,6 +109,93 @@ pub fn ucb1_value(
     exploitation + exploration
 }
 
+/// Number of children scored per batch by [`ucb1_scores`] callers
+///
+/// Batches are kept on the stack, so this bounds the scratch space needed
+/// when scoring wide nodes.
+pub const SCORE_BATCH_SIZE: usize = 64;
+
+/// Calculates UCB1 values for a batch of children at once
+///
+/// `values` and `visits` hold the average reward and visit count of each child
+/// in contiguous arrays, and `ln_parent_visits` is hoisted out of the loop so it
+/// is computed once per node rather than once per child. The loop body is
+/// branch-free so the compiler can vectorize it. Children with zero visits
+/// score `f64::INFINITY`, matching [`ucb1_value`].
+///
+/// # Panics
+///
+/// Panics if the three slices do not have the same length.
+pub fn ucb1_scores(
+    values: &[f64],
+    visits: &[f64],
+    ln_parent_visits: f64,
+    exploration_constant: f64,
+    scores: &mut [f64],
+) {
+    assert_eq!(values.len(), visits.len());
+    assert_eq!(values.len(), scores.len());
+
+    for ((score, &value), &n) in scores.iter_mut().zip(values).zip(visits) {
+        let bounded = value + exploration_constant * (ln_parent_visits / n).sqrt();
+        *score = if n == 0.0 { f64::INFINITY } else { bounded };
+    }
+}
+
+/// Calculates the variance upper bound used by UCB1-Tuned
+///
+/// This is `min(1/4, V + sqrt(2 ln(N) / n))`, where `V` is the sample variance
+/// of the rewards seen through the node. The cap of 1/4 is the maximum variance
+/// of a random variable bounded in [0, 1].
+pub fn ucb1_tuned_variance_bound(
+    total_reward: f64,
+    sum_squared_reward: f64,
+    visits: u64,
+    parent_visits: u64,
+) -> f64 {
+    if visits == 0 {
+        return 0.25;
+    }
+
+    let n = visits as f64;
+    let mean = total_reward / n;
+    let variance = (sum_squared_reward / n - mean * mean).max(0.0);
+    let correction = (2.0 * (parent_visits.max(1) as f64).ln() / n).sqrt();
+
+    f64::min(0.25, variance + correction)
+}
+
+/// Calculates the UCB1-Tuned value for a node
+///
+/// UCB1-Tuned replaces the constant exploration width of UCB1 with one that
+/// shrinks for low-variance nodes:
+///
+/// ```text
+/// UCB1-Tuned = mean + C * sqrt((ln(N) / n) * min(1/4, V + sqrt(2 ln(N) / n)))
+/// ```
+///
+/// With `C = 1.0` this is the formula from Auer et al. (2002).
+pub fn ucb1_tuned_value(
+    total_reward: f64,
+    sum_squared_reward: f64,
+    visits: u64,
+    parent_visits: u64,
+    exploration_constant: f64,
+) -> f64 {
+    if visits == 0 {
+        return f64::INFINITY;
+    }
+
+    let n = visits as f64;
+    let exploitation = exploitation_term(total_reward, visits);
+    let variance_bound =
+        ucb1_tuned_variance_bound(total_reward, sum_squared_reward, visits, parent_visits);
+    let exploration =
+        exploration_constant * ((parent_visits.max(1) as f64).ln() / n * variance_bound).sqrt();
+
+    exploitation + exploration
+}
+
 /// Safely calculates the win rate from wins and visits
 ///
 /// Returns 0.0 if no visits have occurred.
@@ -53,3 +205,67 @@ pub fn win_rate(wins: u64, visits: u64) -> f64 {
     }
     wins as f64 / visits as f64
 }
+
+/// Returns the quantile function (inverse CDF) of the standard normal distribution
+///
+/// Uses Acklam's rational approximation, accurate to about 1e-9 over the
+/// whole range. Returns infinities at `p = 0` and `p = 1` and NaN outside
+/// `[0, 1]`.
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
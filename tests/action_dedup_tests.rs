@@ -0,0 +1,106 @@
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(usize);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Three plies whose move generator lists step 1 twice
+#[derive(Clone, Debug)]
+struct Stutter {
+    moves: Vec<usize>,
+}
+
+impl GameState for Stutter {
+    type Action = Step;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return vec![];
+        }
+        vec![Step(0), Step(1), Step(1), Step(2)]
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut moves = self.moves.clone();
+        moves.push(action.0);
+        Stutter { moves }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.moves.len() >= 3
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.moves.iter().sum::<usize>() as f64 / 6.0
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn search(config: MCTSConfig) -> MCTS<Stutter> {
+    let mut mcts = MCTS::new(Stutter { moves: vec![] }, config.with_max_iterations(300));
+    mcts.search().unwrap();
+    mcts
+}
+
+fn child_ids(mcts: &MCTS<Stutter>) -> Vec<usize> {
+    mcts.root()
+        .children
+        .iter()
+        .map(|child| child.action.as_ref().unwrap().id())
+        .collect()
+}
+
+#[test]
+fn test_duplicates_split_statistics_by_default() {
+    let mcts = search(MCTSConfig::default());
+
+    let mut ids = child_ids(&mcts);
+    ids.sort();
+    assert_eq!(ids, vec![0, 1, 1, 2]);
+    assert_eq!(mcts.get_statistics().duplicate_actions, 0);
+}
+
+#[test]
+fn test_deduplication_keeps_one_child_per_id() {
+    let mcts = search(MCTSConfig::default().with_action_deduplication(true));
+
+    let mut ids = child_ids(&mcts);
+    ids.sort();
+    assert_eq!(ids, vec![0, 1, 2]);
+    for child in &mcts.root().children {
+        assert!(child.children.len() <= 3);
+    }
+
+    // The root and every expanded node below it lost one action each
+    let stats = mcts.get_statistics();
+    assert!(stats.duplicate_actions > 3, "{}", stats.duplicate_actions);
+    assert!(stats.summary().contains("Duplicate actions"));
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_deduplication_passes_strict_mode() {
+    let mcts = search(
+        MCTSConfig::default()
+            .with_action_deduplication(true)
+            .with_strict_mode(true),
+    );
+    assert_eq!(mcts.get_statistics().iterations, 300);
+}
@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    history::HistoryTable,
+    macro_action::{MacroAction, MACRO_KEY_KIND},
+    ActionKey, GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+/// Two kinds of move whose ids overlap
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Move {
+    Place(usize),
+    Remove(usize),
+}
+
+impl Action for Move {
+    fn id(&self) -> usize {
+        match self {
+            Move::Place(square) | Move::Remove(square) => *square,
+        }
+    }
+
+    fn key(&self) -> ActionKey {
+        match self {
+            Move::Place(square) => ActionKey::new(0, *square as u64),
+            Move::Remove(square) => ActionKey::new(1, *square as u64),
+        }
+    }
+}
+
+/// Two moves; removing scores better than placing
+#[derive(Clone, Debug)]
+struct Board {
+    moves: Vec<Move>,
+}
+
+impl GameState for Board {
+    type Action = Move;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return vec![];
+        }
+        vec![
+            Move::Place(0),
+            Move::Place(1),
+            Move::Remove(0),
+            Move::Remove(1),
+        ]
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut moves = self.moves.clone();
+        moves.push(action.clone());
+        Board { moves }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.moves.len() >= 2
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        let removals = self
+            .moves
+            .iter()
+            .filter(|m| matches!(m, Move::Remove(_)))
+            .count();
+        removals as f64 / 2.0
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Plain(usize);
+
+impl Action for Plain {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+#[test]
+fn test_default_key_wraps_the_id() {
+    assert_eq!(Plain(5).key(), ActionKey::from(5));
+    assert_eq!(Plain(5).key(), ActionKey::new(0, 5));
+    assert_ne!(ActionKey::new(1, 5), ActionKey::from(5));
+    assert_eq!(
+        ActionKey::hashed(2, &("e2", "e4")),
+        ActionKey::hashed(2, &("e2", "e4"))
+    );
+    assert_ne!(
+        ActionKey::hashed(2, &("e2", "e4")),
+        ActionKey::hashed(3, &("e2", "e4"))
+    );
+}
+
+#[test]
+fn test_colliding_ids_stay_apart_by_key() {
+    let history = Arc::new(HistoryTable::new(1.0));
+    let config = MCTSConfig::default()
+        .with_max_iterations(200)
+        .with_action_deduplication(true);
+    let mut mcts = MCTS::new(Board { moves: vec![] }, config).with_history(history.clone());
+
+    let best = mcts.search().unwrap();
+    assert!(matches!(best, Move::Remove(_)), "{:?}", best);
+
+    // Deduplication by id would have merged placing and removing
+    assert_eq!(mcts.root().children.len(), 4);
+    assert_eq!(mcts.get_statistics().duplicate_actions, 0);
+    let child = mcts.root().find_child(&Move::Remove(1)).unwrap();
+    assert_eq!(mcts.root().children[child].action, Some(Move::Remove(1)));
+
+    let placed = history.value(&Move::Place(0).key()).unwrap();
+    let removed = history.value(&Move::Remove(0).key()).unwrap();
+    assert!(removed > placed);
+}
+
+#[test]
+fn test_macro_keys_derive_from_primitives() {
+    let single = MacroAction::primitive(Move::Remove(1));
+    assert_eq!(single.key(), Move::Remove(1).key());
+
+    let pair = MacroAction::new(vec![Move::Place(0), Move::Remove(0)]);
+    assert_eq!(pair.key().kind, MACRO_KEY_KIND);
+    assert_eq!(
+        pair.key(),
+        MacroAction::new(vec![Move::Place(0), Move::Remove(0)]).key()
+    );
+    // Same ids, different kinds of primitive
+    assert_ne!(
+        pair.key(),
+        MacroAction::new(vec![Move::Place(0), Move::Place(0)]).key()
+    );
+}
@@ -0,0 +1,165 @@
+use arboriter_mcts::{
+    game_state::{Action, ActionFeatures, NoPlayer},
+    policy::{expansion::ActionSampler, selection::KernelRegressionPolicy},
+    GameState, MCTSConfig, MCTS,
+};
+use rand::{Rng, RngCore};
+
+/// A continuous setting in `[0, 1]`
+#[derive(Clone, Debug, PartialEq)]
+struct Setting(f64);
+
+impl Action for Setting {
+    fn id(&self) -> usize {
+        (self.0 * 1e9) as usize
+    }
+}
+
+impl ActionFeatures for Setting {
+    fn features(&self) -> Vec<f64> {
+        vec![self.0]
+    }
+}
+
+/// Pick two settings whose sum should be as close to 1.4 as possible
+#[derive(Clone, Debug)]
+struct Tuning {
+    settings: Vec<f64>,
+}
+
+impl GameState for Tuning {
+    type Action = Setting;
+    type Player = NoPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        // The action space cannot be listed
+        Vec::new()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut settings = self.settings.clone();
+        settings.push(action.0);
+        Tuning { settings }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.settings.len() == 2
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        let sum: f64 = self.settings.iter().sum();
+        1.0 - (sum - 1.4).abs() / 1.4
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        NoPlayer
+    }
+}
+
+fn sampler(_state: &Tuning, rng: &mut dyn RngCore) -> Setting {
+    Setting(rng.gen_range(0.0..1.0))
+}
+
+/// Plays the rest of the game with random settings
+#[derive(Clone)]
+struct RandomSettings;
+
+impl arboriter_mcts::SimulationPolicy<Tuning> for RandomSettings {
+    fn simulate(&self, state: &Tuning) -> (f64, Vec<Setting>) {
+        let mut rng = rand::thread_rng();
+        let mut state = state.clone();
+        let mut trace = Vec::new();
+        while !state.is_terminal() {
+            let action = sampler(&state, &mut rng);
+            state = state.apply_action(&action);
+            trace.push(action);
+        }
+        (state.get_result(&NoPlayer), trace)
+    }
+
+    fn clone_box(&self) -> Box<dyn arboriter_mcts::SimulationPolicy<Tuning>> {
+        Box::new(self.clone())
+    }
+}
+
+fn tuning() -> Tuning {
+    Tuning {
+        settings: Vec::new(),
+    }
+}
+
+#[test]
+fn test_sampler_draws_actions_on_every_visit_without_widening() {
+    let mut mcts = MCTS::new(tuning(), MCTSConfig::default().with_max_iterations(50))
+        .with_action_sampler(sampler)
+        .with_simulation_policy(RandomSettings);
+    mcts.search().unwrap();
+    assert_eq!(mcts.root().children.len(), 50);
+    assert_eq!(mcts.get_statistics().max_depth, 0);
+}
+
+#[test]
+fn test_sampler_with_progressive_widening_finds_good_settings() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(2000)
+        .with_progressive_widening(1.0, 0.5);
+    let mut mcts = MCTS::new(tuning(), config)
+        .with_action_sampler(sampler)
+        .with_simulation_policy(RandomSettings);
+    let action = mcts.search().unwrap();
+
+    // ceil(sqrt(1999)) children at most, each searched below
+    let root = mcts.root();
+    assert!(root.children.len() <= 45, "{}", root.children.len());
+    assert!(root.children.iter().any(|child| !child.children.is_empty()));
+    assert!(action.0 > 0.3, "{:?}", action);
+}
+
+#[test]
+fn test_sampler_respects_branching_cap() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(200)
+        .with_max_children(4);
+    let mut mcts = MCTS::new(tuning(), config)
+        .with_action_sampler(sampler)
+        .with_simulation_policy(RandomSettings);
+    mcts.search().unwrap();
+    assert_eq!(mcts.root().children.len(), 4);
+    assert!(mcts
+        .root()
+        .children
+        .iter()
+        .all(|child| child.children.len() <= 4));
+}
+
+#[test]
+fn test_sampled_duplicate_revisits_child() {
+    let fixed = |_: &Tuning, _: &mut dyn RngCore| Setting(0.7);
+    let mut mcts = MCTS::new(tuning(), MCTSConfig::default().with_max_iterations(20))
+        .with_action_sampler(fixed)
+        .with_simulation_policy(RandomSettings);
+    let action = mcts.search().unwrap();
+    assert_eq!(action, Setting(0.7));
+    assert_eq!(mcts.root().children.len(), 1);
+    assert_eq!(mcts.root().children[0].visits(), 20);
+
+    let closure: &dyn ActionSampler<Tuning> = &fixed;
+    assert_eq!(
+        closure.sample_action(&tuning(), &mut rand::thread_rng()),
+        Setting(0.7)
+    );
+}
+
+#[test]
+fn test_kernel_regression_with_sampled_actions() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(1000)
+        .with_progressive_widening(1.0, 0.5);
+    let mut mcts = MCTS::new(tuning(), config)
+        .with_action_sampler(sampler)
+        .with_selection_policy(KernelRegressionPolicy::new(0.5, 0.1))
+        .with_simulation_policy(RandomSettings);
+    let action = mcts.search().unwrap();
+    assert!(action.0 > 0.3, "{:?}", action);
+    assert!(mcts.root().children.len() <= 32);
+}
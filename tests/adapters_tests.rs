@@ -0,0 +1,120 @@
+use arboriter_mcts::{
+    adapters::{ClosureEngine, EngineMove, EnginePlayer, EngineState, GameEngine},
+    config::RewardPerspective,
+    game_state::Action,
+    ActionKey, GameState, MCTSConfig, MCTS,
+};
+
+/// Nim on a single pile, as a foreign engine with its own names
+#[derive(Debug)]
+struct Nim {
+    max_take: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Pile {
+    stones: u32,
+    first_to_move: bool,
+}
+
+impl GameEngine for Nim {
+    type Position = Pile;
+    type Move = u32;
+    type Player = bool;
+
+    fn legal_moves(&self, pile: &Pile) -> Vec<u32> {
+        (1..=pile.stones.min(self.max_take)).collect()
+    }
+
+    fn play(&self, pile: &Pile, take: &u32) -> Pile {
+        Pile {
+            stones: pile.stones - take,
+            first_to_move: !pile.first_to_move,
+        }
+    }
+
+    fn to_move(&self, pile: &Pile) -> bool {
+        pile.first_to_move
+    }
+
+    // Taking the last stone wins, so the player to move has lost
+    fn result(&self, pile: &Pile, player: &bool) -> f64 {
+        if pile.first_to_move == *player {
+            0.0
+        } else {
+            1.0
+        }
+    }
+}
+
+fn pile(stones: u32) -> Pile {
+    Pile {
+        stones,
+        first_to_move: true,
+    }
+}
+
+#[test]
+fn test_engine_state_delegates_to_the_engine() {
+    let state = EngineState::new(Nim { max_take: 3 }, pile(2));
+    assert_eq!(state.get_current_player(), EnginePlayer(true));
+
+    let moves = state.get_legal_actions();
+    assert_eq!(moves.iter().map(|m| *m.inner()).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(moves[0].key(), ActionKey::hashed(0, &1u32));
+    assert_eq!(moves[0].id(), moves[0].key().id as usize);
+
+    let end = state.apply_action(&moves[1]);
+    assert_eq!(
+        end.position(),
+        &Pile {
+            stones: 0,
+            first_to_move: false
+        }
+    );
+    assert!(end.is_terminal());
+    assert!(end.get_legal_actions().is_empty());
+    assert_eq!(end.get_result(&EnginePlayer(true)), 1.0);
+    assert_eq!(end.get_result(&EnginePlayer(false)), 0.0);
+    // The original position is untouched
+    assert_eq!(state.position().stones, 2);
+}
+
+#[test]
+fn test_search_over_a_trait_engine() {
+    // From 5 stones, taking 1 leaves the opponent a losing multiple of 4
+    let state = EngineState::new(Nim { max_take: 3 }, pile(5));
+    let config = MCTSConfig::default()
+        .with_max_iterations(3000)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let mut mcts = MCTS::new(state, config);
+    let best: EngineMove<u32> = mcts.search().unwrap();
+    assert_eq!(best.into_inner(), 1);
+}
+
+#[test]
+fn test_closure_engine_with_custom_terminal_and_hash() {
+    // Counting up by 1 or 2; reaching 6 or more ends the game, exactly 6 wins
+    let state = ClosureEngine::new(
+        |_: &u32| vec![1u32, 2],
+        |count: &u32, step: &u32| count + step,
+        |_: &u32| (),
+        |count: &u32, _: &()| if *count == 6 { 1.0 } else { 0.0 },
+    )
+    .with_terminal(|count: &u32| *count >= 6)
+    .with_hash(|count: &u32| *count as u64 + 1)
+    .into_state(4);
+
+    assert!(!state.is_terminal());
+    assert_eq!(state.hash(), 5);
+    assert!(!state
+        .apply_action(&state.get_legal_actions()[0])
+        .is_terminal());
+    assert!(state.with_position(7).get_legal_actions().is_empty());
+
+    let config = MCTSConfig::default()
+        .with_max_iterations(500)
+        .with_transpositions(true);
+    let mut mcts = MCTS::new(state, config);
+    assert_eq!(*mcts.search().unwrap().inner(), 2);
+}
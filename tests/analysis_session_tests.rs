@@ -0,0 +1,265 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use arboriter_mcts::{
+    config::Budget,
+    game_state::{Action, Player},
+    AnalysisSession, GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Climber;
+
+impl Player for Climber {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(usize);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Six picks of 0, 1 or 2; the result is the sum of the picks over their
+/// maximum, so always picking 2 is best
+#[derive(Clone, Debug)]
+struct Ladder {
+    picks: Vec<usize>,
+}
+
+const PICKS: usize = 6;
+
+impl GameState for Ladder {
+    type Action = Step;
+    type Player = Climber;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return Vec::new();
+        }
+        (0..3).map(Step).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut picks = self.picks.clone();
+        picks.push(action.0);
+        Ladder { picks }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picks.len() == PICKS
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.picks.iter().sum::<usize>() as f64 / (2 * PICKS) as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Climber
+    }
+
+    fn hash(&self) -> u64 {
+        self.picks
+            .iter()
+            .fold(1, |hash, &pick| hash * 4 + pick as u64)
+    }
+}
+
+fn start() -> Ladder {
+    Ladder { picks: Vec::new() }
+}
+
+fn session() -> AnalysisSession<Ladder> {
+    AnalysisSession::new(MCTS::new(start(), MCTSConfig::default())).with_slice(200)
+}
+
+#[test]
+fn test_go_spends_the_iteration_budget() {
+    let mut session = session();
+    session.go(Budget::Iterations(1500));
+    let best = session.wait().unwrap();
+
+    assert!(!session.is_searching());
+    assert_eq!(session.iterations(), 1500);
+    assert_eq!(session.mcts().unwrap().root().visits(), 1500);
+
+    let lines = session.lines(2);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].action, best);
+    assert_eq!(lines[0].moves[0], lines[0].action);
+    assert!(lines[0].visits >= lines[1].visits);
+    assert!(lines[0].moves.len() > 1);
+}
+
+#[test]
+fn test_lines_update_while_searching() {
+    let mut session = session();
+    session.go(Budget::Unlimited);
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while session.lines(1).is_empty() || session.iterations() < 1000 {
+        assert!(Instant::now() < deadline, "no lines were published");
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(session.is_searching());
+    assert!(session.mcts().is_none());
+
+    let best = session.stop().unwrap();
+    assert!(!session.is_searching());
+    assert_eq!(best, Step(2));
+
+    // The instance's own stop token is left clear for later searches
+    let mut mcts = session.into_inner();
+    let visits = mcts.root().visits();
+    mcts.search_for_iterations(100).unwrap();
+    assert_eq!(mcts.root().visits(), visits + 100);
+}
+
+#[test]
+fn test_stop_token_stops_from_another_thread() {
+    let mut session = session();
+    session.go(Budget::Unlimited);
+    let token = session.stop_token();
+
+    let stopper = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        token.stop();
+    });
+    assert!(session.wait().is_ok());
+    stopper.join().unwrap();
+    assert!(session.iterations() > 0);
+}
+
+#[test]
+fn test_searches_continue_on_the_same_tree() {
+    let mut session = session();
+    session.go(Budget::Iterations(300));
+    session.wait().unwrap();
+    session.go(Budget::Iterations(300));
+    session.wait().unwrap();
+
+    assert_eq!(session.iterations(), 300);
+    assert_eq!(session.mcts().unwrap().root().visits(), 600);
+}
+
+#[test]
+fn test_time_budget_ends_the_search() {
+    let mut session = session();
+    let started = Instant::now();
+    session.go(Budget::Time(Duration::from_millis(50)));
+    session.wait().unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(session.iterations() > 0);
+}
+
+#[test]
+fn test_set_position_keeps_the_subtree() {
+    let mut session = session();
+    session.go(Budget::Iterations(2000));
+    session.wait().unwrap();
+
+    // Our move and the next one are both within reach of the old root
+    let next = start().apply_action(&Step(2)).apply_action(&Step(2));
+    let visits = {
+        let root = session.mcts().unwrap().root();
+        let child = &root.children[root.find_child(&Step(2)).unwrap()];
+        child.children[child.find_child(&Step(2)).unwrap()].visits()
+    };
+    assert!(session.set_position(next));
+
+    let mcts = session.mcts().unwrap();
+    assert_eq!(mcts.root().visits(), visits);
+    assert_eq!(mcts.root().depth, 0);
+    assert!(mcts.check_invariants().is_ok());
+    assert!(!session.lines(3).is_empty());
+
+    // A position the tree does not hold starts over
+    assert!(!session.set_position(start().apply_action(&Step(0)).apply_action(&Step(0))));
+    assert_eq!(session.mcts().unwrap().root().visits(), 0);
+    assert!(session.lines(3).is_empty());
+}
+
+#[test]
+fn test_advance_root_without_a_hash_starts_over() {
+    #[derive(Clone, Debug)]
+    struct Unhashed(Ladder);
+
+    impl GameState for Unhashed {
+        type Action = Step;
+        type Player = Climber;
+
+        fn get_legal_actions(&self) -> Vec<Step> {
+            self.0.get_legal_actions()
+        }
+        fn apply_action(&self, action: &Step) -> Self {
+            Unhashed(self.0.apply_action(action))
+        }
+        fn is_terminal(&self) -> bool {
+            self.0.is_terminal()
+        }
+        fn get_result(&self, player: &Climber) -> f64 {
+            self.0.get_result(player)
+        }
+        fn get_current_player(&self) -> Climber {
+            Climber
+        }
+    }
+
+    let mut mcts = MCTS::new(Unhashed(start()), MCTSConfig::default());
+    mcts.search_for_iterations(200).unwrap();
+    assert!(!mcts.advance_root(Unhashed(start().apply_action(&Step(2)))));
+    assert_eq!(mcts.root().visits(), 0);
+}
+
+#[test]
+fn test_principal_variation_follows_the_most_visited_children() {
+    let mut mcts = MCTS::new(start(), MCTSConfig::default().with_max_iterations(3000));
+    let best = mcts.search().unwrap();
+
+    let pv = mcts.principal_variation();
+    assert_eq!(pv[0], best);
+    assert!(pv.len() <= PICKS);
+    assert_eq!(mcts.top_lines(1)[0].moves, pv);
+    assert_eq!(mcts.top_lines(10).len(), 3);
+}
+
+#[test]
+fn test_extracted_plan_stops_where_visits_run_out() {
+    let mut mcts = MCTS::new(start(), MCTSConfig::default().with_max_iterations(3000));
+    mcts.search().unwrap();
+    let pv = mcts.principal_variation();
+
+    let plan = mcts.extract_plan(PICKS, 0);
+    assert_eq!(plan.len(), pv.len());
+    let actions: Vec<Step> = plan.iter().map(|step| step.action.clone()).collect();
+    assert_eq!(actions, pv);
+
+    // Visits and confidence only shrink along the plan
+    let mut parent_visits = mcts.root().visits();
+    let mut confidence = 1.0;
+    for step in &plan {
+        assert!(step.visits <= parent_visits);
+        assert!((step.share - step.visits as f64 / parent_visits as f64).abs() < 1e-12);
+        assert!((step.confidence - confidence * step.share).abs() < 1e-12);
+        assert!(step.confidence <= confidence);
+        parent_visits = step.visits;
+        confidence = step.confidence;
+    }
+
+    assert!(plan.len() >= 2);
+    let truncated = mcts.extract_plan(PICKS, plan[1].visits + 1);
+    assert_eq!(truncated[..], plan[..1]);
+    assert_eq!(mcts.extract_plan(2, 0)[..], plan[..2]);
+}
+
+#[test]
+fn test_continue_search_keeps_a_pooled_tree() {
+    let mut mcts = MCTS::with_node_pool(start(), MCTSConfig::default(), 100);
+    mcts.continue_search(Budget::Iterations(150)).unwrap();
+    mcts.continue_search(Budget::Iterations(150)).unwrap();
+
+    assert_eq!(mcts.root().visits(), 300);
+    assert_eq!(mcts.config().max_time, None);
+}
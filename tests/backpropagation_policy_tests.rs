@@ -1,6 +1,9 @@
 use arboriter_mcts::{
     game_state::{Action, Player},
-    policy::backpropagation::{BackpropagationPolicy, RavePolicy, StandardPolicy, WeightedPolicy},
+    policy::backpropagation::{
+        BackpropContext, BackpropagationPolicy, GaussianPolicy, PowerMeanPolicy, RavePolicy,
+        StandardPolicy, WeightedPolicy,
+    },
     tree::MCTSNode,
     GameState,
 };
@@ -173,6 +176,61 @@ fn test_rave_policy() {
     assert_eq!(child.rave_value(), 1.0); // No change
 }
 
+#[test]
+fn test_power_mean_policy() {
+    let state = TestGameState {
+        terminal: false,
+        player: TestPlayer(1),
+    };
+    let mut node = MCTSNode::new(state, None, None, 0);
+    let policy = PowerMeanPolicy::new(4.0);
+
+    // Without visited children the node averages like the standard policy
+    policy.update_stats(&mut node, 0.2, None);
+    assert_eq!(node.visits(), 1);
+    assert!((node.value() - 0.2).abs() < 1e-6);
+
+    // One strong child and one weak one, as if updated leaf first
+    node.expand(0);
+    node.expand(0);
+    node.children[0].increment_visits();
+    node.children[0].add_reward(1.0);
+    node.children[1].increment_visits();
+    node.children[1].add_reward(0.0);
+    policy.update_stats(&mut node, 0.0, None);
+
+    // (0.5 * 1^4 + 0.5 * 0^4)^(1/4) is well above the plain average of 0.5
+    let expected = 0.5f64.powf(0.25);
+    assert_eq!(node.visits(), 2);
+    assert!((node.value() - expected).abs() < 1e-6);
+    assert!((node.power_mean_value(1.0) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_gaussian_policy() {
+    let state = TestGameState {
+        terminal: false,
+        player: TestPlayer(1),
+    };
+    let mut node = MCTSNode::new(state, None, None, 0);
+    let policy = GaussianPolicy::new(0.5, 1.0, 4.0);
+    assert_eq!(node.posterior_precision(), 0.0);
+
+    // One win moves the mean from the prior 0.5 towards 1.0: (0.5 + 4) / 5
+    policy.update_stats(&mut node, 1.0, None);
+    assert!((node.posterior_mean() - 0.9).abs() < 1e-6);
+    assert!((node.posterior_precision() - 5.0).abs() < 1e-6);
+
+    // A loss pulls it back: (5 * 0.9 + 0) / 9
+    policy.update_stats(&mut node, 0.0, None);
+    assert!((node.posterior_mean() - 0.5).abs() < 1e-6);
+    assert!((node.posterior_precision() - 9.0).abs() < 1e-6);
+
+    // Ordinary statistics are kept too
+    assert_eq!(node.visits(), 2);
+    assert_eq!(node.total_reward(), 1.0);
+}
+
 #[test]
 fn test_backpropagation_policy_cloning() {
     let policy = StandardPolicy::new();
@@ -181,3 +239,97 @@ fn test_backpropagation_policy_cloning() {
 
     // Just testing that we don't panic
 }
+
+#[test]
+fn test_update_with_context_defaults_to_update_stats() {
+    let state = TestGameState {
+        terminal: true,
+        player: TestPlayer(1),
+    };
+    let mut node = MCTSNode::new(state.clone(), Some(TestAction(1)), None, 1);
+
+    // RAVE reads the trace from the context when called through the default
+    let trace = vec![TestAction(1)];
+    let mut context = BackpropContext::new(&state);
+    context.trace = Some(&trace);
+
+    let policy = RavePolicy::new(0.5);
+    policy.update_with_context(&mut node, 1.0, &context);
+
+    assert_eq!(node.visits(), 1);
+    assert_eq!(node.rave_visits(), 1);
+}
+
+#[test]
+fn test_shared_updates_from_many_threads() {
+    let state = TestGameState {
+        terminal: false,
+        player: TestPlayer(1),
+    };
+    let mut root = MCTSNode::new(state.clone(), None, None, 0);
+    root.expand(1).unwrap();
+    let root = root;
+    let child = &root.children[0];
+
+    let trace = [TestAction(1)];
+    let context = BackpropContext {
+        trace: Some(&trace),
+        ..BackpropContext::new(&state)
+    };
+
+    // Eight workers backpropagate along the same path at once, holding only
+    // shared references to the tree
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    for node in [child, &root] {
+                        assert!(StandardPolicy::new().update_shared(node, 0.5, &context));
+                        assert!(RavePolicy::new(0.5).update_shared(node, 0.25, &context));
+                    }
+                }
+            });
+        }
+    });
+
+    for node in [child, &root] {
+        assert_eq!(node.visits(), 16_000);
+        assert!((node.total_reward() - 6_000.0).abs() < 1e-5);
+    }
+    // Only the child has an action, and it was played in the trace
+    assert_eq!(child.rave_visits(), 8_000);
+    assert!((child.rave_value() - 0.25).abs() < 1e-5);
+    assert_eq!(root.rave_visits(), 0);
+}
+
+#[test]
+fn test_weighted_shared_update_matches_exclusive_update() {
+    let state = TestGameState {
+        terminal: false,
+        player: TestPlayer(1),
+    };
+    let policy = WeightedPolicy::new(0.5);
+    let mut exclusive = MCTSNode::new(state.clone(), None, None, 2);
+    let shared = MCTSNode::new(state.clone(), None, None, 2);
+
+    policy.update_stats(&mut exclusive, 0.8, None);
+    assert!(policy.update_shared(&shared, 0.8, &BackpropContext::new(&state)));
+
+    assert_eq!(shared.visits(), exclusive.visits());
+    assert_eq!(shared.total_reward(), exclusive.total_reward());
+}
+
+#[test]
+fn test_policies_needing_exclusive_access_decline_shared_updates() {
+    let state = TestGameState {
+        terminal: false,
+        player: TestPlayer(1),
+    };
+    let node = MCTSNode::new(state.clone(), None, None, 0);
+    let context = BackpropContext::new(&state);
+
+    assert!(!PowerMeanPolicy::new(2.0).update_shared(&node, 0.5, &context));
+    assert!(!GaussianPolicy::default().update_shared(&node, 0.5, &context));
+    assert_eq!(node.visits(), 0);
+    assert_eq!(node.total_reward(), 0.0);
+}
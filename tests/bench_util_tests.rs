@@ -0,0 +1,42 @@
+#![cfg(feature = "bench-util")]
+
+use arboriter_mcts::{
+    bench::{run_profile, run_profile_with, BenchGameState, ProfileConfig},
+    policy::selection::UCB1TunedPolicy,
+    GameState, MCTSConfig,
+};
+
+#[test]
+fn test_bench_game_shape() {
+    let state = BenchGameState::new(3, 2);
+    assert_eq!(state.get_legal_actions().len(), 3);
+
+    let leaf = state
+        .apply_action(&state.get_legal_actions()[0])
+        .apply_action(&state.get_legal_actions()[1]);
+    assert!(leaf.is_terminal());
+    assert!(leaf.get_legal_actions().is_empty());
+}
+
+#[test]
+fn test_run_profile_collects_every_search() {
+    let profile = ProfileConfig::new(3, 4)
+        .with_mcts_config(MCTSConfig::default().with_max_iterations(100))
+        .with_searches(3);
+
+    let report = run_profile(&profile);
+    assert_eq!(report.runs.len(), 3);
+    assert_eq!(report.total_iterations(), 300);
+    assert!(report.iterations_per_second() > 0.0);
+}
+
+#[test]
+fn test_run_profile_with_custom_policy() {
+    let profile =
+        ProfileConfig::new(5, 3).with_mcts_config(MCTSConfig::default().with_max_iterations(50));
+
+    let report = run_profile_with(&profile, |mcts| {
+        mcts.with_selection_policy(UCB1TunedPolicy::new(1.0))
+    });
+    assert_eq!(report.total_iterations(), 50);
+}
@@ -0,0 +1,210 @@
+use arboriter_mcts::{
+    book::{export_records, BookRecord, OpeningBook},
+    config::RewardPerspective,
+    game_state::{Action, ActionKey, Player},
+    GameState, MCTSConfig, MCTSNode, MCTS,
+};
+
+/// Nim with one pile: take one or two stones, whoever takes the last one wins
+#[derive(Clone, Debug)]
+struct Nim {
+    stones: u32,
+    to_move: Side,
+    hashed: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    A,
+    B,
+}
+
+impl Player for Side {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Take(u32);
+
+impl Action for Take {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl GameState for Nim {
+    type Action = Take;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.stones.min(2)).map(Take).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Nim {
+            stones: self.stones - action.0,
+            to_move: match self.to_move {
+                Side::A => Side::B,
+                Side::B => Side::A,
+            },
+            hashed: self.hashed,
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stones == 0
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        // The player who just moved took the last stone
+        if *for_player == self.to_move {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.to_move
+    }
+
+    fn hash(&self) -> u64 {
+        if !self.hashed {
+            return 0;
+        }
+        1 + 2 * self.stones as u64 + (self.to_move == Side::B) as u64
+    }
+}
+
+fn nim(stones: u32) -> Nim {
+    Nim {
+        stones,
+        to_move: Side::A,
+        hashed: true,
+    }
+}
+
+fn searched(state: Nim, iterations: usize) -> MCTS<Nim> {
+    let config = MCTSConfig::default()
+        .with_max_iterations(iterations)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let mut mcts = MCTS::new(state, config);
+    mcts.search().unwrap();
+    mcts
+}
+
+/// Counts the edges whose child has at least `min_visits` visits
+fn edges_with(node: &MCTSNode<Nim>, min_visits: u64) -> usize {
+    node.children
+        .iter()
+        .filter(|child| child.visits() >= min_visits)
+        .map(|child| 1 + edges_with(child, min_visits))
+        .sum()
+}
+
+#[test]
+fn test_export_keeps_edges_above_the_threshold() {
+    let mcts = searched(nim(7), 500);
+    let records = export_records(mcts.root(), 20);
+
+    assert_eq!(records.len(), edges_with(mcts.root(), 20));
+    assert!(records.iter().all(|record| record.visits >= 20));
+
+    // The root's edges come first and match its children
+    let root_hash = nim(7).hash();
+    let children = mcts
+        .root()
+        .children
+        .iter()
+        .filter(|child| child.visits() >= 20);
+    for (record, child) in records.iter().zip(children) {
+        assert_eq!(record.state_hash, root_hash);
+        assert_eq!(record.action, child.action.as_ref().unwrap().key());
+        assert_eq!(record.visits, child.visits());
+        assert_eq!(record.value, child.value());
+    }
+
+    // A threshold above the root's children exports nothing
+    assert!(export_records(mcts.root(), 501).is_empty());
+}
+
+#[test]
+fn test_unhashed_states_are_not_exported() {
+    let state = Nim {
+        hashed: false,
+        ..nim(5)
+    };
+    let mcts = searched(state, 200);
+    assert!(export_records(mcts.root(), 1).is_empty());
+}
+
+#[test]
+fn test_book_sums_records_across_games() {
+    let mut book = OpeningBook::new();
+    assert!(book.is_empty());
+    let records = [
+        BookRecord {
+            state_hash: 15,
+            action: ActionKey::from(1),
+            visits: 30,
+            value: 0.2,
+        },
+        BookRecord {
+            state_hash: 15,
+            action: ActionKey::from(2),
+            visits: 10,
+            value: 0.9,
+        },
+    ];
+    book.add_records(records);
+    book.add_records([BookRecord {
+        visits: 10,
+        value: 0.6,
+        ..records[0]
+    }]);
+
+    assert_eq!(book.len(), 2);
+    let known = book.lookup(&nim(7));
+    assert_eq!(known.len(), 2);
+    assert_eq!(known[0].0, Take(1));
+    assert_eq!(known[0].1.visits, 40.0);
+    assert!((known[0].1.value() - 0.3).abs() < 1e-12);
+
+    // Other positions are unknown
+    assert!(book.lookup(&nim(6)).is_empty());
+
+    // Exported records restore the same book
+    let restored = OpeningBook::from_records(book.records());
+    assert_eq!(restored.records(), book.records());
+}
+
+#[test]
+fn test_book_warm_starts_the_root() {
+    let mut book = OpeningBook::new();
+    for _ in 0..3 {
+        book.record(searched(nim(7), 300).root(), 5);
+    }
+
+    let config = MCTSConfig::default()
+        .with_max_iterations(50)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let mut mcts = MCTS::new(nim(7), config);
+    assert_eq!(book.warm_start(&mut mcts, 25).unwrap(), 2);
+
+    let root = mcts.root();
+    assert_eq!(root.visits(), 50);
+    for child in &root.children {
+        assert_eq!(child.visits(), 25);
+        let (_, entry) = book
+            .lookup(&nim(7))
+            .into_iter()
+            .find(|(action, _)| Some(action) == child.action.as_ref())
+            .unwrap();
+        assert!((child.value() - entry.value()).abs() < 1e-6);
+    }
+
+    // Taking one stone from seven leaves six, a loss for the side to move
+    assert_eq!(mcts.search().unwrap(), Take(1));
+
+    // Positions the book does not know are left alone
+    let mut unknown = MCTS::new(nim(8), MCTSConfig::default());
+    assert_eq!(book.warm_start(&mut unknown, 25).unwrap(), 0);
+}
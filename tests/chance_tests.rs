@@ -0,0 +1,255 @@
+use arboriter_mcts::{
+    game_state::{sample_chance_outcome, Action, NoPlayer},
+    GameState, MCTSConfig, MCTSError, MCTS,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Bet {
+    Safe,
+    Gamble,
+}
+
+impl Action for Bet {
+    fn id(&self) -> usize {
+        match self {
+            Bet::Safe => 0,
+            Bet::Gamble => 1,
+        }
+    }
+}
+
+/// Take a sure 0.6 or roll a die that wins three times in four
+#[derive(Clone, Debug, PartialEq)]
+enum Dice {
+    Choose,
+    Rolling,
+    Done(f64),
+}
+
+impl GameState for Dice {
+    type Action = Bet;
+    type Player = NoPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self {
+            Dice::Choose => vec![Bet::Safe, Bet::Gamble],
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        match action {
+            Bet::Safe => Dice::Done(0.6),
+            Bet::Gamble => Dice::Rolling,
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(self, Dice::Done(_))
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        match self {
+            Dice::Done(result) => *result,
+            _ => 0.5,
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        NoPlayer
+    }
+
+    fn chance_outcomes(&self) -> Option<Vec<(Self, f64)>> {
+        match self {
+            Dice::Rolling => Some(vec![(Dice::Done(1.0), 3.0), (Dice::Done(0.0), 1.0)]),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_afterstate_becomes_chance_node() {
+    let mut mcts = MCTS::new(
+        Dice::Choose,
+        MCTSConfig::default().with_max_iterations(2000),
+    );
+    let action = mcts.search().unwrap();
+    assert_eq!(action, Bet::Gamble);
+
+    let root = mcts.root();
+    let gamble = &root.children[root.find_child(&Bet::Gamble).unwrap()];
+    assert!(gamble.is_chance());
+    assert!(gamble.unexpanded_actions.is_empty());
+    assert_eq!(gamble.children.len(), 2);
+    assert!(gamble
+        .children
+        .iter()
+        .all(|outcome| outcome.action.is_none()));
+    assert!((gamble.children[0].prior() - 0.75).abs() < 1e-6);
+    assert!((gamble.value() - 0.75).abs() < 0.1, "{}", gamble.value());
+
+    // Outcomes are visited in proportion to their probabilities
+    let share = gamble.children[0].visits() as f64 / gamble.visits() as f64;
+    assert!((share - 0.75).abs() < 0.1, "{}", share);
+
+    let safe = &root.children[root.find_child(&Bet::Safe).unwrap()];
+    assert!(!safe.is_chance());
+}
+
+#[test]
+fn test_chance_root_is_rejected() {
+    let mut mcts = MCTS::new(Dice::Choose, MCTSConfig::default().with_max_iterations(10));
+    mcts.apply_move(&Bet::Gamble);
+    assert!(mcts.root().is_chance());
+    assert!(matches!(
+        mcts.search(),
+        Err(MCTSError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_playouts_resolve_chance_events() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let wins = (0..1000)
+        .filter(|_| sample_chance_outcome(&Dice::Rolling, &mut rng) == Some(Dice::Done(1.0)))
+        .count();
+    assert!((650..850).contains(&wins), "{}", wins);
+    assert_eq!(sample_chance_outcome(&Dice::Choose, &mut rng), None);
+
+    let (result, trace) = Dice::Rolling.simulate_random_playout(&NoPlayer);
+    assert!(result == 1.0 || result == 0.0);
+    assert!(trace.is_empty());
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(i32);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        (self.0 + 1) as usize
+    }
+}
+
+/// Walk towards a target on a line; every step is blurred by continuous noise
+#[derive(Clone, Debug)]
+struct NoisyWalk {
+    position: f64,
+    steps_left: u32,
+    pending: bool,
+}
+
+impl NoisyWalk {
+    fn new() -> Self {
+        NoisyWalk {
+            position: 0.0,
+            steps_left: 3,
+            pending: false,
+        }
+    }
+}
+
+impl GameState for NoisyWalk {
+    type Action = Step;
+    type Player = NoPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() || self.pending {
+            Vec::new()
+        } else {
+            vec![Step(-1), Step(0), Step(1)]
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        NoisyWalk {
+            position: self.position + action.0 as f64,
+            steps_left: self.steps_left - 1,
+            pending: true,
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.steps_left == 0 && !self.pending
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        (1.0 - (self.position - 3.0).abs() / 6.0).clamp(0.0, 1.0)
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        NoPlayer
+    }
+
+    fn is_chance_event(&self) -> bool {
+        self.pending
+    }
+
+    fn sample_chance_outcome(&self, rng: &mut dyn rand::RngCore) -> Option<Self> {
+        use rand::Rng;
+
+        self.pending.then(|| NoisyWalk {
+            position: self.position + rng.gen_range(-0.3..0.3),
+            pending: false,
+            ..self.clone()
+        })
+    }
+}
+
+#[test]
+fn test_sampled_outcomes_grow_with_visits() {
+    // Without outcome widening every visit draws a fresh outcome
+    let mut mcts = MCTS::new(
+        NoisyWalk::new(),
+        MCTSConfig::default().with_max_iterations(300),
+    );
+    mcts.search().unwrap();
+    let root = mcts.root();
+    let child = &root.children[0];
+    assert!(child.is_chance() && child.sampled_outcomes);
+    assert_eq!(child.children.len() as u64, child.visits());
+
+    // With it, outcomes are limited by the square root of the visits
+    let mut mcts = MCTS::new(
+        NoisyWalk::new(),
+        MCTSConfig::default()
+            .with_max_iterations(300)
+            .with_outcome_widening(1.0, 0.5),
+    );
+    let action = mcts.search().unwrap();
+    assert_eq!(action, Step(1));
+    for child in &mcts.root().children {
+        let limit = (child.visits() as f64).sqrt().ceil() as usize;
+        assert!(
+            child.children.len() <= limit.max(1),
+            "{}",
+            child.children.len()
+        );
+        assert!(child
+            .children
+            .iter()
+            .all(|outcome| outcome.action.is_none()));
+    }
+    assert!(mcts.get_statistics().max_depth > 2);
+}
+
+#[test]
+fn test_progressive_widening_limits_children() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_progressive_widening(1.0, 0.5)
+        .with_outcome_widening(1.0, 0.5);
+    let mut mcts = MCTS::new(NoisyWalk::new(), config);
+    mcts.search().unwrap();
+
+    // The root allows ceil(sqrt(99)) children, but there are only three actions
+    assert_eq!(mcts.root().children.len(), 3);
+
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_progressive_widening(0.5, 0.25);
+    let mut mcts = MCTS::new(NoisyWalk::new(), config);
+    mcts.search().unwrap();
+    assert_eq!(mcts.root().children.len(), 2);
+    assert_eq!(mcts.root().unexpanded_actions.len(), 1);
+}
@@ -0,0 +1,108 @@
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    tree::{AtomicReward, AtomicVisits, MCTSNode, MAX_ACCUMULATED_REWARD},
+    GameState,
+};
+use std::sync::atomic::Ordering;
+
+#[derive(Clone, Debug)]
+struct TestGameState;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TestPlayer;
+
+impl Player for TestPlayer {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TestAction;
+
+impl Action for TestAction {
+    fn id(&self) -> usize {
+        0
+    }
+}
+
+impl GameState for TestGameState {
+    type Action = TestAction;
+    type Player = TestPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        vec![TestAction]
+    }
+
+    fn apply_action(&self, _action: &Self::Action) -> Self {
+        TestGameState
+    }
+
+    fn is_terminal(&self) -> bool {
+        false
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        0.5
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        TestPlayer
+    }
+}
+
+#[test]
+fn test_stats_accessors_round_trip() {
+    let node = MCTSNode::new(TestGameState, None, None, 0);
+
+    for _ in 0..1000 {
+        node.increment_visits();
+        node.add_reward(0.75);
+        node.add_squared_reward(0.75);
+    }
+    node.set_prior(0.3);
+
+    // The accessor API is f64/u64 regardless of the storage mode
+    assert_eq!(node.visits(), 1000);
+    assert!((node.total_reward() - 750.0).abs() < 1e-3);
+    assert!((node.value() - 0.75).abs() < 1e-6);
+    assert!((node.sum_squared_reward() - 562.5).abs() < 1e-3);
+    assert!((node.prior() - 0.3).abs() < 1e-6);
+}
+
+#[test]
+fn test_reward_totals_saturate_instead_of_wrapping() {
+    let node = MCTSNode::new(TestGameState, None, None, 0);
+    node.add_reward(1.0);
+    assert!(!node.saturated.load(Ordering::Relaxed));
+
+    for _ in 0..3 {
+        node.add_reward(MAX_ACCUMULATED_REWARD * 0.75);
+    }
+    assert!(node.saturated.load(Ordering::Relaxed));
+    assert!(node.total_reward() >= MAX_ACCUMULATED_REWARD * 0.99);
+    assert!(node.total_reward().is_finite());
+}
+
+#[cfg(feature = "compact-stats")]
+#[test]
+fn test_compact_stats_use_32_bit_storage() {
+    assert_eq!(std::mem::size_of::<AtomicVisits>(), 4);
+    assert_eq!(std::mem::size_of::<AtomicReward>(), 4);
+}
+
+#[cfg(not(feature = "compact-stats"))]
+#[test]
+fn test_default_stats_use_64_bit_storage() {
+    assert_eq!(std::mem::size_of::<AtomicVisits>(), 8);
+    assert_eq!(std::mem::size_of::<AtomicReward>(), 8);
+}
+
+#[cfg(all(feature = "f64-stats", not(feature = "compact-stats")))]
+#[test]
+fn test_f64_stats_keep_small_and_negative_rewards() {
+    let node = MCTSNode::new(TestGameState, None, None, 0);
+    node.increment_visits();
+    node.add_reward(0.5 + 1e-9);
+    assert_eq!(node.total_reward(), 0.5 + 1e-9);
+
+    node.increment_visits();
+    node.add_reward(-2.0);
+    assert!((node.value() - (-1.5 + 1e-9) / 2.0).abs() < 1e-15);
+}
@@ -1,4 +1,7 @@
-use arboriter_mcts::{config::BestChildCriteria, MCTSConfig};
+use arboriter_mcts::{
+    config::{BestChildCriteria, Budget, ProgressiveWidening, RewardPerspective, RewardTransform},
+    MCTSConfig,
+};
 use std::time::Duration;
 
 #[test]
@@ -9,6 +12,7 @@ fn test_config_builder_methods() {
         .with_max_iterations(5000)
         .with_max_time(Duration::from_secs(30))
         .with_max_depth(20)
+        .with_max_children(8)
         .with_transpositions(true)
         .with_best_child_criteria(BestChildCriteria::HighestValue);
 
@@ -17,7 +21,8 @@ fn test_config_builder_methods() {
     assert_eq!(config.max_iterations, 5000);
     assert_eq!(config.max_time, Some(Duration::from_secs(30)));
     assert_eq!(config.max_depth, Some(20));
-    assert_eq!(config.use_transpositions, true);
+    assert_eq!(config.max_children, Some(8));
+    assert!(config.use_transpositions);
     assert_eq!(config.best_child_criteria, BestChildCriteria::HighestValue);
 }
 
@@ -31,6 +36,107 @@ fn test_config_default_values() {
     assert_eq!(config.max_iterations, 10_000);
     assert_eq!(config.max_time, None);
     assert_eq!(config.max_depth, None);
-    assert_eq!(config.use_transpositions, false);
+    assert_eq!(config.max_children, None);
+    assert!(!config.use_transpositions);
     assert_eq!(config.best_child_criteria, BestChildCriteria::MostVisits);
 }
+
+#[test]
+fn test_reward_transforms() {
+    assert_eq!(RewardTransform::Identity.apply(42.0, None), 42.0);
+
+    let clamp = RewardTransform::Clamp { min: 0.0, max: 1.0 };
+    assert_eq!(clamp.apply(3.0, None), 1.0);
+    assert_eq!(clamp.apply(-3.0, None), 0.0);
+
+    let min_max = RewardTransform::MinMax;
+    assert_eq!(min_max.apply(15.0, Some((10.0, 30.0))), 0.25);
+    assert_eq!(min_max.apply(10.0, Some((10.0, 10.0))), 0.5);
+
+    let tanh = RewardTransform::Tanh { scale: 10.0 };
+    assert_eq!(tanh.apply(0.0, None), 0.5);
+    assert!(tanh.apply(1000.0, None) > 0.99);
+    assert!(tanh.apply(-1000.0, None) < 0.01);
+
+    let custom = RewardTransform::Custom(|result| result / 100.0);
+    assert_eq!(custom.apply(50.0, None), 0.5);
+
+    assert!(matches!(
+        MCTSConfig::default().reward_transform,
+        RewardTransform::Identity
+    ));
+}
+
+#[test]
+fn test_reward_perspective() {
+    assert_eq!(
+        MCTSConfig::default().reward_perspective,
+        RewardPerspective::Shared
+    );
+
+    let shared = RewardPerspective::Shared;
+    assert_eq!(shared.reward_for(0.8, &1, &2), 0.8);
+
+    let per_player = RewardPerspective::PerPlayer;
+    assert_eq!(per_player.reward_for(0.8, &1, &1), 0.8);
+    assert!((per_player.reward_for(0.8, &1, &2) - 0.2).abs() < 1e-12);
+}
+
+#[test]
+fn test_progressive_widening_schedule() {
+    let config = MCTSConfig::default();
+    assert_eq!(config.action_widening, None);
+    assert_eq!(config.outcome_widening, None);
+
+    let config = config
+        .with_progressive_widening(2.0, 0.5)
+        .with_outcome_widening(1.0, 0.25);
+    assert_eq!(
+        config.action_widening,
+        Some(ProgressiveWidening::new(2.0, 0.5))
+    );
+    assert_eq!(
+        config.outcome_widening,
+        Some(ProgressiveWidening::new(1.0, 0.25))
+    );
+
+    let widening = ProgressiveWidening::new(2.0, 0.5);
+    assert_eq!(widening.max_children(0), 1);
+    assert_eq!(widening.max_children(1), 2);
+    assert_eq!(widening.max_children(10), 7);
+    assert_eq!(ProgressiveWidening::new(0.1, 0.5).max_children(4), 1);
+}
+
+#[test]
+fn test_budget_view_of_limits() {
+    let second = Duration::from_secs(1);
+    let config = MCTSConfig::default();
+    assert_eq!(config.budget(), Budget::Iterations(10_000));
+    assert_eq!(
+        config.with_max_time(second).budget(),
+        Budget::Both(10_000, second)
+    );
+
+    // Zero and usize::MAX both mean no iteration limit
+    for unlimited in [0, usize::MAX] {
+        let config = MCTSConfig::default().with_max_iterations(unlimited);
+        assert_eq!(config.budget(), Budget::Unlimited);
+        assert_eq!(config.with_max_time(second).budget(), Budget::Time(second));
+    }
+
+    for budget in [
+        Budget::Iterations(50),
+        Budget::Time(second),
+        Budget::Both(50, second),
+        Budget::Unlimited,
+    ] {
+        assert_eq!(MCTSConfig::default().with_budget(budget).budget(), budget);
+    }
+    let config = MCTSConfig::default().with_budget(Budget::Time(second));
+    assert_eq!(config.max_iterations, 0);
+    assert_eq!(config.max_time, Some(second));
+
+    assert_eq!(Budget::Both(50, second).iterations(), Some(50));
+    assert_eq!(Budget::Both(50, second).time(), Some(second));
+    assert_eq!(Budget::Unlimited.iterations(), None);
+}
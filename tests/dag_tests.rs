@@ -0,0 +1,253 @@
+use arboriter_mcts::{tree::MCTSNode, Action, GameState, MCTSConfig, Player, MCTS};
+
+// Grid walk: each move increments one coordinate, so (1, 0) -> (1, 1) and
+// (0, 1) -> (1, 1) reach the same state by different move orders
+#[derive(Clone, Debug)]
+struct GridWalk {
+    x: u8,
+    y: u8,
+    size: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(u8);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Walker;
+
+impl Player for Walker {}
+
+impl GameState for GridWalk {
+    type Action = Step;
+    type Player = Walker;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return vec![];
+        }
+        vec![Step(0), Step(1)]
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut next = self.clone();
+        if action.0 == 0 {
+            next.x += 1;
+        } else {
+            next.y += 1;
+        }
+        next
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.x + self.y >= self.size
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        // Reward walks that end near the diagonal
+        1.0 - (self.x as f64 - self.y as f64).abs() / self.size as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Walker
+    }
+
+    fn hash(&self) -> u64 {
+        1 + ((self.x as u64) << 8 | self.y as u64)
+    }
+}
+
+// Two positions that can be toggled between forever until someone stops
+#[derive(Clone, Debug)]
+struct Toggle {
+    position: u8,
+    stopped: bool,
+}
+
+impl GameState for Toggle {
+    type Action = Step;
+    type Player = Walker;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.stopped {
+            return vec![];
+        }
+        vec![Step(0), Step(1)]
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        match action.0 {
+            0 => Toggle {
+                position: 1 - self.position,
+                stopped: false,
+            },
+            _ => Toggle {
+                position: self.position,
+                stopped: true,
+            },
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stopped
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        self.position as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Walker
+    }
+
+    fn hash(&self) -> u64 {
+        1 + self.position as u64 + 2 * self.stopped as u64
+    }
+}
+
+fn count_nodes<S: GameState>(node: &MCTSNode<S>, links: &mut usize) -> usize {
+    if node.is_transposition() {
+        *links += 1;
+    }
+    1 + node
+        .children
+        .iter()
+        .map(|child| count_nodes(child, links))
+        .sum::<usize>()
+}
+
+#[test]
+fn test_dag_mode_merges_transpositions() {
+    let state = GridWalk {
+        x: 0,
+        y: 0,
+        size: 6,
+    };
+    let config = MCTSConfig::default()
+        .with_max_iterations(500)
+        .with_dag_mode(true);
+
+    let mut mcts = MCTS::new(state, config);
+    assert!(mcts.search().is_ok());
+
+    let stats = mcts.get_statistics();
+    assert!(stats.transposition_hits > 0, "Grid walks should transpose");
+
+    // Links never get their own subtree
+    let mut links = 0;
+    count_nodes(mcts.root(), &mut links);
+    assert_eq!(links, stats.transposition_hits);
+
+    // Every iteration passes through the root exactly once
+    assert_eq!(mcts.root().visits(), 500);
+
+    // A non-terminal link's canonical node is updated whenever the link is
+    // followed; the only missed update is the iteration that created the link
+    fn check_links<S: GameState>(root: &MCTSNode<S>, node: &MCTSNode<S>) {
+        if let Some(target) = &node.transposition {
+            let canonical = root.descendant(target).unwrap();
+            assert!(!canonical.is_transposition());
+            // A terminal link is never followed: its result is backed up
+            // from the link itself, so its canonical node can fall behind
+            if !node.state.is_terminal() {
+                assert!(canonical.visits() + 1 >= node.visits());
+            }
+        }
+        for child in &node.children {
+            check_links(root, child);
+        }
+    }
+    check_links(mcts.root(), mcts.root());
+}
+
+#[test]
+fn test_tree_mode_has_no_links() {
+    let state = GridWalk {
+        x: 0,
+        y: 0,
+        size: 6,
+    };
+    let config = MCTSConfig::default().with_max_iterations(500);
+
+    let mut mcts = MCTS::new(state, config);
+    assert!(mcts.search().is_ok());
+
+    let mut links = 0;
+    count_nodes(mcts.root(), &mut links);
+    assert_eq!(links, 0);
+    assert_eq!(mcts.get_statistics().transposition_hits, 0);
+}
+
+#[test]
+fn test_dag_mode_does_not_cycle() {
+    let state = Toggle {
+        position: 0,
+        stopped: false,
+    };
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_dag_mode(true);
+
+    let mut mcts = MCTS::new(state, config);
+    assert!(mcts.search().is_ok());
+    assert_eq!(mcts.get_statistics().iterations, 300);
+    assert!(mcts.get_statistics().transposition_hits > 0);
+}
+
+#[test]
+fn test_dag_mode_detects_exhaustion_through_links() {
+    let state = GridWalk {
+        x: 0,
+        y: 0,
+        size: 3,
+    };
+    let config = MCTSConfig::default()
+        .with_max_iterations(200)
+        .with_dag_mode(true);
+
+    let mut mcts = MCTS::new(state, config);
+    assert!(mcts.search().is_ok());
+    assert!(mcts.get_statistics().transposition_hits > 0);
+    assert!(mcts.get_statistics().tree_exhausted);
+}
+
+#[test]
+fn test_cycle_detection_cuts_repeated_states() {
+    // Without DAG mode, toggling back and forth grows an endless chain of
+    // repeated positions; cycle detection stops it at the first repeat
+    let state = Toggle {
+        position: 0,
+        stopped: false,
+    };
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_cycle_detection(0.25)
+        .with_stop_when_exhausted(true);
+
+    let mut mcts = MCTS::new(state, config);
+    assert!(mcts.search().is_ok());
+
+    // Root -> toggle -> toggle repeats the root position
+    fn max_depth<S: GameState>(node: &MCTSNode<S>) -> usize {
+        node.children
+            .iter()
+            .map(max_depth)
+            .max()
+            .unwrap_or(node.depth)
+    }
+    assert!(max_depth(mcts.root()) <= 2);
+
+    let toggled = &mcts.root().children[mcts.root().find_child(&Step(0)).unwrap()];
+    let repeat = &toggled.children[toggled.find_child(&Step(0)).unwrap()];
+    assert!(repeat.closes_cycle);
+    assert!(repeat.children.is_empty());
+    assert!((repeat.value() - 0.25).abs() < 1e-6);
+
+    // Cut-off cycles count as resolved, so the tiny tree is exhausted
+    assert!(mcts.get_statistics().tree_exhausted);
+}
@@ -0,0 +1,116 @@
+use arboriter_mcts::{
+    config::BestChildCriteria,
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTSError, SearchOutcome, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Door(usize);
+
+impl Action for Door {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Door 0 leads to a room with no exits that never ends the game; door 1
+/// ends it with a draw
+#[derive(Clone, Debug)]
+struct Rooms {
+    door: Option<usize>,
+}
+
+impl GameState for Rooms {
+    type Action = Door;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.door {
+            None => vec![Door(0), Door(1)],
+            Some(_) => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Rooms {
+            door: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.door == Some(1)
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        0.5
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn start() -> Rooms {
+    Rooms { door: None }
+}
+
+#[test]
+fn test_dead_ends_are_reported_by_default() {
+    let config = MCTSConfig::default().with_max_iterations(20);
+    let mut mcts = MCTS::new(start(), config);
+
+    match mcts.search() {
+        Err(MCTSError::DomainInconsistency(message)) => {
+            assert!(message.contains("no legal actions"), "{}", message);
+        }
+        other => panic!("expected DomainInconsistency, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_dead_ends_score_the_configured_reward() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_dead_end_reward(1.0)
+        .with_stop_when_exhausted(true)
+        .with_best_child_criteria(BestChildCriteria::HighestValue);
+    let mut mcts = MCTS::new(start(), config);
+
+    assert_eq!(mcts.search().unwrap(), Door(0));
+
+    let root = mcts.root();
+    let dead_end = root
+        .find_child(&Door(0))
+        .map(|i| &root.children[i])
+        .unwrap();
+    assert_eq!(dead_end.solved, Some(1.0));
+    assert!((dead_end.total_reward() - dead_end.visits() as f64).abs() < 1e-5);
+
+    // Every leaf is settled, so the search stops early instead of spinning
+    let stats = mcts.get_statistics();
+    assert_eq!(stats.outcome, SearchOutcome::TreeExhausted);
+    assert!(stats.iterations < 100);
+}
+
+#[test]
+fn test_strict_mode_settles_dead_ends_with_a_reward() {
+    // Greedy deepening expands the fresh dead end in the iteration that
+    // created it, before it is settled
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_dead_end_reward(1.0)
+        .with_strict_mode(true)
+        .with_greedy_deepening(1)
+        .with_best_child_criteria(BestChildCriteria::HighestValue);
+    let mut mcts = MCTS::new(start(), config);
+
+    assert_eq!(mcts.search().unwrap(), Door(0));
+    let root = mcts.root();
+    let dead_end = &root.children[root.find_child(&Door(0)).unwrap()];
+    assert_eq!(dead_end.solved, Some(1.0));
+}
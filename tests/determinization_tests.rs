@@ -0,0 +1,151 @@
+use arboriter_mcts::{
+    determinization::search_determinizations,
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTSError,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Guess(usize);
+
+impl Action for Guess {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Guesser;
+
+impl Player for Guesser {}
+
+/// Guess the opponent's hidden card; only cards still in the deck can be guessed
+#[derive(Clone, Debug)]
+struct HiddenCard {
+    card: usize,
+    deck: Vec<usize>,
+    guess: Option<usize>,
+}
+
+impl GameState for HiddenCard {
+    type Action = Guess;
+    type Player = Guesser;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.guess.is_some() {
+            Vec::new()
+        } else {
+            self.deck.iter().copied().map(Guess).collect()
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        HiddenCard {
+            guess: Some(action.0),
+            ..self.clone()
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.guess.is_some()
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        if self.guess == Some(self.card) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Guesser
+    }
+}
+
+fn hidden(card: usize) -> HiddenCard {
+    HiddenCard {
+        card,
+        deck: vec![0, 1, 2],
+        guess: None,
+    }
+}
+
+fn config() -> MCTSConfig {
+    MCTSConfig::default().with_max_iterations(300)
+}
+
+#[test]
+fn test_ensemble_prefers_most_likely_determinization() {
+    let likelihoods = [0.6, 0.3, 0.1];
+    let result = search_determinizations(|i| (hidden(i), likelihoods[i]), 3, &config()).unwrap();
+
+    assert_eq!(result.determinizations, 3);
+    assert_eq!(result.best_action, Some(Guess(0)));
+    assert_eq!(result.actions.len(), 3);
+    assert_eq!(result.actions[0].action, Guess(0));
+    assert!(result.actions.iter().all(|a| a.support == 3));
+
+    let total: f64 = result.actions.iter().map(|a| a.visits).sum();
+    assert!((total - 1.0).abs() < 1e-9, "{}", total);
+
+    // Each guess only wins in its own determinization
+    let value = |card| {
+        result
+            .actions
+            .iter()
+            .find(|a| a.action == Guess(card))
+            .unwrap()
+            .value
+    };
+    assert!(value(0) > value(2));
+}
+
+#[test]
+fn test_ensemble_weights_override_sample_count() {
+    // Two unlikely samples with card 1 against one likely sample with card 2
+    let samples = [(1, 0.1), (1, 0.1), (2, 0.8)];
+    let result = search_determinizations(
+        |i| (hidden(samples[i].0), samples[i].1),
+        samples.len(),
+        &config(),
+    )
+    .unwrap();
+    assert_eq!(result.best_action, Some(Guess(2)));
+}
+
+#[test]
+fn test_ensemble_counts_support_per_action() {
+    // Card 2 is only in the deck of the second sample
+    let result = search_determinizations(
+        |i| {
+            let mut state = hidden(i);
+            state.deck = if i == 0 { vec![0, 1] } else { vec![0, 1, 2] };
+            (state, 1.0)
+        },
+        2,
+        &config(),
+    )
+    .unwrap();
+
+    let support = |card| {
+        result
+            .actions
+            .iter()
+            .find(|a| a.action == Guess(card))
+            .map(|a| a.support)
+    };
+    assert_eq!(support(0), Some(2));
+    assert_eq!(support(2), Some(1));
+}
+
+#[test]
+fn test_ensemble_rejects_invalid_input() {
+    let none = search_determinizations(|i| (hidden(i), 1.0), 0, &config());
+    assert!(matches!(none, Err(MCTSError::InvalidConfiguration(_))));
+
+    let weightless = search_determinizations(|i| (hidden(i), 0.0), 2, &config());
+    assert!(matches!(
+        weightless,
+        Err(MCTSError::InvalidConfiguration(_))
+    ));
+}
@@ -0,0 +1,153 @@
+use std::thread;
+
+use arboriter_mcts::{
+    config::RewardPerspective,
+    distributed::{ChannelTransport, DistributedSearch, EdgeDelta, RootDelta},
+    game_state::{Action, Player},
+    ActionKey, GameState, MCTSConfig, MCTS,
+};
+
+/// Nim with a single pile: take one to three stones, taking the last one wins
+#[derive(Clone, Debug)]
+struct Nim {
+    stones: u32,
+    player: Side,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Side(u8);
+
+impl Player for Side {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Take(u32);
+
+impl Action for Take {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl GameState for Nim {
+    type Action = Take;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.stones.min(3)).map(Take).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Nim {
+            stones: self.stones - action.0,
+            player: Side(1 - self.player.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stones == 0
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        if self.player == *for_player {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.player
+    }
+
+    fn hash(&self) -> u64 {
+        1 + 2 * self.stones as u64 + self.player.0 as u64
+    }
+}
+
+fn peer(stones: u32, transport: ChannelTransport) -> DistributedSearch<Nim, ChannelTransport> {
+    let config = MCTSConfig::default().with_reward_perspective(RewardPerspective::PerPlayer);
+    let nim = Nim {
+        stones,
+        player: Side(0),
+    };
+    DistributedSearch::new(MCTS::new(nim, config), transport).with_sync_interval(100)
+}
+
+#[test]
+fn test_root_delta_round_trips_through_bytes() {
+    let delta = RootDelta {
+        root_hash: 42,
+        edges: vec![
+            EdgeDelta {
+                key: ActionKey::new(0, 3),
+                visits: 17,
+                total_reward: 8.5,
+            },
+            EdgeDelta {
+                key: ActionKey::new(2, u64::MAX),
+                visits: 1,
+                total_reward: -0.25,
+            },
+        ],
+    };
+    let bytes = delta.to_bytes();
+    assert_eq!(RootDelta::from_bytes(&bytes), Some(delta));
+    assert_eq!(RootDelta::from_bytes(&bytes[..bytes.len() - 1]), None);
+    assert_eq!(RootDelta::from_bytes(&[1, 2, 3]), None);
+}
+
+#[test]
+fn test_peers_converge_on_the_combined_root_statistics() {
+    let mut peers: Vec<_> = ChannelTransport::mesh(2)
+        .into_iter()
+        .map(|transport| peer(10, transport))
+        .collect();
+    peers[0].search(400).unwrap();
+    peers[1].search(600).unwrap();
+    peers[0].sync().unwrap();
+
+    let (first, second) = (peers[0].mcts().root(), peers[1].mcts().root());
+    assert_eq!(first.visits(), 1000);
+    assert_eq!(second.visits(), 1000);
+    for child in &first.children {
+        let other = &second.children[second.find_child(child.action.as_ref().unwrap()).unwrap()];
+        assert_eq!(child.visits(), other.visits());
+        assert!((child.value() - other.value()).abs() < 1e-3);
+    }
+
+    // Nothing new to share, so another round changes nothing
+    assert_eq!(peers[1].sync().unwrap(), 0);
+    assert_eq!(peers[0].sync().unwrap(), 0);
+    assert_eq!(peers[0].mcts().root().visits(), 1000);
+}
+
+#[test]
+fn test_messages_about_another_position_are_dropped() {
+    let mut transports = ChannelTransport::mesh(2);
+    let mut other = peer(7, transports.pop().unwrap());
+    let mut ours = peer(10, transports.pop().unwrap());
+    other.search(200).unwrap();
+    ours.search(200).unwrap();
+
+    // One message per slice of the other search
+    assert_eq!(ours.dropped_messages(), 2);
+    assert_eq!(ours.mcts().root().visits(), 200);
+}
+
+#[test]
+fn test_peers_on_threads_agree_on_the_winning_move() {
+    let handles: Vec<_> = ChannelTransport::mesh(3)
+        .into_iter()
+        .map(|transport| {
+            thread::spawn(move || {
+                let mut search = peer(10, transport);
+                search.search(1500).unwrap()
+            })
+        })
+        .collect();
+
+    // Leaving a multiple of four stones wins
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Take(2));
+    }
+}
@@ -0,0 +1,127 @@
+use arboriter_mcts::{
+    config::RewardPerspective,
+    game_state::{Action, NoPlayer},
+    GameState, MCTSConfig, MCTSError, MCTS,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Play {
+    Steady,
+    Risky,
+    Pick(usize),
+}
+
+impl Action for Play {
+    fn id(&self) -> usize {
+        match self {
+            Play::Steady => 0,
+            Play::Risky => 1,
+            Play::Pick(card) => 2 + card,
+        }
+    }
+}
+
+/// Take a sure 0.6, or flip a coin: heads draws four cards of which only
+/// one scores, tails pays 0.5
+#[derive(Clone, Debug, PartialEq)]
+enum Solitaire {
+    Start,
+    Flipping,
+    Drawn,
+    Done(f64),
+}
+
+impl GameState for Solitaire {
+    type Action = Play;
+    type Player = NoPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self {
+            Solitaire::Start => vec![Play::Steady, Play::Risky],
+            Solitaire::Drawn => (0..4).map(Play::Pick).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        match action {
+            Play::Steady => Solitaire::Done(0.6),
+            Play::Risky => Solitaire::Flipping,
+            Play::Pick(card) => Solitaire::Done(if *card == 0 { 1.0 } else { 0.0 }),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(self, Solitaire::Done(_))
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        match self {
+            Solitaire::Done(result) => *result,
+            _ => 0.5,
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        NoPlayer
+    }
+
+    fn chance_outcomes(&self) -> Option<Vec<(Self, f64)>> {
+        match self {
+            Solitaire::Flipping => Some(vec![(Solitaire::Drawn, 0.5), (Solitaire::Done(0.5), 0.5)]),
+            _ => None,
+        }
+    }
+}
+
+fn search(expectimax: bool) -> (MCTS<Solitaire>, Play) {
+    let config = MCTSConfig::default()
+        .with_max_iterations(500)
+        .with_expectimax(expectimax);
+    let mut mcts = MCTS::new(Solitaire::Start, config);
+    let best = mcts.search().unwrap();
+    (mcts, best)
+}
+
+#[test]
+fn test_expectimax_takes_expectations_at_chance_nodes_and_maxima_elsewhere() {
+    let (mcts, best) = search(true);
+    let root = mcts.root();
+    let risky = &root.children[root.find_child(&Play::Risky).unwrap()];
+    assert!(risky.is_chance());
+
+    // Heads is worth its best card, 1.0; tails pays 0.5
+    let drawn = risky
+        .children
+        .iter()
+        .find(|outcome| outcome.state == Solitaire::Drawn)
+        .unwrap();
+    assert!((drawn.value() - 1.0).abs() < 1e-6, "{}", drawn.value());
+    assert!((risky.value() - 0.75).abs() < 1e-6, "{}", risky.value());
+    assert!((root.value() - 0.75).abs() < 1e-6, "{}", root.value());
+    assert!((risky.expectimax_value() - risky.value()).abs() < 1e-6);
+    assert_eq!(best, Play::Risky);
+}
+
+#[test]
+fn test_averages_are_kept_by_default() {
+    let (mcts, _) = search(false);
+    let root = mcts.root();
+    let risky = &root.children[root.find_child(&Play::Risky).unwrap()];
+
+    // Exploring the losing cards drags the average below the expectimax value
+    assert!(risky.value() < 0.74, "{}", risky.value());
+}
+
+#[test]
+fn test_expectimax_rejects_per_player_rewards() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(10)
+        .with_expectimax(true)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let mut mcts = MCTS::new(Solitaire::Start, config);
+    assert!(matches!(
+        mcts.search(),
+        Err(MCTSError::InvalidConfiguration(_))
+    ));
+}
@@ -0,0 +1,169 @@
+use arboriter_mcts::{
+    experience::Experience,
+    game_state::{Action, Player},
+    history::HistoryEntry,
+    stats::{RootActionStats, RootSnapshot},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// One decision; action 2 is the only good one
+#[derive(Clone, Debug)]
+struct Choice {
+    picked: Option<usize>,
+}
+
+impl GameState for Choice {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.picked {
+            None => (0..3).map(Pick).collect(),
+            Some(_) => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Choice {
+            picked: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picked.is_some()
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        if self.picked == Some(2) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn snapshot(actions: &[(usize, u64, f64)]) -> RootSnapshot<Pick> {
+    RootSnapshot {
+        root_visits: actions.iter().map(|(_, visits, _)| visits).sum(),
+        actions: actions
+            .iter()
+            .map(|&(id, visits, value)| RootActionStats {
+                action: Pick(id),
+                visits,
+                value,
+            })
+            .collect(),
+        best_action: None,
+    }
+}
+
+#[test]
+fn test_experience_aggregates_and_decays_games() {
+    let mut experience = Experience::new(0.5);
+    experience.record(&snapshot(&[(0, 10, 0.2), (1, 30, 0.6)]));
+    experience.record(&snapshot(&[(1, 10, 1.0), (2, 0, 0.0)]));
+
+    // Nothing counts until the game ends
+    assert!(experience.is_empty());
+    experience.finish_game();
+
+    assert_eq!(experience.len(), 2);
+    let entry = experience.get(Pick(1).key()).unwrap();
+    assert_eq!(entry.visits, 40.0);
+    assert!((entry.value() - 0.7).abs() < 1e-12);
+    assert_eq!(experience.value(Pick(2).key()), None);
+
+    // The next game weighs the first one by half
+    experience.record(&snapshot(&[(0, 20, 1.0)]));
+    experience.finish_game();
+    let entry = experience.get(Pick(0).key()).unwrap();
+    assert_eq!(entry.visits, 25.0);
+    assert!((entry.value() - (1.0 + 20.0) / 25.0).abs() < 1e-12);
+    assert_eq!(experience.get(Pick(1).key()).unwrap().visits, 20.0);
+}
+
+#[test]
+fn test_experience_priors_are_capped() {
+    let experience = Experience::from_entries(
+        1.0,
+        vec![
+            (
+                Pick(0).key(),
+                HistoryEntry {
+                    visits: 500.0,
+                    total_reward: 100.0,
+                },
+            ),
+            (
+                Pick(1).key(),
+                HistoryEntry {
+                    visits: 0.2,
+                    total_reward: 0.1,
+                },
+            ),
+        ],
+    );
+
+    let priors = experience.priors(&[Pick(0), Pick(1), Pick(2)], 25);
+    assert_eq!(priors, vec![(Pick(0), 0.2, 25)]);
+}
+
+#[test]
+fn test_experience_warm_starts_the_next_game() {
+    let config = MCTSConfig::default().with_max_iterations(60);
+    let mut experience = Experience::new(1.0);
+
+    let mut mcts = MCTS::new(Choice { picked: None }, config.clone());
+    assert_eq!(experience.warm_start(&mut mcts, 10).unwrap(), 0);
+    assert_eq!(mcts.search().unwrap(), Pick(2));
+    experience.record(&mcts.root_snapshot());
+    experience.finish_game();
+
+    let mut mcts = MCTS::new(Choice { picked: None }, config);
+    assert_eq!(experience.warm_start(&mut mcts, 2).unwrap(), 3);
+    let root = mcts.root();
+    assert_eq!(root.visits(), 6);
+    for child in &root.children {
+        assert_eq!(child.visits(), 2);
+        let expected = if child.action == Some(Pick(2)) {
+            1.0
+        } else {
+            0.0
+        };
+        assert!((child.value() - expected).abs() < 1e-6);
+    }
+
+    // The search builds on the seeded statistics
+    mcts.search().unwrap();
+    assert_eq!(mcts.root().visits(), 66);
+}
+
+#[test]
+fn test_experience_round_trips_through_entries() {
+    let mut experience = Experience::new(0.9);
+    experience.record(&snapshot(&[(0, 4, 0.5), (2, 8, 0.25)]));
+    experience.finish_game();
+
+    let restored = Experience::from_entries(0.9, experience.entries());
+    for id in 0..3 {
+        assert_eq!(restored.get(Pick(id).key()), experience.get(Pick(id).key()));
+    }
+}
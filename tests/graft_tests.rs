@@ -0,0 +1,199 @@
+use arboriter_mcts::{
+    config::RewardPerspective,
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTSError, MCTS,
+};
+
+/// Nim with a single pile: take one to three stones, taking the last one wins
+#[derive(Clone, Debug)]
+struct Nim {
+    stones: u32,
+    player: Side,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Side(u8);
+
+impl Player for Side {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Take(u32);
+
+impl Action for Take {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl GameState for Nim {
+    type Action = Take;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.stones.min(3)).map(Take).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Nim {
+            stones: self.stones - action.0,
+            player: Side(1 - self.player.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stones == 0
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        if self.player == *for_player {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.player
+    }
+}
+
+fn nim(stones: u32) -> Nim {
+    Nim {
+        stones,
+        player: Side(0),
+    }
+}
+
+fn mcts(stones: u32, iterations: usize) -> MCTS<Nim> {
+    let config = MCTSConfig::default()
+        .with_max_iterations(iterations)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    MCTS::new(nim(stones), config)
+}
+
+#[test]
+fn test_graft_adds_statistics_along_the_path() {
+    let mut mcts = mcts(7, 10);
+    mcts.graft(&[Take(1), Take(2)], 40, 0.8).unwrap();
+
+    let root = mcts.root();
+    let first = &root.children[root.find_child(&Take(1)).unwrap()];
+    let second = &first.children[first.find_child(&Take(2)).unwrap()];
+    assert_eq!(second.visits(), 40);
+    assert!((second.value() - 0.8).abs() < 1e-6);
+
+    // The nodes above were entered by the other player
+    assert_eq!(first.visits(), 40);
+    assert!((first.value() - 0.2).abs() < 1e-6);
+    assert_eq!(root.visits(), 40);
+    assert!((first.prior() - 1.0 / 3.0).abs() < 1e-6);
+    assert_eq!(mcts.get_statistics().tree_size, 3);
+}
+
+#[test]
+fn test_graft_refreshes_cached_selection_stats() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_reward_perspective(RewardPerspective::PerPlayer)
+        .with_selection_stats_cache(true);
+    let mut mcts = MCTS::new(nim(7), config);
+    mcts.search().unwrap();
+    mcts.graft(&[Take(1), Take(2)], 40, 0.8).unwrap();
+
+    let root = mcts.root();
+    let first = &root.children[root.find_child(&Take(1)).unwrap()];
+    let second = &first.children[first.find_child(&Take(2)).unwrap()];
+    for node in [root, first, second] {
+        assert_eq!(node.selection_stats(), (node.visits(), node.value()));
+    }
+}
+
+#[test]
+fn test_graft_rejects_illegal_paths_without_touching_the_tree() {
+    let mut mcts = mcts(3, 10);
+    let result = mcts.graft(&[Take(2), Take(2)], 10, 1.0);
+    assert!(matches!(result, Err(MCTSError::InvalidConfiguration(_))));
+    assert_eq!(mcts.root().visits(), 0);
+    assert!(mcts.root().children.is_empty());
+}
+
+#[test]
+fn test_next_search_builds_on_grafted_statistics() {
+    let mut mcts = mcts(7, 200);
+    mcts.graft(&[Take(3)], 500, 1.0).unwrap();
+    let best = mcts.search().unwrap();
+
+    assert_eq!(best, Take(3));
+    assert!(mcts.root().visits() >= 700, "{}", mcts.root().visits());
+}
+
+#[test]
+fn test_merged_tree_sums_worker_statistics() {
+    let mut first = mcts(9, 300);
+    let mut second = mcts(9, 300);
+    first.search().unwrap();
+    second.search().unwrap();
+    let before = first.root().visits();
+
+    let skipped = first.merge_tree(second.root()).unwrap();
+    assert_eq!(skipped, 0);
+
+    let root = first.root();
+    assert_eq!(root.visits(), before + second.root().visits());
+    for theirs in &second.root().children {
+        let ours = &root.children[root.find_child(theirs.action.as_ref().unwrap()).unwrap()];
+        assert!(ours.visits() >= theirs.visits());
+    }
+    assert_eq!(
+        first.get_statistics().tree_size,
+        first.root().subtree_size()
+    );
+}
+
+#[test]
+fn test_merge_creates_nodes_only_the_other_tree_has() {
+    let mut ours = mcts(9, 10);
+    let mut theirs = mcts(9, 10);
+    theirs.graft(&[Take(1), Take(1), Take(1)], 5, 0.7).unwrap();
+
+    assert_eq!(ours.merge_tree(theirs.root()).unwrap(), 0);
+    let root = ours.root();
+    let node = &root.children[root.find_child(&Take(1)).unwrap()];
+    let node = &node.children[node.find_child(&Take(1)).unwrap()];
+    let node = &node.children[node.find_child(&Take(1)).unwrap()];
+    assert_eq!(node.visits(), 5);
+    assert!((node.value() - 0.7).abs() < 1e-6);
+    assert_eq!(ours.get_statistics().tree_size, 4);
+}
+
+#[test]
+fn test_merge_refreshes_cached_selection_stats() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_reward_perspective(RewardPerspective::PerPlayer)
+        .with_selection_stats_cache(true);
+    let mut ours = MCTS::new(nim(7), config.clone());
+    let mut theirs = MCTS::new(nim(7), config);
+    ours.search().unwrap();
+    theirs.search().unwrap();
+    theirs
+        .graft(&[Take(1), Take(1), Take(1), Take(1)], 40, 0.8)
+        .unwrap();
+    ours.merge_tree(theirs.root()).unwrap();
+
+    let mut stack = vec![ours.root()];
+    while let Some(node) = stack.pop() {
+        assert_eq!(node.selection_stats(), (node.visits(), node.value()));
+        stack.extend(&node.children);
+    }
+}
+
+#[test]
+fn test_merge_rejects_a_different_root() {
+    let mut ours = mcts(9, 10);
+    let theirs = mcts(2, 10);
+    assert!(matches!(
+        ours.merge_tree(theirs.root()),
+        Err(MCTSError::InvalidConfiguration(_))
+    ));
+}
@@ -0,0 +1,127 @@
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTSNode, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Bit(usize);
+
+impl Action for Bit {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Writes `length` bits; the result is the share of ones
+#[derive(Clone, Debug)]
+struct Bits {
+    bits: Vec<usize>,
+    length: usize,
+}
+
+impl Bits {
+    fn new(length: usize) -> Self {
+        Bits {
+            bits: Vec::new(),
+            length,
+        }
+    }
+}
+
+impl GameState for Bits {
+    type Action = Bit;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            Vec::new()
+        } else {
+            vec![Bit(0), Bit(1)]
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut bits = self.bits.clone();
+        bits.push(action.0);
+        Bits {
+            bits,
+            length: self.length,
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.bits.len() >= self.length
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.bits.iter().sum::<usize>() as f64 / self.length as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn tree_depth(node: &MCTSNode<Bits>) -> usize {
+    node.children
+        .iter()
+        .map(|child| 1 + tree_depth(child))
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_greedy_deepening_expands_a_line_per_iteration() {
+    let config = MCTSConfig::default().with_greedy_deepening(4);
+    let mut mcts = MCTS::new(Bits::new(12), config);
+
+    mcts.search_for_iterations(1).unwrap();
+
+    assert_eq!(tree_depth(mcts.root()), 5);
+    assert_eq!(mcts.get_statistics().tree_size, 6);
+
+    // Every node on the line was credited with the playout
+    let mut node = mcts.root();
+    while let Some(child) = node.children.first() {
+        assert_eq!(node.children.len(), 1);
+        assert_eq!(child.visits(), 1);
+        node = child;
+    }
+}
+
+#[test]
+fn test_greedy_deepening_stops_at_terminal_states() {
+    let config = MCTSConfig::default().with_greedy_deepening(10);
+    let mut mcts = MCTS::new(Bits::new(3), config);
+
+    mcts.search_for_iterations(1).unwrap();
+
+    assert_eq!(tree_depth(mcts.root()), 3);
+}
+
+#[test]
+fn test_one_node_per_iteration_by_default() {
+    let mut mcts = MCTS::new(Bits::new(12), MCTSConfig::default());
+
+    mcts.search_for_iterations(1).unwrap();
+
+    assert_eq!(tree_depth(mcts.root()), 1);
+}
+
+#[test]
+fn test_greedy_deepening_builds_deeper_trees() {
+    let depth_with = |plies| {
+        let config = MCTSConfig::default().with_greedy_deepening(plies);
+        let mut mcts = MCTS::new(Bits::new(12), config);
+        mcts.search_for_iterations(50).unwrap();
+        assert_eq!(mcts.root().visits(), 50);
+        tree_depth(mcts.root())
+    };
+
+    assert!(depth_with(3) > depth_with(0));
+}
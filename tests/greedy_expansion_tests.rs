@@ -0,0 +1,203 @@
+use arboriter_mcts::{
+    config::RewardPerspective,
+    game_state::{Action, Player},
+    policy::{
+        expansion::{
+            ExpansionPolicy, GreedyExpansionPolicy, HeuristicExpansionPolicy, DEFAULT_PRIOR_FLOOR,
+        },
+        selection::PUCTPolicy,
+    },
+    GameState, MCTSConfig, MCTS,
+};
+
+/// Nim with one pile: take one to three stones, whoever takes the last one wins
+#[derive(Clone, Debug)]
+struct Nim {
+    stones: u32,
+    to_move: Side,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    A,
+    B,
+}
+
+impl Player for Side {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Take(u32);
+
+impl Action for Take {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl GameState for Nim {
+    type Action = Take;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.stones.min(3)).map(Take).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Nim {
+            stones: self.stones - action.0,
+            to_move: match self.to_move {
+                Side::A => Side::B,
+                Side::B => Side::A,
+            },
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stones == 0
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        // The player who just moved took the last stone
+        if *for_player == self.to_move {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.to_move
+    }
+}
+
+fn nim(stones: u32) -> Nim {
+    Nim {
+        stones,
+        to_move: Side::A,
+    }
+}
+
+/// Favours leaving a multiple of four stones, the winning strategy
+fn leave_multiple_of_four(state: &Nim, action: &Take) -> f64 {
+    if (state.stones - action.0).is_multiple_of(4) {
+        6.0
+    } else {
+        1.0
+    }
+}
+
+/// Asserts that two lists of priors match up to rounding
+fn assert_priors(priors: Vec<f64>, expected: &[f64]) {
+    assert_eq!(priors.len(), expected.len());
+    for (prior, expected) in priors.iter().zip(expected) {
+        assert!(
+            (prior - expected).abs() < 1e-12,
+            "{priors:?} != {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn test_priors_are_uniform_without_score_priors() {
+    let policy = HeuristicExpansionPolicy::new(leave_multiple_of_four);
+    let mcts = MCTS::new(nim(10), MCTSConfig::default());
+
+    assert_eq!(policy.action_priors(mcts.root()), None);
+    let (index, prior) = policy.select_action_to_expand(mcts.root()).unwrap();
+    assert_eq!(index, 1);
+    assert!((prior - 1.0 / 3.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_priors_are_proportional_to_scores() {
+    let policy = HeuristicExpansionPolicy::new(leave_multiple_of_four).with_score_priors(0.0);
+    let mcts = MCTS::new(nim(10), MCTSConfig::default());
+
+    // Taking two leaves eight
+    let priors = policy.action_priors(mcts.root()).unwrap();
+    assert_eq!(priors, vec![0.125, 0.75, 0.125]);
+    assert_eq!(policy.select_action_to_expand(mcts.root()), Some((1, 0.75)));
+}
+
+#[test]
+fn test_prior_floor_is_spread_uniformly() {
+    let policy = HeuristicExpansionPolicy::new(leave_multiple_of_four).with_score_priors(0.3);
+    let mcts = MCTS::new(nim(10), MCTSConfig::default());
+
+    let priors = policy.action_priors(mcts.root()).unwrap();
+    assert_priors(priors, &[0.1875, 0.625, 0.1875]);
+}
+
+#[test]
+fn test_unusable_scores_fall_back_to_uniform_priors() {
+    let mcts = MCTS::new(nim(10), MCTSConfig::default());
+
+    let score = |_: &Nim, action: &Take| match action.0 {
+        1 => -2.0,
+        2 => f64::NAN,
+        _ => 1.0,
+    };
+    let policy = HeuristicExpansionPolicy::new(score).with_score_priors(0.0);
+    assert_eq!(
+        policy.action_priors(mcts.root()).unwrap(),
+        vec![0.0, 0.0, 1.0]
+    );
+
+    // The floor keeps actions scoring zero explorable
+    let policy = HeuristicExpansionPolicy::new(score).with_score_priors(0.3);
+    assert_priors(policy.action_priors(mcts.root()).unwrap(), &[0.1, 0.1, 0.8]);
+
+    let policy = HeuristicExpansionPolicy::new(|_: &Nim, _: &Take| 0.0).with_score_priors(0.05);
+    let priors = policy.action_priors(mcts.root()).unwrap();
+    assert!(priors.iter().all(|prior| (prior - 1.0 / 3.0).abs() < 1e-12));
+}
+
+#[test]
+fn test_greedy_policy_uses_score_priors_with_a_floor() {
+    let mcts = MCTS::new(nim(10), MCTSConfig::default());
+
+    // 5% spread uniformly by default
+    let policy = GreedyExpansionPolicy::new(leave_multiple_of_four);
+    let expected: Vec<f64> = [0.125, 0.75, 0.125]
+        .iter()
+        .map(|share| (1.0 - DEFAULT_PRIOR_FLOOR) * share + DEFAULT_PRIOR_FLOOR / 3.0)
+        .collect();
+    assert_priors(policy.action_priors(mcts.root()).unwrap(), &expected);
+    assert_eq!(policy.select_action_to_expand(mcts.root()).unwrap().0, 1);
+
+    let policy = GreedyExpansionPolicy::new(leave_multiple_of_four).with_prior_floor(0.3);
+    assert_priors(
+        policy.action_priors(mcts.root()).unwrap(),
+        &[0.1875, 0.625, 0.1875],
+    );
+}
+
+#[test]
+fn test_best_scoring_actions_are_expanded_first() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(2)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let mut mcts = MCTS::new(nim(10), config).with_expansion_policy(
+        HeuristicExpansionPolicy::new(leave_multiple_of_four).with_score_priors(0.0),
+    );
+    mcts.search().unwrap();
+
+    // Priors keep their share of all actions as the node fills up
+    let root = mcts.root();
+    assert_eq!(root.children.len(), 2);
+    assert_eq!(root.children[0].action, Some(Take(2)));
+    assert!((root.children[0].prior() - 0.75).abs() < 1e-6);
+    assert!((root.children[1].prior() - 0.125).abs() < 1e-6);
+}
+
+#[test]
+fn test_greedy_priors_guide_puct() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let mut mcts = MCTS::new(nim(13), config)
+        .with_selection_policy(PUCTPolicy::new(1.0))
+        .with_expansion_policy(GreedyExpansionPolicy::new(leave_multiple_of_four));
+
+    assert_eq!(mcts.search().unwrap(), Take(1));
+}
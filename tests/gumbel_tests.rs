@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use arboriter_mcts::{
+    config::Budget,
+    game_state::{Action, Player},
+    policy_codec::{IdCodec, PolicyHeadExpansionPolicy},
+    GameState, MCTSConfig, MCTSError, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Arm(usize);
+
+impl Action for Arm {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+const ARMS: usize = 16;
+
+/// The arm that pays best
+const BEST: usize = 11;
+
+/// One pull of sixteen arms with fixed payouts
+#[derive(Clone, Debug)]
+struct Bandit {
+    pulled: Option<usize>,
+}
+
+impl GameState for Bandit {
+    type Action = Arm;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.pulled {
+            Some(_) => Vec::new(),
+            None => (0..ARMS).map(Arm).collect(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Bandit {
+            pulled: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.pulled.is_some()
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        match self.pulled {
+            Some(BEST) => 0.9,
+            Some(arm) => 0.2 + 0.02 * arm as f64,
+            None => 0.0,
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn bandit() -> Bandit {
+    Bandit { pulled: None }
+}
+
+#[test]
+fn test_gumbel_root_finds_the_best_arm_with_a_small_budget() {
+    for _ in 0..10 {
+        let config = MCTSConfig::default()
+            .with_max_iterations(64)
+            .with_gumbel_root(ARMS);
+        let mut mcts = MCTS::new(bandit(), config);
+        assert_eq!(mcts.search().unwrap(), Arm(BEST));
+        assert_eq!(mcts.root().visits(), 64);
+    }
+}
+
+#[test]
+fn test_only_considered_actions_are_searched() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(40)
+        .with_gumbel_root(4);
+    let mut mcts = MCTS::new(bandit(), config);
+    mcts.search().unwrap();
+
+    let root = mcts.root();
+    assert_eq!(root.children.len(), 4);
+    assert_eq!(root.visits(), 40);
+
+    // Two phases of 40 / (2 * 4) = 5 visits, then the better half gets the rest
+    let mut visits: Vec<u64> = root.children.iter().map(|child| child.visits()).collect();
+    visits.sort_unstable();
+    assert_eq!(&visits[..2], [5, 5]);
+}
+
+#[test]
+fn test_gumbel_candidates_follow_the_priors() {
+    let mut policy = vec![1e-9; ARMS];
+    policy[3] = 1.0;
+    let expansion =
+        PolicyHeadExpansionPolicy::new(IdCodec::new(ARMS), move |_: &Bandit| policy.clone());
+    let config = MCTSConfig::default()
+        .with_max_iterations(16)
+        .with_gumbel_root(1);
+    let mut mcts = MCTS::new(bandit(), config).with_expansion_policy(expansion);
+
+    assert_eq!(mcts.search().unwrap(), Arm(3));
+    assert_eq!(mcts.root().children.len(), 1);
+    assert_eq!(mcts.root().children[0].visits(), 16);
+}
+
+#[test]
+fn test_gumbel_root_needs_an_iteration_budget() {
+    let config = MCTSConfig::default().with_gumbel_root(4);
+    let mut mcts = MCTS::new(bandit(), config);
+    assert!(matches!(
+        mcts.continue_search(Budget::Time(Duration::from_millis(10))),
+        Err(MCTSError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_gumbel_choice_goes_through_the_result_validator() {
+    for _ in 0..10 {
+        let config = MCTSConfig::default()
+            .with_max_iterations(64)
+            .with_gumbel_root(ARMS);
+        let mut mcts = MCTS::new(bandit(), config).with_result_validator(|_, arm: &Arm| {
+            if arm.0 == BEST {
+                Err("the best arm is jammed".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        let chosen = mcts.search().unwrap();
+        assert_ne!(chosen, Arm(BEST));
+        assert_eq!(mcts.planned_line().first(), Some(&chosen));
+    }
+}
+
+#[test]
+fn test_gumbel_search_fails_when_every_action_is_rejected() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(40)
+        .with_gumbel_root(4);
+    let mut mcts = MCTS::new(bandit(), config)
+        .with_result_validator(|_, _: &Arm| Err("no pulls today".to_string()));
+    assert!(matches!(mcts.search(), Err(MCTSError::ActionsRejected(_))));
+}
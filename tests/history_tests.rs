@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    history::HistoryTable,
+    policy::{
+        selection::{ProgressiveHistoryPolicy, SelectionContext, SelectionPolicy},
+        simulation::{MastPolicy, NstPolicy, SimulationPolicy},
+    },
+    tree::MCTSNode,
+    ActionKey, GameState, MCTSConfig, MCTS,
+};
+
+/// Three moves in a row, each picking a digit; the result is the fraction of
+/// moves that picked digit 1
+#[derive(Clone, Debug)]
+struct Digits {
+    picked: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Digit(usize);
+
+impl Action for Digit {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+const MOVES: usize = 3;
+
+impl GameState for Digits {
+    type Action = Digit;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return vec![];
+        }
+        (0..4).map(Digit).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut picked = self.picked.clone();
+        picked.push(action.0);
+        Digits { picked }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picked.len() >= MOVES
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        self.picked.iter().filter(|&&digit| digit == 1).count() as f64 / MOVES as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn start() -> Digits {
+    Digits { picked: vec![] }
+}
+
+fn key(digit: usize) -> ActionKey {
+    Digit(digit).key()
+}
+
+#[test]
+fn test_history_table_record_and_decay() {
+    let table = HistoryTable::new(0.5);
+    assert!(table.is_empty());
+    assert_eq!(table.value(&7), None);
+
+    table.record(7, 1.0);
+    table.record(7, 0.0);
+    table.record(7, 0.5);
+    assert_eq!(table.len(), 1);
+    assert_eq!(table.value(&7), Some(0.5));
+
+    // Decay keeps the average but shrinks the weight of the evidence
+    table.decay();
+    let entry = table.get(&7).unwrap();
+    assert_eq!(entry.visits, 1.5);
+    assert_eq!(entry.value(), 0.5);
+
+    let forgetful = HistoryTable::new(0.0);
+    forgetful.record(1, 1.0);
+    forgetful.decay();
+    assert!(forgetful.is_empty());
+}
+
+#[test]
+fn test_search_records_history_and_decays_on_move() {
+    let history = Arc::new(HistoryTable::new(0.5));
+    let mut mcts = MCTS::new(start(), MCTSConfig::default().with_max_iterations(200))
+        .with_history(history.clone());
+
+    let action = mcts.search().unwrap();
+    assert_eq!(action, Digit(1));
+
+    // Digit 1 is worth more than any other digit wherever it is played
+    let best = history.value(&key(1)).unwrap();
+    for digit in [0, 2, 3] {
+        assert!(best > history.value(&key(digit)).unwrap());
+    }
+
+    let visits = history.get(&key(1)).unwrap().visits;
+    mcts.apply_move(&action);
+    assert_eq!(history.get(&key(1)).unwrap().visits, visits * 0.5);
+}
+
+#[test]
+fn test_progressive_history_uses_context_table() {
+    let mut node = MCTSNode::new(start(), None, None, 0);
+    for _ in 0..4 {
+        node.expand(0);
+    }
+    for child in &node.children {
+        child.increment_visits();
+        child.add_reward(0.5);
+        node.increment_visits();
+    }
+    let favored = node.children[2].action.clone().unwrap();
+
+    let table = HistoryTable::new(1.0);
+    table.record(favored.key(), 1.0);
+
+    let policy = ProgressiveHistoryPolicy::new(0.0, 1.0);
+    let mut rng = rand::thread_rng();
+    let mut context = SelectionContext::new(&mut rng);
+    context.history = Some(&table);
+    assert_eq!(policy.select_child(&node, &mut context), 2);
+}
+
+#[test]
+fn test_mast_rollouts_prefer_good_actions() {
+    let history = Arc::new(HistoryTable::new(1.0));
+    for digit in 0..4 {
+        history.record(key(digit), if digit == 1 { 1.0 } else { 0.0 });
+    }
+
+    let policy = MastPolicy::new(history, 0.1);
+    let total: f64 = (0..50).map(|_| policy.simulate(&start()).0).sum();
+
+    // exp(10) makes digit 1 overwhelmingly likely on every move
+    assert!(total / 50.0 > 0.9);
+}
+
+#[test]
+fn test_nst_scores_continuations() {
+    let policy = NstPolicy::new(2, 0.1, 0.5);
+    policy.record_playout(&[key(3), key(2)], 1.0);
+    policy.record_playout(&[key(3), key(1)], 0.0);
+    policy.record_playout(&[key(0), key(2)], 0.0);
+
+    // Unigrams [3], [2], [1], [0] and bigrams [3, 2], [3, 1], [0, 2]
+    assert_eq!(policy.ngrams.len(), 7);
+
+    // [2] averages 0.5; the bigram decides which context favours it
+    assert_eq!(policy.score(&[key(3)], key(2)), 0.75);
+    assert_eq!(policy.score(&[key(0)], key(2)), 0.25);
+    assert_eq!(policy.score(&[], key(2)), 0.5);
+    assert_eq!(policy.score(&[key(1)], key(7)), policy.unseen_value);
+}
+
+#[test]
+fn test_nst_learns_during_search_and_decays() {
+    let nst = NstPolicy::new(3, 0.2, 0.5);
+    let ngrams = nst.ngrams.clone();
+    let mut mcts = MCTS::new(start(), MCTSConfig::default().with_max_iterations(200))
+        .with_simulation_policy(nst);
+
+    assert_eq!(mcts.search().unwrap(), Digit(1));
+    assert!(!ngrams.is_empty());
+    assert!(ngrams.value(&vec![key(1)]).unwrap() > ngrams.value(&vec![key(0)]).unwrap());
+
+    let visits = ngrams.get(&vec![key(1)]).unwrap().visits;
+    mcts.apply_move(&Digit(1));
+    assert_eq!(ngrams.get(&vec![key(1)]).unwrap().visits, visits * 0.5);
+}
@@ -1,8 +1,16 @@
 use arboriter_mcts::{
-    config::BestChildCriteria,
-    policy::{selection::UCB1Policy, simulation::RandomPolicy},
-    Action, GameState, MCTSConfig, Player, MCTS,
+    config::{BestChildCriteria, RewardPerspective, RewardTransform},
+    policy::{
+        backpropagation::{BackpropContext, BackpropagationPolicy, StandardPolicy},
+        expansion::HeuristicExpansionPolicy,
+        selection::{SelectionContext, SelectionPolicy, UCB1Policy},
+        simulation::RandomPolicy,
+    },
+    stats::{RootActionStats, RootSnapshot, SearchLogger},
+    tree::MCTSNode,
+    Action, GameState, MCTSConfig, MCTSError, Player, SearchOutcome, SearchStatistics, MCTS,
 };
+use std::sync::{Arc, Mutex};
 
 // Simple tic-tac-toe implementation for testing
 #[derive(Clone, Debug)]
@@ -244,3 +252,869 @@ fn test_empty_game_state() {
         _ => panic!("Unexpected result"),
     }
 }
+
+#[test]
+fn test_selection_stats_cache_tracks_live_stats() {
+    let game = create_specific_board();
+
+    let config = MCTSConfig::default()
+        .with_exploration_constant(0.5)
+        .with_max_iterations(1000)
+        .with_selection_stats_cache(true);
+
+    let mut mcts = MCTS::new(game, config);
+
+    // Caching must not change the search outcome
+    let result = mcts.search().unwrap();
+    assert_eq!(result.position, 7, "MCTS should find the winning move");
+
+    // Every visited node's snapshot matches its atomics after the search
+    let root = mcts.root();
+    assert_eq!(root.cached_stats, Some((root.visits(), root.value())));
+    for child in &root.children {
+        assert_eq!(child.selection_stats(), (child.visits(), child.value()));
+    }
+}
+
+/// `(depth, path, iteration)` seen by a selection policy
+type ContextRecord = (usize, Vec<usize>, usize);
+
+/// Selection policy that records the context it was called with
+#[derive(Clone)]
+struct RecordingPolicy {
+    inner: UCB1Policy,
+    calls: Arc<Mutex<Vec<ContextRecord>>>,
+}
+
+impl SelectionPolicy<TicTacToe> for RecordingPolicy {
+    fn select_child(
+        &self,
+        node: &MCTSNode<TicTacToe>,
+        context: &mut SelectionContext<'_>,
+    ) -> usize {
+        self.calls
+            .lock()
+            .unwrap()
+            .push((context.depth, context.path.to_vec(), context.iteration));
+        self.inner.select_child(node, context)
+    }
+
+    fn clone_box(&self) -> Box<dyn SelectionPolicy<TicTacToe>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[test]
+fn test_selection_policy_receives_context() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let policy = RecordingPolicy {
+        inner: UCB1Policy::new(1.414),
+        calls: calls.clone(),
+    };
+
+    let config = MCTSConfig::default().with_max_iterations(200);
+    let mut mcts = MCTS::new(TicTacToe::new(), config).with_selection_policy(policy);
+    mcts.search().unwrap();
+
+    let calls = calls.lock().unwrap();
+    assert!(!calls.is_empty(), "Policy should have been consulted");
+
+    let mut last_iteration = 0;
+    for (depth, path, iteration) in calls.iter() {
+        // The path leads from the root to the node being selected from
+        assert_eq!(*depth, path.len());
+        assert!(*iteration < 200);
+        assert!(
+            *iteration >= last_iteration,
+            "Iterations should not go back"
+        );
+        last_iteration = *iteration;
+    }
+    assert!(calls.iter().any(|(depth, _, _)| *depth > 0));
+}
+
+/// Backpropagation policy that counts lifecycle signals
+#[derive(Clone, Default)]
+struct LifecyclePolicy {
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl BackpropagationPolicy<TicTacToe> for LifecyclePolicy {
+    fn update_stats(
+        &self,
+        node: &mut MCTSNode<TicTacToe>,
+        result: f64,
+        trace: Option<&[TicTacMove]>,
+    ) {
+        StandardPolicy::new().update_stats(node, result, trace);
+    }
+
+    fn clone_box(&self) -> Box<dyn BackpropagationPolicy<TicTacToe>> {
+        Box::new(self.clone())
+    }
+
+    fn on_search_start(&mut self, root: &TicTacToe) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("start:{}", root.moves_played));
+    }
+
+    fn on_search_end(&mut self, root: &MCTSNode<TicTacToe>) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("end:{}", root.visits()));
+    }
+
+    fn on_move_played(&mut self, action: &TicTacMove, state: &TicTacToe) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("move:{}:{}", action.position, state.moves_played));
+    }
+}
+
+#[test]
+fn test_policy_lifecycle_hooks() {
+    let policy = LifecyclePolicy::default();
+    let events = policy.events.clone();
+
+    let config = MCTSConfig::default().with_max_iterations(50);
+    let mut mcts = MCTS::new(TicTacToe::new(), config).with_backpropagation_policy(policy);
+
+    let action = mcts.search().unwrap();
+    mcts.apply_move(&action);
+    mcts.search().unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(
+        *events,
+        vec![
+            "start:0".to_string(),
+            "end:50".to_string(),
+            format!("move:{}:1", action.position),
+            "start:1".to_string(),
+            "end:50".to_string(),
+        ]
+    );
+
+    // The root moved to the position after the played move
+    assert_eq!(mcts.root().state.moves_played, 1);
+}
+
+/// `(node depth, leaf depth, leaf moves played, iteration)` seen during backpropagation
+type BackpropRecord = (usize, usize, usize, usize);
+
+/// Backpropagation policy that discounts rewards by leaf depth and records its context
+#[derive(Clone, Default)]
+struct LeafAwarePolicy {
+    calls: Arc<Mutex<Vec<BackpropRecord>>>,
+}
+
+impl BackpropagationPolicy<TicTacToe> for LeafAwarePolicy {
+    fn update_stats(
+        &self,
+        node: &mut MCTSNode<TicTacToe>,
+        result: f64,
+        trace: Option<&[TicTacMove]>,
+    ) {
+        StandardPolicy::new().update_stats(node, result, trace);
+    }
+
+    fn update_with_context(
+        &self,
+        node: &mut MCTSNode<TicTacToe>,
+        result: f64,
+        context: &BackpropContext<'_, TicTacToe>,
+    ) {
+        self.calls.lock().unwrap().push((
+            node.depth,
+            context.path_depth,
+            context.leaf_state.moves_played,
+            context.iteration,
+        ));
+        let discount = 0.9_f64.powi(context.path_depth as i32);
+        self.update_stats(node, result * discount, context.trace);
+    }
+
+    fn clone_box(&self) -> Box<dyn BackpropagationPolicy<TicTacToe>> {
+        Box::new(self.clone())
+    }
+}
+
+#[test]
+fn test_backpropagation_policy_receives_context() {
+    let policy = LeafAwarePolicy::default();
+    let calls = policy.calls.clone();
+
+    let config = MCTSConfig::default().with_max_iterations(100);
+    let mut mcts = MCTS::new(TicTacToe::new(), config).with_backpropagation_policy(policy);
+    mcts.search().unwrap();
+
+    let calls = calls.lock().unwrap();
+    assert_eq!(calls.iter().filter(|call| call.0 == 0).count(), 100);
+    for &(node_depth, leaf_depth, leaf_moves, iteration) in calls.iter() {
+        // Every node on the path sees the same leaf, which is below it
+        assert!(node_depth <= leaf_depth);
+        assert_eq!(leaf_moves, leaf_depth);
+        assert!(iteration < 100);
+    }
+    assert!(calls.iter().any(|call| call.1 > 1));
+}
+
+#[test]
+fn test_warm_start_seeds_root_children() {
+    let game = create_specific_board();
+    let config = MCTSConfig::default().with_max_iterations(100);
+    let mut mcts = MCTS::with_node_pool(game, config, 100);
+
+    mcts.warm_start(&[
+        (TicTacMove { position: 7 }, 0.9, 40),
+        (TicTacMove { position: 3 }, 0.2, 10),
+    ])
+    .unwrap();
+
+    // Children are created eagerly with the injected statistics
+    let root = mcts.root();
+    assert_eq!(root.children.len(), 2);
+    assert_eq!(root.visits(), 50);
+    let best = &root.children[root.find_child(&TicTacMove { position: 7 }).unwrap()];
+    assert_eq!(best.visits(), 40);
+    assert!((best.value() - 0.9).abs() < 1e-6);
+
+    // The next search builds on the seeded tree rather than recycling it
+    let action = mcts.search().unwrap();
+    assert_eq!(action.position, 7);
+    assert_eq!(mcts.root().visits(), 150);
+}
+
+#[test]
+fn test_warm_start_refreshes_cached_selection_stats() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_selection_stats_cache(true);
+    let mut mcts = MCTS::new(create_specific_board(), config);
+    mcts.search().unwrap();
+    mcts.warm_start(&[
+        (TicTacMove { position: 7 }, 0.9, 400),
+        (TicTacMove { position: 3 }, 0.2, 10),
+    ])
+    .unwrap();
+
+    let root = mcts.root();
+    assert_eq!(root.selection_stats(), (root.visits(), root.value()));
+    for child in &root.children {
+        assert_eq!(child.selection_stats(), (child.visits(), child.value()));
+    }
+}
+
+#[test]
+fn test_best_action_criteria_on_same_tree() {
+    let mut mcts = MCTS::new(create_specific_board(), MCTSConfig::default());
+    assert_eq!(
+        mcts.best_action(BestChildCriteria::MostVisits).unwrap(),
+        TicTacMove { position: 3 },
+        "falls back to the first legal action"
+    );
+
+    // A lucky long shot, a solid move, and the most explored move
+    mcts.warm_start(&[
+        (TicTacMove { position: 3 }, 0.9, 2),
+        (TicTacMove { position: 5 }, 0.6, 100),
+        (TicTacMove { position: 7 }, 0.5, 150),
+    ])
+    .unwrap();
+
+    let best = |criteria| mcts.best_action(criteria).unwrap().position;
+    assert_eq!(best(BestChildCriteria::MostVisits), 7);
+    assert_eq!(best(BestChildCriteria::HighestValue), 3);
+    assert_eq!(best(BestChildCriteria::SecureChild), 5);
+}
+
+#[test]
+fn test_result_validator_falls_back() {
+    // Forbid the winning square, as an external rule might
+    let config = MCTSConfig::default().with_max_iterations(500);
+    let mut mcts = MCTS::new(create_specific_board(), config.clone()).with_result_validator(
+        |_, action: &TicTacMove| match action.position {
+            7 => Err("square 7 is forbidden".to_string()),
+            _ => Ok(()),
+        },
+    );
+    let action = mcts.search().unwrap();
+    assert!([3, 5].contains(&action.position));
+    let by_value = mcts.best_action(BestChildCriteria::HighestValue).unwrap();
+    assert_ne!(by_value.position, 7);
+
+    // Before any search the unexpanded actions are checked in order
+    let mcts = MCTS::new(create_specific_board(), config.clone()).with_result_validator(
+        |_, action: &TicTacMove| {
+            if action.position == 3 {
+                Err("no".to_string())
+            } else {
+                Ok(())
+            }
+        },
+    );
+    assert_eq!(
+        mcts.best_action(BestChildCriteria::MostVisits).unwrap(),
+        TicTacMove { position: 5 }
+    );
+
+    let mut mcts = MCTS::new(create_specific_board(), config)
+        .with_result_validator(|_, _: &TicTacMove| Err("adjourned".to_string()));
+    match mcts.search() {
+        Err(MCTSError::ActionsRejected(reason)) => assert_eq!(reason, "adjourned"),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_warm_start_rejects_illegal_actions() {
+    let game = create_specific_board();
+    let mut mcts = MCTS::new(game, MCTSConfig::default());
+
+    // Position 0 is already occupied
+    let result = mcts.warm_start(&[
+        (TicTacMove { position: 7 }, 0.9, 40),
+        (TicTacMove { position: 0 }, 0.5, 10),
+    ]);
+    assert!(result.is_err());
+    assert!(mcts.root().children.is_empty());
+    assert_eq!(mcts.root().visits(), 0);
+}
+
+#[test]
+fn test_root_constraints_reserve_budget() {
+    // Three moves are left, so 0.6 is well above an even share
+    let config = MCTSConfig::default().with_max_iterations(1000);
+    let mut mcts = MCTS::new(create_specific_board(), config);
+    let constrained = TicTacMove { position: 7 };
+    mcts.add_root_constraint(constrained.clone(), 0.6).unwrap();
+    for _ in 0..2 {
+        // Constraints survive the tree being recycled between searches
+        mcts.search().unwrap();
+        let root = mcts.root();
+        let visits = root.children[root.find_child(&constrained).unwrap()].visits();
+        assert!(visits as f64 >= 0.6 * root.visits() as f64 - 1.0);
+    }
+
+    mcts.apply_move(&TicTacMove { position: 3 });
+    assert!(mcts.root_constraints().is_empty());
+}
+
+#[test]
+fn test_root_constraints_are_validated() {
+    let mut mcts = MCTS::new(create_specific_board(), MCTSConfig::default());
+
+    // Square 0 is already taken
+    assert!(mcts
+        .add_root_constraint(TicTacMove { position: 0 }, 0.2)
+        .is_err());
+    assert!(mcts
+        .add_root_constraint(TicTacMove { position: 3 }, 0.0)
+        .is_err());
+    assert!(mcts
+        .add_root_constraint(TicTacMove { position: 3 }, 1.5)
+        .is_err());
+
+    mcts.add_root_constraint(TicTacMove { position: 3 }, 0.6)
+        .unwrap();
+    assert!(mcts
+        .add_root_constraint(TicTacMove { position: 7 }, 0.5)
+        .is_err());
+
+    // Constraining the same action again replaces its fraction
+    mcts.add_root_constraint(TicTacMove { position: 3 }, 0.3)
+        .unwrap();
+    mcts.add_root_constraint(TicTacMove { position: 7 }, 0.5)
+        .unwrap();
+    assert_eq!(mcts.root_constraints().len(), 2);
+
+    mcts.clear_root_constraints();
+    assert!(mcts.root_constraints().is_empty());
+}
+
+#[test]
+fn test_exhausted_tree_is_reported() {
+    // Only three empty squares, so the whole tree is a handful of nodes
+    let game = create_specific_board();
+    let config = MCTSConfig::default().with_max_iterations(200);
+
+    let mut mcts = MCTS::new(game.clone(), config.clone());
+    mcts.search().unwrap();
+    let stats = mcts.get_statistics();
+    assert!(stats.tree_exhausted);
+    assert!(mcts.root().exhausted);
+    assert_eq!(stats.iterations, 200, "Stopping is opt-in");
+    assert_eq!(stats.outcome, SearchOutcome::Completed);
+
+    let mut mcts = MCTS::new(game, config.with_stop_when_exhausted(true));
+    mcts.search().unwrap();
+    let stats = mcts.get_statistics();
+    assert!(stats.tree_exhausted);
+    assert_eq!(stats.outcome, SearchOutcome::TreeExhausted);
+    assert!(stats.iterations < 200);
+}
+
+#[test]
+fn test_max_children_caps_branching() {
+    fn check(node: &MCTSNode<TicTacToe>) {
+        assert!(node.children.len() <= 3);
+        if node.children.len() == 3 {
+            assert!(node.is_fully_expanded());
+        }
+        node.children.iter().for_each(check);
+    }
+
+    // Prefer the centre, then corners, then edges
+    let heuristic = |_: &TicTacToe, action: &TicTacMove| match action.position {
+        4 => 2.0,
+        0 | 2 | 6 | 8 => 1.0,
+        _ => 0.0,
+    };
+    let config = MCTSConfig::default()
+        .with_max_iterations(500)
+        .with_max_children(3);
+    let mut mcts = MCTS::new(TicTacToe::new(), config)
+        .with_expansion_policy(HeuristicExpansionPolicy::new(heuristic));
+    mcts.search().unwrap();
+
+    check(mcts.root());
+    let root = mcts.root();
+    assert_eq!(root.children.len(), 3);
+    assert!(root.unexpanded_actions.is_empty());
+    assert_eq!(root.children[0].action, Some(TicTacMove { position: 4 }));
+    for child in &root.children[1..] {
+        let position = child.action.as_ref().unwrap().position;
+        assert!([0, 2, 6, 8].contains(&position));
+    }
+}
+
+#[test]
+fn test_open_tree_is_not_exhausted() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(200)
+        .with_stop_when_exhausted(true);
+
+    let mut mcts = MCTS::new(TicTacToe::new(), config);
+    mcts.search().unwrap();
+    let stats = mcts.get_statistics();
+    assert!(!stats.tree_exhausted);
+    assert!(!mcts.root().exhausted);
+    assert_eq!(stats.iterations, 200);
+}
+
+#[test]
+fn test_repeated_pooled_searches() {
+    let config = MCTSConfig::default().with_max_iterations(100);
+    let mut mcts = MCTS::with_node_pool(TicTacToe::new(), config, 200);
+
+    // Each search starts from a fresh root with its legal actions restored
+    for _ in 0..3 {
+        assert!(mcts.search().is_ok());
+        assert_eq!(mcts.root().visits(), 100);
+        assert_eq!(mcts.get_statistics().iterations, 100);
+    }
+}
+
+#[test]
+fn test_reset_root_recycles_tree() {
+    let config = MCTSConfig::default().with_max_iterations(100);
+    let mut mcts = MCTS::with_node_pool(TicTacToe::new(), config, 200);
+    mcts.search().unwrap();
+    let recycled_before = mcts
+        .get_statistics()
+        .node_pool_stats
+        .as_ref()
+        .unwrap()
+        .total_returned;
+
+    mcts.reset_root(create_specific_board());
+
+    // The old tree went back to the pool and statistics were cleared
+    let stats = mcts.get_statistics();
+    assert_eq!(stats.iterations, 0);
+    let pool_stats = stats.node_pool_stats.as_ref().unwrap();
+    assert!(pool_stats.total_returned > recycled_before);
+
+    // The new root is unvisited and has the new state's legal actions
+    let root = mcts.root();
+    assert_eq!(root.visits(), 0);
+    assert!(root.children.is_empty());
+    assert_eq!(root.unexpanded_actions.len(), 3);
+
+    // The next search runs from the new root
+    assert!(mcts.search().is_ok());
+    assert_eq!(mcts.root().visits(), 100);
+}
+
+#[test]
+fn test_node_pool_builds_nodes_on_demand() {
+    use arboriter_mcts::tree::NodePool;
+
+    // Reserving room does not fabricate nodes or need a state
+    let mut pool: NodePool<TicTacToe> = NodePool::new(100);
+    assert_eq!(pool.available_nodes(), 0);
+    assert_eq!(pool.capacity(), 0);
+
+    let node = pool.create_node(TicTacToe::new(), None, None, 0);
+    assert_eq!(node.unexpanded_actions.len(), 9);
+    assert_eq!(pool.capacity(), 1);
+
+    pool.recycle_node(node);
+    assert_eq!(pool.available_nodes(), 1);
+    let reused = pool.create_node(create_specific_board(), None, None, 0);
+    assert_eq!(reused.unexpanded_actions.len(), 3);
+    assert_eq!(pool.capacity(), 1);
+}
+
+#[test]
+fn test_node_pool_free_list_limit_and_shrink() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_node_pool_max_free(50);
+    let mut mcts = MCTS::with_node_pool(TicTacToe::new(), config, 200);
+
+    mcts.search().unwrap();
+    let pool_stats = mcts.get_statistics().node_pool_stats.clone().unwrap();
+    assert!(pool_stats.available <= 50);
+
+    // Recycling the whole tree keeps no more than the limit
+    mcts.recycle_tree();
+    let pool_stats = mcts.get_statistics().node_pool_stats.clone().unwrap();
+    assert_eq!(pool_stats.available, 50);
+    assert!(pool_stats.retained_bytes > 0);
+
+    mcts.shrink_node_pool(10);
+    let shrunk = mcts.get_statistics().node_pool_stats.clone().unwrap();
+    assert_eq!(shrunk.available, 10);
+    assert!(shrunk.retained_bytes < pool_stats.retained_bytes);
+
+    // Dropped nodes no longer count towards the pool's capacity
+    assert_eq!(shrunk.capacity, shrunk.available);
+}
+
+#[test]
+fn test_iteration_events_cross_threads() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_event_interval(25);
+    let mut mcts = MCTS::new(create_specific_board(), config);
+    let events = mcts.iteration_events();
+
+    let handle = std::thread::spawn(move || mcts.search().unwrap());
+
+    let received: Vec<_> = events.iter().take(4).collect();
+    handle.join().unwrap();
+
+    let iterations: Vec<_> = received.iter().map(|event| event.iteration).collect();
+    assert_eq!(iterations, vec![25, 50, 75, 100]);
+    assert!(received
+        .iter()
+        .all(|event| event.best_action_so_far.is_some()));
+    assert!(received
+        .windows(2)
+        .all(|w| w[0].tree_size <= w[1].tree_size));
+}
+
+#[test]
+fn test_iteration_events_disabled_by_default() {
+    let config = MCTSConfig::default().with_max_iterations(50);
+    let mut mcts = MCTS::new(create_specific_board(), config);
+    let events = mcts.iteration_events();
+
+    mcts.search().unwrap();
+    assert!(events.try_recv().is_err());
+}
+
+#[test]
+fn test_root_snapshot_diff() {
+    let stats = |position, visits, value| RootActionStats {
+        action: TicTacMove { position },
+        visits,
+        value,
+    };
+    let before = RootSnapshot {
+        root_visits: 30,
+        actions: vec![stats(0, 20, 0.6), stats(1, 10, 0.4)],
+        best_action: Some(TicTacMove { position: 0 }),
+    };
+    let after = RootSnapshot {
+        root_visits: 130,
+        actions: vec![stats(0, 40, 0.5), stats(1, 70, 0.7), stats(2, 20, 0.3)],
+        best_action: Some(TicTacMove { position: 1 }),
+    };
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.root_visits_gained, 100);
+    assert!(diff.best_action_flipped());
+    let order: Vec<usize> = diff.actions.iter().map(|d| d.action.position).collect();
+    assert_eq!(order, vec![1, 0, 2]);
+    assert_eq!(diff.actions[1].visits_gained(), 20);
+    assert!((diff.actions[1].value_change() + 0.1).abs() < 1e-12);
+    assert_eq!(diff.actions[2].visits_before, 0);
+    assert!((diff.visit_share(&TicTacMove { position: 1 }) - 0.6).abs() < 1e-12);
+    assert!(diff.summary().contains("(flipped)"));
+
+    assert!(!before.diff(&before).best_action_flipped());
+}
+
+#[test]
+fn test_root_snapshot_across_continued_search() {
+    let mut mcts = MCTS::new(TicTacToe::new(), MCTSConfig::default());
+    mcts.search_for_iterations(200).unwrap();
+    let before = mcts.root_snapshot();
+    assert_eq!(before.root_visits, 200);
+
+    mcts.search_for_iterations(300).unwrap();
+    let after = mcts.root_snapshot();
+    let diff = before.diff(&after);
+
+    assert_eq!(diff.root_visits_gained, 300);
+    let gained: u64 = diff.actions.iter().map(|delta| delta.visits_gained()).sum();
+    assert_eq!(gained, 300);
+    assert_eq!(
+        diff.best_action_flipped(),
+        before.best_action != after.best_action
+    );
+}
+
+#[test]
+fn test_search_logger_writes_json_lines() {
+    let config = MCTSConfig::default().with_max_iterations(300);
+    let mut mcts = MCTS::new(TicTacToe::new(), config.clone());
+    let mut logger = SearchLogger::new(Vec::new(), 3);
+
+    mcts.search().unwrap();
+    logger.log(&mcts).unwrap();
+    mcts.apply_move(&TicTacMove { position: 4 });
+    mcts.search().unwrap();
+    logger.log(&mcts).unwrap();
+    assert_eq!(logger.searches(), 2);
+
+    let output = String::from_utf8(logger.into_inner()).unwrap();
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+
+    let first = &lines[0];
+    assert_eq!(first["search"], 0);
+    assert_eq!(lines[1]["search"], 1);
+    assert_eq!(
+        first["config"],
+        format!("{:016x}", config.fingerprint()).as_str()
+    );
+    assert_eq!(first["root_visits"], 300);
+    assert_eq!(first["statistics"]["iterations"], 300);
+    assert_eq!(first["statistics"]["outcome"], "Completed");
+
+    // The top actions are the most visited, and the best one leads
+    let top = first["top_actions"].as_array().unwrap();
+    assert_eq!(top.len(), 3);
+    assert_eq!(top[0]["action"], first["best_action"]);
+    assert!(top[0]["visits"].as_u64() >= top[1]["visits"].as_u64());
+    assert!(first["best_action"]
+        .as_str()
+        .unwrap()
+        .starts_with("TicTacMove"));
+}
+
+#[test]
+fn test_statistics_csv_export() {
+    let header = SearchStatistics::csv_header();
+    let columns: Vec<&str> = header.split(',').collect();
+    assert_eq!(columns[0], "iterations");
+    assert!(columns.contains(&"simulation_s"));
+    assert!(columns.contains(&"pool_total_returned"));
+
+    // Without a pool the pool columns are present but empty
+    let config = MCTSConfig::default().with_max_iterations(100);
+    let mut mcts = MCTS::new(TicTacToe::new(), config.clone());
+    mcts.search().unwrap();
+    let row = mcts.get_statistics().to_csv_row();
+    let fields: Vec<&str> = row.split(',').collect();
+    assert_eq!(fields.len(), columns.len());
+    assert_eq!(fields[0], "100");
+    assert_eq!(fields[columns.len() - 1], "");
+
+    // With a pool and a result cache they are filled in
+    let config = config.with_result_cache(64);
+    let mut mcts = MCTS::with_node_pool(TicTacToe::new(), config, 200);
+    mcts.search().unwrap();
+    let stats = mcts.get_statistics();
+    let row = stats.to_tsv_row();
+    let fields: Vec<&str> = row.split('\t').collect();
+    assert_eq!(fields.len(), columns.len());
+    assert!(fields.iter().all(|field| !field.is_empty()));
+
+    // Phase timings add up to no more than the whole search
+    let phases = &stats.phase_times;
+    let total = phases.selection + phases.expansion + phases.simulation + phases.backpropagation;
+    assert!(total <= stats.total_time);
+    assert!(phases.simulation > std::time::Duration::ZERO);
+}
+
+/// One-move game paying raw amounts of money rather than rewards in [0, 1]
+#[derive(Clone, Debug)]
+struct Casino {
+    chosen: Option<usize>,
+}
+
+const PAYOUTS: [f64; 3] = [10.0, 250.0, -40.0];
+
+impl GameState for Casino {
+    type Action = TicTacMove;
+    type Player = TicTacPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.chosen.is_some() {
+            return vec![];
+        }
+        (0..PAYOUTS.len())
+            .map(|position| TicTacMove { position })
+            .collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Casino {
+            chosen: Some(action.position),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.chosen.is_some()
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        self.chosen.map_or(0.0, |arm| PAYOUTS[arm])
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        TicTacPlayer::X
+    }
+}
+
+#[test]
+fn test_static_evaluation() {
+    // Deterministic payouts have no spread
+    let mcts = MCTS::new(Casino { chosen: None }, MCTSConfig::default());
+    let evaluations = mcts.static_evaluation(5).unwrap();
+    assert_eq!(evaluations.len(), PAYOUTS.len());
+    for (evaluation, payout) in evaluations.iter().zip(PAYOUTS) {
+        assert_eq!(evaluation.samples, 5);
+        assert_eq!(evaluation.mean, payout);
+        assert_eq!(evaluation.std_dev, 0.0);
+    }
+    assert!(mcts.static_evaluation(0).is_err());
+
+    // Random playouts spread out, and the tree is left alone
+    let mcts = MCTS::new(TicTacToe::new(), MCTSConfig::default());
+    let evaluations = mcts.static_evaluation(50).unwrap();
+    assert_eq!(evaluations.len(), 9);
+    assert!(evaluations.iter().all(|e| (0.0..=1.0).contains(&e.mean)));
+    assert!(evaluations.iter().any(|e| e.std_dev > 0.0));
+    assert!(mcts.root().children.is_empty());
+    assert_eq!(mcts.get_statistics().iterations, 0);
+
+    let mut finished = TicTacToe::new();
+    finished.board = [Some(TicTacPlayer::X); 9];
+    let mcts = MCTS::new(finished, MCTSConfig::default());
+    assert!(mcts.static_evaluation(5).is_err());
+}
+
+#[test]
+fn test_min_max_reward_transform() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_reward_transform(RewardTransform::MinMax);
+    let mut mcts = MCTS::new(Casino { chosen: None }, config);
+
+    let action = mcts.search().unwrap();
+    assert_eq!(action.position, 1);
+    assert_eq!(mcts.get_statistics().reward_range, Some((-40.0, 250.0)));
+    for child in &mcts.root().children {
+        assert!((0.0..=1.0).contains(&child.value()));
+    }
+
+    // The range survives into the next search on the same tree
+    mcts.search_for_iterations(10).unwrap();
+    assert_eq!(mcts.get_statistics().reward_range, Some((-40.0, 250.0)));
+
+    // Without a transform the range is not tracked
+    let mut plain = MCTS::new(Casino { chosen: None }, MCTSConfig::default());
+    plain.search_for_iterations(10).unwrap();
+    assert_eq!(plain.get_statistics().reward_range, None);
+}
+
+#[test]
+fn test_search_tracks_win_rate() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_reward_transform(RewardTransform::Tanh { scale: 100.0 });
+    let mut mcts = MCTS::new(Casino { chosen: None }, config);
+    mcts.search().unwrap();
+
+    // Every result through a child has the same sign, so its win rate is 0 or 1
+    for child in &mcts.root().children {
+        let payout = PAYOUTS[child.action.as_ref().unwrap().position];
+        let expected = if payout > 0.0 { 1.0 } else { 0.0 };
+        assert!((child.win_rate() - expected).abs() < 1e-6);
+    }
+    assert!(mcts.root().win_rate() > 0.0 && mcts.root().win_rate() < 1.0);
+}
+
+#[test]
+fn test_warm_start_counts_outcomes_like_backpropagation() {
+    let mut mcts = MCTS::new(create_specific_board(), MCTSConfig::default());
+    mcts.warm_start(&[
+        (TicTacMove { position: 7 }, 0.9, 30),
+        (TicTacMove { position: 3 }, 0.5, 10),
+        (TicTacMove { position: 5 }, 0.2, 10),
+    ])
+    .unwrap();
+
+    // A 0.9 result is a win, 0.5 a draw and 0.2 a loss
+    let root = mcts.root();
+    let win_rate =
+        |position| root.children[root.find_child(&TicTacMove { position }).unwrap()].win_rate();
+    assert!((win_rate(7) - 1.0).abs() < 1e-6);
+    assert!((win_rate(3) - 0.5).abs() < 1e-6);
+    assert!(win_rate(5).abs() < 1e-6);
+    assert!((root.win_rate() - 0.7).abs() < 1e-6);
+}
+
+#[test]
+fn test_per_player_perspective_blocks_opponent() {
+    // O threatens the top row; X has no immediate win and must block at 2
+    // O O -
+    // X - -
+    // - - X
+    let mut game = TicTacToe::new();
+    game.board[0] = Some(TicTacPlayer::O);
+    game.board[1] = Some(TicTacPlayer::O);
+    game.board[3] = Some(TicTacPlayer::X);
+    game.board[8] = Some(TicTacPlayer::X);
+    game.moves_played = 4;
+    game.current_player = TicTacPlayer::X;
+
+    let config = MCTSConfig::default()
+        .with_max_iterations(3000)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let mut mcts = MCTS::new(game, config);
+
+    let action = mcts.search().unwrap();
+    assert_eq!(action.position, 2);
+
+    // Children of the root hold X's view, grandchildren O's
+    let block = &mcts.root().children[mcts.root().find_child(&action).unwrap()];
+    assert_eq!(block.player, TicTacPlayer::X);
+    assert!(block.children.iter().all(|c| c.player == TicTacPlayer::O));
+}
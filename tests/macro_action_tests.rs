@@ -0,0 +1,121 @@
+use arboriter_mcts::{
+    game_state::{Action, NoPlayer},
+    macro_action::{MacroAction, MacroState},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(i32);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        (self.0 + 1) as usize
+    }
+}
+
+/// Reach position 10 of a corridor within 12 steps
+#[derive(Clone, Debug)]
+struct Corridor {
+    position: i32,
+    steps: u32,
+}
+
+impl GameState for Corridor {
+    type Action = Step;
+    type Player = NoPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            Vec::new()
+        } else {
+            vec![Step(-1), Step(1)]
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Corridor {
+            position: self.position + action.0,
+            steps: self.steps + 1,
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.position >= 10 || self.steps >= 12
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        if self.position >= 10 {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        NoPlayer
+    }
+}
+
+fn runs(state: &Corridor) -> Vec<MacroAction<Step>> {
+    let mut macros: Vec<_> = state
+        .get_legal_actions()
+        .into_iter()
+        .map(MacroAction::primitive)
+        .collect();
+    macros.push(MacroAction::new(vec![Step(1); 5]));
+    macros.push(MacroAction::new(vec![Step(-1); 5]));
+    macros
+}
+
+fn start() -> MacroState<Corridor> {
+    MacroState::new(
+        Corridor {
+            position: 0,
+            steps: 0,
+        },
+        runs,
+    )
+}
+
+#[test]
+fn test_macro_ids() {
+    assert_eq!(MacroAction::primitive(Step(1)).id(), Step(1).id());
+    let right = MacroAction::new(vec![Step(1); 5]);
+    assert_eq!(right.id(), MacroAction::new(vec![Step(1); 5]).id());
+    assert_ne!(right.id(), MacroAction::new(vec![Step(-1); 5]).id());
+    assert_ne!(right.id(), MacroAction::new(vec![Step(1); 4]).id());
+    assert_eq!(right.len(), 5);
+    assert_eq!(right.actions()[0], Step(1));
+}
+
+#[test]
+fn test_macro_applies_primitives_atomically() {
+    let state = start();
+    assert_eq!(state.get_legal_actions().len(), 4);
+
+    let after = state.apply_action(&MacroAction::new(vec![Step(1); 5]));
+    assert_eq!(after.state().position, 5);
+    assert_eq!(after.state().steps, 5);
+    assert_eq!(after.get_legal_actions().len(), 4);
+
+    // The macro stops as soon as the game ends
+    let done = after
+        .apply_action(&MacroAction::new(vec![Step(1); 5]))
+        .apply_action(&MacroAction::new(vec![Step(1); 5]));
+    assert!(done.is_terminal());
+    assert_eq!(done.state().position, 10);
+    assert!(done.get_legal_actions().is_empty());
+    assert_eq!(done.get_result(&NoPlayer), 1.0);
+}
+
+#[test]
+fn test_search_attributes_statistics_to_macros() {
+    let mut mcts = MCTS::new(start(), MCTSConfig::default().with_max_iterations(2000));
+    let action = mcts.search().unwrap();
+    assert_eq!(action, MacroAction::new(vec![Step(1); 5]));
+
+    let root = mcts.root();
+    let best = &root.children[root.find_child(&action).unwrap()];
+    assert_eq!(best.state.state().position, 5);
+    assert!(best.value() > 0.5, "{}", best.value());
+}
@@ -0,0 +1,159 @@
+use arboriter_mcts::{
+    config::{BestChildCriteria, RewardPerspective, ValueEstimate},
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    A,
+    B,
+}
+
+impl Player for Side {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Move {
+    Left,
+    Right,
+    Reply(usize),
+}
+
+impl Action for Move {
+    fn id(&self) -> usize {
+        match self {
+            Move::Left => 0,
+            Move::Right => 1,
+            Move::Reply(reply) => 2 + reply,
+        }
+    }
+}
+
+/// A moves left or right, then B replies
+///
+/// After left B has four replies, of which only the last wins for B; after
+/// right both replies draw.
+#[derive(Clone, Debug)]
+struct Trap {
+    moves: Vec<Move>,
+}
+
+impl GameState for Trap {
+    type Action = Move;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.moves[..] {
+            [] => vec![Move::Left, Move::Right],
+            [Move::Left] => (0..4).map(Move::Reply).collect(),
+            [Move::Right] => (0..2).map(Move::Reply).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut moves = self.moves.clone();
+        moves.push(*action);
+        Trap { moves }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.moves.len() == 2
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        let a_result = match self.moves[..] {
+            [Move::Left, Move::Reply(3)] => 0.0,
+            [Move::Left, _] => 1.0,
+            _ => 0.5,
+        };
+        match for_player {
+            Side::A => a_result,
+            Side::B => 1.0 - a_result,
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        if self.moves.len() == 1 {
+            Side::B
+        } else {
+            Side::A
+        }
+    }
+}
+
+/// Seeds a tree where B found its refutation of left only late
+fn seeded(config: MCTSConfig) -> MCTS<Trap> {
+    let mut mcts = MCTS::new(Trap { moves: Vec::new() }, config);
+    for reply in 0..3 {
+        mcts.graft(&[Move::Left, Move::Reply(reply)], 5, 0.0)
+            .unwrap();
+    }
+    mcts.graft(&[Move::Left, Move::Reply(3)], 2, 1.0).unwrap();
+    mcts.graft(&[Move::Right, Move::Reply(0)], 10, 0.5).unwrap();
+    mcts
+}
+
+fn per_player() -> MCTSConfig {
+    MCTSConfig::default()
+        .with_reward_perspective(RewardPerspective::PerPlayer)
+        .with_best_child_criteria(BestChildCriteria::HighestValue)
+}
+
+#[test]
+fn test_max_value_follows_the_best_reply() {
+    let mcts = seeded(per_player());
+    let root = mcts.root();
+    let left = &root.children[0];
+    let right = &root.children[1];
+
+    // Left still averages well for A, but B's best reply refutes it
+    assert!((left.value() - 15.0 / 17.0).abs() < 1e-6);
+    assert!(left.max_value(RewardPerspective::PerPlayer).abs() < 1e-6);
+    assert!((right.max_value(RewardPerspective::PerPlayer) - 0.5).abs() < 1e-6);
+
+    // A leaf reports its mean
+    let leaf = &left.children[3];
+    assert_eq!(leaf.max_value(RewardPerspective::PerPlayer), leaf.value());
+    assert!(
+        (left.estimated_value(ValueEstimate::Max, RewardPerspective::PerPlayer)
+            - left.max_value(RewardPerspective::PerPlayer))
+        .abs()
+            < 1e-12
+    );
+}
+
+#[test]
+fn test_highest_value_ranks_by_the_configured_estimate() {
+    let by_mean = seeded(per_player());
+    assert_eq!(
+        by_mean
+            .best_action(BestChildCriteria::HighestValue)
+            .unwrap(),
+        Move::Left
+    );
+
+    let by_max = seeded(per_player().with_value_estimate(ValueEstimate::Max));
+    assert_eq!(
+        by_max.best_action(BestChildCriteria::HighestValue).unwrap(),
+        Move::Right
+    );
+    let margin = by_max
+        .runner_up_margin(BestChildCriteria::HighestValue)
+        .unwrap();
+    assert!((margin - 0.5).abs() < 1e-6);
+
+    // Other criteria ignore the estimate
+    assert_eq!(
+        by_max.best_action(BestChildCriteria::MostVisits).unwrap(),
+        Move::Left
+    );
+}
+
+#[test]
+fn test_shared_perspective_takes_the_plain_maximum() {
+    let mcts = seeded(MCTSConfig::default());
+    let left = &mcts.root().children[0];
+    assert!((left.max_value(RewardPerspective::Shared) - 1.0).abs() < 1e-6);
+    assert_eq!(MCTSConfig::default().value_estimate, ValueEstimate::Mean);
+}
@@ -0,0 +1,161 @@
+use std::mem::size_of;
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTSNode, SearchStatistics, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Climber;
+
+impl Player for Climber {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(usize);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Six picks of 0, 1 or 2; the result is the sum of the picks over their
+/// maximum. The full tree has over a thousand nodes.
+#[derive(Clone, Debug)]
+struct Ladder {
+    picks: Vec<usize>,
+}
+
+const PICKS: usize = 6;
+
+impl GameState for Ladder {
+    type Action = Step;
+    type Player = Climber;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return Vec::new();
+        }
+        (0..3).map(Step).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut picks = self.picks.clone();
+        picks.push(action.0);
+        Ladder { picks }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picks.len() == PICKS
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.picks.iter().sum::<usize>() as f64 / (2 * PICKS) as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Climber
+    }
+}
+
+fn start() -> Ladder {
+    Ladder { picks: Vec::new() }
+}
+
+/// The limit's estimate of one node: the node plus room for three actions
+fn bytes_per_node() -> usize {
+    size_of::<MCTSNode<Ladder>>() + 3 * size_of::<Step>()
+}
+
+#[test]
+fn test_memory_limit_defaults_to_unbounded() {
+    assert_eq!(MCTSConfig::default().max_memory_bytes, None);
+    assert_eq!(
+        MCTSConfig::default()
+            .with_max_memory_bytes(4096)
+            .max_memory_bytes,
+        Some(4096)
+    );
+
+    let mut mcts = MCTS::new(start(), MCTSConfig::default().with_max_iterations(300));
+    mcts.search().unwrap();
+    assert!(!mcts.get_statistics().memory_limited);
+    assert!(mcts.get_statistics().tree_size > 100);
+}
+
+#[test]
+fn test_expansion_stops_at_the_memory_limit() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(500)
+        .with_max_memory_bytes(100 * bytes_per_node());
+    let mut mcts = MCTS::new(start(), config);
+
+    mcts.search().unwrap();
+
+    // Every iteration still ran, from the leaves of a tree that stopped
+    // growing at the limit
+    let stats = mcts.get_statistics();
+    assert!(stats.memory_limited);
+    assert_eq!(stats.tree_size, 100);
+    assert_eq!(mcts.root().subtree_size(), 100);
+    assert_eq!(stats.iterations, 500);
+    assert_eq!(mcts.root().visits(), 500);
+    assert!(mcts.check_invariants().is_ok());
+    assert!(stats.summary().contains("Memory limited"));
+}
+
+#[test]
+fn test_batched_search_respects_the_memory_limit() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(400)
+        .with_rollout_batch_size(4)
+        .with_max_memory_bytes(60 * bytes_per_node());
+    let mut mcts = MCTS::new(start(), config);
+
+    mcts.search().unwrap();
+
+    // The limit is checked per leaf, so a batch cannot overshoot it
+    let stats = mcts.get_statistics();
+    assert!(stats.memory_limited);
+    assert_eq!(mcts.root().subtree_size(), 60);
+    assert_eq!(mcts.root().visits(), 400);
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_reused_tree_counts_against_the_limit() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(200)
+        .with_max_memory_bytes(80 * bytes_per_node());
+    let mut mcts = MCTS::new(start(), config);
+
+    mcts.search().unwrap();
+    assert_eq!(mcts.root().subtree_size(), 80);
+
+    // The second search starts at the limit and adds nothing
+    mcts.search().unwrap();
+    let stats = mcts.get_statistics();
+    assert!(stats.memory_limited);
+    assert_eq!(stats.tree_size, 1);
+    assert_eq!(mcts.root().subtree_size(), 80);
+    assert_eq!(mcts.root().visits(), 400);
+}
+
+#[test]
+fn test_memory_limited_is_a_csv_column() {
+    let header = SearchStatistics::csv_header();
+    assert!(header.split(',').any(|column| column == "memory_limited"));
+
+    let config = MCTSConfig::default()
+        .with_max_iterations(50)
+        .with_max_memory_bytes(10 * bytes_per_node());
+    let mut mcts = MCTS::new(start(), config);
+    mcts.search().unwrap();
+
+    let row = mcts.get_statistics().to_csv_row();
+    let index = header
+        .split(',')
+        .position(|column| column == "memory_limited")
+        .unwrap();
+    assert_eq!(row.split(',').nth(index), Some("true"));
+}
@@ -0,0 +1,164 @@
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    policy::{
+        backpropagation::BackpropagationPolicy,
+        ments::MentsPolicy,
+        selection::{SelectionContext, SelectionPolicy},
+    },
+    tree::MCTSNode,
+    GameState, MCTSConfig, MCTS,
+};
+
+/// One-move game where action `i` is worth `payoffs[i]`
+#[derive(Clone, Debug)]
+struct Bandit {
+    payoffs: Vec<f64>,
+    chosen: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Arm(usize);
+
+impl Action for Arm {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Gambler;
+
+impl Player for Gambler {}
+
+impl GameState for Bandit {
+    type Action = Arm;
+    type Player = Gambler;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.chosen.is_some() {
+            return vec![];
+        }
+        (0..self.payoffs.len()).map(Arm).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Bandit {
+            payoffs: self.payoffs.clone(),
+            chosen: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.chosen.is_some()
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        self.chosen.map_or(0.5, |arm| self.payoffs[arm])
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Gambler
+    }
+}
+
+fn bandit(payoffs: &[f64]) -> Bandit {
+    Bandit {
+        payoffs: payoffs.to_vec(),
+        chosen: None,
+    }
+}
+
+#[test]
+fn test_soft_backup() {
+    let mut node = MCTSNode::new(bandit(&[1.0, 0.0]), None, None, 0);
+    let policy = MentsPolicy::new(0.5, 1.0);
+
+    // A leaf averages its results
+    policy.update_stats(&mut node, 0.4, None);
+    assert!((node.value() - 0.4).abs() < 1e-6);
+
+    node.expand(0);
+    node.expand(0);
+    for (child, reward) in node.children.iter().zip([1.0, 0.0]) {
+        child.increment_visits();
+        child.add_reward(reward);
+    }
+    policy.update_stats(&mut node, 0.0, None);
+
+    // τ ln(e^(1/τ) + e^(0/τ)) with τ = 0.5
+    let expected = 0.5 * (2.0f64.exp() + 1.0).ln();
+    assert_eq!(node.visits(), 2);
+    assert!((node.value() - expected).abs() < 1e-6);
+    assert!(node.value() > 1.0, "soft value exceeds the best child");
+}
+
+#[test]
+fn test_e2w_probabilities() {
+    let mut node = MCTSNode::new(bandit(&[1.0, 0.0, 0.0]), None, None, 0);
+    let policy = MentsPolicy::new(0.1, 0.1);
+
+    node.expand(0);
+    node.expand(0);
+    node.expand(0);
+
+    // Without visits the distribution is uniform
+    let probabilities = policy.e2w_probabilities(&node);
+    assert_eq!(probabilities.len(), 3);
+    for probability in &probabilities {
+        assert!((probability - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    // Once visited, the best child dominates but every child keeps some mass
+    for (child, reward) in node.children.iter().zip([0.0, 0.9, 0.1]) {
+        child.increment_visits();
+        child.add_reward(reward);
+    }
+    for _ in 0..1000 {
+        node.increment_visits();
+    }
+    let probabilities = policy.e2w_probabilities(&node);
+    assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(probabilities[1] > 0.8);
+    assert!(probabilities.iter().all(|p| *p > 0.0));
+}
+
+#[test]
+fn test_ments_child_scores_are_e2w_probabilities() {
+    let mut node = MCTSNode::new(bandit(&[1.0, 0.0, 0.0]), None, None, 0);
+    let policy = MentsPolicy::new(0.1, 0.1);
+    for (reward, _) in [0.0, 0.9, 0.1].iter().zip(0..3) {
+        node.expand(0);
+        let child = node.children.last().unwrap();
+        child.increment_visits();
+        child.add_reward(*reward);
+    }
+    for _ in 0..1000 {
+        node.increment_visits();
+    }
+
+    let mut rng = rand::thread_rng();
+    let scores = policy
+        .child_scores(&node, &mut SelectionContext::new(&mut rng))
+        .unwrap();
+    let probabilities = policy.e2w_probabilities(&node);
+    for (score, probability) in scores.iter().zip(&probabilities) {
+        assert!((score.score - probability).abs() < 1e-9);
+        assert!((score.exploitation + score.exploration - score.score).abs() < 1e-12);
+    }
+    // Every child gets the same uniform share
+    assert!(scores
+        .iter()
+        .all(|s| (s.exploration - scores[0].exploration).abs() < 1e-12));
+}
+
+#[test]
+fn test_ments_search_finds_best_arm() {
+    let ments = MentsPolicy::new(0.05, 0.5);
+    let config = MCTSConfig::default().with_max_iterations(500);
+    let mut mcts = MCTS::new(bandit(&[0.2, 0.9, 0.4, 0.1]), config)
+        .with_selection_policy(ments.clone())
+        .with_backpropagation_policy(ments);
+
+    let action = mcts.search().unwrap();
+    assert_eq!(action, Arm(1));
+}
@@ -0,0 +1,158 @@
+#![cfg(feature = "metrics")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    stats::metric_names,
+    GameState, MCTSConfig, MCTS,
+};
+use metrics::{
+    Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+
+/// Counts down from a number; either move takes one or two off
+#[derive(Clone, Debug)]
+struct Countdown(u32);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(u32);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+impl GameState for Countdown {
+    type Action = Step;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.0.min(2)).map(Step).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Countdown(self.0 - action.0)
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        0.5
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+/// Sums counters and collects histogram samples, keyed by metric name
+#[derive(Default)]
+struct Totals {
+    counters: Mutex<HashMap<String, u64>>,
+    histograms: Mutex<HashMap<String, Vec<f64>>>,
+}
+
+struct Handle {
+    name: String,
+    totals: Arc<Totals>,
+}
+
+impl CounterFn for Handle {
+    fn increment(&self, value: u64) {
+        *self
+            .totals
+            .counters
+            .lock()
+            .unwrap()
+            .entry(self.name.clone())
+            .or_default() += value;
+    }
+
+    fn absolute(&self, value: u64) {
+        self.totals
+            .counters
+            .lock()
+            .unwrap()
+            .insert(self.name.clone(), value);
+    }
+}
+
+impl HistogramFn for Handle {
+    fn record(&self, value: f64) {
+        self.totals
+            .histograms
+            .lock()
+            .unwrap()
+            .entry(self.name.clone())
+            .or_default()
+            .push(value);
+    }
+}
+
+#[derive(Default)]
+struct TotalsRecorder {
+    totals: Arc<Totals>,
+}
+
+impl TotalsRecorder {
+    fn handle(&self, key: &Key) -> Arc<Handle> {
+        Arc::new(Handle {
+            name: key.name().to_string(),
+            totals: self.totals.clone(),
+        })
+    }
+}
+
+impl Recorder for TotalsRecorder {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.handle(key))
+    }
+
+    fn register_gauge(&self, _key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(self.handle(key))
+    }
+}
+
+#[test]
+fn test_search_records_metrics() {
+    let recorder = TotalsRecorder::default();
+    let totals = recorder.totals.clone();
+
+    let mut mcts = MCTS::new(Countdown(8), MCTSConfig::default().with_max_iterations(40));
+    metrics::with_local_recorder(&recorder, || {
+        mcts.search().unwrap();
+        mcts.search().unwrap();
+    });
+
+    let counters = totals.counters.lock().unwrap();
+    assert_eq!(counters[metric_names::SEARCHES], 2);
+    assert_eq!(counters[metric_names::ITERATIONS], 80);
+    assert!(counters[metric_names::NODES_ALLOCATED] > 0);
+
+    let histograms = totals.histograms.lock().unwrap();
+    assert_eq!(histograms[metric_names::SEARCH_DURATION].len(), 2);
+    let rollouts = &histograms[metric_names::ROLLOUT_LENGTH];
+    assert_eq!(rollouts.len(), 80);
+    assert!(rollouts.iter().all(|&length| length <= 8.0));
+}
@@ -0,0 +1,134 @@
+use arboriter_mcts::{
+    config::{BestChildCriteria, MoveHysteresis},
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Value of each pick; the last two are nearly equal
+const VALUES: [f64; 3] = [0.1, 0.5, 0.52];
+
+/// Picks until `length` have been made; the score is the mean pick value
+#[derive(Clone, Debug)]
+struct Picks {
+    picks: Vec<usize>,
+    length: usize,
+}
+
+impl GameState for Picks {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return Vec::new();
+        }
+        (0..VALUES.len()).map(Pick).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut picks = self.picks.clone();
+        picks.push(action.0);
+        Picks {
+            picks,
+            length: self.length,
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picks.len() >= self.length
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.picks.iter().map(|&pick| VALUES[pick]).sum::<f64>() / self.length as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn picks(length: usize) -> Picks {
+    Picks {
+        picks: Vec::new(),
+        length,
+    }
+}
+
+/// Plans `planned` from seeded statistics, then searches the same root again
+/// on a fresh tree
+fn replan(config: MCTSConfig, planned: Pick) -> Pick {
+    // The pool makes the second search start from a fresh tree
+    let mut mcts = MCTS::with_node_pool(picks(1), config.with_max_iterations(300), 16);
+    mcts.warm_start(&[(planned.clone(), 0.9, 1000)]).unwrap();
+    mcts.search().unwrap();
+    assert_eq!(mcts.planned_line(), [planned]);
+
+    mcts.search().unwrap()
+}
+
+#[test]
+fn test_planned_move_is_kept_within_the_margin() {
+    // Pick 2 beats the planned pick 1 by 0.02
+    let by_value = MCTSConfig::default().with_best_child_criteria(BestChildCriteria::HighestValue);
+    assert_eq!(replan(by_value.clone(), Pick(1)), Pick(2));
+    assert_eq!(
+        replan(by_value.clone().with_move_hysteresis(0.0, 0.1), Pick(1)),
+        Pick(1)
+    );
+    assert_eq!(
+        replan(by_value.with_move_hysteresis(0.0, 0.01), Pick(1)),
+        Pick(2)
+    );
+}
+
+#[test]
+fn test_visit_margin_applies_to_most_visits() {
+    // Any lead is within a margin of all the root's visits
+    let config = MCTSConfig::default().with_move_hysteresis(1.0, 0.0);
+    assert_eq!(replan(config, Pick(0)), Pick(0));
+}
+
+#[test]
+fn test_plan_follows_moves_along_the_line() {
+    let mut mcts = MCTS::new(picks(3), MCTSConfig::default().with_max_iterations(2000));
+    let best = mcts.search().unwrap();
+    let line = mcts.planned_line().to_vec();
+    assert_eq!(line.len(), 3);
+    assert_eq!(line[0], best);
+    assert_eq!(line[1..], mcts.principal_variation()[1..]);
+
+    mcts.apply_move(&line[0]);
+    assert_eq!(mcts.planned_line(), &line[1..]);
+
+    // Leaving the line forgets the plan
+    let other = if line[1] == Pick(0) { Pick(1) } else { Pick(0) };
+    mcts.apply_move(&other);
+    assert!(mcts.planned_line().is_empty());
+
+    mcts.search().unwrap();
+    assert!(!mcts.planned_line().is_empty());
+    mcts.reset_root(picks(3));
+    assert!(mcts.planned_line().is_empty());
+}
+
+#[test]
+fn test_negative_margins_are_raised_to_zero() {
+    assert_eq!(
+        MoveHysteresis::new(-1.0, -0.5),
+        MoveHysteresis::new(0.0, 0.0)
+    );
+}
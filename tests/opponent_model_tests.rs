@@ -0,0 +1,138 @@
+use arboriter_mcts::{
+    config::RewardPerspective,
+    game_state::{Action, Player},
+    policy::opponent::{select_opponent_child, OpponentModel},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    Me,
+    Them,
+}
+
+impl Player for Side {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Move {
+    Safe,
+    Trap,
+    Punish,
+    Blunder,
+}
+
+impl Action for Move {
+    fn id(&self) -> usize {
+        match self {
+            Move::Safe => 0,
+            Move::Trap => 1,
+            Move::Punish => 2,
+            Move::Blunder => 3,
+        }
+    }
+}
+
+/// Play safe for a draw, or set a trap that wins unless the opponent sees it
+#[derive(Clone, Debug)]
+struct Trap {
+    history: Vec<Move>,
+}
+
+impl GameState for Trap {
+    type Action = Move;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.history.as_slice() {
+            [] => vec![Move::Safe, Move::Trap],
+            [Move::Trap] => vec![Move::Punish, Move::Blunder],
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut history = self.history.clone();
+        history.push(action.clone());
+        Trap { history }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.get_legal_actions().is_empty()
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        let mine = match self.history.last() {
+            Some(Move::Blunder) => 1.0,
+            Some(Move::Punish) => 0.0,
+            _ => 0.5,
+        };
+        match for_player {
+            Side::Me => mine,
+            Side::Them => 1.0 - mine,
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        if self.history.is_empty() {
+            Side::Me
+        } else {
+            Side::Them
+        }
+    }
+}
+
+/// The opponent almost never sees the trap
+fn careless(_state: &Trap, action: &Move) -> f64 {
+    match action {
+        Move::Blunder => 0.9,
+        _ => 0.1,
+    }
+}
+
+fn config() -> MCTSConfig {
+    MCTSConfig::default()
+        .with_max_iterations(500)
+        .with_reward_perspective(RewardPerspective::PerPlayer)
+}
+
+#[test]
+fn test_without_model_assumes_best_reply() {
+    let mut mcts = MCTS::new(Trap { history: vec![] }, config());
+    assert_eq!(mcts.search().unwrap(), Move::Safe);
+}
+
+#[test]
+fn test_trusted_model_exploits_predicted_mistake() {
+    let mut mcts = MCTS::new(Trap { history: vec![] }, config()).with_opponent_model(careless, 1.0);
+    assert_eq!(mcts.search().unwrap(), Move::Trap);
+
+    // Our own moves are still chosen by the selection policy
+    let root = mcts.root();
+    assert!(root.children.iter().all(|child| child.visits() > 0));
+}
+
+#[test]
+fn test_select_opponent_child_mixes_prediction() {
+    let mut root = arboriter_mcts::MCTSNode::new(Trap { history: vec![] }, None, None, 0);
+    root.expand(root.find_unexpanded(&Move::Trap).unwrap());
+    let node = &mut root.children[0];
+    for action in [Move::Punish, Move::Blunder] {
+        let index = node.find_unexpanded(&action).unwrap();
+        node.expand(index);
+    }
+
+    // Unvisited children come first
+    assert_eq!(select_opponent_child(&careless, node, 1.0, 1.0), 0);
+
+    node.inject_stats(20, 0.5);
+    node.children[0].inject_stats(10, 1.0);
+    node.children[1].inject_stats(10, 0.0);
+    let punish = node.find_child(&Move::Punish).unwrap();
+    let blunder = node.find_child(&Move::Blunder).unwrap();
+
+    assert_eq!(select_opponent_child(&careless, node, 0.0, 1.0), punish);
+    assert_eq!(select_opponent_child(&careless, node, 1.0, 1.0), blunder);
+
+    let model: &dyn OpponentModel<Trap> = &careless;
+    assert_eq!(model.probability(&node.state, &Move::Blunder), 0.9);
+}
@@ -0,0 +1,154 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    GameState, MCTSConfig, SimulationPolicy, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Branch(usize);
+
+impl Action for Branch {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Three branches, each a long random walk whose playouts look like a coin flip
+#[derive(Clone, Debug)]
+struct Walk {
+    branch: Option<usize>,
+    steps: usize,
+}
+
+impl GameState for Walk {
+    type Action = Branch;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return Vec::new();
+        }
+        match self.branch {
+            None => (0..3).map(Branch).collect(),
+            Some(_) => vec![Branch(0), Branch(1)],
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        match self.branch {
+            None => Walk {
+                branch: Some(action.0),
+                steps: 0,
+            },
+            Some(branch) => Walk {
+                branch: Some(branch),
+                steps: self.steps * 2 + action.0 + 1,
+            },
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.steps >= 30
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        (self.steps % 2) as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+/// Knows that branch 2 is won and the others are lost
+fn tablebase(state: &Walk, _: &Solo) -> Option<f64> {
+    state
+        .branch
+        .map(|branch| if branch == 2 { 1.0 } else { 0.0 })
+}
+
+/// Counts playouts
+#[derive(Clone)]
+struct CountingPolicy(Arc<AtomicUsize>);
+
+impl SimulationPolicy<Walk> for CountingPolicy {
+    fn simulate(&self, state: &Walk) -> (f64, Vec<Branch>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        state.simulate_random_playout(&Solo)
+    }
+
+    fn clone_box(&self) -> Box<dyn SimulationPolicy<Walk>> {
+        Box::new(self.clone())
+    }
+}
+
+fn start() -> Walk {
+    Walk {
+        branch: None,
+        steps: 0,
+    }
+}
+
+#[test]
+fn test_oracle_values_guide_the_search() {
+    let config = MCTSConfig::default().with_max_iterations(60);
+    let mut mcts = MCTS::new(start(), config).with_oracle(tablebase);
+
+    let best = mcts.search().unwrap();
+    assert_eq!(best, Branch(2));
+
+    let root = mcts.root();
+    for child in &root.children {
+        let expected = if child.action == Some(Branch(2)) {
+            1.0
+        } else {
+            0.0
+        };
+        assert_eq!(child.solved, Some(expected));
+        assert!(child.is_solved());
+        assert!(child.children.is_empty(), "solved nodes are not expanded");
+        assert_eq!(child.total_reward(), expected * child.visits() as f64);
+    }
+    assert_eq!(mcts.get_statistics().oracle_hits, 3);
+}
+
+#[test]
+fn test_solved_nodes_skip_playouts() {
+    let playouts = Arc::new(AtomicUsize::new(0));
+    let config = MCTSConfig::default().with_max_iterations(40);
+    let mut mcts = MCTS::new(start(), config)
+        .with_simulation_policy(CountingPolicy(Arc::clone(&playouts)))
+        .with_oracle(tablebase);
+
+    mcts.search().unwrap();
+
+    assert_eq!(playouts.load(Ordering::SeqCst), 0);
+    assert_eq!(
+        mcts.root().visits(),
+        mcts.get_statistics().iterations as u64
+    );
+}
+
+#[test]
+fn test_unknown_states_are_simulated() {
+    let playouts = Arc::new(AtomicUsize::new(0));
+    let config = MCTSConfig::default().with_max_iterations(40);
+    let mut mcts = MCTS::new(start(), config)
+        .with_simulation_policy(CountingPolicy(Arc::clone(&playouts)))
+        .with_oracle(|_: &Walk, _: &Solo| None);
+
+    mcts.search().unwrap();
+
+    assert!(playouts.load(Ordering::SeqCst) > 0);
+    assert_eq!(mcts.get_statistics().oracle_hits, 0);
+    assert!(mcts.root().children.iter().all(|child| !child.is_solved()));
+}
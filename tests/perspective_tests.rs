@@ -0,0 +1,196 @@
+use arboriter_mcts::{
+    config::Perspective,
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTSError, SimulationPolicy, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Seat {
+    North,
+    East,
+    South,
+}
+
+impl Player for Seat {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// North picks one of three deals, East passes, and the deal pays each seat
+/// differently
+#[derive(Clone, Debug)]
+struct Deal {
+    moves: Vec<usize>,
+}
+
+const PAYOFFS: [[f64; 3]; 3] = [
+    // North, East, South
+    [0.9, 0.1, 0.1],
+    [0.1, 0.9, 0.2],
+    [0.2, 0.2, 0.9],
+];
+
+impl GameState for Deal {
+    type Action = Pick;
+    type Player = Seat;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.moves.len() {
+            0 => (0..3).map(Pick).collect(),
+            1 => vec![Pick(0)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut moves = self.moves.clone();
+        moves.push(action.0);
+        Deal { moves }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.moves.len() == 2
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        let seat = match for_player {
+            Seat::North => 0,
+            Seat::East => 1,
+            Seat::South => 2,
+        };
+        PAYOFFS[self.moves[0]][seat]
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        match self.moves.len() % 3 {
+            0 => Seat::North,
+            1 => Seat::East,
+            _ => Seat::South,
+        }
+    }
+}
+
+fn start() -> Deal {
+    Deal { moves: Vec::new() }
+}
+
+/// Scores the first deal high for whoever is to move, without `simulate_for`
+#[derive(Clone)]
+struct Flattery;
+
+impl SimulationPolicy<Deal> for Flattery {
+    fn simulate(&self, state: &Deal) -> (f64, Vec<Pick>) {
+        let result = if state.moves.first() == Some(&0) {
+            0.8
+        } else {
+            0.3
+        };
+        (result, Vec::new())
+    }
+
+    fn clone_box(&self) -> Box<dyn SimulationPolicy<Deal>> {
+        Box::new(self.clone())
+    }
+}
+
+#[test]
+fn test_root_player_perspective_scores_for_the_root() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_perspective(Perspective::RootPlayer);
+    let mut mcts = MCTS::new(start(), config);
+
+    assert_eq!(mcts.search().unwrap(), Pick(0));
+
+    let root = mcts.root();
+    for child in &root.children {
+        let deal = child.action.as_ref().unwrap().0;
+        let mean = child.total_reward() / child.visits() as f64;
+        assert!((mean - PAYOFFS[deal][0]).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_perspective_player_overrides_config() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_perspective(Perspective::RootPlayer);
+    let mut mcts = MCTS::new(start(), config).with_perspective_player(Seat::South);
+
+    assert_eq!(mcts.search().unwrap(), Pick(2));
+
+    let root = mcts.root();
+    for child in &root.children {
+        let deal = child.action.as_ref().unwrap().0;
+        let mean = child.total_reward() / child.visits() as f64;
+        assert!((mean - PAYOFFS[deal][2]).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_node_player_perspective_is_the_default() {
+    assert_eq!(MCTSConfig::default().perspective, Perspective::NodePlayer);
+}
+
+#[test]
+fn test_policies_without_simulate_for_are_flipped() {
+    // One iteration per deal, each ending in a single playout from East's turn
+    let config = MCTSConfig::default()
+        .with_max_iterations(3)
+        .with_perspective(Perspective::RootPlayer);
+    let mut mcts = MCTS::new(start(), config).with_simulation_policy(Flattery);
+
+    mcts.search().unwrap();
+
+    let root = mcts.root();
+    assert_eq!(root.children.len(), 3);
+    for child in &root.children {
+        let expected = if child.action == Some(Pick(0)) {
+            1.0 - 0.8
+        } else {
+            1.0 - 0.3
+        };
+        assert_eq!(child.visits(), 1);
+        assert!((child.total_reward() - expected).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_root_player_is_the_player_to_move() {
+    let mcts = MCTS::new(start(), MCTSConfig::default());
+    assert_eq!(*mcts.root_player(), Seat::North);
+
+    let east = start().apply_action(&Pick(1));
+    let mcts = MCTS::new(east, MCTSConfig::default());
+    assert_eq!(*mcts.root_player(), Seat::East);
+}
+
+#[test]
+fn test_expected_root_player_passes_when_it_matches() {
+    let config = MCTSConfig::default().with_max_iterations(30);
+    let mut mcts = MCTS::new(start(), config).with_expected_root_player(Seat::North);
+
+    assert!(mcts.search().is_ok());
+}
+
+#[test]
+fn test_expected_root_player_rejects_another_player() {
+    let config = MCTSConfig::default().with_max_iterations(30);
+    let mut mcts = MCTS::new(start(), config).with_expected_root_player(Seat::North);
+
+    // One ply off: East is to move
+    mcts.reset_root(start().apply_action(&Pick(0)));
+    match mcts.search() {
+        Err(MCTSError::InvalidConfiguration(message)) => {
+            assert!(message.contains("North") && message.contains("East"));
+        }
+        other => panic!("expected InvalidConfiguration, got {:?}", other),
+    }
+    assert_eq!(mcts.root().visits(), 0);
+}
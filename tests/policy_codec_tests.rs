@@ -0,0 +1,182 @@
+use std::sync::{Arc, Mutex};
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    policy::selection::PUCTPolicy,
+    policy_codec::{IdCodec, PolicyCodec, PolicyHeadExpansionPolicy},
+    GameState, MCTSConfig, MCTSError, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Slot(usize);
+
+impl Action for Slot {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// One pick among the odd slots of a six-slot policy head; slot 3 pays
+#[derive(Clone, Debug)]
+struct OddSlots {
+    picked: Option<usize>,
+}
+
+impl GameState for OddSlots {
+    type Action = Slot;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.picked {
+            Some(_) => Vec::new(),
+            None => vec![Slot(1), Slot(3), Slot(5)],
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        OddSlots {
+            picked: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picked.is_some()
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        if self.picked == Some(3) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+/// Maps slots to indices in reverse order
+#[derive(Clone)]
+struct Reversed;
+
+impl PolicyCodec<Slot> for Reversed {
+    fn policy_size(&self) -> usize {
+        6
+    }
+
+    fn index_of(&self, action: &Slot) -> Option<usize> {
+        5usize.checked_sub(action.0)
+    }
+}
+
+/// A network output that puts most mass on illegal slots
+const POLICY: [f64; 6] = [0.3, 0.1, 0.2, 0.2, 0.1, 0.1];
+
+#[test]
+fn test_masked_priors_drop_illegal_entries_and_renormalize() {
+    let legal = [Slot(1), Slot(3), Slot(5)];
+    let priors = IdCodec::new(6).masked_priors(&POLICY, &legal).unwrap();
+    assert_eq!(priors.len(), 3);
+    assert!((priors[0] - 0.25).abs() < 1e-12);
+    assert!((priors[1] - 0.5).abs() < 1e-12);
+    assert!((priors[2] - 0.25).abs() < 1e-12);
+
+    let mask = IdCodec::new(6).legal_mask(&legal).unwrap();
+    assert_eq!(mask, [false, true, false, true, false, true]);
+
+    // Nothing legal has mass, so the priors fall back to uniform
+    let priors = IdCodec::new(6)
+        .masked_priors(&[1.0, 0.0, 0.0, 0.0, 0.0, 0.0], &legal)
+        .unwrap();
+    assert!(priors.iter().all(|prior| (prior - 1.0 / 3.0).abs() < 1e-12));
+}
+
+#[test]
+fn test_masked_softmax_ignores_illegal_logits() {
+    let logits = [100.0, 0.0, 100.0, 2.0_f64.ln(), 100.0, f64::NEG_INFINITY];
+    let priors = IdCodec::new(6)
+        .masked_softmax(&logits, &[Slot(1), Slot(3), Slot(5)])
+        .unwrap();
+    assert!((priors[0] - 1.0 / 3.0).abs() < 1e-12);
+    assert!((priors[1] - 2.0 / 3.0).abs() < 1e-12);
+    assert_eq!(priors[2], 0.0);
+}
+
+#[test]
+fn test_misaligned_actions_and_vectors_are_rejected() {
+    let codec = IdCodec::new(4);
+    let invalid = |result: Result<Vec<f64>, MCTSError>| {
+        matches!(result, Err(MCTSError::InvalidConfiguration(_)))
+    };
+    assert!(invalid(
+        codec.masked_priors(&[0.25; 4], &[Slot(1), Slot(5)])
+    ));
+    assert!(invalid(codec.masked_priors(&POLICY, &[Slot(1)])));
+    assert!(invalid(
+        codec.masked_priors(&[0.25; 4], &[Slot(1), Slot(1)])
+    ));
+    assert!(invalid(Reversed.masked_priors(&POLICY, &[Slot(7)])));
+}
+
+#[test]
+fn test_policy_head_sets_priors_and_expands_the_favourite_first() {
+    let expansion = PolicyHeadExpansionPolicy::new(Reversed, |_: &OddSlots| POLICY.to_vec());
+    let mut mcts = MCTS::new(
+        OddSlots { picked: None },
+        MCTSConfig::default().with_max_iterations(1),
+    )
+    .with_expansion_policy(expansion);
+    mcts.search().unwrap();
+
+    // Reversed, slot 1 reads index 4, slot 3 index 2 and slot 5 index 0
+    let root = mcts.root();
+    assert_eq!(root.children.len(), 1);
+    assert_eq!(root.children[0].action, Some(Slot(5)));
+    assert!((root.children[0].prior() - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_policy_head_feeds_the_root_prior_filter() {
+    let expansion = PolicyHeadExpansionPolicy::new(IdCodec::new(6), |_: &OddSlots| {
+        vec![0.0, 0.05, 0.0, 0.9, 0.0, 0.05]
+    });
+    let config = MCTSConfig::default()
+        .with_max_iterations(50)
+        .with_root_prior_filter(0.1, 1);
+    let mut mcts = MCTS::new(OddSlots { picked: None }, config)
+        .with_selection_policy(PUCTPolicy::new(1.0))
+        .with_expansion_policy(expansion);
+
+    assert_eq!(mcts.search().unwrap(), Slot(3));
+    assert_eq!(mcts.root().children.len(), 1);
+    assert_eq!(mcts.get_statistics().filtered_root_actions, 2);
+}
+
+#[test]
+fn test_policy_target_places_visit_shares_at_codec_indices() {
+    let targets = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&targets);
+    let mut mcts = MCTS::new(
+        OddSlots { picked: None },
+        MCTSConfig::default().with_max_iterations(90),
+    );
+    mcts.on_search_complete(move |result| {
+        sink.lock()
+            .unwrap()
+            .push(result.policy_target(&Reversed).unwrap());
+    });
+    mcts.search().unwrap();
+
+    let targets = targets.lock().unwrap();
+    let target = &targets[0];
+    assert_eq!(target.len(), 6);
+    assert_eq!(target[1] + target[3] + target[5], 0.0);
+    assert!((target.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(target[2] > target[0] && target[2] > target[4]);
+}
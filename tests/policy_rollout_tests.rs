@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use arboriter_mcts::{
+    config::{PlayoutLimits, Unfinished},
+    game_state::{Action, Player},
+    history::HistoryTable,
+    policy::{
+        selection::UCB1Policy,
+        simulation::{
+            PolicyRollout, RandomPolicy, RolloutContext, RolloutSelector, SimulationPolicy,
+            SoftmaxSelector,
+        },
+    },
+    GameState, MCTSConfig, MCTS,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+const STEPS: usize = 8;
+
+/// Eight picks among four symbols; the score is the share of picks of symbol 3
+#[derive(Clone, Debug)]
+struct Collect {
+    picks: Vec<usize>,
+}
+
+impl GameState for Collect {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.picks.len() < STEPS {
+            (0..4).map(Pick).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut picks = self.picks.clone();
+        picks.push(action.0);
+        Collect { picks }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picks.len() >= STEPS
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.picks.iter().filter(|&&pick| pick == 3).count() as f64 / STEPS as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn start() -> Collect {
+    Collect { picks: Vec::new() }
+}
+
+fn legal() -> Vec<Pick> {
+    start().get_legal_actions()
+}
+
+/// A table where symbol 2 has paid 0.6 over ten records and symbol 0 0.2 over ten
+fn history() -> HistoryTable {
+    let history = HistoryTable::new(1.0);
+    for _ in 0..10 {
+        history.record(Pick(2).key(), 0.6);
+        history.record(Pick(0).key(), 0.2);
+    }
+    history
+}
+
+#[test]
+fn test_ucb1_selector_tries_unseen_actions_then_the_best() {
+    let history = history();
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut context = RolloutContext {
+        trace: &[],
+        rng: &mut rng,
+        history: Some(&history),
+    };
+    let ucb = UCB1Policy::new(0.1);
+
+    // Symbols 1 and 3 are unseen, and neither is always tried first
+    let mut counts = [0; 4];
+    for _ in 0..100 {
+        counts[ucb.select_action(&start(), &legal(), &mut context)] += 1;
+    }
+    assert_eq!(counts[0] + counts[2], 0, "{:?}", counts);
+    assert!(counts[1] > 20 && counts[3] > 20, "{:?}", counts);
+
+    for _ in 0..10 {
+        history.record(Pick(1).key(), 0.1);
+        history.record(Pick(3).key(), 0.1);
+    }
+    assert_eq!(ucb.select_action(&start(), &legal(), &mut context), 2);
+}
+
+#[test]
+fn test_softmax_selector_follows_history_values() {
+    let history = history();
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut context = RolloutContext {
+        trace: &[],
+        rng: &mut rng,
+        history: Some(&history),
+    };
+
+    // Unseen actions are worth 1.0 by default, so only known ones compete
+    let mut selector = SoftmaxSelector::new(0.01);
+    selector.unseen_value = 0.0;
+    for _ in 0..20 {
+        assert_eq!(selector.select_action(&start(), &legal(), &mut context), 2);
+    }
+
+    // Without a table every action is alike
+    let mut context = RolloutContext {
+        trace: &[],
+        rng: &mut rng,
+        history: None,
+    };
+    let mut counts = [0; 4];
+    for _ in 0..400 {
+        counts[selector.select_action(&start(), &legal(), &mut context)] += 1;
+    }
+    assert!(counts.iter().all(|&count| count > 50), "{:?}", counts);
+}
+
+#[test]
+fn test_closure_selectors_see_the_state_and_the_trace() {
+    let selector = |state: &Collect, legal: &[Pick], context: &mut RolloutContext<'_, Pick>| {
+        assert_eq!(state.picks.len(), context.trace.len() + 2);
+        legal.len() - 1 - context.trace.len() % 2
+    };
+    let rollout = PolicyRollout::new(selector);
+    let state = start().apply_action(&Pick(0)).apply_action(&Pick(0));
+
+    let (result, trace) = rollout.simulate(&state);
+    assert_eq!(trace, [3, 2, 3, 2, 3, 2].map(Pick));
+    assert_eq!(result, 3.0 / 8.0);
+
+    let limits = PlayoutLimits {
+        max_length: Some(2),
+        unfinished: Unfinished::Draw,
+        cycle_reward: None,
+    };
+    let (_, trace) = rollout.simulate_limited(&state, &limits);
+    assert_eq!(trace.len(), 2);
+}
+
+#[test]
+fn test_history_guided_rollouts_beat_random_ones() {
+    let history = Arc::new(HistoryTable::new(1.0));
+    let rollout = PolicyRollout::new(UCB1Policy::new(0.1)).with_history(history.clone());
+    let mut mcts = MCTS::new(start(), MCTSConfig::default().with_max_iterations(300))
+        .with_history(history)
+        .with_simulation_policy(rollout.clone());
+    mcts.search().unwrap();
+
+    let mean = |policy: &dyn SimulationPolicy<Collect>| {
+        (0..200).map(|_| policy.simulate(&start()).0).sum::<f64>() / 200.0
+    };
+    let guided = mean(&rollout);
+    let random = mean(&RandomPolicy::new());
+    assert!(guided > random + 0.3, "guided {} random {}", guided, random);
+}
@@ -0,0 +1,146 @@
+use arboriter_mcts::{
+    config::PreSearch,
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Value of each root action
+const VALUES: [f64; 4] = [0.2, 1.0, 0.0, 0.7];
+
+/// One decision whose outcome is known as soon as it is made
+#[derive(Clone, Debug)]
+struct Choice {
+    picked: Option<usize>,
+}
+
+impl GameState for Choice {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.picked {
+            None => (0..VALUES.len()).map(Pick).collect(),
+            Some(_) => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Choice {
+            picked: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picked.is_some()
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.picked.map_or(0.5, |picked| VALUES[picked])
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn child_actions(mcts: &MCTS<Choice>) -> Vec<usize> {
+    let mut actions: Vec<usize> = mcts
+        .root()
+        .children
+        .iter()
+        .map(|child| child.action.as_ref().unwrap().0)
+        .collect();
+    actions.sort();
+    actions
+}
+
+#[test]
+fn test_pre_search_prunes_hopeless_root_actions() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_pre_search(0.2, 0.4);
+    let mut mcts = MCTS::new(Choice { picked: None }, config);
+
+    assert_eq!(mcts.search().unwrap(), Pick(1));
+
+    // 20 of 100 iterations spread over 4 actions
+    let stats = mcts.get_statistics();
+    assert_eq!(stats.pre_search_playouts, 20);
+    assert_eq!(stats.iterations, 80);
+    assert_eq!(child_actions(&mcts), vec![1, 3]);
+
+    // The survivors keep their pre-search visits
+    assert_eq!(mcts.root().visits(), 80 + 2 * 5);
+}
+
+#[test]
+fn test_pre_search_sets_priors_from_values() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(40)
+        .with_pre_search(0.5, 1.0);
+    let mut mcts = MCTS::new(Choice { picked: None }, config);
+
+    mcts.search_for_iterations(40).unwrap();
+
+    assert_eq!(child_actions(&mcts), vec![0, 1, 2, 3]);
+    let total: f64 = VALUES.iter().sum();
+    for child in &mcts.root().children {
+        let value = VALUES[child.action.as_ref().unwrap().0];
+        assert!((child.prior() - value / total).abs() < 1e-5);
+        assert!(child.visits() >= 5);
+    }
+}
+
+#[test]
+fn test_pre_search_skipped_without_budget_for_every_action() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(10)
+        .with_pre_search(0.2, 0.0);
+    let mut mcts = MCTS::new(Choice { picked: None }, config);
+
+    mcts.search().unwrap();
+
+    let stats = mcts.get_statistics();
+    assert_eq!(stats.pre_search_playouts, 0);
+    assert_eq!(stats.iterations, 10);
+    assert_eq!(child_actions(&mcts), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn test_pre_search_keeps_constrained_actions() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_pre_search(0.2, 0.0);
+    let mut mcts = MCTS::new(Choice { picked: None }, config);
+    mcts.add_root_constraint(Pick(2), 0.1).unwrap();
+
+    mcts.search_for_iterations(100).unwrap();
+
+    assert_eq!(child_actions(&mcts), vec![1, 2]);
+}
+
+#[test]
+fn test_pre_search_settings_are_clamped() {
+    let settings = PreSearch::new(1.5, -0.2);
+    assert_eq!(settings.fraction, 1.0);
+    assert_eq!(settings.prune_margin, 0.0);
+
+    let settings = PreSearch::new(0.1, 0.3);
+    assert_eq!(settings.playouts_per_action(1000, 7), 14);
+    assert_eq!(settings.playouts_per_action(20, 3), 0);
+    assert_eq!(settings.playouts_per_action(1000, 0), 0);
+}
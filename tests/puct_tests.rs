@@ -115,10 +115,9 @@ fn test_expansion_policy_sets_prior() {
     let root = mcts.root();
     assert_eq!(root.children.len(), 1, "Should have expanded 1 child");
 
-    // Verify prior was set
-    assert_eq!(
-        root.children[0].prior(),
-        0.8,
+    // Verify prior was set (compact-stats stores priors as f32)
+    assert!(
+        (root.children[0].prior() - 0.8).abs() < 1e-6,
         "Prior should be set by policy"
     );
 
@@ -166,5 +165,5 @@ fn test_mcts_respects_expansion_policy() {
     // Child action should be TestAction(2).
 
     assert_eq!(root.children[0].action.as_ref().unwrap().0, 2);
-    assert_eq!(root.children[0].prior(), 0.99);
+    assert!((root.children[0].prior() - 0.99).abs() < 1e-6);
 }
@@ -0,0 +1,168 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    result_cache::ResultCache,
+    GameState, MCTSConfig, SearchStatistics, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// One decision between three endings, counting how often they are scored
+#[derive(Clone, Debug)]
+struct Ending {
+    picked: Option<usize>,
+    scored: Arc<AtomicUsize>,
+}
+
+impl GameState for Ending {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.picked {
+            None => (0..3).map(Pick).collect(),
+            Some(_) => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Ending {
+            picked: Some(action.0),
+            scored: Arc::clone(&self.scored),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picked.is_some()
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.scored.fetch_add(1, Ordering::SeqCst);
+        self.picked.map_or(0.5, |picked| picked as f64 / 2.0)
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+
+    fn hash(&self) -> u64 {
+        self.picked.map_or(1, |picked| picked as u64 + 2)
+    }
+}
+
+fn ending() -> (Ending, Arc<AtomicUsize>) {
+    let scored = Arc::new(AtomicUsize::new(0));
+    let state = Ending {
+        picked: None,
+        scored: Arc::clone(&scored),
+    };
+    (state, scored)
+}
+
+#[test]
+fn test_result_cache_lookup_and_capacity() {
+    let mut cache = ResultCache::new(2);
+    assert!(cache.is_empty());
+
+    assert!(cache.insert(7, &'a', 1.0));
+    assert!(cache.insert(7, &'b', 0.0));
+    assert_eq!(cache.get(7, &'a'), Some(1.0));
+    assert_eq!(cache.get(7, &'b'), Some(0.0));
+    assert_eq!(cache.get(7, &'c'), None);
+    assert_eq!(cache.len(), 1);
+
+    // Hash 0 means "no hash" and is never cached
+    assert!(!cache.insert(0, &'a', 1.0));
+
+    assert!(cache.insert(8, &'a', 0.5));
+    assert!(!cache.insert(9, &'a', 0.5), "the cache is full");
+    assert!(
+        cache.insert(8, &'b', 0.25),
+        "known states still take players"
+    );
+    assert_eq!(cache.len(), cache.capacity());
+
+    cache.clear();
+    assert_eq!(cache.get(8, &'a'), None);
+}
+
+#[test]
+fn test_terminal_results_are_scored_once() {
+    let (state, scored) = ending();
+    let config = MCTSConfig::default()
+        .with_max_iterations(100)
+        .with_result_cache(16);
+    let mut mcts = MCTS::new(state, config);
+
+    assert_eq!(mcts.search().unwrap(), Pick(2));
+
+    let cache = mcts.get_statistics().result_cache_stats.clone().unwrap();
+    assert_eq!(cache.misses, 3);
+    assert_eq!(cache.hits, 97);
+    assert_eq!(cache.entries, 3);
+    assert_eq!(cache.capacity, 16);
+    assert!((cache.hit_rate() - 0.97).abs() < 1e-12);
+    assert_eq!(scored.load(Ordering::SeqCst), 3);
+
+    // Later searches keep the cached results
+    mcts.search().unwrap();
+    let cache = mcts.get_statistics().result_cache_stats.clone().unwrap();
+    assert_eq!(cache.misses, 0);
+    assert_eq!(scored.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_full_result_cache_scores_the_rest() {
+    let (state, scored) = ending();
+    let config = MCTSConfig::default()
+        .with_max_iterations(30)
+        .with_result_cache(1);
+    let mut mcts = MCTS::new(state, config);
+
+    mcts.search().unwrap();
+
+    let cache = mcts.get_statistics().result_cache_stats.clone().unwrap();
+    assert_eq!(cache.entries, 1);
+    assert_eq!(cache.hits + cache.misses, 30);
+    assert!(cache.misses > 3);
+    assert_eq!(scored.load(Ordering::SeqCst), cache.misses);
+}
+
+#[test]
+fn test_result_cache_reported_only_when_enabled() {
+    let (state, _) = ending();
+    let mut mcts = MCTS::new(state, MCTSConfig::default().with_max_iterations(10));
+    mcts.search().unwrap();
+    let stats = mcts.get_statistics();
+    assert!(stats.result_cache_stats.is_none());
+    assert!(!stats.summary().contains("Result Cache"));
+
+    let (state, _) = ending();
+    let config = MCTSConfig::default()
+        .with_max_iterations(10)
+        .with_result_cache(4);
+    let mut mcts = MCTS::new(state, config);
+    mcts.search().unwrap();
+    let stats = mcts.get_statistics();
+    assert!(stats.summary().contains("Result Cache"));
+    assert_eq!(
+        stats.to_csv_row().split(',').count(),
+        SearchStatistics::csv_header().split(',').count()
+    );
+}
@@ -0,0 +1,198 @@
+use arboriter_mcts::{
+    config::RewardTransform,
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTSError, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// One pick out of four, scored by a buggy `get_result`
+#[derive(Clone, Debug)]
+struct Buggy {
+    picked: Option<usize>,
+}
+
+impl GameState for Buggy {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.picked {
+            Some(_) => Vec::new(),
+            None => (0..4).map(Pick).collect(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Buggy {
+            picked: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picked.is_some()
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        match self.picked {
+            Some(0) => f64::NAN,
+            Some(1) => 1e300,
+            Some(2) => 0.6,
+            _ => f64::NEG_INFINITY,
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn mcts(config: MCTSConfig) -> MCTS<Buggy> {
+    MCTS::new(Buggy { picked: None }, config.with_max_iterations(200))
+}
+
+fn assert_finite_statistics(mcts: &MCTS<Buggy>) {
+    assert!(mcts.root().value().is_finite());
+    for child in &mcts.root().children {
+        assert!(child.value().is_finite(), "{:?}", child.action);
+    }
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_non_finite_results_are_dropped() {
+    let mut mcts = mcts(MCTSConfig::default());
+    mcts.search().unwrap();
+
+    let stats = mcts.get_statistics();
+    assert!(stats.dropped_rewards > 0);
+    assert_eq!(stats.clipped_rewards, 0);
+    assert_eq!(stats.iterations, 200);
+    assert!(mcts.check_invariants().is_ok());
+
+    // Dropped playouts count as losses, so the search moves on to the
+    // children with usable results
+    assert!(stats.dropped_rewards < 50, "{}", stats.dropped_rewards);
+    for dropped in [Pick(0), Pick(3)] {
+        let child = mcts.root().find_child(&dropped).unwrap();
+        assert_eq!(mcts.root().children[child].value(), 0.0);
+    }
+}
+
+#[test]
+fn test_clipping_bounds_huge_and_infinite_results() {
+    let mut mcts = mcts(MCTSConfig::default().with_reward_clipping(0.0, 1.0));
+    let best = mcts.search().unwrap();
+
+    let stats = mcts.get_statistics();
+    assert!(stats.clipped_rewards > 0);
+    // NaN is never clipped
+    assert!(stats.dropped_rewards > 0);
+    assert_eq!(best, Pick(1));
+    assert_finite_statistics(&mcts);
+    for child in &mcts.root().children {
+        assert!((0.0..=1.0).contains(&child.value()));
+    }
+
+    let summary = stats.summary();
+    assert!(summary.contains("Rewards clipped"), "{}", summary);
+}
+
+#[test]
+fn test_huge_results_saturate_node_totals() {
+    let mut unclipped = mcts(MCTSConfig::default());
+    unclipped.search().unwrap();
+
+    let stats = unclipped.get_statistics();
+    assert!(stats.saturated_updates > 0);
+    assert_finite_statistics(&unclipped);
+    assert!(stats.summary().contains("Saturated"), "{}", stats.summary());
+
+    // Clipped results stay far below the ceiling
+    let mut clipped = mcts(MCTSConfig::default().with_reward_clipping(0.0, 1.0));
+    clipped.search().unwrap();
+    assert_eq!(clipped.get_statistics().saturated_updates, 0);
+}
+
+#[test]
+fn test_strict_mode_fails_on_nan() {
+    let mut mcts = mcts(
+        MCTSConfig::default()
+            .with_reward_clipping(0.0, 1.0)
+            .with_strict_mode(true),
+    );
+    match mcts.search() {
+        Err(MCTSError::DomainInconsistency(message)) => {
+            assert!(message.contains("NaN"), "{}", message)
+        }
+        other => panic!("expected a domain inconsistency, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_transforms_that_return_nan_are_guarded() {
+    fn broken(result: f64) -> f64 {
+        if result > 0.5 {
+            f64::NAN
+        } else {
+            result
+        }
+    }
+
+    let mut mcts = mcts(
+        MCTSConfig::default()
+            .with_reward_clipping(0.0, 1.0)
+            .with_reward_transform(RewardTransform::Custom(broken)),
+    );
+    mcts.search().unwrap();
+    assert!(mcts.get_statistics().dropped_rewards > 0);
+    assert_finite_statistics(&mcts);
+}
+
+#[test]
+fn test_batches_drop_results_and_release_virtual_loss() {
+    let mut mcts = mcts(
+        MCTSConfig::default()
+            .with_reward_clipping(0.0, 1.0)
+            .with_rollout_batch_size(4),
+    );
+    mcts.search().unwrap();
+
+    assert!(mcts.get_statistics().dropped_rewards > 0);
+    assert_finite_statistics(&mcts);
+    assert_eq!(mcts.root().visits(), 200);
+    let child_visits: u64 = mcts.root().children.iter().map(|c| c.visits()).sum();
+    assert_eq!(child_visits, 200);
+}
+
+#[test]
+fn test_counters_are_csv_columns() {
+    let mut mcts = mcts(MCTSConfig::default().with_reward_clipping(0.0, 1.0));
+    mcts.search().unwrap();
+    let stats = mcts.get_statistics();
+
+    let header = arboriter_mcts::SearchStatistics::csv_header();
+    let row = stats.to_csv_row();
+    let columns: Vec<&str> = header.split(',').collect();
+    let values: Vec<&str> = row.split(',').collect();
+    assert_eq!(columns.len(), values.len());
+    let value = |name: &str| values[columns.iter().position(|c| *c == name).unwrap()];
+    assert_eq!(value("clipped_rewards"), stats.clipped_rewards.to_string());
+    assert_eq!(value("dropped_rewards"), stats.dropped_rewards.to_string());
+    assert_eq!(
+        value("saturated_updates"),
+        stats.saturated_updates.to_string()
+    );
+}
@@ -0,0 +1,301 @@
+use arboriter_mcts::{
+    config::{BestChildCriteria, VirtualLoss, VirtualLossMode},
+    game_state::{Action, Player},
+    policy::backpropagation::{PowerMeanPolicy, RavePolicy},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Climber;
+
+impl Player for Climber {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(usize);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Three picks of 0, 1 or 2; the result is the sum of the picks over their
+/// maximum, so always picking 2 is best
+#[derive(Clone, Debug)]
+struct Ladder {
+    picks: Vec<usize>,
+}
+
+const PICKS: usize = 3;
+
+impl GameState for Ladder {
+    type Action = Step;
+    type Player = Climber;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return Vec::new();
+        }
+        (0..3).map(Step).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut picks = self.picks.clone();
+        picks.push(action.0);
+        Ladder { picks }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picks.len() == PICKS
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.picks.iter().sum::<usize>() as f64 / (2 * PICKS) as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Climber
+    }
+}
+
+fn start() -> Ladder {
+    Ladder { picks: Vec::new() }
+}
+
+#[test]
+fn test_rollout_batch_size_defaults_to_one() {
+    assert_eq!(MCTSConfig::default().rollout_batch_size, 1);
+    assert_eq!(
+        MCTSConfig::default()
+            .with_rollout_batch_size(0)
+            .rollout_batch_size,
+        1
+    );
+}
+
+#[test]
+fn test_every_leaf_in_a_batch_is_an_iteration() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(203)
+        .with_rollout_batch_size(8);
+    let mut mcts = MCTS::new(start(), config);
+
+    mcts.search().unwrap();
+
+    // The last batch is cut short by the budget, and every virtual loss is
+    // gone by the time the search returns
+    assert_eq!(mcts.get_statistics().iterations, 203);
+    assert_eq!(mcts.root().visits(), 203);
+    let child_visits: u64 = mcts.root().children.iter().map(|c| c.visits()).sum();
+    assert_eq!(child_visits, 203);
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_batched_search_finds_the_best_action() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(1000)
+        .with_rollout_batch_size(4)
+        .with_best_child_criteria(BestChildCriteria::HighestValue);
+    let mut mcts = MCTS::new(start(), config);
+
+    assert_eq!(mcts.search().unwrap(), Step(2));
+}
+
+#[test]
+fn test_batches_end_at_a_repeated_leaf() {
+    // Once the tree is exhausted every selection reaches a terminal leaf
+    // that is already in the batch, so each batch holds one leaf
+    let one_move = start().apply_action(&Step(2)).apply_action(&Step(2));
+    let config = MCTSConfig::default()
+        .with_max_iterations(40)
+        .with_rollout_batch_size(4);
+    let mut mcts = MCTS::new(one_move, config);
+
+    mcts.search().unwrap();
+
+    let stats = mcts.get_statistics();
+    assert_eq!(stats.iterations, 40);
+    assert!(stats.batch_collisions > 0);
+    assert!(stats.rollout_batches < 40);
+    assert_eq!(mcts.root().visits(), 40);
+    assert!(mcts.check_invariants().is_ok());
+}
+
+/// Collisions of a batched search on three terminal moves
+fn collisions_with(virtual_loss: VirtualLoss) -> usize {
+    let one_move = start().apply_action(&Step(2)).apply_action(&Step(2));
+    let config = MCTSConfig::default()
+        .with_max_iterations(60)
+        .with_rollout_batch_size(3)
+        .with_virtual_loss(virtual_loss);
+    let mut mcts = MCTS::new(one_move, config);
+    mcts.search().unwrap();
+    assert_eq!(mcts.root().visits(), 60);
+    mcts.get_statistics().batch_collisions
+}
+
+#[test]
+fn test_virtual_loss_defaults() {
+    let virtual_loss = MCTSConfig::default().virtual_loss;
+    assert_eq!(virtual_loss, VirtualLoss::new(1));
+    assert_eq!(virtual_loss.mode, VirtualLossMode::PerNode);
+    assert!(!virtual_loss.revert_on_backpropagation);
+}
+
+#[test]
+fn test_stronger_virtual_loss_spreads_batches() {
+    // Without virtual loss every selection after the first expansions
+    // returns to the same leaf
+    let none = collisions_with(VirtualLoss::new(0));
+    let strong = collisions_with(VirtualLoss::new(10));
+    assert!(
+        none > strong,
+        "{} collisions without, {} with",
+        none,
+        strong
+    );
+}
+
+#[test]
+fn test_virtual_loss_modes_leave_no_trace() {
+    for virtual_loss in [
+        VirtualLoss::new(3).with_mode(VirtualLossMode::PerEdge),
+        VirtualLoss::new(3).with_revert_on_backpropagation(true),
+        VirtualLoss::new(2)
+            .with_mode(VirtualLossMode::PerEdge)
+            .with_revert_on_backpropagation(true),
+    ] {
+        let config = MCTSConfig::default()
+            .with_max_iterations(150)
+            .with_rollout_batch_size(5)
+            .with_virtual_loss(virtual_loss);
+        let mut mcts = MCTS::new(start(), config);
+
+        mcts.search().unwrap();
+
+        assert_eq!(mcts.root().visits(), 150);
+        let child_visits: u64 = mcts.root().children.iter().map(|c| c.visits()).sum();
+        assert_eq!(child_visits, 150);
+        assert!(mcts.check_invariants().is_ok());
+    }
+}
+
+#[test]
+fn test_batches_are_reported() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(40)
+        .with_rollout_batch_size(4);
+    let mut mcts = MCTS::new(start(), config);
+    mcts.search().unwrap();
+
+    let stats = mcts.get_statistics();
+    assert!(stats.rollout_batches >= 10);
+    assert!(stats.summary().contains("Rollout batches"));
+
+    let mut sequential = MCTS::new(start(), MCTSConfig::default().with_max_iterations(40));
+    sequential.search().unwrap();
+    assert_eq!(sequential.get_statistics().rollout_batches, 0);
+    assert!(!sequential
+        .get_statistics()
+        .summary()
+        .contains("Rollout batches"));
+}
+
+#[test]
+fn test_batched_search_sends_every_event() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(40)
+        .with_rollout_batch_size(3)
+        .with_event_interval(10);
+    let mut mcts = MCTS::new(start(), config);
+    let events = mcts.iteration_events();
+
+    mcts.search().unwrap();
+
+    let iterations: Vec<_> = events.try_iter().map(|event| event.iteration).collect();
+    assert_eq!(iterations, vec![10, 20, 30, 40]);
+}
+
+#[test]
+fn test_batched_search_with_cached_stats() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_rollout_batch_size(6)
+        .with_selection_stats_cache(true);
+    let mut mcts = MCTS::new(start(), config);
+
+    mcts.search().unwrap();
+
+    for child in &mcts.root().children {
+        assert_eq!(child.selection_stats().0, child.visits());
+    }
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_batches_backpropagate_with_any_policy() {
+    // RAVE updates work through shared references and PowerMean needs
+    // exclusive access; both must account for every leaf exactly once
+    let config = MCTSConfig::default()
+        .with_max_iterations(200)
+        .with_rollout_batch_size(8);
+    let mut rave =
+        MCTS::new(start(), config.clone()).with_backpropagation_policy(RavePolicy::new(0.5));
+    let mut power_mean =
+        MCTS::new(start(), config).with_backpropagation_policy(PowerMeanPolicy::new(2.0));
+
+    for mcts in [&mut rave, &mut power_mean] {
+        mcts.search().unwrap();
+        assert_eq!(mcts.root().visits(), 200);
+        let child_visits: u64 = mcts.root().children.iter().map(|c| c.visits()).sum();
+        assert_eq!(child_visits, 200);
+        assert!(mcts.check_invariants().is_ok());
+    }
+}
+
+/// Visits and values of every node, parent before child
+fn tree_snapshot(mcts: &MCTS<Ladder>) -> Vec<(Vec<usize>, u64, f64)> {
+    fn walk(node: &arboriter_mcts::MCTSNode<Ladder>, out: &mut Vec<(Vec<usize>, u64, f64)>) {
+        out.push((node.state.picks.clone(), node.visits(), node.value()));
+        for child in &node.children {
+            walk(child, out);
+        }
+    }
+    let mut out = Vec::new();
+    walk(mcts.root(), &mut out);
+    out
+}
+
+fn seeded_search(seed: u64, batch_size: usize) -> Vec<(Vec<usize>, u64, f64)> {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_rollout_batch_size(batch_size)
+        .with_seed(seed);
+    let mut mcts = MCTS::new(start(), config);
+    mcts.search().unwrap();
+    tree_snapshot(&mcts)
+}
+
+#[test]
+fn test_seeded_searches_are_reproducible() {
+    for batch_size in [1, 8] {
+        assert_eq!(seeded_search(7, batch_size), seeded_search(7, batch_size));
+    }
+    assert_ne!(seeded_search(7, 8), seeded_search(8, 8));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_seeded_batches_do_not_depend_on_the_thread_count() {
+    let on_threads = |threads: usize| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap()
+            .install(|| seeded_search(11, 8))
+    };
+    let single = on_threads(1);
+    assert_eq!(on_threads(4), single);
+    assert_eq!(on_threads(4), single);
+}
@@ -0,0 +1,119 @@
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// One pick out of four; only picking 0 scores
+#[derive(Clone, Debug)]
+struct Choice {
+    picked: Option<usize>,
+}
+
+impl GameState for Choice {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.picked {
+            Some(_) => Vec::new(),
+            None => (0..4).map(Pick).collect(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Choice {
+            picked: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picked.is_some()
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        match self.picked {
+            Some(0) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+/// A greedy search that spends everything on the leader once it is found
+fn greedy(floor: u64) -> MCTS<Choice> {
+    let config = MCTSConfig::default()
+        .with_exploration_constant(0.0)
+        .with_root_exploration_floor(floor);
+    MCTS::new(Choice { picked: None }, config)
+}
+
+fn root_visits(mcts: &MCTS<Choice>) -> Vec<(usize, u64)> {
+    let mut visits: Vec<(usize, u64)> = mcts
+        .root()
+        .children
+        .iter()
+        .map(|child| (child.action.as_ref().unwrap().0, child.visits()))
+        .collect();
+    visits.sort_unstable();
+    visits
+}
+
+#[test]
+fn test_floor_is_disabled_by_default() {
+    assert_eq!(MCTSConfig::default().root_exploration_floor, 0);
+
+    let mut mcts = greedy(0);
+    mcts.search_for_iterations(100).unwrap();
+    assert_eq!(root_visits(&mcts), [(0, 97), (1, 1), (2, 1), (3, 1)]);
+}
+
+#[test]
+fn test_every_root_child_reaches_the_floor() {
+    let mut mcts = greedy(10);
+    let best = mcts.search_for_iterations(100).unwrap();
+
+    assert_eq!(best, Pick(0));
+    assert_eq!(root_visits(&mcts), [(0, 70), (1, 10), (2, 10), (3, 10)]);
+}
+
+#[test]
+fn test_short_searches_spread_evenly_below_the_floor() {
+    let mut mcts = greedy(100);
+    mcts.search_for_iterations(40).unwrap();
+
+    assert_eq!(root_visits(&mcts), [(0, 10), (1, 10), (2, 10), (3, 10)]);
+}
+
+#[test]
+fn test_floor_covers_warm_started_children() {
+    let mut mcts = greedy(5);
+    mcts.warm_start(&[(Pick(1), 0.9, 50), (Pick(2), 0.8, 2)])
+        .unwrap();
+    mcts.search_for_iterations(30).unwrap();
+
+    let visits = root_visits(&mcts);
+    assert_eq!(visits.len(), 4);
+    assert!(
+        visits.iter().all(|&(_, visits)| visits >= 5),
+        "{:?}",
+        visits
+    );
+    assert!(mcts.check_invariants().is_ok());
+}
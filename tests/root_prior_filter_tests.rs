@@ -0,0 +1,163 @@
+use arboriter_mcts::{
+    config::RootPriorFilter,
+    game_state::{Action, Player},
+    policy::expansion::ExpansionPolicy,
+    tree::MCTSNode,
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Prior of each root action, as a policy network might give them
+const PRIORS: [f64; 6] = [0.02, 0.5, 0.03, 0.3, 0.01, 0.14];
+
+/// One decision; the priors are right about which picks are good
+#[derive(Clone, Debug)]
+struct Choice {
+    picked: Option<usize>,
+}
+
+impl GameState for Choice {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.picked {
+            None => (0..PRIORS.len()).map(Pick).collect(),
+            Some(_) => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Choice {
+            picked: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picked.is_some()
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.picked.map_or(0.5, |picked| PRIORS[picked] * 2.0)
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+/// Expands in order and knows every action's prior up front
+#[derive(Clone)]
+struct PriorTable;
+
+impl ExpansionPolicy<Choice> for PriorTable {
+    fn select_action_to_expand(&self, node: &MCTSNode<Choice>) -> Option<(usize, f64)> {
+        let action = node.unexpanded_actions.first()?;
+        Some((0, PRIORS[action.0]))
+    }
+
+    fn action_priors(&self, node: &MCTSNode<Choice>) -> Option<Vec<f64>> {
+        Some(
+            node.unexpanded_actions
+                .iter()
+                .map(|a| PRIORS[a.0])
+                .collect(),
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn ExpansionPolicy<Choice>> {
+        Box::new(self.clone())
+    }
+}
+
+fn child_actions(mcts: &MCTS<Choice>) -> Vec<usize> {
+    let mut actions: Vec<usize> = mcts
+        .root()
+        .children
+        .iter()
+        .map(|child| child.action.as_ref().unwrap().0)
+        .collect();
+    actions.sort();
+    actions
+}
+
+fn search(config: MCTSConfig) -> MCTS<Choice> {
+    let mut mcts = MCTS::new(Choice { picked: None }, config.with_max_iterations(100))
+        .with_expansion_policy(PriorTable);
+    assert_eq!(mcts.search().unwrap(), Pick(1));
+    mcts
+}
+
+#[test]
+fn test_low_prior_root_actions_are_dropped() {
+    let mcts = search(MCTSConfig::default().with_root_prior_filter(0.1, 1));
+    assert_eq!(child_actions(&mcts), [1, 3, 5]);
+    assert!(mcts.root().unexpanded_actions.is_empty());
+    assert_eq!(mcts.get_statistics().filtered_root_actions, 3);
+    assert!(mcts
+        .get_statistics()
+        .summary()
+        .contains("Root actions filtered by prior: 3"));
+}
+
+#[test]
+fn test_filtered_root_passes_strict_mode() {
+    let mcts = search(
+        MCTSConfig::default()
+            .with_root_prior_filter(0.1, 1)
+            .with_strict_mode(true),
+    );
+    assert_eq!(child_actions(&mcts), [1, 3, 5]);
+}
+
+#[test]
+fn test_filter_keeps_the_best_actions_as_a_safety_net() {
+    let mcts = search(MCTSConfig::default().with_root_prior_filter(0.9, 2));
+    assert_eq!(child_actions(&mcts), [1, 3]);
+    assert_eq!(mcts.get_statistics().filtered_root_actions, 4);
+}
+
+#[test]
+fn test_filter_keeps_constrained_actions() {
+    let mut mcts = MCTS::new(
+        Choice { picked: None },
+        MCTSConfig::default()
+            .with_max_iterations(100)
+            .with_root_prior_filter(0.1, 1),
+    )
+    .with_expansion_policy(PriorTable);
+    mcts.add_root_constraint(Pick(4), 0.1).unwrap();
+    mcts.search().unwrap();
+    assert_eq!(child_actions(&mcts), [1, 3, 4, 5]);
+}
+
+#[test]
+fn test_nothing_is_dropped_without_priors() {
+    let mut mcts = MCTS::new(
+        Choice { picked: None },
+        MCTSConfig::default()
+            .with_max_iterations(100)
+            .with_root_prior_filter(0.1, 1),
+    );
+    assert_eq!(mcts.search().unwrap(), Pick(1));
+    assert_eq!(child_actions(&mcts), [0, 1, 2, 3, 4, 5]);
+    assert_eq!(mcts.get_statistics().filtered_root_actions, 0);
+}
+
+#[test]
+fn test_filter_keeps_at_least_one_action() {
+    assert_eq!(RootPriorFilter::new(0.5, 0).keep_at_least, 1);
+}
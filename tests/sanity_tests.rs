@@ -0,0 +1,74 @@
+//! Checks that search beats random play in the bundled example games
+//!
+//! The examples are compiled in as modules, so a perspective or
+//! backpropagation bug in them, or in the search, fails here instead of
+//! only showing up as weak play.
+
+use arboriter_mcts::{
+    config::RewardPerspective,
+    sanity::{play_against_random, play_against_random_with, RandomMatchReport},
+    MCTSConfig, MCTS,
+};
+
+#[allow(dead_code)]
+#[path = "../examples/tic_tac_toe.rs"]
+mod tic_tac_toe;
+
+#[allow(dead_code)]
+#[path = "../examples/connect_four.rs"]
+mod connect_four;
+
+fn config(iterations: usize) -> MCTSConfig {
+    MCTSConfig::default()
+        .with_max_iterations(iterations)
+        .with_reward_perspective(RewardPerspective::PerPlayer)
+}
+
+#[test]
+fn test_search_beats_random_at_tic_tac_toe() {
+    let report = play_against_random(&tic_tac_toe::TicTacToe::new(), &config(300), 20, 1).unwrap();
+    assert_eq!(report.games, 20);
+    assert_eq!(report.wins + report.draws + report.losses, 20);
+    assert!(report.beats_random(), "{:?}", report);
+}
+
+#[test]
+fn test_search_beats_random_at_connect_four() {
+    let report =
+        play_against_random(&connect_four::ConnectFour::new(), &config(200), 12, 2).unwrap();
+    assert!(report.beats_random(), "{:?}", report);
+}
+
+#[test]
+fn test_shared_rewards_do_not_beat_random_at_two_player_games() {
+    // Every node maximizing the leaf player's result helps the opponent
+    let shared = MCTSConfig::default().with_max_iterations(300);
+    let report = play_against_random_with(&tic_tac_toe::TicTacToe::new(), 20, 1, |state| {
+        MCTS::new(state, shared.clone())
+    })
+    .unwrap();
+    assert!(!report.beats_random(), "{:?}", report);
+}
+
+#[test]
+fn test_report_statistics() {
+    let report = RandomMatchReport {
+        games: 16,
+        wins: 12,
+        draws: 2,
+        losses: 2,
+    };
+    assert!((report.score() - 0.8125).abs() < 1e-9);
+    assert!((report.z_score() - 2.5).abs() < 1e-9);
+    assert!(report.beats_random());
+
+    let even = RandomMatchReport {
+        games: 16,
+        wins: 6,
+        draws: 4,
+        losses: 6,
+    };
+    assert_eq!(even.z_score(), 0.0);
+    assert!(!even.beats_random());
+    assert_eq!(RandomMatchReport::default().score(), 0.5);
+}
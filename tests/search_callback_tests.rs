@@ -0,0 +1,219 @@
+use std::sync::{Arc, Mutex};
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTSError, SearchId, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Climber;
+
+impl Player for Climber {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(usize);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Three picks of 0, 1 or 2; the result is the sum of the picks over their
+/// maximum, so always picking 2 is best
+#[derive(Clone, Debug)]
+struct Ladder {
+    picks: Vec<usize>,
+}
+
+impl GameState for Ladder {
+    type Action = Step;
+    type Player = Climber;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return Vec::new();
+        }
+        (0..3).map(Step).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut picks = self.picks.clone();
+        picks.push(action.0);
+        Ladder { picks }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picks.len() == 3
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.picks.iter().sum::<usize>() as f64 / 6.0
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Climber
+    }
+}
+
+fn mcts(iterations: usize) -> MCTS<Ladder> {
+    MCTS::new(
+        Ladder { picks: Vec::new() },
+        MCTSConfig::default().with_max_iterations(iterations),
+    )
+}
+
+/// What a callback saw of one search
+#[derive(Debug, Clone, PartialEq)]
+struct Seen {
+    id: SearchId,
+    action: Option<Step>,
+    iterations: usize,
+}
+
+fn recorder(mcts: &mut MCTS<Ladder>) -> Arc<Mutex<Vec<Seen>>> {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&seen);
+    mcts.on_search_complete(move |result| {
+        log.lock().unwrap().push(Seen {
+            id: result.id,
+            action: result.action.cloned(),
+            iterations: result.statistics.iterations,
+        });
+    });
+    seen
+}
+
+#[test]
+fn test_callback_runs_after_every_search() {
+    let mut mcts = mcts(300);
+    assert_eq!(mcts.last_search_id(), None);
+    let seen = recorder(&mut mcts);
+
+    let first = mcts.search().unwrap();
+    let first_id = mcts.last_search_id().unwrap();
+    let second = mcts.search_for_iterations(200).unwrap();
+    let second_id = mcts.last_search_id().unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(
+        *seen,
+        vec![
+            Seen {
+                id: first_id,
+                action: Some(first),
+                iterations: 300,
+            },
+            Seen {
+                id: second_id,
+                action: Some(second),
+                iterations: 200,
+            },
+        ]
+    );
+    assert!(second_id > first_id);
+}
+
+#[test]
+fn test_ids_are_unique_across_instances() {
+    let mut a = mcts(10);
+    let mut b = mcts(10);
+    a.search().unwrap();
+    b.search().unwrap();
+    a.search().unwrap();
+
+    let ids = [a.last_search_id().unwrap(), b.last_search_id().unwrap()];
+    assert_ne!(ids[0], ids[1]);
+    assert!(ids[0] > ids[1]);
+    assert_eq!(format!("{}", ids[0]), format!("search-{}", ids[0].get()));
+}
+
+#[test]
+fn test_callbacks_run_in_order_and_can_be_cleared() {
+    let mut mcts = mcts(50);
+    let order = Arc::new(Mutex::new(Vec::new()));
+    for name in ["telemetry", "curriculum"] {
+        let order = Arc::clone(&order);
+        mcts.on_search_complete(move |_| order.lock().unwrap().push(name));
+    }
+
+    mcts.search().unwrap();
+    assert_eq!(*order.lock().unwrap(), ["telemetry", "curriculum"]);
+
+    mcts.clear_search_callbacks();
+    mcts.search().unwrap();
+    assert_eq!(order.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_failed_searches_run_no_callbacks() {
+    let mut mcts = MCTS::new(
+        Ladder {
+            picks: vec![2, 2, 2],
+        },
+        MCTSConfig::default().with_max_iterations(10),
+    );
+    let seen = recorder(&mut mcts);
+
+    assert!(matches!(mcts.search(), Err(MCTSError::NoLegalActions)));
+    assert!(seen.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_vetoed_searches_report_no_action() {
+    let mut mcts = mcts(100).with_result_validator(|_, _| Err("no moves allowed".to_string()));
+    let seen = recorder(&mut mcts);
+
+    assert!(mcts.search().is_err());
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].action, None);
+    assert_eq!(seen[0].iterations, 100);
+}
+
+#[test]
+fn test_visit_distribution_and_prior_disagreement() {
+    let mut mcts = mcts(2000);
+    let measured = Arc::new(Mutex::new(None));
+    let out = Arc::clone(&measured);
+    mcts.on_search_complete(move |result| {
+        let distribution: Vec<(usize, f64)> = result
+            .visit_distribution()
+            .into_iter()
+            .map(|(action, share)| (action.0, share))
+            .collect();
+        *out.lock().unwrap() = Some((distribution, result.prior_disagreement()));
+    });
+    mcts.search().unwrap();
+
+    let (distribution, disagreement) = measured.lock().unwrap().take().unwrap();
+    assert_eq!(distribution.len(), 3);
+    let total: f64 = distribution.iter().map(|(_, share)| share).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    let best = distribution
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+    assert_eq!(best.0, 2);
+
+    // Uniform priors against visits concentrated on the best pick
+    assert!(
+        disagreement > 0.2 && disagreement < 2.0 / 3.0,
+        "{}",
+        disagreement
+    );
+}
+
+#[test]
+fn test_callbacks_run_on_spawned_searches() {
+    let mut mcts = mcts(200);
+    let seen = recorder(&mut mcts);
+
+    let (mcts, result) = mcts.spawn_search().join();
+    let action = result.unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].id, mcts.last_search_id().unwrap());
+    assert_eq!(seen[0].action, Some(action));
+}
@@ -1,8 +1,12 @@
 use arboriter_mcts::{
-    game_state::{Action, Player},
-    policy::selection::{PUCTPolicy, SelectionPolicy, UCB1Policy, UCB1TunedPolicy},
-    tree::MCTSNode,
-    GameState,
+    config::Unfinished,
+    game_state::{Action, ActionFeatures, Player},
+    policy::selection::{
+        BlendedValuePolicy, KernelRegressionPolicy, PUCTPolicy, PosteriorQuantilePolicy,
+        SelectionContext, SelectionPolicy, SimilarityKernel, UCB1Policy, UCB1TunedPolicy,
+    },
+    tree::{MCTSNode, NodePath},
+    GameState, MCTSConfig, MCTS,
 };
 
 /// Simple game state for testing
@@ -27,6 +31,12 @@ impl Action for TestAction {
     }
 }
 
+impl ActionFeatures for TestAction {
+    fn features(&self) -> Vec<f64> {
+        vec![self.0 as f64 / 10.0]
+    }
+}
+
 impl GameState for TestGameState {
     type Action = TestAction;
     type Player = TestPlayer;
@@ -106,7 +116,8 @@ fn test_ucb1_exploration_exploitation_balance() {
     // With low exploration constant, exploitation dominates
     // Child 0 has higher value (0.9) than Child 1 (0.4), so should be chosen
     let policy_exploitative = UCB1Policy::new(0.1);
-    let choice_exploitative = policy_exploitative.select_child(&node);
+    let choice_exploitative = policy_exploitative
+        .select_child(&node, &mut SelectionContext::new(&mut rand::thread_rng()));
     assert_eq!(
         choice_exploitative, 0,
         "With low exploration constant, should prefer child with higher value"
@@ -115,7 +126,8 @@ fn test_ucb1_exploration_exploitation_balance() {
     // With extremely high exploration constant, exploration dominates
     // Child 1 has fewer visits (10) than Child 0 (50), so should be chosen
     let policy_explorative = UCB1Policy::new(100.0);
-    let choice_explorative = policy_explorative.select_child(&node);
+    let choice_explorative =
+        policy_explorative.select_child(&node, &mut SelectionContext::new(&mut rand::thread_rng()));
     assert_eq!(
         choice_explorative, 1,
         "With very high exploration constant, should prefer less-visited child"
@@ -127,7 +139,7 @@ fn test_ucb1_tuned_policy() {
     let node = create_test_node_for_policy();
 
     let policy = UCB1TunedPolicy::new(1.414);
-    let choice = policy.select_child(&node);
+    let choice = policy.select_child(&node, &mut SelectionContext::new(&mut rand::thread_rng()));
 
     // We're just ensuring it runs without crashing here
     // Deeper verification would require more specific test cases
@@ -142,19 +154,23 @@ fn test_puct_policy() {
     let node = create_test_node_for_policy();
 
     let policy_default = PUCTPolicy::new(1.0);
-    let choice_default = policy_default.select_child(&node);
+    let choice_default =
+        policy_default.select_child(&node, &mut SelectionContext::new(&mut rand::thread_rng()));
     assert!(
         choice_default == 0 || choice_default == 1,
         "PUCT with default priors should select a valid child"
     );
 
     // Test with explicit priors set on the nodes
-    let mut node_with_priors = create_test_node_for_policy();
+    let node_with_priors = create_test_node_for_policy();
     node_with_priors.children[0].set_prior(0.1);
     node_with_priors.children[1].set_prior(0.9);
 
     let policy_with_priors = PUCTPolicy::new(1.0); // Policy itself doesn't need priors now
-    let choice_with_priors = policy_with_priors.select_child(&node_with_priors);
+    let choice_with_priors = policy_with_priors.select_child(
+        &node_with_priors,
+        &mut SelectionContext::new(&mut rand::thread_rng()),
+    );
 
     // With strong prior for child 1, it should be selected
     assert_eq!(
@@ -163,6 +179,60 @@ fn test_puct_policy() {
     );
 }
 
+#[test]
+fn test_posterior_quantile_policy() {
+    let node = create_test_node_for_policy();
+
+    // Both children share a posterior mean, but child 1 is far less certain
+    node.children[0].set_posterior(0.6, 200.0);
+    node.children[1].set_posterior(0.6, 5.0);
+
+    let mut rng = rand::thread_rng();
+    let optimistic = PosteriorQuantilePolicy::new(0.95);
+    assert_eq!(
+        optimistic.select_child(&node, &mut SelectionContext::new(&mut rng)),
+        1
+    );
+
+    // At the median only the means count, so a better mean wins
+    node.children[0].set_posterior(0.7, 200.0);
+    let median = PosteriorQuantilePolicy::new(0.5);
+    assert_eq!(
+        median.select_child(&node, &mut SelectionContext::new(&mut rng)),
+        0
+    );
+}
+
+#[test]
+fn test_blended_value_policy() {
+    let node = create_test_node_for_policy();
+
+    // Child 0 always wins narrowly; child 1 loses half the time but wins big
+    for _ in 0..50 {
+        node.children[0].add_outcome(0.55);
+    }
+    for i in 0..10 {
+        node.children[1].add_outcome(if i % 2 == 0 { 1.0 } else { 0.0 });
+    }
+    node.children[1].add_reward(6.0);
+
+    assert!((node.children[0].win_rate() - 1.0).abs() < 1e-6);
+    assert!((node.children[1].win_rate() - 0.5).abs() < 1e-6);
+
+    let mut rng = rand::thread_rng();
+    let win_first = BlendedValuePolicy::new(0.0, 1.0);
+    assert_eq!(
+        win_first.select_child(&node, &mut SelectionContext::new(&mut rng)),
+        0
+    );
+
+    let score_first = BlendedValuePolicy::new(0.0, 0.0);
+    assert_eq!(
+        score_first.select_child(&node, &mut SelectionContext::new(&mut rng)),
+        1
+    );
+}
+
 #[test]
 fn test_clone_box() {
     let policy1 = UCB1Policy::new(1.414);
@@ -172,3 +242,249 @@ fn test_clone_box() {
     // If we got here without panicking, it works
     // We can't really compare the cloned box easily
 }
+
+#[test]
+fn test_ucb1_paths_agree_for_a_parent_without_visits() {
+    let actions: Vec<TestAction> = (0..6).map(|i| TestAction(i as u8)).collect();
+    let state = TestGameState {
+        terminal: false,
+        actions: actions.clone(),
+        player: TestPlayer(1),
+    };
+
+    // Children carry visits the parent does not, as with virtual loss
+    let mut node = MCTSNode::new(state, None, None, 0);
+    for _ in 0..actions.len() {
+        node.expand(0);
+    }
+    for (i, child) in node.children.iter().enumerate() {
+        for _ in 0..1 + i * 3 {
+            child.increment_visits();
+            child.add_reward(((i * 5) % 7) as f64 / 7.0);
+        }
+    }
+    assert_eq!(node.visits(), 0);
+
+    // With ln(1) = 0 both paths pick on value alone
+    let policy = UCB1Policy::new(1.414);
+    let batched = policy.select_child_batched(&node);
+    let scalar = policy.select_child(&node, &mut SelectionContext::new(&mut rand::thread_rng()));
+    assert_eq!(scalar, batched);
+    assert_eq!(policy.ucb1_value(0.4, 3, 0), policy.ucb1_value(0.4, 3, 1),);
+}
+
+#[test]
+fn test_ucb1_batched_matches_scalar_on_wide_node() {
+    let actions: Vec<TestAction> = (0..150).map(|i| TestAction(i as u8)).collect();
+    let state = TestGameState {
+        terminal: false,
+        actions: actions.clone(),
+        player: TestPlayer(1),
+    };
+
+    let mut node = MCTSNode::new(state, None, None, 0);
+    for _ in 0..actions.len() {
+        node.expand(0);
+    }
+    for _ in 0..5000 {
+        node.increment_visits();
+    }
+
+    // Give every child a different visit count and value
+    for (i, child) in node.children.iter().enumerate() {
+        let visits = 1 + (i * 7) % 40;
+        for _ in 0..visits {
+            child.increment_visits();
+            child.add_reward(((i * 13) % 17) as f64 / 17.0);
+        }
+    }
+
+    let policy = UCB1Policy::new(1.414);
+    let parent_visits = node.visits();
+    let scalar_best = node
+        .children
+        .iter()
+        .enumerate()
+        .fold((0, f64::NEG_INFINITY), |(best_i, best), (i, child)| {
+            let value = policy.ucb1_value(child.value(), child.visits(), parent_visits);
+            if value > best {
+                (i, value)
+            } else {
+                (best_i, best)
+            }
+        })
+        .0;
+
+    assert_eq!(policy.select_child_batched(&node), scalar_best);
+    assert_eq!(
+        policy.select_child(&node, &mut SelectionContext::new(&mut rand::thread_rng())),
+        scalar_best
+    );
+
+    // An unvisited child in a later batch is still picked first
+    let mut wide = node;
+    let extra = wide.state.clone();
+    wide.children.push(MCTSNode::new(
+        extra,
+        Some(TestAction(200)),
+        Some(TestPlayer(1)),
+        1,
+    ));
+    assert_eq!(
+        policy.select_child(&wide, &mut SelectionContext::new(&mut rand::thread_rng())),
+        wide.children.len() - 1
+    );
+}
+
+#[test]
+fn test_similarity_kernels() {
+    let gaussian = SimilarityKernel::Gaussian { bandwidth: 2.0 };
+    assert_eq!(gaussian.similarity(&[1.0, 2.0], &[1.0, 2.0]), 1.0);
+    assert!((gaussian.similarity(&[0.0, 0.0], &[2.0, 0.0]) - (-0.5f64).exp()).abs() < 1e-12);
+
+    let exact = SimilarityKernel::Custom(|a, b| if a == b { 1.0 } else { 0.0 });
+    assert_eq!(exact.similarity(&[1.0], &[1.5]), 0.0);
+}
+
+#[test]
+fn test_kernel_regression_pools_similar_children() {
+    let state = TestGameState {
+        terminal: false,
+        actions: vec![TestAction(0), TestAction(1), TestAction(100)],
+        player: TestPlayer(1),
+    };
+    let mut node = MCTSNode::new(state, None, None, 0);
+    for action in [0, 1, 100] {
+        let index = node.find_unexpanded(&TestAction(action)).unwrap();
+        node.expand(index);
+    }
+    node.inject_stats(42, 0.5);
+    node.children[0].inject_stats(20, 0.9);
+    node.children[1].inject_stats(2, 0.2);
+    node.children[2].inject_stats(20, 0.5);
+
+    // The unlucky child next to a strong one borrows its estimate
+    let policy = KernelRegressionPolicy::new(1.0, 1.0);
+    let stats = policy.kernel_stats(&node);
+    assert!(stats[1].0 > 20.0, "{:?}", stats);
+    assert!(stats[1].1 > 0.8, "{:?}", stats);
+    assert!((stats[2].0 - 20.0).abs() < 1e-6);
+    assert!((stats[2].1 - 0.5).abs() < 1e-6);
+
+    // Without similarity the raw statistics come back
+    let isolated = KernelRegressionPolicy::with_kernel(
+        1.0,
+        SimilarityKernel::Custom(|a, b| if a == b { 1.0 } else { 0.0 }),
+    );
+    let stats = isolated.kernel_stats(&node);
+    assert!((stats[1].0 - 2.0).abs() < 1e-9);
+    assert!((stats[1].1 - 0.2).abs() < 1e-6);
+
+    // A child with nothing similar visited is explored first
+    let extra = node.state.clone();
+    node.children.push(MCTSNode::new(
+        extra,
+        Some(TestAction(250)),
+        Some(TestPlayer(1)),
+        1,
+    ));
+    let mut rng = rand::thread_rng();
+    assert_eq!(
+        policy.select_child(&node, &mut SelectionContext::new(&mut rng)),
+        3
+    );
+}
+
+/// Checks that a policy's score breakdown adds up and picks its own choice
+fn assert_scores_explain_choice(
+    policy: &dyn SelectionPolicy<TestGameState>,
+    node: &MCTSNode<TestGameState>,
+) {
+    let mut rng = rand::thread_rng();
+    let scores = policy
+        .child_scores(node, &mut SelectionContext::new(&mut rng))
+        .expect("built-in policies report scores");
+    assert_eq!(scores.len(), node.children.len());
+
+    let mut best = 0;
+    for (i, score) in scores.iter().enumerate() {
+        assert_eq!(score.score, score.exploitation + score.exploration);
+        assert!((score.prior - node.children[i].prior()).abs() < 1e-9);
+        if score.score > scores[best].score {
+            best = i;
+        }
+    }
+    let choice = policy.select_child(node, &mut SelectionContext::new(&mut rng));
+    assert_eq!(best, choice, "{:?}", scores);
+}
+
+#[test]
+fn test_child_scores_explain_selection() {
+    let node = create_test_node_for_policy();
+    node.children[0].set_prior(0.1);
+    node.children[1].set_prior(0.9);
+
+    let policies: Vec<Box<dyn SelectionPolicy<TestGameState>>> = vec![
+        Box::new(UCB1Policy::new(0.1)),
+        Box::new(UCB1Policy::new(100.0)),
+        Box::new(UCB1TunedPolicy::new(1.414)),
+        Box::new(PUCTPolicy::new(1.0)),
+        Box::new(PosteriorQuantilePolicy::new(0.9)),
+        Box::new(BlendedValuePolicy::new(1.0, 0.5)),
+        Box::new(KernelRegressionPolicy::new(1.0, 0.5)),
+    ];
+    for policy in &policies {
+        assert_scores_explain_choice(policy.as_ref(), &node);
+    }
+
+    // UCB1 splits into the mean and the exploration bonus
+    let mut rng = rand::thread_rng();
+    let scores = UCB1Policy::new(1.0)
+        .child_scores(&node, &mut SelectionContext::new(&mut rng))
+        .unwrap();
+    assert!((scores[0].exploitation - 0.9).abs() < 1e-6);
+    assert!((scores[1].exploitation - 0.4).abs() < 1e-6);
+    let bonus = ((100f64).ln() / 10.0).sqrt();
+    assert!((scores[1].exploration - bonus).abs() < 1e-9);
+}
+
+#[test]
+fn test_child_scores_of_unvisited_children_are_infinite() {
+    let mut node = create_test_node_for_policy();
+    node.expand(0);
+
+    let mut rng = rand::thread_rng();
+    let scores = UCB1Policy::new(1.0)
+        .child_scores(&node, &mut SelectionContext::new(&mut rng))
+        .unwrap();
+    assert_eq!(scores[2].exploration, f64::INFINITY);
+    assert_eq!(scores[2].score, f64::INFINITY);
+}
+
+#[test]
+fn test_mcts_child_scores_by_path() {
+    let state = TestGameState {
+        terminal: false,
+        actions: vec![TestAction(0), TestAction(1), TestAction(2)],
+        player: TestPlayer(1),
+    };
+    // The test game never ends, so playouts are cut short
+    let config = MCTSConfig::default()
+        .with_max_iterations(30)
+        .with_max_playout_length(3, Unfinished::Draw);
+    let mut mcts = MCTS::new(state, config);
+    mcts.search().unwrap();
+
+    let root_scores = mcts.child_scores(&NodePath::new()).unwrap();
+    assert_eq!(root_scores.len(), mcts.root().children.len());
+    for (score, child) in root_scores.iter().zip(&mcts.root().children) {
+        assert!((score.exploitation - child.selection_stats().1).abs() < 1e-9);
+    }
+
+    let child_scores = mcts.child_scores(&NodePath::from_indices(vec![0])).unwrap();
+    assert_eq!(child_scores.len(), mcts.root().children[0].children.len());
+
+    assert!(mcts
+        .child_scores(&NodePath::from_indices(vec![7]))
+        .is_none());
+}
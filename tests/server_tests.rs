@@ -0,0 +1,306 @@
+#![cfg(feature = "server")]
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+
+use arboriter_mcts::{
+    config::{Budget, RewardPerspective},
+    game_state::{Action, Player},
+    server::{
+        SearchServer, SerdeCodec, StateCodec, INVALID_PARAMS, INVALID_REQUEST, MAX_HEADER_BYTES,
+        METHOD_NOT_FOUND, PARSE_ERROR, SEARCH_ERROR,
+    },
+    GameState, MCTSConfig,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Nim with a single pile: take one to three stones, taking the last one wins
+#[derive(Clone, Debug, Deserialize)]
+struct Nim {
+    stones: u32,
+    player: Side,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+struct Side(u8);
+
+impl Player for Side {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct Take(u32);
+
+impl Action for Take {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl GameState for Nim {
+    type Action = Take;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.stones.min(3)).map(Take).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Nim {
+            stones: self.stones - action.0,
+            player: Side(1 - self.player.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stones == 0
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        if self.player == *for_player {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.player
+    }
+}
+
+/// Search configuration for a two-player game
+fn config(iterations: usize) -> MCTSConfig {
+    MCTSConfig::default()
+        .with_max_iterations(iterations)
+        .with_reward_perspective(RewardPerspective::PerPlayer)
+}
+
+fn server() -> SearchServer<Nim, SerdeCodec> {
+    SearchServer::new(SerdeCodec, config(3000))
+}
+
+fn call(server: &SearchServer<Nim, SerdeCodec>, request: Value) -> Value {
+    let response = server.handle_request(&request.to_string()).unwrap();
+    serde_json::from_str(&response).unwrap()
+}
+
+fn search(params: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": 7, "method": "search", "params": params})
+}
+
+fn error_code(response: &Value) -> i64 {
+    response["error"]["code"].as_i64().unwrap()
+}
+
+#[test]
+fn test_search_finds_the_winning_move() {
+    // Leaving a multiple of four stones wins
+    let response = call(
+        &server(),
+        search(json!({
+            "state": {"stones": 10, "player": 0},
+            "budget": {"iterations": 4000},
+            "lines": 3,
+        })),
+    );
+
+    assert_eq!(response["jsonrpc"], "2.0");
+    assert_eq!(response["id"], 7);
+    let result = &response["result"];
+    assert_eq!(result["action"], 2);
+    assert_eq!(result["iterations"], 4000);
+    let lines = result["lines"].as_array().unwrap();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["moves"][0], 2);
+    assert!(lines[0]["visits"].as_u64().unwrap() >= lines[1]["visits"].as_u64().unwrap());
+}
+
+#[test]
+fn test_positional_params_use_the_configured_budget() {
+    let response = call(&server(), search(json!([{"stones": 5, "player": 1}])));
+
+    assert_eq!(response["result"]["action"], 1);
+    assert_eq!(response["result"]["iterations"], 3000);
+    assert_eq!(response["result"]["lines"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_budget_is_capped() {
+    let capped = server().with_max_budget(Budget::Iterations(500));
+    let response = call(
+        &capped,
+        search(json!({"state": {"stones": 9, "player": 0}, "budget": {"iterations": 100000}})),
+    );
+    assert_eq!(response["result"]["iterations"], 500);
+
+    // A request without limits gets the cap rather than running forever
+    let response = call(
+        &capped,
+        search(json!({"state": {"stones": 9, "player": 0}, "budget": {}})),
+    );
+    assert_eq!(response["result"]["iterations"], 500);
+
+    let response = call(
+        &server(),
+        search(json!({"state": {"stones": 9, "player": 0}, "budget": {}})),
+    );
+    assert_eq!(error_code(&response), INVALID_PARAMS);
+}
+
+#[test]
+fn test_batches_share_the_budget_cap() {
+    let capped = server().with_max_budget(Budget::Iterations(500));
+    let request =
+        search(json!({"state": {"stones": 9, "player": 0}, "budget": {"iterations": 300}}));
+    let response = call(&capped, json!([request, request, request]));
+    let responses = response.as_array().unwrap();
+
+    // The second search gets what the first left, and the third gets nothing
+    assert_eq!(responses[0]["result"]["iterations"], 300);
+    assert_eq!(responses[1]["result"]["iterations"], 200);
+    assert_eq!(error_code(&responses[2]), SEARCH_ERROR);
+
+    // Each body starts with the full cap
+    let response = call(&capped, request);
+    assert_eq!(response["result"]["iterations"], 300);
+}
+
+#[test]
+fn test_errors_follow_json_rpc() {
+    let server = server();
+
+    let response: Value =
+        serde_json::from_str(&server.handle_request("{not json").unwrap()).unwrap();
+    assert_eq!(error_code(&response), PARSE_ERROR);
+    assert_eq!(response["id"], Value::Null);
+
+    let response = call(
+        &server,
+        json!({"jsonrpc": "1.0", "id": 1, "method": "search"}),
+    );
+    assert_eq!(error_code(&response), INVALID_REQUEST);
+
+    let response = call(
+        &server,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "ponder"}),
+    );
+    assert_eq!(error_code(&response), METHOD_NOT_FOUND);
+
+    for params in [
+        json!({}),
+        json!({"state": {"stones": "many"}}),
+        json!({"state": {"stones": 3, "player": 0}, "budget": {"iterations": -1}}),
+        json!({"state": {"stones": 3, "player": 0}, "lines": "all"}),
+        json!("state"),
+    ] {
+        let response = call(&server, search(params.clone()));
+        assert_eq!(error_code(&response), INVALID_PARAMS, "{}", params);
+    }
+
+    let response = call(
+        &server,
+        search(json!({"state": {"stones": 0, "player": 0}})),
+    );
+    assert_eq!(error_code(&response), SEARCH_ERROR);
+}
+
+#[test]
+fn test_notifications_and_batches() {
+    let server = server();
+    let notification = json!({"jsonrpc": "2.0", "method": "search",
+        "params": {"state": {"stones": 4, "player": 0}}});
+    assert_eq!(server.handle_request(&notification.to_string()), None);
+
+    let batch = json!([
+        search(json!({"state": {"stones": 6, "player": 0}, "budget": {"iterations": 2000}})),
+        notification,
+        {"jsonrpc": "2.0", "id": 8, "method": "nope"},
+    ]);
+    let response = call(&server, batch);
+    let responses = response.as_array().unwrap();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["result"]["action"], 2);
+    assert_eq!(error_code(&responses[1]), METHOD_NOT_FOUND);
+
+    let response = call(&server, json!([]));
+    assert_eq!(error_code(&response), INVALID_REQUEST);
+}
+
+#[test]
+fn test_custom_codec() {
+    /// Takes the pile size as a bare number and names actions
+    struct Stones;
+
+    impl StateCodec<Nim> for Stones {
+        fn decode_state(&self, state: Value) -> Result<Nim, String> {
+            let stones = state.as_u64().ok_or("state must be a pile size")?;
+            Ok(Nim {
+                stones: stones as u32,
+                player: Side(0),
+            })
+        }
+
+        fn encode_action(&self, action: &Take) -> Value {
+            json!(format!("take {}", action.0))
+        }
+    }
+
+    let server = SearchServer::new(Stones, config(2000));
+    let response: Value = serde_json::from_str(
+        &server
+            .handle_request(&search(json!({"state": 7})).to_string())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response["result"]["action"], "take 3");
+}
+
+/// Sends a raw HTTP request and returns the status line and body
+fn http(addr: std::net::SocketAddr, request: &str) -> (String, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    (head.lines().next().unwrap().to_string(), body.to_string())
+}
+
+#[test]
+fn test_serves_over_http() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Arc::new(server());
+    let serving = Arc::clone(&server);
+    std::thread::spawn(move || serving.serve_listener(listener));
+
+    let body =
+        search(json!({"state": {"stones": 10, "player": 0}, "budget": {"iterations": 3000}}))
+            .to_string();
+    let (status, response) = http(
+        addr,
+        &format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ),
+    );
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    let response: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["result"]["action"], 2);
+
+    let (status, _) = http(addr, "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n");
+    assert_eq!(status, "HTTP/1.1 405 Method Not Allowed");
+
+    let (status, _) = http(
+        addr,
+        "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: 99999999\r\n\r\n",
+    );
+    assert_eq!(status, "HTTP/1.1 413 Payload Too Large");
+
+    // A header line that never ends is cut off at the limit
+    let mut request = "POST / HTTP/1.1\r\nX-Padding: ".to_string();
+    request.push_str(&"a".repeat(MAX_HEADER_BYTES - request.len()));
+    let (status, _) = http(addr, &request);
+    assert_eq!(status, "HTTP/1.1 431 Request Header Fields Too Large");
+}
@@ -1,7 +1,13 @@
+use std::sync::Arc;
+
 use arboriter_mcts::{
+    config::{RewardPerspective, Unfinished},
     game_state::{Action, Player},
-    policy::simulation::{HeuristicPolicy, MixturePolicy, RandomPolicy, SimulationPolicy},
-    GameState,
+    history::HistoryTable,
+    policy::simulation::{
+        HeuristicPolicy, MastPolicy, MinimaxPolicy, MixturePolicy, RandomPolicy, SimulationPolicy,
+    },
+    GameState, MCTSConfig, MCTS,
 };
 
 /// Simple game state for testing
@@ -167,3 +173,324 @@ fn test_simulation_policy_cloning() {
 
     // Mainly testing that we don't panic
 }
+
+/// Puzzle with reversible moves: random playouts almost never reach the goal
+#[derive(Clone, Debug)]
+struct SlidingPuzzle {
+    position: i32,
+}
+
+impl GameState for SlidingPuzzle {
+    type Action = TestAction;
+    type Player = TestPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        vec![TestAction(0), TestAction(1)]
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let step = if action.0 == 0 { -1 } else { 1 };
+        SlidingPuzzle {
+            position: self.position + step,
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.position.abs() >= 1_000_000
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        // Closer to the right edge is better
+        (self.position as f64 / 2_000_000.0 + 0.5).clamp(0.0, 1.0)
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        TestPlayer(0)
+    }
+}
+
+#[test]
+fn test_capped_random_playout() {
+    let policy = RandomPolicy::new();
+    let state = SlidingPuzzle { position: 0 };
+
+    let limits = MCTSConfig::default()
+        .with_max_playout_length(10, Unfinished::Draw)
+        .playout_limits();
+    let (result, trace) = policy.simulate_limited(&state, &limits);
+    assert_eq!(trace.len(), 10);
+    assert_eq!(result, 0.5);
+
+    let state = SlidingPuzzle { position: 400_000 };
+    let limits = MCTSConfig::default()
+        .with_max_playout_length(10, Unfinished::Heuristic)
+        .playout_limits();
+    let (result, trace) = policy.simulate_limited(&state, &limits);
+    assert_eq!(trace.len(), 10);
+    assert!(result > 0.69 && result < 0.71);
+}
+
+#[test]
+fn test_capped_playout_reports_finished_games() {
+    // Games that end before the cap keep their real result
+    let state = TestGameState {
+        terminal: false,
+        actions: vec![TestAction(0)],
+        player: TestPlayer(1),
+        result: 0.9,
+    };
+    let limits = MCTSConfig::default()
+        .with_max_playout_length(10, Unfinished::Draw)
+        .playout_limits();
+    let (result, trace) = state.simulate_random_playout_limited(&TestPlayer(1), &limits);
+    assert_eq!(result, 0.9);
+    assert_eq!(trace.len(), 1);
+}
+
+#[test]
+fn test_max_playout_length_applies_to_search() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(50)
+        .with_max_playout_length(20, Unfinished::Draw);
+
+    let mut mcts = MCTS::new(SlidingPuzzle { position: 0 }, config);
+    assert!(mcts.search().is_ok());
+    assert_eq!(mcts.get_statistics().iterations, 50);
+    assert!((mcts.root().value() - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_random_playout_stops_on_cycle() {
+    // Hashing only the parity makes every second position a repeat
+    #[derive(Clone, Debug)]
+    struct Parity(SlidingPuzzle);
+
+    impl GameState for Parity {
+        type Action = TestAction;
+        type Player = TestPlayer;
+
+        fn get_legal_actions(&self) -> Vec<Self::Action> {
+            self.0.get_legal_actions()
+        }
+
+        fn apply_action(&self, action: &Self::Action) -> Self {
+            Parity(self.0.apply_action(action))
+        }
+
+        fn is_terminal(&self) -> bool {
+            self.0.is_terminal()
+        }
+
+        fn get_result(&self, for_player: &Self::Player) -> f64 {
+            self.0.get_result(for_player)
+        }
+
+        fn get_current_player(&self) -> Self::Player {
+            self.0.get_current_player()
+        }
+
+        fn hash(&self) -> u64 {
+            1 + self.0.position.rem_euclid(2) as u64
+        }
+    }
+
+    let limits = MCTSConfig::default()
+        .with_cycle_detection(0.1)
+        .playout_limits();
+    let state = Parity(SlidingPuzzle { position: 0 });
+    let (result, trace) = RandomPolicy::new().simulate_limited(&state, &limits);
+    assert_eq!(result, 0.1);
+    assert_eq!(trace.len(), 2);
+}
+
+/// Take one or two stones; whoever takes the last stone wins
+#[derive(Clone, Debug)]
+struct Nim {
+    stones: u8,
+    to_move: TestPlayer,
+}
+
+impl Nim {
+    fn new(stones: u8) -> Self {
+        Nim {
+            stones,
+            to_move: TestPlayer(0),
+        }
+    }
+}
+
+impl GameState for Nim {
+    type Action = TestAction;
+    type Player = TestPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.stones.min(2)).map(TestAction).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Nim {
+            stones: self.stones - action.0,
+            to_move: TestPlayer(1 - self.to_move.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stones == 0
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        // The player to move at the end did not take the last stone
+        if *for_player == self.to_move {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.to_move.clone()
+    }
+}
+
+fn unknown(_: &Nim, _: &TestPlayer) -> f64 {
+    0.5
+}
+
+#[test]
+fn test_minimax_policy_solves_short_tactics() {
+    let policy = MinimaxPolicy::new(4, unknown);
+
+    // Four stones: take one and leave a lost position
+    let (value, line) = policy.simulate(&Nim::new(4));
+    assert_eq!(value, 1.0);
+    assert_eq!(line.first(), Some(&TestAction(1)));
+
+    // Three stones are lost whatever the mover does
+    let (value, _) = policy.simulate(&Nim::new(3));
+    assert_eq!(value, 0.0);
+}
+
+#[test]
+fn test_minimax_policy_uses_heuristic_at_the_frontier() {
+    let policy = MinimaxPolicy::new(0, unknown);
+    assert_eq!(policy.simulate(&Nim::new(4)), (0.5, Vec::new()));
+
+    // One ply sees the win but not beyond it
+    let policy = MinimaxPolicy::new(1, unknown);
+    assert_eq!(policy.simulate(&Nim::new(2)).0, 1.0);
+    assert_eq!(policy.simulate(&Nim::new(5)).0, 0.5);
+
+    // A terminal leaf is scored by the game
+    assert_eq!(policy.simulate(&Nim::new(0)).0, 0.0);
+}
+
+#[test]
+fn test_minimax_policy_respects_playout_length() {
+    let policy = MinimaxPolicy::new(6, unknown);
+    let config = MCTSConfig::default().with_max_playout_length(1, Unfinished::Draw);
+
+    let (value, line) = policy.simulate_limited(&Nim::new(4), &config.playout_limits());
+    assert_eq!(value, 0.5);
+    assert!(line.len() <= 1);
+}
+
+#[test]
+fn test_minimax_policy_in_search() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(200)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let mut mcts =
+        MCTS::new(Nim::new(7), config).with_simulation_policy(MinimaxPolicy::new(3, unknown));
+
+    // Leave a multiple of three
+    assert_eq!(mcts.search().unwrap(), TestAction(1));
+}
+
+/// An exchange in progress: recapturing is forced, and any other move loses
+#[derive(Clone, Debug)]
+struct Exchange {
+    recaptures: u8,
+    result: Option<f64>,
+}
+
+const RECAPTURE: TestAction = TestAction(0);
+const BLUNDER: TestAction = TestAction(1);
+const SETTLE: TestAction = TestAction(2);
+
+impl GameState for Exchange {
+    type Action = TestAction;
+    type Player = TestPlayer;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match (self.result, self.recaptures) {
+            (Some(_), _) => Vec::new(),
+            (None, 0) => vec![SETTLE],
+            (None, _) => vec![RECAPTURE, BLUNDER],
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        match action.0 {
+            0 => Exchange {
+                recaptures: self.recaptures - 1,
+                result: None,
+            },
+            1 => Exchange {
+                recaptures: self.recaptures,
+                result: Some(0.0),
+            },
+            _ => Exchange {
+                recaptures: 0,
+                result: Some(1.0),
+            },
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.result.is_some()
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        self.result.unwrap_or(0.5)
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        TestPlayer(0)
+    }
+
+    fn forced_continuation(&self) -> Option<Self::Action> {
+        (self.result.is_none() && self.recaptures > 0).then_some(RECAPTURE)
+    }
+}
+
+fn exchange() -> Exchange {
+    Exchange {
+        recaptures: 3,
+        result: None,
+    }
+}
+
+#[test]
+fn test_playouts_resolve_forced_moves_first() {
+    let state = exchange();
+    for _ in 0..20 {
+        let (result, trace) = RandomPolicy::new().simulate(&state);
+        assert_eq!(result, 1.0);
+        assert_eq!(trace, vec![RECAPTURE, RECAPTURE, RECAPTURE, SETTLE]);
+    }
+
+    let mast = MastPolicy::new(Arc::new(HistoryTable::new(1.0)), 1.0);
+    for _ in 0..20 {
+        assert_eq!(mast.simulate(&state).0, 1.0);
+    }
+}
+
+#[test]
+fn test_forced_moves_count_towards_playout_length() {
+    let config = MCTSConfig::default().with_max_playout_length(2, Unfinished::Draw);
+    let limits = config.playout_limits();
+
+    let (result, trace) = RandomPolicy::new().simulate_limited(&exchange(), &limits);
+    assert_eq!(result, 0.5);
+    assert_eq!(trace, vec![RECAPTURE, RECAPTURE]);
+}
@@ -0,0 +1,372 @@
+//! Playing-strength regression suite
+//!
+//! Unit tests check that the search runs correctly, not that it plays well;
+//! a change to selection or backpropagation can cost a lot of strength
+//! without failing any of them. This suite searches a battery of tactical
+//! positions several times each and compares the share of searches that find
+//! a correct move against the rate recorded for that position.
+//!
+//! The searches take a while, so the suite is behind the `strength-tests`
+//! feature and its tests are ignored by default:
+//!
+//! ```bash
+//! cargo test --release --features strength-tests --test strength_tests -- --ignored --nocapture
+//! ```
+//!
+//! The report printed for each battery lists the measured rates. After a
+//! change that is meant to make the engine stronger, record the new rates in
+//! the puzzle tables.
+
+#![cfg(feature = "strength-tests")]
+
+use arboriter_mcts::{
+    config::RewardPerspective,
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTS,
+};
+
+/// Searches run per puzzle
+const TRIALS: usize = 20;
+
+/// How far a measured solve rate may fall below its recorded rate
+///
+/// Rollouts are random, so rates move a little between runs; a drop beyond
+/// this is a regression.
+const TOLERANCE: f64 = 0.15;
+
+/// A position with the moves that solve it
+struct Puzzle<S: GameState> {
+    name: &'static str,
+    state: S,
+    /// Action ids of every correct move
+    solutions: &'static [usize],
+    iterations: usize,
+    /// Share of searches that found a solution when the puzzle was recorded
+    recorded: f64,
+}
+
+/// Returns the share of `TRIALS` searches of `puzzle` that pick a solution
+fn solve_rate<S: GameState + 'static>(puzzle: &Puzzle<S>) -> f64 {
+    let config = MCTSConfig::default()
+        .with_max_iterations(puzzle.iterations)
+        .with_reward_perspective(RewardPerspective::PerPlayer);
+    let solved = (0..TRIALS)
+        .filter(|_| {
+            let mut mcts = MCTS::new(puzzle.state.clone(), config.clone());
+            let action = mcts.search().expect("puzzles have legal moves");
+            puzzle.solutions.contains(&action.id())
+        })
+        .count();
+    solved as f64 / TRIALS as f64
+}
+
+/// Measures every puzzle, prints a report and fails on any regression
+fn check<S: GameState + 'static>(battery: &str, puzzles: &[Puzzle<S>]) {
+    println!("{} ({} searches per puzzle)", battery, TRIALS);
+    let mut regressions = Vec::new();
+    for puzzle in puzzles {
+        let rate = solve_rate(puzzle);
+        println!(
+            "  {:<32} iterations {:>6}  solved {:>4.2}  recorded {:>4.2}",
+            puzzle.name, puzzle.iterations, rate, puzzle.recorded
+        );
+        if rate < puzzle.recorded - TOLERANCE {
+            regressions.push(format!(
+                "{}: solved {:.2}, recorded {:.2}",
+                puzzle.name, rate, puzzle.recorded
+            ));
+        }
+    }
+    assert!(
+        regressions.is_empty(),
+        "{} lost strength:\n{}",
+        battery,
+        regressions.join("\n")
+    );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    First,
+    Second,
+}
+
+impl Player for Side {}
+
+impl Side {
+    fn other(self) -> Side {
+        match self {
+            Side::First => Side::Second,
+            Side::Second => Side::First,
+        }
+    }
+}
+
+/// Places a stone in a square or column
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Place(usize);
+
+impl Action for Place {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Result for `for_player` of a game won by `winner`, if anyone
+fn score(winner: Option<Side>, for_player: Side) -> f64 {
+    match winner {
+        Some(winner) if winner == for_player => 1.0,
+        Some(_) => 0.0,
+        None => 0.5,
+    }
+}
+
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+#[derive(Clone, Debug)]
+struct TicTacToe {
+    board: [Option<Side>; 9],
+    to_move: Side,
+}
+
+impl TicTacToe {
+    /// Reads a board written row by row as `X`, `O` and `.`; X moves first
+    fn parse(board: &str) -> Self {
+        let mut squares = [None; 9];
+        for (square, mark) in squares.iter_mut().zip(board.chars()) {
+            *square = match mark {
+                'X' => Some(Side::First),
+                'O' => Some(Side::Second),
+                _ => None,
+            };
+        }
+        let placed = squares.iter().flatten().count();
+        TicTacToe {
+            board: squares,
+            to_move: if placed % 2 == 0 {
+                Side::First
+            } else {
+                Side::Second
+            },
+        }
+    }
+
+    fn winner(&self) -> Option<Side> {
+        LINES.iter().find_map(|&[a, b, c]| {
+            (self.board[a].is_some()
+                && self.board[a] == self.board[b]
+                && self.board[a] == self.board[c])
+                .then_some(self.board[a])
+                .flatten()
+        })
+    }
+}
+
+impl GameState for TicTacToe {
+    type Action = Place;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.winner().is_some() {
+            return Vec::new();
+        }
+        (0..9)
+            .filter(|&square| self.board[square].is_none())
+            .map(Place)
+            .collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut next = self.clone();
+        next.board[action.0] = Some(self.to_move);
+        next.to_move = self.to_move.other();
+        next
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.winner().is_some() || self.board.iter().all(Option::is_some)
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        score(self.winner(), *for_player)
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.to_move
+    }
+}
+
+const COLUMNS: usize = 7;
+const ROWS: usize = 6;
+
+#[derive(Clone, Debug)]
+struct ConnectFour {
+    /// Columns from the bottom up
+    columns: [Vec<Side>; COLUMNS],
+    to_move: Side,
+    winner: Option<Side>,
+}
+
+impl ConnectFour {
+    /// Plays `moves`, given as column numbers, from the empty board
+    fn after(moves: &[usize]) -> Self {
+        let empty = ConnectFour {
+            columns: Default::default(),
+            to_move: Side::First,
+            winner: None,
+        };
+        moves
+            .iter()
+            .fold(empty, |game, &column| game.apply_action(&Place(column)))
+    }
+
+    fn at(&self, column: isize, row: isize) -> Option<Side> {
+        if column < 0 || row < 0 {
+            return None;
+        }
+        self.columns
+            .get(column as usize)?
+            .get(row as usize)
+            .copied()
+    }
+
+    /// Returns true if the stone at `column`, `row` completes four in a row
+    fn connects(&self, column: usize, row: usize) -> bool {
+        let side = self.at(column as isize, row as isize);
+        [(1, 0), (0, 1), (1, 1), (1, -1)].iter().any(|&(dc, dr)| {
+            let run = |sign: isize| {
+                (1..4)
+                    .take_while(|&step| {
+                        self.at(
+                            column as isize + sign * step * dc,
+                            row as isize + sign * step * dr,
+                        ) == side
+                    })
+                    .count()
+            };
+            1 + run(1) + run(-1) >= 4
+        })
+    }
+}
+
+impl GameState for ConnectFour {
+    type Action = Place;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.winner.is_some() {
+            return Vec::new();
+        }
+        (0..COLUMNS)
+            .filter(|&column| self.columns[column].len() < ROWS)
+            .map(Place)
+            .collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut next = self.clone();
+        next.columns[action.0].push(self.to_move);
+        if next.connects(action.0, next.columns[action.0].len() - 1) {
+            next.winner = Some(self.to_move);
+        }
+        next.to_move = self.to_move.other();
+        next
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.winner.is_some() || self.columns.iter().all(|column| column.len() == ROWS)
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        score(self.winner, *for_player)
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.to_move
+    }
+}
+
+#[test]
+#[ignore = "strength suite; run with --ignored"]
+fn test_tic_tac_toe_tactics() {
+    check(
+        "Tic-Tac-Toe",
+        &[
+            Puzzle {
+                name: "win in one",
+                state: TicTacToe::parse("XX.OO...."),
+                solutions: &[2],
+                iterations: 500,
+                recorded: 1.0,
+            },
+            Puzzle {
+                name: "block the open line",
+                state: TicTacToe::parse("X..OO...X"),
+                solutions: &[5],
+                iterations: 1_000,
+                recorded: 1.0,
+            },
+            Puzzle {
+                name: "create a fork",
+                state: TicTacToe::parse("XO..X...O"),
+                solutions: &[3, 6],
+                iterations: 2_000,
+                recorded: 1.0,
+            },
+            Puzzle {
+                name: "defuse the corner fork",
+                state: TicTacToe::parse("X...O...X"),
+                solutions: &[1, 3, 5, 7],
+                iterations: 3_000,
+                recorded: 1.0,
+            },
+        ],
+    );
+}
+
+#[test]
+#[ignore = "strength suite; run with --ignored"]
+fn test_connect_four_tactics() {
+    check(
+        "Connect Four",
+        &[
+            Puzzle {
+                name: "win in one",
+                state: ConnectFour::after(&[3, 4, 3, 4, 3, 4]),
+                solutions: &[3],
+                iterations: 2_000,
+                recorded: 1.0,
+            },
+            Puzzle {
+                name: "block the column",
+                state: ConnectFour::after(&[3, 0, 3, 0, 5, 0]),
+                solutions: &[0],
+                iterations: 3_000,
+                recorded: 1.0,
+            },
+            Puzzle {
+                name: "open three wins in two",
+                state: ConnectFour::after(&[2, 2, 3, 3]),
+                solutions: &[1, 4],
+                iterations: 10_000,
+                recorded: 1.0,
+            },
+            Puzzle {
+                name: "don't play under a threat",
+                state: ConnectFour::after(&[1, 2, 3, 1, 6, 3, 6, 2]),
+                solutions: &[1, 2, 3, 5, 6],
+                iterations: 5_000,
+                recorded: 1.0,
+            },
+        ],
+    );
+}
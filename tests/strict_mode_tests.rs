@@ -0,0 +1,196 @@
+use arboriter_mcts::{
+    policy::selection::{ChildScore, SelectionContext, SelectionPolicy, UCB1Policy},
+    tree::MCTSNode,
+    Action, GameState, MCTSConfig, MCTSError, Player, MCTS,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+// Counting game whose branching factor is read from shared state, so tests can
+// change the legal actions behind the search's back
+#[derive(Clone, Debug)]
+struct Shifting {
+    depth: usize,
+    width: Arc<AtomicUsize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+impl GameState for Shifting {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return vec![];
+        }
+        (0..self.width.load(Ordering::SeqCst)).map(Pick).collect()
+    }
+
+    fn apply_action(&self, _action: &Self::Action) -> Self {
+        Shifting {
+            depth: self.depth + 1,
+            width: self.width.clone(),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.depth >= 4
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        0.5
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn shifting(width: usize) -> (Shifting, Arc<AtomicUsize>) {
+    let width = Arc::new(AtomicUsize::new(width));
+    let state = Shifting {
+        depth: 0,
+        width: width.clone(),
+    };
+    (state, width)
+}
+
+#[test]
+fn test_strict_mode_accepts_consistent_domains() {
+    let (state, _) = shifting(3);
+    let config = MCTSConfig::default()
+        .with_max_iterations(200)
+        .with_strict_mode(true);
+
+    let mut mcts = MCTS::new(state, config);
+    assert!(mcts.search().is_ok());
+    assert_eq!(mcts.get_statistics().iterations, 200);
+}
+
+#[test]
+fn test_strict_mode_reports_changed_legal_actions() {
+    let (state, width) = shifting(3);
+    let config = MCTSConfig::default()
+        .with_max_iterations(50)
+        .with_strict_mode(true);
+
+    let mut mcts = MCTS::new(state, config);
+    width.store(2, Ordering::SeqCst);
+
+    match mcts.search() {
+        Err(MCTSError::DomainInconsistency(message)) => {
+            assert!(message.contains("legal actions"), "{}", message)
+        }
+        other => panic!("expected a domain inconsistency, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_strict_mode_reports_vanished_actions() {
+    let (state, width) = shifting(3);
+    let config = MCTSConfig::default()
+        .with_max_iterations(50)
+        .with_strict_mode(true);
+
+    let mut mcts = MCTS::new(state, config);
+    width.store(0, Ordering::SeqCst);
+
+    assert!(matches!(
+        mcts.search(),
+        Err(MCTSError::DomainInconsistency(_))
+    ));
+}
+
+// Selection policy that always points past the end of the children
+#[derive(Clone)]
+struct OutOfBoundsPolicy;
+
+impl SelectionPolicy<Shifting> for OutOfBoundsPolicy {
+    fn select_child(
+        &self,
+        node: &MCTSNode<Shifting>,
+        _context: &mut SelectionContext<'_>,
+    ) -> usize {
+        node.children.len()
+    }
+
+    fn clone_box(&self) -> Box<dyn SelectionPolicy<Shifting>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[test]
+fn test_strict_mode_reports_invalid_child_index() {
+    let (state, _) = shifting(2);
+    let config = MCTSConfig::default()
+        .with_max_iterations(50)
+        .with_strict_mode(true);
+
+    let mut mcts = MCTS::new(state, config).with_selection_policy(OutOfBoundsPolicy);
+
+    match mcts.search() {
+        Err(MCTSError::DomainInconsistency(message)) => {
+            assert!(message.contains("selection policy"), "{}", message)
+        }
+        other => panic!("expected a domain inconsistency, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_child_score_finiteness() {
+    assert!(ChildScore::new(0.5, 0.2, 0.3).is_finite());
+    // Unvisited children are scored infinite on purpose
+    assert!(ChildScore::new(0.0, f64::INFINITY, 0.3).is_finite());
+    assert!(!ChildScore::new(0.5, f64::NAN, 0.3).is_finite());
+    assert!(!ChildScore::new(f64::INFINITY, 0.2, 0.3).is_finite());
+    assert!(!ChildScore::new(0.5, 0.2, f64::NAN).is_finite());
+    assert!(!ChildScore::new(0.5, f64::NEG_INFINITY, 0.3).is_finite());
+}
+
+#[test]
+fn test_strict_mode_reports_non_finite_scores() {
+    let (state, _) = shifting(2);
+    let config = MCTSConfig::default()
+        .with_max_iterations(50)
+        .with_strict_mode(true);
+
+    let mut mcts = MCTS::new(state, config).with_selection_policy(UCB1Policy::new(f64::NAN));
+
+    match mcts.search() {
+        Err(MCTSError::DomainInconsistency(message)) => {
+            assert!(message.contains("as NaN"), "{}", message);
+            assert!(message.contains("visits"), "{}", message);
+        }
+        other => panic!("expected a domain inconsistency, got {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "selection policy scored child")]
+fn test_debug_builds_panic_on_non_finite_scores() {
+    let (state, _) = shifting(2);
+    let config = MCTSConfig::default().with_max_iterations(50);
+
+    let mut mcts = MCTS::new(state, config).with_selection_policy(UCB1Policy::new(f64::NAN));
+    let _ = mcts.search();
+}
@@ -0,0 +1,230 @@
+use std::thread;
+use std::time::Duration;
+
+use arboriter_mcts::{
+    config::{BestChildCriteria, SubtreeGc},
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTSNode, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Climber;
+
+impl Player for Climber {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(usize);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Six picks of 0, 1 or 2; the result is the sum of the picks over their
+/// maximum, so always picking 2 is best
+#[derive(Clone, Debug)]
+struct Ladder {
+    picks: Vec<usize>,
+}
+
+const PICKS: usize = 6;
+
+impl GameState for Ladder {
+    type Action = Step;
+    type Player = Climber;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return Vec::new();
+        }
+        (0..3).map(Step).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut picks = self.picks.clone();
+        picks.push(action.0);
+        Ladder { picks }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picks.len() == PICKS
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        self.picks.iter().sum::<usize>() as f64 / (2 * PICKS) as f64
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Climber
+    }
+
+    fn hash(&self) -> u64 {
+        self.picks
+            .iter()
+            .fold(1, |hash, &pick| hash * 4 + pick as u64)
+    }
+}
+
+fn start() -> Ladder {
+    Ladder { picks: Vec::new() }
+}
+
+/// A collector that only runs when asked to
+fn manual() -> SubtreeGc {
+    SubtreeGc::new(Duration::from_secs(3600))
+}
+
+/// Checks that no node below the root holds children while under `min_visits`
+fn assert_no_stale_subtrees(node: &MCTSNode<Ladder>, min_visits: f64) {
+    for child in &node.children {
+        if !child.children.is_empty() {
+            assert!(child.visits() as f64 >= min_visits);
+        }
+        assert_no_stale_subtrees(child, min_visits);
+    }
+}
+
+#[test]
+fn test_subtree_gc_defaults() {
+    assert_eq!(MCTSConfig::default().subtree_gc, None);
+
+    let gc = SubtreeGc::new(Duration::from_secs(1))
+        .with_max_idle(Duration::from_secs(10))
+        .with_min_visit_fraction(0.01);
+    assert_eq!(gc.interval, Duration::from_secs(1));
+    assert_eq!(gc.max_idle, Some(Duration::from_secs(10)));
+    assert_eq!(gc.min_visit_fraction, Some(0.01));
+    assert_eq!(
+        MCTSConfig::default().with_subtree_gc(gc).subtree_gc,
+        Some(gc)
+    );
+
+    // Without a collector nothing is pruned
+    let mut mcts = MCTS::new(start(), MCTSConfig::default().with_max_iterations(200));
+    mcts.search().unwrap();
+    assert_eq!(mcts.collect_garbage(), 0);
+    assert!(mcts.root().children[0].idle_time().is_none());
+}
+
+#[test]
+fn test_rarely_visited_subtrees_are_collapsed() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(800)
+        .with_subtree_gc(manual().with_min_visit_fraction(0.05));
+    let mut mcts = MCTS::new(start(), config);
+    mcts.search().unwrap();
+    let before = mcts.root().subtree_size();
+
+    let removed = mcts.collect_garbage();
+
+    assert!(removed > 0);
+    assert_eq!(mcts.root().subtree_size(), before - removed);
+    assert_eq!(mcts.get_statistics().pruned_nodes, removed);
+    assert_eq!(mcts.root().visits(), 800);
+    assert_no_stale_subtrees(mcts.root(), 0.05 * 800.0);
+    assert!(mcts.check_invariants().is_ok());
+
+    // Collapsed nodes expand again in the next search
+    mcts.search().unwrap();
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_idle_subtrees_are_collapsed() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_subtree_gc(manual().with_max_idle(Duration::from_millis(20)));
+    let mut mcts = MCTS::new(start(), config);
+    mcts.search().unwrap();
+    assert!(mcts.root().children[0].idle_time().is_some());
+
+    thread::sleep(Duration::from_millis(60));
+    mcts.collect_garbage();
+
+    // Every root child is now a leaf that keeps its statistics and can
+    // expand all of its actions again
+    assert_eq!(mcts.root().children.len(), 3);
+    for child in &mcts.root().children {
+        assert!(child.children.is_empty());
+        assert!(child.visits() > 0);
+        assert_eq!(child.unexpanded_actions.len(), 3);
+    }
+    assert_eq!(mcts.root().subtree_size(), 4);
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_search_collects_periodically() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(3000)
+        .with_best_child_criteria(BestChildCriteria::HighestValue)
+        .with_subtree_gc(SubtreeGc::new(Duration::ZERO).with_min_visit_fraction(0.02));
+    let mut mcts = MCTS::new(start(), config);
+
+    assert_eq!(mcts.search().unwrap(), Step(2));
+
+    let stats = mcts.get_statistics();
+    assert!(stats.pruned_nodes > 0);
+    assert!(stats.summary().contains("Pruned nodes"));
+    assert_eq!(mcts.root().visits(), 3000);
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_collapsed_nodes_return_to_the_pool() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(600)
+        .with_subtree_gc(manual().with_min_visit_fraction(0.05));
+    let mut mcts = MCTS::with_node_pool(start(), config, 0);
+    mcts.search().unwrap();
+    let available = |mcts: &MCTS<Ladder>| {
+        mcts.get_statistics()
+            .node_pool_stats
+            .as_ref()
+            .unwrap()
+            .available
+    };
+    let before = available(&mcts);
+
+    let removed = mcts.collect_garbage();
+
+    assert!(removed > 0);
+    assert_eq!(available(&mcts), before + removed);
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_dag_mode_is_never_collected() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(300)
+        .with_dag_mode(true)
+        .with_subtree_gc(manual().with_min_visit_fraction(0.5));
+    let mut mcts = MCTS::new(start(), config);
+    mcts.search().unwrap();
+    let size = mcts.root().subtree_size();
+
+    assert_eq!(mcts.collect_garbage(), 0);
+    assert_eq!(mcts.root().subtree_size(), size);
+}
+
+#[test]
+fn test_collection_lifts_the_memory_limit() {
+    let bytes_per_node = std::mem::size_of::<MCTSNode<Ladder>>() + 3 * std::mem::size_of::<Step>();
+    let config = MCTSConfig::default()
+        .with_max_iterations(2000)
+        .with_max_memory_bytes(100 * bytes_per_node)
+        .with_subtree_gc(SubtreeGc::new(Duration::ZERO).with_min_visit_fraction(0.01));
+    let mut mcts = MCTS::new(start(), config);
+
+    mcts.search().unwrap();
+
+    // The tree hit the limit, was pruned, and grew again past its first
+    // hundred nodes without ever holding more than a hundred at once
+    let stats = mcts.get_statistics();
+    assert!(stats.memory_limited);
+    assert!(stats.pruned_nodes > 0);
+    assert!(stats.tree_size > 100);
+    assert!(mcts.root().subtree_size() <= 100);
+    assert!(mcts.check_invariants().is_ok());
+}
@@ -0,0 +1,187 @@
+use std::sync::{Arc, Mutex};
+
+use arboriter_mcts::{
+    config::{BestChildCriteria, TieBreak},
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pick(usize);
+
+impl Action for Pick {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// One pick out of `options`, each scoring the same
+#[derive(Clone, Debug)]
+struct Choice {
+    options: usize,
+    picked: bool,
+}
+
+impl GameState for Choice {
+    type Action = Pick;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        if self.picked {
+            return Vec::new();
+        }
+        (0..self.options).map(Pick).collect()
+    }
+
+    fn apply_action(&self, _: &Self::Action) -> Self {
+        Choice {
+            options: self.options,
+            picked: true,
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picked
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        0.5
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+/// A search over three options whose root children are seeded with `stats`
+fn seeded(tie_break: TieBreak, stats: &[(Pick, f64, u64)]) -> MCTS<Choice> {
+    let config = MCTSConfig::default().with_tie_break(tie_break);
+    let mut mcts = MCTS::new(
+        Choice {
+            options: 3,
+            picked: false,
+        },
+        config,
+    );
+    mcts.warm_start(stats).unwrap();
+    mcts
+}
+
+#[test]
+fn test_visit_ties_go_to_the_higher_value_by_default() {
+    let stats = [(Pick(0), 0.2, 10), (Pick(1), 0.8, 10), (Pick(2), 0.5, 4)];
+
+    let mcts = seeded(TieBreak::default(), &stats);
+    assert_eq!(
+        mcts.best_action(BestChildCriteria::MostVisits).unwrap(),
+        Pick(1)
+    );
+    assert_eq!(
+        mcts.runner_up_margin(BestChildCriteria::MostVisits),
+        Some(0.0)
+    );
+
+    let mcts = seeded(TieBreak::FirstChild, &stats);
+    assert_eq!(
+        mcts.best_action(BestChildCriteria::MostVisits).unwrap(),
+        Pick(0)
+    );
+}
+
+#[test]
+fn test_value_ties_go_to_the_more_visited_child() {
+    let stats = [(Pick(0), 0.5, 5), (Pick(1), 0.5, 9), (Pick(2), 0.1, 20)];
+
+    let mcts = seeded(TieBreak::HighestValue, &stats);
+    assert_eq!(
+        mcts.best_action(BestChildCriteria::HighestValue).unwrap(),
+        Pick(1)
+    );
+
+    let mcts = seeded(TieBreak::FirstChild, &stats);
+    assert_eq!(
+        mcts.best_action(BestChildCriteria::HighestValue).unwrap(),
+        Pick(0)
+    );
+}
+
+#[test]
+fn test_full_ties_keep_the_first_child_unless_random() {
+    let stats = [(Pick(2), 0.5, 8), (Pick(0), 0.5, 8), (Pick(1), 0.5, 8)];
+
+    let mcts = seeded(TieBreak::HighestValue, &stats);
+    assert_eq!(
+        mcts.best_action(BestChildCriteria::MostVisits).unwrap(),
+        Pick(2)
+    );
+
+    // A seed always makes the same choice, and the choices cover every child
+    let mut chosen = Vec::new();
+    for seed in 0..32 {
+        let pick = |seed| {
+            seeded(TieBreak::Random(seed), &stats)
+                .best_action(BestChildCriteria::MostVisits)
+                .unwrap()
+        };
+        let first = pick(seed);
+        assert_eq!(pick(seed), first);
+        chosen.push(first.0);
+    }
+    chosen.sort_unstable();
+    chosen.dedup();
+    assert_eq!(chosen, [0, 1, 2]);
+}
+
+#[test]
+fn test_random_tie_break_respects_the_statistics() {
+    let stats = [(Pick(0), 0.4, 8), (Pick(1), 0.6, 8), (Pick(2), 0.9, 3)];
+    for seed in 0..16 {
+        let mcts = seeded(TieBreak::Random(seed), &stats);
+        assert_eq!(
+            mcts.best_action(BestChildCriteria::MostVisits).unwrap(),
+            Pick(1)
+        );
+    }
+}
+
+#[test]
+fn test_runner_up_margin() {
+    let stats = [(Pick(0), 0.25, 10), (Pick(1), 0.75, 30), (Pick(2), 0.5, 5)];
+    let mcts = seeded(TieBreak::default(), &stats);
+
+    assert_eq!(
+        mcts.runner_up_margin(BestChildCriteria::MostVisits),
+        Some(20.0)
+    );
+    let margin = mcts
+        .runner_up_margin(BestChildCriteria::HighestValue)
+        .unwrap();
+    assert!((margin - 0.25).abs() < 1e-6, "{}", margin);
+
+    let single = seeded(TieBreak::default(), &[(Pick(1), 0.5, 3)]);
+    assert_eq!(single.runner_up_margin(BestChildCriteria::MostVisits), None);
+}
+
+#[test]
+fn test_search_result_reports_the_margin() {
+    let mut mcts = MCTS::new(
+        Choice {
+            options: 4,
+            picked: false,
+        },
+        MCTSConfig::default().with_max_iterations(200),
+    );
+    let margins = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&margins);
+    mcts.on_search_complete(move |result| log.lock().unwrap().push(result.margin));
+
+    mcts.search().unwrap();
+    let margin = mcts.runner_up_margin(BestChildCriteria::MostVisits);
+    assert!(margin.is_some());
+    assert_eq!(*margins.lock().unwrap(), [margin]);
+}
@@ -1,4 +1,6 @@
-use arboriter_mcts::{Action, GameState, MCTSConfig, Player, MCTS};
+use arboriter_mcts::{
+    config::Budget, Action, GameState, MCTSConfig, Player, SearchOutcome, SimulationPolicy, MCTS,
+};
 use std::time::{Duration, Instant};
 
 // Simple game state for testing time limits with a depth limit to avoid infinite loops
@@ -89,6 +91,7 @@ fn test_time_limited_search() {
         mcts.get_statistics().stopped_early,
         "Statistics should indicate early stopping due to time limit"
     );
+    assert_eq!(mcts.get_statistics().outcome, SearchOutcome::TimeLimit);
 }
 
 #[test]
@@ -123,5 +126,189 @@ fn test_search_for_time() {
 
     // Check that an action was returned
     let action = result.unwrap();
-    assert_eq!(action.id() < 3, true, "Should return a valid action ID");
+    assert!(action.id() < 3, "Should return a valid action ID");
+}
+
+#[test]
+fn test_completed_search_outcome() {
+    let game = TimeLimitGame {
+        depth: 0,
+        is_terminal: false,
+    };
+
+    let config = MCTSConfig::default().with_max_iterations(50);
+    let mut mcts = MCTS::new(game, config);
+    assert!(mcts.search().is_ok());
+
+    let stats = mcts.get_statistics();
+    assert_eq!(stats.outcome, SearchOutcome::Completed);
+    assert!(!stats.outcome.is_early());
+    assert_eq!(stats.iterations, 50);
+}
+
+#[test]
+fn test_stop_token_interrupts_search() {
+    let game = TimeLimitGame {
+        depth: 0,
+        is_terminal: false,
+    };
+
+    // Without the stop request this would run for a long time
+    let config = MCTSConfig::default().with_max_iterations(usize::MAX);
+    let mut mcts = MCTS::new(game, config);
+
+    let token = mcts.stop_token();
+    let stopper = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        token.stop();
+    });
+
+    let result = mcts.search();
+    stopper.join().unwrap();
+
+    // A stopped search still returns its best action so far
+    assert!(result.is_ok());
+    assert_eq!(mcts.get_statistics().outcome, SearchOutcome::Stopped);
+    assert!(!mcts.get_statistics().stopped_early);
+
+    // The request does not leak into the next search
+    assert!(!mcts.stop_token().is_stopped());
+}
+
+#[test]
+fn test_spawned_search_can_be_stopped() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(usize::MAX)
+        .with_event_interval(10);
+    let game = TimeLimitGame {
+        depth: 0,
+        is_terminal: false,
+    };
+    let handle = MCTS::new(game, config).spawn_search();
+
+    // Wait until the search has reported some progress, then stop it
+    let start = Instant::now();
+    while handle.best_action_now().is_none() {
+        assert!(start.elapsed() < Duration::from_secs(10), "no progress");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    handle.stop();
+
+    let (mcts, result) = handle.join();
+    assert!(result.is_ok());
+    let stats = mcts.get_statistics();
+    assert_eq!(stats.outcome, SearchOutcome::Stopped);
+    assert!(stats.iterations >= 10);
+}
+
+/// Playouts that take longer than the tests' time limit
+#[derive(Clone)]
+struct SlowPlayouts;
+
+impl SimulationPolicy<TimeLimitGame> for SlowPlayouts {
+    fn simulate(&self, _state: &TimeLimitGame) -> (f64, Vec<SimpleAction>) {
+        std::thread::sleep(Duration::from_millis(3));
+        (0.5, Vec::new())
+    }
+
+    fn clone_box(&self) -> Box<dyn SimulationPolicy<TimeLimitGame>> {
+        Box::new(self.clone())
+    }
+}
+
+fn iterations_with_time_check_interval(interval: usize) -> usize {
+    let game = TimeLimitGame {
+        depth: 0,
+        is_terminal: false,
+    };
+    let config = MCTSConfig::default()
+        .with_max_time(Duration::from_millis(1))
+        .with_max_iterations(1_000)
+        .with_time_check_interval(interval);
+    let mut mcts = MCTS::new(game, config).with_simulation_policy(SlowPlayouts);
+    mcts.search().unwrap();
+
+    assert_eq!(mcts.get_statistics().outcome, SearchOutcome::TimeLimit);
+    mcts.get_statistics().iterations
+}
+
+#[test]
+fn test_time_check_interval() {
+    assert_eq!(MCTSConfig::default().time_check_interval, 1);
+    assert_eq!(
+        MCTSConfig::default()
+            .with_time_check_interval(0)
+            .time_check_interval,
+        1
+    );
+
+    // Every iteration overruns the deadline, so the search stops at the
+    // first check after it
+    assert_eq!(iterations_with_time_check_interval(1), 1);
+    assert_eq!(iterations_with_time_check_interval(4), 4);
+}
+
+#[test]
+fn test_zero_iterations_means_time_only() {
+    let game = TimeLimitGame {
+        depth: 0,
+        is_terminal: false,
+    };
+    let time_limit = Duration::from_millis(20);
+    let config = MCTSConfig::default()
+        .with_max_iterations(0)
+        .with_max_time(time_limit);
+    assert_eq!(config.budget(), Budget::Time(time_limit));
+
+    let mut mcts = MCTS::new(game, config).with_simulation_policy(SlowPlayouts);
+    assert!(mcts.search().is_ok());
+
+    let stats = mcts.get_statistics();
+    assert!(
+        stats.iterations > 0,
+        "a zero iteration count still searches"
+    );
+    assert_eq!(stats.outcome, SearchOutcome::TimeLimit);
+    assert_eq!(stats.budget, Budget::Time(time_limit));
+}
+
+#[test]
+fn test_iteration_budget_reports_completion() {
+    let game = TimeLimitGame {
+        depth: 0,
+        is_terminal: false,
+    };
+    let budget = Budget::Both(25, Duration::from_secs(60));
+    let mut mcts = MCTS::new(game, MCTSConfig::default().with_budget(budget));
+    assert!(mcts.search().is_ok());
+
+    let stats = mcts.get_statistics();
+    assert_eq!(stats.iterations, 25);
+    assert_eq!(stats.outcome, SearchOutcome::Completed);
+    assert_eq!(stats.budget, budget);
+}
+
+#[test]
+fn test_unlimited_budget_runs_until_stopped() {
+    let config = MCTSConfig::default()
+        .with_budget(Budget::Unlimited)
+        .with_event_interval(10);
+    let game = TimeLimitGame {
+        depth: 0,
+        is_terminal: false,
+    };
+    let handle = MCTS::new(game, config).spawn_search();
+
+    let start = Instant::now();
+    while handle.best_action_now().is_none() {
+        assert!(start.elapsed() < Duration::from_secs(10), "no progress");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    handle.stop();
+
+    let (mcts, result) = handle.join();
+    assert!(result.is_ok());
+    let stats = mcts.get_statistics();
+    assert_eq!(stats.outcome, SearchOutcome::Stopped);
+    assert_eq!(stats.budget, Budget::Unlimited);
 }
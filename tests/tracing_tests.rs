@@ -0,0 +1,108 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    GameState, MCTSConfig, MCTS,
+};
+use tracing::{span, Event, Metadata, Subscriber};
+
+/// Counts down from a number; either move takes one or two off
+#[derive(Clone, Debug)]
+struct Countdown(u32);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step(u32);
+
+impl Action for Step {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+impl GameState for Countdown {
+    type Action = Step;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.0.min(2)).map(Step).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Countdown(self.0 - action.0)
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        0.5
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+/// Records the name of every span created while it is the default subscriber
+#[derive(Default)]
+struct SpanRecorder {
+    names: Arc<Mutex<Vec<&'static str>>>,
+    next_id: AtomicU64,
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &span::Attributes<'_>) -> span::Id {
+        self.names
+            .lock()
+            .unwrap()
+            .push(attributes.metadata().name());
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+#[test]
+fn test_search_emits_phase_spans() {
+    let recorder = SpanRecorder::default();
+    let names = recorder.names.clone();
+
+    let mut mcts = MCTS::new(Countdown(6), MCTSConfig::default().with_max_iterations(25));
+    tracing::subscriber::with_default(recorder, || mcts.search().unwrap());
+
+    let names = names.lock().unwrap();
+    let count = |name| names.iter().filter(|&&span| span == name).count();
+    assert_eq!(count("search"), 1);
+    for phase in [
+        "iteration",
+        "selection",
+        "expansion",
+        "simulation",
+        "backpropagation",
+    ] {
+        assert_eq!(count(phase), 25, "{} spans", phase);
+    }
+}
@@ -0,0 +1,266 @@
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    tree::{MCTSNode, NodePath},
+    GameState, MCTSConfig, MCTSError, MCTS,
+};
+use proptest::prelude::*;
+
+// Irregular game tree derived from a seed: the branching factor and result of
+// every state are pseudo-random functions of the path that reached it
+#[derive(Clone, Debug)]
+struct RandomTree {
+    seed: u64,
+    path_hash: u64,
+    depth: usize,
+    max_depth: usize,
+    max_branch: u64,
+    merge_states: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Branch(usize);
+
+impl Action for Branch {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Mover(usize);
+
+impl Player for Mover {}
+
+/// SplitMix64 finalizer, used as a cheap deterministic hash
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+impl RandomTree {
+    fn new(seed: u64, max_depth: usize, max_branch: u64, merge_states: bool) -> Self {
+        RandomTree {
+            seed,
+            path_hash: mix(seed),
+            depth: 0,
+            max_depth,
+            max_branch,
+            merge_states,
+        }
+    }
+
+    fn branching(&self) -> usize {
+        if self.depth >= self.max_depth {
+            return 0;
+        }
+        // The root always has moves; deeper states may end early
+        let low = if self.depth == 0 { 1 } else { 0 };
+        (low + mix(self.path_hash ^ self.seed) % (self.max_branch + 1 - low)) as usize
+    }
+}
+
+impl GameState for RandomTree {
+    type Action = Branch;
+    type Player = Mover;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (0..self.branching()).map(Branch).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut next = self.clone();
+        next.path_hash = mix(self.path_hash ^ (action.0 as u64 + 1));
+        next.depth += 1;
+        next
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.branching() == 0
+    }
+
+    fn get_result(&self, _for_player: &Self::Player) -> f64 {
+        (mix(self.path_hash) % 101) as f64 / 100.0
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Mover(self.depth % 2)
+    }
+
+    fn hash(&self) -> u64 {
+        if !self.merge_states {
+            return 0;
+        }
+        // Few distinct hashes per depth, so DAG mode merges often
+        1 + (self.depth as u64) * 4 + self.path_hash % 3
+    }
+}
+
+/// Visits every node with the path that reaches it
+fn for_each_node<S: GameState>(
+    node: &MCTSNode<S>,
+    path: &mut NodePath,
+    visit: &mut dyn FnMut(&MCTSNode<S>, &NodePath),
+) {
+    visit(node, path);
+    for (i, child) in node.children.iter().enumerate() {
+        path.push(i);
+        for_each_node(child, path, visit);
+        path.indices.pop();
+    }
+}
+
+fn count_nodes<S: GameState>(root: &MCTSNode<S>) -> usize {
+    let mut count = 0;
+    for_each_node(root, &mut NodePath::new(), &mut |_, _| count += 1);
+    count
+}
+
+fn check_tree_invariants<S: GameState>(root: &MCTSNode<S>, dag_mode: bool) {
+    for_each_node(root, &mut NodePath::new(), &mut |node, path| {
+        // Paths resolve to the node they were built from
+        let resolved = root.descendant(path).expect("path resolves");
+        assert!(std::ptr::eq(resolved, node));
+        assert_eq!(node.depth, path.len());
+
+        // An action is either expanded into a child or still unexpanded
+        for child in &node.children {
+            let id = child.action.as_ref().unwrap().id();
+            assert!(node.unexpanded_actions.iter().all(|a| a.id() != id));
+        }
+
+        if let Some(target) = &node.transposition {
+            let canonical = root.descendant(target).expect("link target resolves");
+            assert!(!canonical.is_transposition());
+        }
+
+        // In a tree every visit to a child went through its parent. Links make
+        // that false in DAG mode, since canonical nodes are reached directly.
+        if !dag_mode {
+            let child_visits: u64 = node.children.iter().map(|c| c.visits()).sum();
+            assert!(node.visits() >= child_visits);
+        }
+    });
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn search_preserves_tree_invariants(
+        seed in any::<u64>(),
+        max_depth in 1usize..6,
+        max_branch in 1u64..5,
+        iterations in 1usize..300,
+        dag_mode in any::<bool>(),
+    ) {
+        let state = RandomTree::new(seed, max_depth, max_branch, dag_mode);
+        let config = MCTSConfig::default()
+            .with_max_iterations(iterations)
+            .with_dag_mode(dag_mode);
+
+        let mut mcts = MCTS::new(state, config);
+        prop_assert!(mcts.search().is_ok());
+        prop_assert_eq!(mcts.root().visits(), iterations as u64);
+        check_tree_invariants(mcts.root(), dag_mode);
+        prop_assert!(mcts.check_invariants().is_ok());
+    }
+
+    #[test]
+    fn recycling_never_loses_nodes(
+        seed in any::<u64>(),
+        max_depth in 1usize..6,
+        max_branch in 1u64..5,
+        iterations in 1usize..200,
+        pool_size in 0usize..64,
+        max_free in proptest::option::of(0usize..32),
+        searches in 1usize..4,
+    ) {
+        let state = RandomTree::new(seed, max_depth, max_branch, false);
+        let mut config = MCTSConfig::default().with_max_iterations(iterations);
+        config.node_pool_max_free = max_free;
+        let mut mcts = MCTS::with_node_pool(state.clone(), config, pool_size);
+
+        // Every node is either in the tree or free in the pool, except the
+        // initial root, which was allocated outside the pool. Nodes dropped by
+        // the free-list limit no longer count towards the capacity.
+        let balance = |mcts: &MCTS<RandomTree>| {
+            let pool = mcts.get_statistics().node_pool_stats.clone().unwrap();
+            (pool.available + count_nodes(mcts.root())) as i64 - pool.capacity as i64
+        };
+
+        for _ in 0..searches {
+            prop_assert!(mcts.search().is_ok());
+            prop_assert_eq!(balance(&mcts), 1);
+            if let Some(max) = max_free {
+                let pool = mcts.get_statistics().node_pool_stats.clone().unwrap();
+                prop_assert!(pool.available <= max);
+            }
+            check_tree_invariants(mcts.root(), false);
+            prop_assert!(mcts.check_invariants().is_ok());
+        }
+
+        mcts.recycle_tree();
+        prop_assert_eq!(balance(&mcts), 1);
+        prop_assert_eq!(count_nodes(mcts.root()), 1);
+
+        mcts.reset_root(state);
+        prop_assert_eq!(balance(&mcts), 1);
+        prop_assert!(mcts.check_invariants().is_ok());
+    }
+}
+
+#[test]
+fn test_check_invariants_reports_inflated_child_visits() {
+    let state = RandomTree::new(7, 4, 3, false);
+    let mut mcts = MCTS::new(state, MCTSConfig::default().with_max_iterations(50));
+    mcts.search().unwrap();
+    assert!(mcts.check_invariants().is_ok());
+
+    // A child can never have more visits than the parent it was reached through
+    let root_visits = mcts.root().visits();
+    mcts.root().children[0].inject_stats(root_visits + 1, 0.5);
+    match mcts.check_invariants() {
+        Err(MCTSError::InvariantViolation(message)) => {
+            assert!(message.contains("visits"), "{}", message);
+        }
+        other => panic!("expected InvariantViolation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_handles_notice_recycled_nodes() {
+    let state = RandomTree::new(11, 4, 3, false);
+    let config = MCTSConfig::default().with_max_iterations(100);
+    let mut mcts = MCTS::with_node_pool(state, config, 64);
+    mcts.search().unwrap();
+
+    let path = NodePath::from_indices(vec![0]);
+    let handle = mcts.root().handle(&path).unwrap();
+    let node = mcts.root().resolve(&handle).unwrap();
+    assert!(std::ptr::eq(node, &mcts.root().children[0]));
+    assert_eq!(handle.generation(), node.generation);
+
+    // The next search recycles the tree and rebuilds it from pooled nodes:
+    // the same path now leads to a different use of the node
+    mcts.search().unwrap();
+    assert!(mcts.root().descendant(handle.path()).is_some());
+    assert!(mcts.root().resolve(&handle).is_none());
+    assert!(mcts.check_invariants().is_ok());
+}
+
+#[test]
+fn test_handles_to_missing_nodes() {
+    let state = RandomTree::new(3, 3, 2, false);
+    let mut mcts = MCTS::new(state.clone(), MCTSConfig::default().with_max_iterations(30));
+    mcts.search().unwrap();
+    assert!(mcts
+        .root()
+        .handle(&NodePath::from_indices(vec![9]))
+        .is_none());
+
+    let handle = mcts.root().handle(&NodePath::new()).unwrap();
+    mcts.reset_root(state);
+    assert!(mcts.root().resolve(&handle).is_none());
+}
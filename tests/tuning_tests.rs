@@ -0,0 +1,134 @@
+use arboriter_mcts::{
+    config::RewardPerspective,
+    game_state::{Action, Player},
+    tuning::{play_match, tune_exploration, SpsaTuner},
+    GameState, MCTSConfig,
+};
+
+/// Nim with one pile: take one or two stones, whoever takes the last one wins
+#[derive(Clone, Debug)]
+struct Nim {
+    stones: u32,
+    to_move: Side,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    A,
+    B,
+}
+
+impl Player for Side {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Take(u32);
+
+impl Action for Take {
+    fn id(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl GameState for Nim {
+    type Action = Take;
+    type Player = Side;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        (1..=self.stones.min(2)).map(Take).collect()
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Nim {
+            stones: self.stones - action.0,
+            to_move: match self.to_move {
+                Side::A => Side::B,
+                Side::B => Side::A,
+            },
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stones == 0
+    }
+
+    fn get_result(&self, for_player: &Self::Player) -> f64 {
+        // The player who just moved took the last stone
+        if *for_player == self.to_move {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        self.to_move
+    }
+}
+
+fn nim(stones: u32) -> Nim {
+    Nim {
+        stones,
+        to_move: Side::A,
+    }
+}
+
+fn config(iterations: usize) -> MCTSConfig {
+    MCTSConfig::default()
+        .with_max_iterations(iterations)
+        .with_reward_perspective(RewardPerspective::PerPlayer)
+}
+
+#[test]
+fn test_spsa_moves_towards_winning_candidate() {
+    let mut tuner = SpsaTuner::new(3.0).with_seed(1);
+    for _ in 0..300 {
+        tuner.step(|plus, minus| {
+            if (plus - 1.0).abs() < (minus - 1.0).abs() {
+                1.0
+            } else {
+                0.0
+            }
+        });
+    }
+    assert_eq!(tuner.iteration(), 300);
+    assert!((tuner.value() - 1.0).abs() < 0.2, "{}", tuner.value());
+
+    // An even match leaves the value where it was
+    let mut tuner = SpsaTuner::new(1.4).with_seed(2);
+    tuner.step(|_, _| 0.5);
+    assert_eq!(tuner.value(), 1.4);
+
+    // Updates without candidates are ignored
+    assert_eq!(tuner.update(1.0), 1.4);
+}
+
+#[test]
+fn test_spsa_respects_bounds() {
+    let mut tuner = SpsaTuner::new(5.0).with_bounds(0.5, 2.0).with_seed(3);
+    assert_eq!(tuner.value(), 2.0);
+    for _ in 0..50 {
+        let (plus, minus) = tuner.candidates();
+        assert!((0.5..=2.0).contains(&plus) && (0.5..=2.0).contains(&minus));
+        tuner.update(if plus < minus { 1.0 } else { 0.0 });
+    }
+    assert!(tuner.value() >= 0.5);
+}
+
+#[test]
+fn test_play_match_favours_stronger_config() {
+    // The side to move from seven stones wins with perfect play, and the
+    // weaker engine barely searches
+    let score = play_match(&nim(7), &config(300), &config(2), 10).unwrap();
+    assert!(score > 0.5, "{}", score);
+
+    let even = play_match(&nim(7), &config(300), &config(2), 0).unwrap();
+    assert_eq!(even, 0.5);
+}
+
+#[test]
+fn test_tune_exploration_step() {
+    let mut tuner = SpsaTuner::new(1.4).with_seed(4);
+    let value = tune_exploration(&mut tuner, &nim(5), &config(50), 2).unwrap();
+    assert_eq!(tuner.iteration(), 1);
+    assert_eq!(value, tuner.value());
+}
@@ -1,6 +1,6 @@
 use arboriter_mcts::{
     game_state::{Action, Player},
-    policy::selection::{SelectionPolicy, UCB1TunedPolicy},
+    policy::selection::{SelectionContext, SelectionPolicy, UCB1TunedPolicy},
     tree::MCTSNode,
     GameState,
 };
@@ -142,7 +142,7 @@ fn test_ucb1_tuned_selection_bias() {
     // So Child 0 (high variance) should be selected over Child 1 (low variance).
 
     let policy = UCB1TunedPolicy::new(1.0);
-    let best_idx = policy.select_child(&root);
+    let best_idx = policy.select_child(&root, &mut SelectionContext::new(&mut rand::thread_rng()));
 
     assert_eq!(
         best_idx, 0,
@@ -0,0 +1,61 @@
+use arboriter_mcts::utils::{
+    exploration_term, normal_quantile, ucb1_tuned_value, ucb1_tuned_variance_bound, ucb1_value,
+};
+
+const EPSILON: f64 = 1e-9;
+
+#[test]
+fn test_ucb1_value_matches_hand_computed() {
+    // mean = 3.0 / 5 = 0.6
+    // exploration = sqrt(ln(20) / 5) = sqrt(2.995732 / 5) = 0.774046
+    let value = ucb1_value(3.0, 5, 20, 1.0);
+    assert!((value - 1.374_045_512_040_989_8).abs() < EPSILON);
+
+    // Unvisited nodes are always explored first
+    assert_eq!(ucb1_value(0.0, 0, 20, 1.0), f64::INFINITY);
+    assert_eq!(exploration_term(20, 0, 1.0), f64::INFINITY);
+}
+
+#[test]
+fn test_ucb1_tuned_variance_bound_is_capped() {
+    // Rewards [1, 1, 0.5, 0.5, 0]: mean = 0.6, E[x^2] = 0.5, V = 0.14
+    // V + sqrt(2 ln(20) / 5) = 0.14 + 1.094665 > 1/4, so the cap applies
+    let bound = ucb1_tuned_variance_bound(3.0, 2.5, 5, 20);
+    assert!((bound - 0.25).abs() < EPSILON);
+
+    // With the cap active, UCB1-Tuned is UCB1 with the width halved:
+    // 0.6 + sqrt(ln(20) / 5 * 0.25) = 0.6 + 0.387023
+    let value = ucb1_tuned_value(3.0, 2.5, 5, 20, 1.0);
+    assert!((value - 0.987_022_756_020_494_9).abs() < EPSILON);
+}
+
+#[test]
+fn test_ucb1_tuned_value_below_cap() {
+    // 400 rewards of 0.5: mean = 0.5, V = 0.0
+    // sqrt(2 ln(1000) / 400) = sqrt(0.034539) = 0.185846 < 1/4
+    let bound = ucb1_tuned_variance_bound(200.0, 100.0, 400, 1000);
+    assert!((bound - 0.185_846_109_442_491_9).abs() < EPSILON);
+
+    // 0.5 + sqrt(ln(1000) / 400 * 0.185846) = 0.5 + 0.056652
+    let value = ucb1_tuned_value(200.0, 100.0, 400, 1000, 1.0);
+    assert!((value - 0.556_651_995_630_773_4).abs() < EPSILON);
+}
+
+#[test]
+fn test_ucb1_tuned_exploration_constant_scales_width() {
+    // The exploration constant multiplies the whole square-root term
+    let base = ucb1_tuned_value(3.0, 2.5, 5, 20, 1.0);
+    let doubled = ucb1_tuned_value(3.0, 2.5, 5, 20, 2.0);
+    assert!(((doubled - 0.6) - 2.0 * (base - 0.6)).abs() < EPSILON);
+
+    assert_eq!(ucb1_tuned_value(0.0, 0.0, 0, 20, 1.0), f64::INFINITY);
+}
+
+#[test]
+fn test_normal_quantile() {
+    assert!(normal_quantile(0.5).abs() < EPSILON);
+    assert!((normal_quantile(0.975) - 1.959_963_984_540_054).abs() < 1e-8);
+    assert!((normal_quantile(0.01) + 2.326_347_874_040_841).abs() < 1e-8);
+    assert_eq!(normal_quantile(1.0), f64::INFINITY);
+    assert!(normal_quantile(1.5).is_nan());
+}
@@ -0,0 +1,186 @@
+use arboriter_mcts::{
+    game_state::{Action, Player},
+    policy::{selection::PUCTPolicy, simulation::HeuristicPolicy},
+    GameState, MCTSConfig, MCTSNode, MCTS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Solo;
+
+impl Player for Solo {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Arm(usize);
+
+impl Action for Arm {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// One pull of three arms paying 0, 95 and 100 points
+#[derive(Clone, Debug)]
+struct Payouts {
+    pulled: Option<usize>,
+}
+
+impl GameState for Payouts {
+    type Action = Arm;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.pulled {
+            Some(_) => Vec::new(),
+            None => (0..3).map(Arm).collect(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        Payouts {
+            pulled: Some(action.0),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.pulled.is_some()
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        match self.pulled {
+            Some(1) => 95.0,
+            Some(2) => 100.0,
+            _ => 0.0,
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+fn visits(normalize: bool) -> Vec<u64> {
+    let config = MCTSConfig::default()
+        .with_max_iterations(200)
+        .with_value_normalization(normalize);
+    let mut mcts =
+        MCTS::new(Payouts { pulled: None }, config).with_selection_policy(PUCTPolicy::new(1.0));
+    mcts.search().unwrap();
+
+    let mut visits = vec![0; 3];
+    for child in &mcts.root().children {
+        visits[child.action.as_ref().unwrap().0] = child.visits();
+    }
+    visits
+}
+
+#[test]
+fn test_raw_payouts_swamp_the_exploration_term() {
+    // A 5 point gap dwarfs an exploration term below 1, so the close
+    // runner-up is never revisited
+    let visits = visits(false);
+    assert_eq!(visits[1], 1);
+}
+
+#[test]
+fn test_normalized_values_keep_exploring_close_actions() {
+    let visits = visits(true);
+    assert!(visits[1] > 5, "visits: {:?}", visits);
+    assert!(visits[2] > visits[1]);
+    assert!(visits[0] < visits[1]);
+}
+
+#[test]
+fn test_bounds_cover_the_child_values() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(30)
+        .with_value_normalization(true);
+    let mut mcts =
+        MCTS::new(Payouts { pulled: None }, config).with_selection_policy(PUCTPolicy::new(1.0));
+    mcts.search().unwrap();
+
+    let root = mcts.root();
+    let (min, max) = root.value_bounds().unwrap();
+    assert!(min.abs() < 1e-6 && (max - 100.0).abs() < 1e-6);
+    assert!((root.normalize_value(50.0) - 0.5).abs() < 1e-6);
+    assert_eq!(root.normalize_value(150.0), 1.0);
+
+    // Without normalization no bounds are tracked
+    let mut mcts = MCTS::new(
+        Payouts { pulled: None },
+        MCTSConfig::default().with_max_iterations(30),
+    );
+    mcts.search().unwrap();
+    assert!(mcts.root().value_bounds().is_none());
+}
+
+/// One forced move, then a pick between a 10 and a 1000 point ending
+#[derive(Clone, Debug)]
+struct Ladder {
+    picks: Vec<usize>,
+}
+
+impl GameState for Ladder {
+    type Action = Arm;
+    type Player = Solo;
+
+    fn get_legal_actions(&self) -> Vec<Self::Action> {
+        match self.picks.len() {
+            0 => vec![Arm(0)],
+            1 => vec![Arm(0), Arm(1)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply_action(&self, action: &Self::Action) -> Self {
+        let mut picks = self.picks.clone();
+        picks.push(action.0);
+        Ladder { picks }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.picks.len() == 2
+    }
+
+    fn get_result(&self, _: &Self::Player) -> f64 {
+        if self.picks[1] == 1 {
+            1000.0
+        } else {
+            10.0
+        }
+    }
+
+    fn get_current_player(&self) -> Self::Player {
+        Solo
+    }
+}
+
+#[test]
+fn test_bounds_only_cover_direct_children() {
+    let config = MCTSConfig::default()
+        .with_max_iterations(50)
+        .with_value_normalization(true);
+    // Unfinished positions are worth 10, so the forced move's average
+    // never reaches the 1000 point ending below it
+    let heuristic = HeuristicPolicy::new(|_: &Ladder| 10.0);
+    let mut mcts =
+        MCTS::new(Ladder { picks: Vec::new() }, config).with_simulation_policy(heuristic);
+    mcts.search().unwrap();
+
+    let root = mcts.root();
+    let forced = &root.children[0];
+    let (_, root_max) = root.value_bounds().unwrap();
+    assert!(root_max < 1000.0, "root bounds reach {}", root_max);
+    let (min, max) = forced.value_bounds().unwrap();
+    assert!((min - 10.0).abs() < 1e-6 && (max - 1000.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_mean_child_value_weights_by_visits() {
+    let mut root = MCTSNode::new(Payouts { pulled: None }, None, None, 0);
+    assert_eq!(root.mean_child_value(), None);
+    root.expand(0);
+    root.expand(0);
+    root.children[0].inject_stats(3, 1.0);
+    root.children[1].inject_stats(1, 5.0);
+    assert!((root.mean_child_value().unwrap() - 2.0).abs() < 1e-6);
+}
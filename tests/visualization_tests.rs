@@ -33,7 +33,7 @@ impl GameState for TestGame {
         }
     }
 
-    fn apply_action(&self, action: &Self::Action) -> Self {
+    fn apply_action(&self, _action: &Self::Action) -> Self {
         Self {
             depth: self.depth + 1,
             max_depth: self.max_depth,
@@ -83,3 +83,34 @@ fn test_tree_visualization() {
     // Print for inspection during test development
     // println!("{}", tree_vis);
 }
+
+#[test]
+fn test_report_lists_root_actions_by_visits() {
+    let game = TestGame {
+        depth: 0,
+        max_depth: 3,
+    };
+    let config = MCTSConfig::default().with_max_iterations(50);
+
+    let mut mcts = MCTS::new(game, config);
+    let _ = mcts.search();
+
+    let report = mcts.report();
+    assert!(report.starts_with("MCTS Search Statistics:"));
+
+    let table: Vec<&str> = report
+        .lines()
+        .skip_while(|line| *line != "Root Actions:")
+        .skip(1)
+        .collect();
+    assert!(table[0].contains("Action") && table[0].contains("Visits"));
+    assert_eq!(table.len(), 3, "Header plus one row per root action");
+
+    // Rows are sorted by visits, most visited first
+    let visits: Vec<u64> = table[1..]
+        .iter()
+        .map(|row| row.split_whitespace().nth(1).unwrap().parse().unwrap())
+        .collect();
+    assert!(visits[0] >= visits[1]);
+    assert_eq!(visits.iter().sum::<u64>(), 50);
+}